@@ -10,10 +10,51 @@ echo "Building WGPU examples..."
     # curl https://rustwasm.github.io/wasm-pack/installer/init.sh -sSf | sh
 # fi
 
+EXAMPLES=(
+  wgpu-triangle
+  wgpu-colored-triangle
+  wgpu-quad
+  wgpu-cube
+  wgpu-time-uniform
+  wgpu-textured-quad
+  wgpu-input
+  wgpu-camera
+  wgpu-staging
+  wgpu-compute
+  wgpu-sprites
+  wgpu-bloom
+  wgpu-text
+  wgpu-deferred
+  wgpu-culling
+  wgpu-skybox
+  wgpu-instancing
+  wgpu-shadow
+  wgpu-tonemap
+  wgpu-particles
+  wgpu-scene-graph
+  wgpu-phong
+  wgpu-render-graph
+  wgpu-gltf
+  wgpu-blend-modes
+  wgpu-post-process
+  wgpu-pbr
+  wgpu-terrain
+  wgpu-offscreen
+  wgpu-tilemap
+  wgpu-textured-cube
+  wgpu-skinning
+  wgpu-fps
+  wgpu-streaming
+  wgpu-camera-path
+  wgpu-video
+)
+
 # WASM 빌드
-cd examples/wgpu-triangle
-wasm-pack build --target web --out-dir ../../src/assets/wasm/wgpu-triangle --release
-cd ../..
+for example in "${EXAMPLES[@]}"; do
+  cd examples/$example
+  wasm-pack build --target web --out-dir ../../src/assets/wasm/$example --release
+  cd ../..
+done
 
 # echo "Building mdBook..."
 # cd ../..
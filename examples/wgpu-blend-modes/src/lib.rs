@@ -0,0 +1,332 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Vec2, Vec4};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BindGroupBuilder, BlendMode, CameraUniform, FormatSelector, FrameTimer, GpuContext, InitError,
+    OrthoCamera, Sprite, SpriteBatch, Texture, UniformBuffer,
+};
+
+// 왼쪽 절반과 오른쪽 절반에서 비교하는 블렌드 모드
+const LEFT_MODE: BlendMode = BlendMode::AlphaBlend;
+const RIGHT_MODE: BlendMode = BlendMode::Additive;
+// 원 텍스처 한 변의 크기
+const CIRCLE_TEXTURE_SIZE: u32 = 64;
+// 세 원이 겹쳐서 도는 색. 알파가 1 보다 작아서 AlphaBlend 에서도 아래 원이 비친다
+const CIRCLE_TINTS: [Vec4; 3] = [
+    Vec4::new(1.0, 0.2, 0.2, 0.8),
+    Vec4::new(0.2, 1.0, 0.2, 0.8),
+    Vec4::new(0.2, 0.2, 1.0, 0.8),
+];
+
+// 가운데가 불투명하고 가장자리로 갈수록 투명해지는 흰 원
+fn create_circle_image() -> image::DynamicImage {
+    let size = CIRCLE_TEXTURE_SIZE;
+    let image = image::RgbaImage::from_fn(size, size, |x, y| {
+        let center = size as f32 / 2.0;
+        let distance = Vec2::new(x as f32 + 0.5 - center, y as f32 + 0.5 - center).length();
+        let t = (distance / center).clamp(0.0, 1.0);
+        let alpha = 1.0 - t * t * (3.0 - 2.0 * t);
+        image::Rgba([255, 255, 255, (alpha * 255.0) as u8])
+    });
+    image::DynamicImage::ImageRgba8(image)
+}
+
+// 캔버스의 왼쪽 아래가 (0, 0), 오른쪽 위가 (width, height) 인 픽셀 좌표계
+fn pixel_camera(size: (u32, u32)) -> OrthoCamera {
+    OrthoCamera::new(0.0, size.0 as f32, 0.0, size.1 as f32)
+}
+
+// center 주위를 도는 세 원
+fn push_circles(batch: &mut SpriteBatch, center: Vec2, radius: f32, time: f32) {
+    batch.clear();
+    for (i, tint) in CIRCLE_TINTS.into_iter().enumerate() {
+        let angle = time * 0.5 + i as f32 * std::f32::consts::TAU / 3.0;
+        let offset = Vec2::new(angle.cos(), angle.sin()) * radius * 0.45;
+        batch.push(Sprite {
+            tint,
+            ..Sprite::new(center + offset, Vec2::splat(radius * 1.6))
+        });
+    }
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    // LEFT_MODE, RIGHT_MODE 순서
+    pipelines: [wgpu::RenderPipeline; 2],
+    batches: [SpriteBatch; 2],
+    camera_uniform: UniformBuffer<CameraUniform>,
+    texture_bind_group: wgpu::BindGroup,
+    frame_timer: FrameTimer,
+    canvas_id: String,
+    size: (u32, u32),
+    time: f32,
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::downlevel_webgl2_defaults(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&pixel_camera(size)),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        let texture = Texture::from_image(&gpu, &create_circle_image(), "Circle");
+        let (texture_bind_group_layout, texture_bind_group) = BindGroupBuilder::new()
+            .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+            .sampler(1, texture.sampler(), wgpu::ShaderStages::FRAGMENT)
+            .build(device, "Texture Bind Group");
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[
+                    camera_uniform.bind_group_layout(),
+                    &texture_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            });
+
+        // 블렌드 상태만 다른 파이프라인을 모드마다 하나씩 만든다
+        let create_pipeline = |mode: BlendMode| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(&format!("{} Pipeline", mode.name())),
+                layout: Some(&render_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[SpriteBatch::vertex_layout()],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_config.format,
+                        blend: mode.to_wgpu(),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let pipelines = [create_pipeline(LEFT_MODE), create_pipeline(RIGHT_MODE)];
+
+        let batches = [
+            SpriteBatch::new(device, CIRCLE_TINTS.len()),
+            SpriteBatch::new(device, CIRCLE_TINTS.len()),
+        ];
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            pipelines,
+            batches,
+            camera_uniform,
+            texture_bind_group,
+            frame_timer: FrameTimer::new(),
+            canvas_id: canvas_id.to_string(),
+            size,
+            time: 0.0,
+        })
+    }
+
+    fn update(&mut self, delta_secs: f32) {
+        self.time += delta_secs;
+
+        let half_width = self.size.0 as f32 / 2.0;
+        let height = self.size.1 as f32;
+        let radius = half_width.min(height) * 0.35;
+        for (i, batch) in self.batches.iter_mut().enumerate() {
+            let center = Vec2::new(half_width * (i as f32 + 0.5), height / 2.0);
+            push_circles(batch, center, radius, self.time);
+            batch.prepare(&self.gpu);
+        }
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        // Additive 가 밝아지는 것이 잘 보이도록 어두운 배경을 쓴다
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.02,
+                            g: 0.02,
+                            b: 0.04,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            render_pass.set_bind_group(1, &self.texture_bind_group, &[]);
+            for (pipeline, batch) in self.pipelines.iter().zip(&self.batches) {
+                render_pass.set_pipeline(pipeline);
+                batch.draw(&mut render_pass);
+            }
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::downlevel_webgl2_defaults();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.camera_uniform.update(
+            self.gpu.queue(),
+            &CameraUniform::from(&pixel_camera(new_size)),
+        );
+    }
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                state.frame_timer.begin_frame();
+
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                let delta_secs = state.frame_timer.delta_secs();
+                state.update(delta_secs);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+
+                state.frame_timer.end_frame();
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = State::new(canvas_id).await?;
+    start_render_loop(Rc::new(RefCell::new(state)));
+    Ok(())
+}
@@ -0,0 +1,288 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{BloomPass, FormatSelector, GpuContext, InitError, Vertex, VertexBuffer};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// 1.0 보다 밝은 HDR 색이어야 블룸이 생긴다
+const VERTICES: &[ColorVertex] = &[
+    ColorVertex {
+        position: [0.0, 0.5, 0.0],
+        color: [4.0, 0.5, 0.5],
+    },
+    ColorVertex {
+        position: [-0.5, -0.5, 0.0],
+        color: [0.5, 4.0, 0.5],
+    },
+    ColorVertex {
+        position: [0.5, -0.5, 0.0],
+        color: [0.5, 0.5, 4.0],
+    },
+];
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: VertexBuffer<ColorVertex>,
+    bloom: BloomPass,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        // 블룸의 블러는 컴퓨트 셰이더로 동작하므로 WebGPU 어댑터가 필요하다
+        if !adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        {
+            return Err(InitError::AdapterUnsupported(
+                "Compute shaders are not supported on this adapter".to_string(),
+            ));
+        }
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        // 버텍스 버퍼 생성
+        let vertex_buffer = VertexBuffer::from_data(device, VERTICES);
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[VertexBuffer::<ColorVertex>::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    // 장면은 서피스가 아니라 블룸의 HDR 텍스처에 그린다
+                    format: BloomPass::HDR_FORMAT,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let bloom = BloomPass::new(&gpu, surface_config.format, size.0, size.1, 1.0, 1.5);
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            vertex_buffer,
+            bloom,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: self.bloom.hdr_view(),
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.02,
+                            g: 0.02,
+                            b: 0.05,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            self.vertex_buffer.draw(&mut render_pass);
+        }
+
+        // 밝은 부분을 블러해서 장면과 합친 결과를 서피스에 그린다
+        self.bloom.apply(&mut encoder, self.bloom.hdr_view(), &view);
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.bloom.resize(new_size.0, new_size.1);
+    }
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,452 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Quat, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BezierSpline, Camera, CameraPath, CameraUniform, DebugLines, DepthTexture, FormatSelector,
+    GpuContext, InitError, InputListeners, InputState, InstanceBuffer, Mesh, Transform,
+    UniformBuffer, Vertex,
+};
+
+// 카메라가 곡선을 따라 움직이는 속도 (초당 월드 단위)
+const PATH_SPEED: f32 = 4.0;
+// 곡선을 선분으로 나눠 그릴 때의 수
+const CURVE_LINES: usize = 256;
+// 기둥이 서 있는 격자의 한 변 개수와 간격
+const PILLAR_GRID: i32 = 5;
+const PILLAR_SPACING: f32 = 6.0;
+
+const CURVE_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+const HANDLE_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 0.6];
+const MARKER_COLOR: [f32; 4] = [1.0, 0.3, 0.3, 1.0];
+
+// 기둥 사이를 누비면서 오르내리는 닫힌 경로
+const WAYPOINTS: [[f32; 3]; 8] = [
+    [9.0, 2.0, 0.0],
+    [3.0, 4.0, 9.0],
+    [-3.0, 1.5, 3.0],
+    [-9.0, 3.0, 9.0],
+    [-9.0, 5.0, -3.0],
+    [-3.0, 2.0, -9.0],
+    [3.0, 1.5, -3.0],
+    [9.0, 3.5, -9.0],
+];
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// (법선, u축, v축) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+const FACES: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+];
+
+// 밑면이 y = 0 에 놓인 한 변이 1 인 큐브. 면마다 밝기를 달리해서 모서리가 보이게 한다
+fn cube_geometry() -> (Vec<ColorVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (i, (normal, u, v)) in FACES.into_iter().enumerate() {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+        let shade = 0.45 + 0.08 * i as f32;
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = (n + u * su + v * sv) * 0.5 + Vec3::Y * 0.5;
+            vertices.push(ColorVertex {
+                position: position.into(),
+                color: [shade * 0.8, shade * 0.9, shade],
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+// 얇게 눌러 놓은 바닥과 격자에 늘어놓은 높이가 제각각인 기둥
+fn scene_transforms() -> Vec<Transform> {
+    let ground_size = (PILLAR_GRID + 1) as f32 * PILLAR_SPACING;
+    let mut transforms = vec![Transform {
+        translation: Vec3::new(0.0, -0.1, 0.0),
+        rotation: Quat::IDENTITY,
+        scale: Vec3::new(ground_size, 0.1, ground_size),
+    }];
+
+    let half = PILLAR_GRID / 2;
+    for z in -half..=half {
+        for x in -half..=half {
+            let height = 2.0 + ((x * 3 + z * 7).rem_euclid(5)) as f32;
+            transforms.push(Transform {
+                translation: Vec3::new(x as f32, 0.0, z as f32) * PILLAR_SPACING,
+                rotation: Quat::IDENTITY,
+                scale: Vec3::new(0.8, height, 0.8),
+            });
+        }
+    }
+    transforms
+}
+
+// 경로를 노란 선으로, 손잡이를 회색 선으로, 세그먼트 끝점을 작은 구로 그린다
+fn add_spline_lines(lines: &mut DebugLines, spline: &BezierSpline) {
+    for i in 0..CURVE_LINES {
+        let a = spline.evaluate(i as f32 / CURVE_LINES as f32);
+        let b = spline.evaluate((i + 1) as f32 / CURVE_LINES as f32);
+        lines.add_line(a, b, CURVE_COLOR);
+    }
+    for [p0, p1, p2, p3] in spline.segments() {
+        lines.add_line(*p0, *p1, HANDLE_COLOR);
+        lines.add_line(*p2, *p3, HANDLE_COLOR);
+        lines.add_sphere(*p0, 0.15, HANDLE_COLOR);
+    }
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    instances: InstanceBuffer,
+    path: CameraPath,
+    // Space 를 누르고 있는 동안 쓰는 위에서 내려다보는 카메라
+    overview: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    debug_lines: DebugLines,
+    input: Rc<RefCell<InputState>>,
+    _input_listeners: InputListeners,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 메시 생성
+        let (vertices, indices) = cube_geometry();
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+        let instances = InstanceBuffer::new(device, &scene_transforms());
+
+        // 경로를 따라가는 카메라와 내려다보는 카메라
+        let aspect = size.0 as f32 / size.1 as f32;
+        let waypoints: Vec<Vec3> = WAYPOINTS.iter().copied().map(Vec3::from).collect();
+        let path = CameraPath::new(
+            BezierSpline::closed_through(&waypoints),
+            PATH_SPEED,
+            Camera::new(Vec3::ZERO, Vec3::NEG_Z, aspect),
+        );
+        let overview = Camera::new(Vec3::new(0.0, 30.0, 28.0), Vec3::ZERO, aspect);
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&path.camera()),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // Space 키를 받는다
+        let input = Rc::new(RefCell::new(InputState::default()));
+        let input_listeners = InputListeners::register(&canvas, Rc::clone(&input))
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[ColorVertex::layout(), InstanceBuffer::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        // 기둥 뒤로 지나가는 경로는 가려지도록 깊이 테스트를 켠다
+        let mut debug_lines = DebugLines::new(
+            &gpu,
+            surface_config.format,
+            camera_uniform.bind_group_layout(),
+        );
+        debug_lines.set_depth_test(Some(depth_texture.view()));
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            mesh,
+            instances,
+            path,
+            overview,
+            camera_uniform,
+            debug_lines,
+            input,
+            _input_listeners: input_listeners,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, dt: f32) {
+        let path_camera = self.path.update(dt);
+
+        let mut input = self.input.borrow_mut();
+        let overview = input.is_key_down("Space");
+        input.end_frame();
+
+        add_spline_lines(&mut self.debug_lines, self.path.spline());
+        let camera = if overview {
+            // 경로 위의 카메라 위치와 바라보는 방향을 표시한다
+            self.debug_lines
+                .add_sphere(path_camera.eye, 0.4, MARKER_COLOR);
+            self.debug_lines.add_line(
+                path_camera.eye,
+                path_camera.eye + (path_camera.target - path_camera.eye) * 2.0,
+                MARKER_COLOR,
+            );
+            self.overview
+        } else {
+            path_camera
+        };
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&camera));
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            self.instances.bind(&mut render_pass, 1);
+            self.mesh
+                .draw_instanced(&mut render_pass, 0..self.instances.len());
+        }
+
+        // 메인 패스가 그린 장면 위에 경로를 덧그린다
+        self.debug_lines
+            .flush(&mut encoder, &view, self.camera_uniform.bind_group());
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        self.debug_lines
+            .set_depth_test(Some(self.depth_texture.view()));
+
+        let aspect = new_size.0 as f32 / new_size.1 as f32;
+        self.path.set_aspect(aspect);
+        self.overview.aspect = aspect;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let mut last = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                // 탭이 오래 숨겨졌다 돌아와도 한 번에 멀리 가지 않도록 자른다
+                let now = now_secs();
+                state.update((now - last).min(0.1));
+                last = now;
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,356 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::Vec3;
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    Camera, CameraUniform, DepthTexture, FormatSelector, GpuContext, InitError, InputListeners,
+    InputState, Mesh, RenderPassBuilder, UniformBuffer, Vertex,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// (법선, u축, v축, 색) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+type Face = ([f32; 3], [f32; 3], [f32; 3], [f32; 3]);
+
+const FACES: [Face; 6] = [
+    (
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ),
+    (
+        [-1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 1.0, 1.0],
+    ),
+    (
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+    ),
+    (
+        [0.0, -1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, -1.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+    ),
+];
+
+// 면마다 색이 다르도록 꼭짓점을 공유하지 않고 면당 4개씩 만든다
+fn cube_geometry() -> (Vec<ColorVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (normal, u, v, color) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = (n + u * su + v * sv) * 0.5;
+            vertices.push(ColorVertex {
+                position: position.into(),
+                color,
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    input: Rc<RefCell<InputState>>,
+    _input_listeners: InputListeners,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 모바일에서 두 손가락 핀치로 확대한다
+        let input = Rc::new(RefCell::new(InputState::default()));
+        let input_listeners = InputListeners::register(&canvas, Rc::clone(&input))
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+
+        // 메시 생성
+        let (vertices, indices) = cube_geometry();
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+
+        // 카메라
+        let camera = Camera::new(
+            Vec3::new(0.0, 1.5, 3.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[ColorVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            mesh,
+            camera,
+            camera_uniform,
+            input,
+            _input_listeners: input_listeners,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        // 큐브 주위를 돌면서 바라본다
+        let radius = 3.0;
+        self.camera.eye = Vec3::new(radius * time_secs.cos(), 1.5, radius * time_secs.sin());
+
+        let mut input = self.input.borrow_mut();
+        self.camera.zoom(input.touch().pinch_scale_delta());
+        input.end_frame();
+
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut frame = RenderPassBuilder::new(&self.gpu, &view)
+            .clear_color(wgpu::Color {
+                r: 0.1,
+                g: 0.2,
+                b: 0.3,
+                a: 1.0,
+            })
+            .depth(self.depth_texture.view());
+
+        frame
+            .set_pipeline(&self.render_pipeline)
+            .draw_mesh(&self.mesh, self.camera_uniform.bind_group());
+        frame.submit();
+
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use std::env;
+
+fn main() {
+    // GPU 가 필요한 테스트는 WGPU_TESTS 를 설정했을 때만 돌리고, 아니면 ignore 로 표시한다
+    println!("cargo:rustc-check-cfg=cfg(wgpu_tests)");
+    println!("cargo:rerun-if-env-changed=WGPU_TESTS");
+    if env::var_os("WGPU_TESTS").is_some() {
+        println!("cargo:rustc-cfg=wgpu_tests");
+    }
+}
@@ -0,0 +1,72 @@
+use crate::MsaaConfig;
+
+/// 계단 현상을 줄이는 방법
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AntiAliasMode {
+    #[default]
+    None,
+    /// 샘플 수가 이 값인 멀티 샘플 텍스처에 그린 뒤 리졸브한다
+    Msaa(u32),
+    /// 장면을 텍스처에 그린 뒤 `FxaaEffect` 로 경계를 흐리게 한다
+    Fxaa,
+    /// 프레임마다 서브 픽셀만큼 흔들어 그린 결과를 `TaaPass` 로 누적한다
+    Taa,
+}
+
+impl AntiAliasMode {
+    /// `"none"`, `"msaa2"`, `"msaa4"`, `"msaa8"`, `"fxaa"`, `"taa"`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(AntiAliasMode::None),
+            "fxaa" => Some(AntiAliasMode::Fxaa),
+            "taa" => Some(AntiAliasMode::Taa),
+            _ => {
+                let count = name.strip_prefix("msaa")?.parse().ok()?;
+                MsaaConfig::from_sample_count(count)?;
+                Some(AntiAliasMode::Msaa(count))
+            }
+        }
+    }
+
+    /// 메인 패스의 멀티 샘플 설정. `Msaa` 의 샘플 수가 1, 2, 4, 8 이 아니면 `None`
+    pub fn msaa(self) -> Option<MsaaConfig> {
+        match self {
+            AntiAliasMode::Msaa(count) => MsaaConfig::from_sample_count(count),
+            _ => Some(MsaaConfig::One),
+        }
+    }
+
+    /// 장면을 화면 대신 중간 텍스처에 그린 뒤 후처리 패스로 화면에 옮겨야 하는지 여부
+    pub fn needs_post_pass(self) -> bool {
+        matches!(self, AntiAliasMode::Fxaa | AntiAliasMode::Taa)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names() {
+        assert_eq!(AntiAliasMode::from_name("none"), Some(AntiAliasMode::None));
+        assert_eq!(
+            AntiAliasMode::from_name("msaa4"),
+            Some(AntiAliasMode::Msaa(4))
+        );
+        assert_eq!(AntiAliasMode::from_name("fxaa"), Some(AntiAliasMode::Fxaa));
+        assert_eq!(AntiAliasMode::from_name("taa"), Some(AntiAliasMode::Taa));
+        assert_eq!(AntiAliasMode::from_name("msaa3"), None);
+        assert_eq!(AntiAliasMode::from_name("smaa"), None);
+    }
+
+    #[test]
+    fn only_msaa_changes_sample_count() {
+        assert_eq!(AntiAliasMode::Msaa(8).msaa(), Some(MsaaConfig::Eight));
+        assert_eq!(AntiAliasMode::Msaa(3).msaa(), None);
+        for mode in [AntiAliasMode::None, AntiAliasMode::Fxaa, AntiAliasMode::Taa] {
+            assert_eq!(mode.msaa(), Some(MsaaConfig::One));
+        }
+        assert!(!AntiAliasMode::Msaa(4).needs_post_pass());
+        assert!(AntiAliasMode::Taa.needs_post_pass());
+    }
+}
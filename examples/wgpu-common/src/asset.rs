@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::error::AssetError;
+use crate::{GpuContext, Texture};
+
+/// `fetch` 로 URL 의 이미지를 받아서 GPU 텍스처로 올린다.
+///
+/// 한 번 불러온 텍스처는 URL 별로 캐시해 두고, 같은 URL 을 다시 요청하면
+/// 네트워크 요청 없이 캐시된 텍스처를 돌려준다.
+#[derive(Default)]
+pub struct AssetLoader {
+    textures: HashMap<String, Texture>,
+}
+
+impl AssetLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn load_texture(
+        &mut self,
+        url: &str,
+        gpu: &GpuContext,
+    ) -> Result<Texture, AssetError> {
+        if let Some(texture) = self.textures.get(url) {
+            return Ok(texture.clone());
+        }
+
+        let bytes = fetch_bytes(url).await?;
+        let texture = Texture::from_bytes(gpu, &bytes, url)?;
+        self.textures.insert(url.to_string(), texture.clone());
+        Ok(texture)
+    }
+
+    /// 이미 불러온 텍스처. 없으면 `None`.
+    pub fn cached_texture(&self, url: &str) -> Option<&Texture> {
+        self.textures.get(url)
+    }
+
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+}
+
+/// `url` 의 응답 본문을 바이트로 읽는다. 2xx 가 아닌 응답은 에러로 처리한다.
+pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>, AssetError> {
+    let window = web_sys::window().ok_or_else(|| AssetError::Fetch("no window".to_string()))?;
+
+    let init = web_sys::RequestInit::new();
+    init.set_method("GET");
+    init.set_mode(web_sys::RequestMode::Cors);
+    let request = web_sys::Request::new_with_str_and_init(url, &init).map_err(AssetError::from)?;
+
+    let response = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(AssetError::from)?
+        .dyn_into::<web_sys::Response>()
+        .map_err(AssetError::from)?;
+    if !response.ok() {
+        return Err(AssetError::Http(response.status()));
+    }
+
+    let buffer = JsFuture::from(response.array_buffer().map_err(AssetError::from)?)
+        .await
+        .map_err(AssetError::from)?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
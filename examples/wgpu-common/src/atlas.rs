@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use crate::{GpuContext, Texture};
+
+// 선형 필터링이 옆 이미지의 텍셀을 섞지 않도록 이미지 사이에 두는 투명한 간격
+const PADDING: u32 = 1;
+
+/// 아틀라스 안에서 이미지 하나가 차지하는 텍스처 좌표 `(u, v, 너비, 높이)`
+pub type AtlasRect = [f32; 4];
+
+// 아틀라스 안에서 이미지 하나가 차지하는 픽셀 영역
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PixelRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/// 작은 이미지 여러 개를 한 장의 텍스처에 모은 아틀라스.
+///
+/// 높이가 큰 이미지부터 왼쪽에서 오른쪽으로 채우고, 줄이 차면 다음 줄(선반)로 넘어간다.
+/// 너비와 높이는 모두 2 의 거듭제곱이고, `create_texture` 는 `write_texture` 한 번으로 올린다.
+pub struct TextureAtlas {
+    image: image::DynamicImage,
+    rects: HashMap<String, PixelRect>,
+}
+
+impl TextureAtlas {
+    /// `(라벨, 이미지 바이트)` 목록을 디코딩해서 아틀라스로 묶는다.
+    /// 같은 라벨이 여러 번 나오면 마지막 이미지의 영역이 남는다.
+    pub fn from_bytes(images: &[(&str, &[u8])]) -> image::ImageResult<Self> {
+        let images = images
+            .iter()
+            .map(|(label, bytes)| Ok((*label, image::load_from_memory(bytes)?.to_rgba8())))
+            .collect::<image::ImageResult<Vec<_>>>()?;
+        Ok(Self::from_images(&images))
+    }
+
+    pub fn from_images(images: &[(&str, image::RgbaImage)]) -> Self {
+        let mut order: Vec<usize> = (0..images.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(images[i].1.height()));
+
+        let total_area: u64 = images
+            .iter()
+            .map(|(_, image)| {
+                u64::from(image.width() + PADDING) * u64::from(image.height() + PADDING)
+            })
+            .sum();
+        let max_width = images
+            .iter()
+            .map(|(_, image)| image.width())
+            .max()
+            .unwrap_or(1);
+        // 넓이가 같은 정사각형에 가깝게 시작해서 남는 높이는 아래에서 2 의 거듭제곱으로 맞춘다
+        let width = ((total_area as f64).sqrt().ceil() as u32)
+            .max(max_width)
+            .max(1)
+            .next_power_of_two();
+
+        let mut rects = HashMap::with_capacity(images.len());
+        let mut placed = Vec::with_capacity(images.len());
+        let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+        for i in order {
+            let (label, image) = &images[i];
+            let (w, h) = image.dimensions();
+            if x > 0 && x + w > width {
+                x = 0;
+                y += shelf_height + PADDING;
+                shelf_height = 0;
+            }
+
+            let rect = PixelRect {
+                x,
+                y,
+                width: w,
+                height: h,
+            };
+            rects.insert(label.to_string(), rect);
+            placed.push((i, rect));
+
+            x += w + PADDING;
+            shelf_height = shelf_height.max(h);
+        }
+        let height = (y + shelf_height).max(1).next_power_of_two();
+
+        let mut atlas = image::RgbaImage::new(width, height);
+        for (i, rect) in placed {
+            image::imageops::replace(
+                &mut atlas,
+                &images[i].1,
+                i64::from(rect.x),
+                i64::from(rect.y),
+            );
+        }
+
+        Self {
+            image: image::DynamicImage::ImageRgba8(atlas),
+            rects,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// 모든 이미지를 배치한 RGBA8 이미지
+    pub fn image(&self) -> &image::DynamicImage {
+        &self.image
+    }
+
+    /// 라벨에 해당하는 이미지의 텍스처 좌표 `(u, v, 너비, 높이)`.
+    /// `Sprite::uv_rect` 에 그대로 넣을 수 있다.
+    pub fn uv_rect(&self, label: &str) -> Option<AtlasRect> {
+        let rect = self.rects.get(label)?;
+        let (width, height) = (self.width() as f32, self.height() as f32);
+        Some([
+            rect.x as f32 / width,
+            rect.y as f32 / height,
+            rect.width as f32 / width,
+            rect.height as f32 / height,
+        ])
+    }
+
+    pub fn create_texture(&self, gpu: &GpuContext, label: &str) -> Texture {
+        Texture::from_image(gpu, &self.image, label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, value: u8) -> image::RgbaImage {
+        image::RgbaImage::from_pixel(width, height, image::Rgba([value, value, value, 255]))
+    }
+
+    fn overlaps(a: [f32; 4], b: [f32; 4]) -> bool {
+        a[0] < b[0] + b[2] && b[0] < a[0] + a[2] && a[1] < b[1] + b[3] && b[1] < a[1] + a[3]
+    }
+
+    #[test]
+    fn packs_64_tiles_without_overlap() {
+        let labels: Vec<String> = (0..64).map(|i| format!("tile{i}")).collect();
+        let images: Vec<(&str, image::RgbaImage)> = labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| (label.as_str(), solid(16, 16, i as u8)))
+            .collect();
+        let atlas = TextureAtlas::from_images(&images);
+
+        assert!(atlas.width().is_power_of_two());
+        assert!(atlas.height().is_power_of_two());
+
+        let rects: Vec<[f32; 4]> = labels
+            .iter()
+            .map(|label| atlas.uv_rect(label).unwrap())
+            .collect();
+        for (i, a) in rects.iter().enumerate() {
+            assert!(a[0] >= 0.0 && a[1] >= 0.0);
+            assert!(a[0] + a[2] <= 1.0 && a[1] + a[3] <= 1.0);
+            for b in &rects[i + 1..] {
+                assert!(!overlaps(*a, *b), "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn copies_pixels_into_rect() {
+        let images = [("small", solid(4, 4, 10)), ("wide", solid(20, 8, 200))];
+        let atlas = TextureAtlas::from_images(&images);
+        let rgba = atlas.image().to_rgba8();
+
+        for (label, image) in &images {
+            let [u, v, _, _] = atlas.uv_rect(label).unwrap();
+            let x = (u * atlas.width() as f32) as u32;
+            let y = (v * atlas.height() as f32) as u32;
+            assert_eq!(rgba.get_pixel(x, y), image.get_pixel(0, 0));
+            assert_eq!(
+                rgba.get_pixel(x + image.width() - 1, y + image.height() - 1),
+                image.get_pixel(0, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_png_bytes() {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(solid(16, 16, 128))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let atlas = TextureAtlas::from_bytes(&[("png", &png)]).unwrap();
+        let (width, height) = (atlas.width() as f32, atlas.height() as f32);
+        assert_eq!(
+            atlas.uv_rect("png"),
+            Some([0.0, 0.0, 16.0 / width, 16.0 / height])
+        );
+        assert_eq!(atlas.uv_rect("missing"), None);
+        assert!(TextureAtlas::from_bytes(&[("broken", &[0, 1, 2])]).is_err());
+    }
+}
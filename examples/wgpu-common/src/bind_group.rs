@@ -0,0 +1,233 @@
+/// 바인드 그룹 레이아웃과 바인드 그룹을 같은 항목 목록으로 한 번에 만든다.
+///
+/// ```ignore
+/// let (layout, bind_group) = BindGroupBuilder::new()
+///     .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+///     .sampler(1, texture.sampler(), wgpu::ShaderStages::FRAGMENT)
+///     .build(&device, "Texture Bind Group");
+/// ```
+#[derive(Default)]
+pub struct BindGroupBuilder<'a> {
+    layout_entries: Vec<wgpu::BindGroupLayoutEntry>,
+    entries: Vec<wgpu::BindGroupEntry<'a>>,
+}
+
+impl<'a> BindGroupBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 필터링 가능한 float 2D 텍스처
+    pub fn texture(
+        self,
+        binding: u32,
+        view: &'a wgpu::TextureView,
+        visibility: wgpu::ShaderStages,
+    ) -> Self {
+        self.entry(
+            binding,
+            visibility,
+            wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            wgpu::BindingResource::TextureView(view),
+        )
+    }
+
+    /// 필터링 샘플러
+    pub fn sampler(
+        self,
+        binding: u32,
+        sampler: &'a wgpu::Sampler,
+        visibility: wgpu::ShaderStages,
+    ) -> Self {
+        self.entry(
+            binding,
+            visibility,
+            wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            wgpu::BindingResource::Sampler(sampler),
+        )
+    }
+
+    pub fn uniform_buffer(
+        self,
+        binding: u32,
+        buffer: &'a wgpu::Buffer,
+        visibility: wgpu::ShaderStages,
+    ) -> Self {
+        self.entry(
+            binding,
+            visibility,
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            buffer.as_entire_binding(),
+        )
+    }
+
+    pub fn storage_buffer(
+        self,
+        binding: u32,
+        buffer: &'a wgpu::Buffer,
+        read_only: bool,
+        visibility: wgpu::ShaderStages,
+    ) -> Self {
+        self.entry(
+            binding,
+            visibility,
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            buffer.as_entire_binding(),
+        )
+    }
+
+    /// 위의 메서드로 표현할 수 없는 바인딩 타입을 추가한다.
+    pub fn entry(
+        mut self,
+        binding: u32,
+        visibility: wgpu::ShaderStages,
+        ty: wgpu::BindingType,
+        resource: wgpu::BindingResource<'a>,
+    ) -> Self {
+        self.layout_entries.push(wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty,
+            count: None,
+        });
+        self.entries
+            .push(wgpu::BindGroupEntry { binding, resource });
+        self
+    }
+
+    /// 레이아웃과 바인드 그룹을 만든다.
+    ///
+    /// 같은 바인딩 번호가 두 번 쓰이거나 레이아웃과 바인드 그룹의 항목이 어긋나면
+    /// wgpu 검증 에러 대신 여기서 panic 한다.
+    pub fn build(
+        self,
+        device: &wgpu::Device,
+        label: &str,
+    ) -> (wgpu::BindGroupLayout, wgpu::BindGroup) {
+        let bindings: Vec<u32> = self.entries.iter().map(|entry| entry.binding).collect();
+        if let Err(message) = check_bindings(&self.layout_entries, &bindings) {
+            panic!("{}: {}", label, message);
+        }
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(label),
+            entries: &self.layout_entries,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout: &layout,
+            entries: &self.entries,
+        });
+
+        (layout, bind_group)
+    }
+}
+
+// 레이아웃 항목의 바인딩 번호가 겹치지 않고, 바인드 그룹 항목의 번호 `bindings` 와 순서대로 같은지 확인한다
+fn check_bindings(
+    layout_entries: &[wgpu::BindGroupLayoutEntry],
+    bindings: &[u32],
+) -> Result<(), String> {
+    if layout_entries.len() != bindings.len() {
+        return Err(format!(
+            "layout has {} entries but bind group has {}",
+            layout_entries.len(),
+            bindings.len()
+        ));
+    }
+
+    for (i, (entry, &binding)) in layout_entries.iter().zip(bindings).enumerate() {
+        if let Some(duplicate) = layout_entries[..i]
+            .iter()
+            .find(|other| other.binding == entry.binding)
+        {
+            return Err(format!(
+                "binding {} is used more than once ({:?} and {:?})",
+                entry.binding, duplicate.ty, entry.ty
+            ));
+        }
+        if entry.binding != binding {
+            return Err(format!(
+                "entry {} is binding {} in the layout but {} in the bind group",
+                i, entry.binding, binding
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout_entry(binding: u32, ty: wgpu::BindingType) -> wgpu::BindGroupLayoutEntry {
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty,
+            count: None,
+        }
+    }
+
+    fn texture_and_sampler(sampler_binding: u32) -> [wgpu::BindGroupLayoutEntry; 2] {
+        [
+            layout_entry(
+                0,
+                wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+            ),
+            layout_entry(
+                sampler_binding,
+                wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            ),
+        ]
+    }
+
+    #[test]
+    fn accepts_unique_matching_bindings() {
+        assert_eq!(check_bindings(&texture_and_sampler(1), &[0, 1]), Ok(()));
+        assert_eq!(check_bindings(&[], &[]), Ok(()));
+    }
+
+    #[test]
+    fn rejects_duplicate_binding() {
+        let error = check_bindings(&texture_and_sampler(0), &[0, 0]).unwrap_err();
+        assert!(
+            error.contains("binding 0 is used more than once"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn rejects_layout_and_bind_group_mismatch() {
+        let error = check_bindings(&texture_and_sampler(1), &[0]).unwrap_err();
+        assert!(
+            error.contains("2 entries but bind group has 1"),
+            "{}",
+            error
+        );
+
+        let error = check_bindings(&texture_and_sampler(1), &[0, 2]).unwrap_err();
+        assert!(
+            error.contains("binding 1 in the layout but 2 in the bind group"),
+            "{}",
+            error
+        );
+    }
+}
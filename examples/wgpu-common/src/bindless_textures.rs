@@ -0,0 +1,239 @@
+use std::num::NonZeroU32;
+
+use wgpu::util::DeviceExt;
+
+use crate::{BindlessTextureError, SamplerBuilder};
+
+/// `BindlessTextureArray` 에 등록할 수 있는 텍스처 수
+pub const MAX_TEXTURES: u32 = 256;
+
+/// 텍스처마다 바인드 그룹을 만드는 대신 `MAX_TEXTURES` 개의 텍스처를 한 바인드 그룹의 배열로 묶는다.
+///
+/// 0번 바인딩은 `binding_array<texture_2d<f32>, 256>`, 1번 바인딩은 모든 텍스처가 함께 쓰는
+/// 샘플러다. 바인드 그룹을 한 번만 설정하고 그리기 명령마다 푸시 상수로 슬롯 번호만 바꾼다.
+///
+/// ```wgsl
+/// struct DrawData {
+///     tex_index: u32,
+/// };
+/// var<push_constant> push_constant: DrawData;
+///
+/// @group(0) @binding(0)
+/// var textures: binding_array<texture_2d<f32>, 256>;
+/// @group(0) @binding(1)
+/// var texture_sampler: sampler;
+///
+/// // ...
+/// textureSample(textures[push_constant.tex_index], texture_sampler, in.uv)
+/// ```
+///
+/// 인덱스가 드로우 안에서 같으므로 `Features::TEXTURE_BINDING_ARRAY` 만 있으면 된다.
+/// WebGPU, WebGL2 에는 없는 기능이라 디바이스를 만들 때 요청하고, 한도도
+/// `Limits::max_binding_array_elements_per_shader_stage` 를 `MAX_TEXTURES` 이상으로 올려야 한다.
+pub struct BindlessTextureArray {
+    device: wgpu::Device,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    // 등록된 순서대로. 인덱스가 곧 슬롯 번호다
+    views: Vec<wgpu::TextureView>,
+    // 비어 있는 슬롯에 대신 바인딩한다
+    placeholder: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+impl BindlessTextureArray {
+    /// 디바이스가 텍스처 바인딩 배열을 지원하지 않으면 `BindlessTextureError` 를 반환한다.
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Result<Self, BindlessTextureError> {
+        Self::check_supported(device)?;
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Bindless Texture Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: NonZeroU32::new(MAX_TEXTURES),
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let sampler = SamplerBuilder::linear()
+            .label("Bindless Texture Sampler")
+            .address_mode(
+                wgpu::AddressMode::Repeat,
+                wgpu::AddressMode::Repeat,
+                wgpu::AddressMode::Repeat,
+            )
+            .build(device);
+        let placeholder = device
+            .create_texture_with_data(
+                queue,
+                &wgpu::TextureDescriptor {
+                    label: Some("Bindless Placeholder Texture"),
+                    size: wgpu::Extent3d {
+                        width: 1,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                },
+                wgpu::util::TextureDataOrder::LayerMajor,
+                &[255, 255, 255, 255],
+            )
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group = create_bind_group(device, &layout, &sampler, &[], &placeholder);
+        Ok(Self {
+            device: device.clone(),
+            layout,
+            sampler,
+            views: Vec::new(),
+            placeholder,
+            bind_group,
+        })
+    }
+
+    /// 디바이스에 `Features::TEXTURE_BINDING_ARRAY` 가 켜져 있고 `MAX_TEXTURES` 가 한도 안에 있는지 확인한다.
+    pub fn check_supported(device: &wgpu::Device) -> Result<(), BindlessTextureError> {
+        check_bindless_textures(device.features(), &device.limits(), MAX_TEXTURES)
+    }
+
+    /// 텍스처를 다음 빈 슬롯에 넣고 슬롯 번호를 반환한다. 셰이더에는 이 번호를 넘긴다.
+    ///
+    /// # Panics
+    ///
+    /// 이미 `MAX_TEXTURES` 개가 등록되어 있으면 panic 한다.
+    pub fn register(&mut self, view: &wgpu::TextureView) -> u32 {
+        assert!(
+            self.views.len() < MAX_TEXTURES as usize,
+            "bindless texture array is full ({} textures)",
+            MAX_TEXTURES
+        );
+        self.views.push(view.clone());
+        // 바인드 그룹은 만든 뒤에 바꿀 수 없으므로 새로 만든다
+        self.bind_group = create_bind_group(
+            &self.device,
+            &self.layout,
+            &self.sampler,
+            &self.views,
+            &self.placeholder,
+        );
+        self.views.len() as u32 - 1
+    }
+
+    /// 등록된 텍스처 수
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    pub fn bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.layout
+    }
+
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+}
+
+fn create_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    views: &[wgpu::TextureView],
+    placeholder: &wgpu::TextureView,
+) -> wgpu::BindGroup {
+    // PARTIALLY_BOUND_BINDING_ARRAY 없이는 배열을 모두 채워야 한다
+    let slots: Vec<&wgpu::TextureView> = views
+        .iter()
+        .chain(std::iter::repeat(placeholder))
+        .take(MAX_TEXTURES as usize)
+        .collect();
+
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Bindless Texture Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureViewArray(&slots),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
+fn check_bindless_textures(
+    features: wgpu::Features,
+    limits: &wgpu::Limits,
+    count: u32,
+) -> Result<(), BindlessTextureError> {
+    if !features.contains(wgpu::Features::TEXTURE_BINDING_ARRAY) {
+        return Err(BindlessTextureError::FeatureNotEnabled);
+    }
+    if count > limits.max_binding_array_elements_per_shader_stage {
+        return Err(BindlessTextureError::ExceedsLimit {
+            count,
+            max: limits.max_binding_array_elements_per_shader_stage,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_missing_feature_and_limit() {
+        let limits = wgpu::Limits {
+            max_binding_array_elements_per_shader_stage: 128,
+            ..wgpu::Limits::default()
+        };
+        assert!(matches!(
+            check_bindless_textures(wgpu::Features::empty(), &limits, 64),
+            Err(BindlessTextureError::FeatureNotEnabled)
+        ));
+        assert!(matches!(
+            check_bindless_textures(wgpu::Features::TEXTURE_BINDING_ARRAY, &limits, 256),
+            Err(BindlessTextureError::ExceedsLimit {
+                count: 256,
+                max: 128
+            })
+        ));
+        assert!(
+            check_bindless_textures(wgpu::Features::TEXTURE_BINDING_ARRAY, &limits, 128).is_ok()
+        );
+
+        // 기본 한도는 0 이라서 디바이스를 만들 때 올려야 한다
+        assert!(matches!(
+            check_bindless_textures(
+                wgpu::Features::TEXTURE_BINDING_ARRAY,
+                &wgpu::Limits::default(),
+                MAX_TEXTURES
+            ),
+            Err(BindlessTextureError::ExceedsLimit { max: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+/// 프래그먼트 색을 렌더 타깃의 기존 색과 섞는 방법
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// 섞지 않고 덮어쓴다
+    #[default]
+    Opaque,
+    /// `src * a + dst * (1 - a)`. 투명한 가장자리가 있는 스프라이트에 쓴다
+    AlphaBlend,
+    /// `src * a + dst`. 겹칠수록 밝아지므로 불꽃이나 빛 효과에 쓴다
+    Additive,
+    /// `src + dst * (1 - a)`. 셰이더가 색에 알파를 미리 곱해서 출력할 때 쓴다
+    Premultiplied,
+    /// `src * dst`. 기존 색을 어둡게 만든다. 투명하게 둘 부분은 흰색이어야 한다
+    Multiply,
+}
+
+impl BlendMode {
+    /// 모드별로 파이프라인을 미리 만들어 둘 때 사용하는 순서. `index` 와 같다.
+    pub const ALL: [BlendMode; 5] = [
+        BlendMode::Opaque,
+        BlendMode::AlphaBlend,
+        BlendMode::Additive,
+        BlendMode::Premultiplied,
+        BlendMode::Multiply,
+    ];
+
+    /// `"opaque"`, `"alpha"`, `"additive"`, `"premultiplied"`, `"multiply"`
+    pub fn name(self) -> &'static str {
+        match self {
+            BlendMode::Opaque => "opaque",
+            BlendMode::AlphaBlend => "alpha",
+            BlendMode::Additive => "additive",
+            BlendMode::Premultiplied => "premultiplied",
+            BlendMode::Multiply => "multiply",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    /// `ALL` 안에서의 위치
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// `wgpu::ColorTargetState::blend` 에 넣을 값. `Opaque` 는 블렌딩을 끈다.
+    pub fn to_wgpu(self) -> Option<wgpu::BlendState> {
+        match self {
+            BlendMode::Opaque => None,
+            BlendMode::AlphaBlend => Some(wgpu::BlendState::ALPHA_BLENDING),
+            BlendMode::Additive => Some(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            }),
+            BlendMode::Premultiplied => Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+            BlendMode::Multiply => Some(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Dst,
+                    dst_factor: wgpu::BlendFactor::Zero,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                // 타깃의 알파는 그대로 둔다
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for (index, mode) in BlendMode::ALL.into_iter().enumerate() {
+            assert_eq!(BlendMode::from_name(mode.name()), Some(mode));
+            assert_eq!(mode.index(), index);
+        }
+        assert_eq!(BlendMode::from_name("screen"), None);
+    }
+
+    #[test]
+    fn only_opaque_disables_blending() {
+        for mode in BlendMode::ALL {
+            assert_eq!(mode.to_wgpu().is_none(), mode == BlendMode::Opaque);
+        }
+    }
+}
@@ -0,0 +1,493 @@
+use wgpu::util::DeviceExt;
+
+use crate::{ComputePass, GpuContext};
+
+/// 블러 커널 크기 범위. 짝수는 다음 홀수로 올린다.
+pub const MIN_KERNEL_SIZE: u32 = 3;
+pub const MAX_KERNEL_SIZE: u32 = 21;
+const DEFAULT_KERNEL_SIZE: u32 = 9;
+// bloom_blur.wgsl 의 @workgroup_size 와 같아야 한다
+const WORKGROUP_SIZE: u32 = 8;
+
+// bloom_blur.wgsl 의 BlurParams 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct BlurParams {
+    direction: [i32; 2],
+    radius: i32,
+    use_threshold: u32,
+    threshold: f32,
+    _padding: [f32; 3],
+    // 가운데부터 바깥쪽으로 최대 11개의 가중치
+    weights: [[f32; 4]; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct CompositeParams {
+    intensity: f32,
+    _padding: [f32; 3],
+}
+
+impl CompositeParams {
+    fn new(intensity: f32) -> Self {
+        Self {
+            intensity,
+            _padding: [0.0; 3],
+        }
+    }
+}
+
+/// HDR 장면에서 밝은 부분을 뽑아 가로/세로 가우시안 블러를 컴퓨트 셰이더로 돌린 뒤
+/// 원래 장면에 더해서 출력 텍스처에 그리는 블룸 후처리.
+///
+/// 장면은 `hdr_view()` 에 `HDR_FORMAT` 으로 그린 다음 `apply` 를 호출한다.
+/// 컴퓨트 셰이더가 필요하므로 WebGL2 에서는 동작하지 않는다.
+pub struct BloomPass {
+    device: wgpu::Device,
+    hdr_texture: wgpu::Texture,
+    hdr_view: wgpu::TextureView,
+    // 가로 블러 결과, 세로 블러 결과
+    blur_views: [wgpu::TextureView; 2],
+    blur_bind_group_layout: wgpu::BindGroupLayout,
+    blur_pipeline: wgpu::ComputePipeline,
+    horizontal_params: wgpu::Buffer,
+    vertical_params: wgpu::Buffer,
+    vertical_bind_group: wgpu::BindGroup,
+    composite_bind_group_layout: wgpu::BindGroupLayout,
+    composite_pipeline: wgpu::RenderPipeline,
+    composite_params: wgpu::Buffer,
+    threshold: f32,
+    intensity: f32,
+    kernel_size: u32,
+    size: (u32, u32),
+}
+
+impl BloomPass {
+    /// 중간 HDR 텍스처 포맷
+    pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+    /// `format` 은 `apply` 에 넘길 출력 텍스처(보통 서피스)의 포맷이다.
+    pub fn new(
+        gpu: &GpuContext,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        threshold: f32,
+        intensity: f32,
+    ) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let blur_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Bloom Blur Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::StorageTexture {
+                            access: wgpu::StorageTextureAccess::WriteOnly,
+                            format: Self::HDR_FORMAT,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let blur_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Bloom Blur Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("bloom_blur.wgsl").into()),
+        });
+        let blur_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Bloom Blur Pipeline Layout"),
+            bind_group_layouts: &[&blur_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let blur_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Bloom Blur Pipeline"),
+            layout: Some(&blur_pipeline_layout),
+            module: &blur_shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        let composite_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Bloom Composite Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let composite_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Bloom Composite Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("bloom_composite.wgsl").into()),
+        });
+        let composite_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Bloom Composite Pipeline Layout"),
+                bind_group_layouts: &[&composite_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let composite_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Bloom Composite Pipeline"),
+            layout: Some(&composite_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &composite_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &composite_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let horizontal_params = create_params_buffer(device, "Bloom Horizontal Params");
+        let vertical_params = create_params_buffer(device, "Bloom Vertical Params");
+        let composite_params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bloom Composite Params"),
+            contents: bytemuck::bytes_of(&CompositeParams::new(intensity)),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let size = (width.max(1), height.max(1));
+        let (hdr_texture, hdr_view, blur_views) = create_textures(device, size);
+        let vertical_bind_group = create_blur_bind_group(
+            device,
+            &blur_bind_group_layout,
+            &blur_views[0],
+            &blur_views[1],
+            &vertical_params,
+        );
+
+        let bloom = Self {
+            device: device.clone(),
+            hdr_texture,
+            hdr_view,
+            blur_views,
+            blur_bind_group_layout,
+            blur_pipeline,
+            horizontal_params,
+            vertical_params,
+            vertical_bind_group,
+            composite_bind_group_layout,
+            composite_pipeline,
+            composite_params,
+            threshold,
+            intensity,
+            kernel_size: DEFAULT_KERNEL_SIZE,
+            size,
+        };
+        bloom.write_blur_params(queue);
+        bloom
+    }
+
+    /// 장면을 그릴 HDR 텍스처
+    pub fn hdr_texture(&self) -> &wgpu::Texture {
+        &self.hdr_texture
+    }
+
+    pub fn hdr_view(&self) -> &wgpu::TextureView {
+        &self.hdr_view
+    }
+
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    pub fn kernel_size(&self) -> u32 {
+        self.kernel_size
+    }
+
+    /// 이 밝기보다 밝은 부분만 번진다.
+    pub fn set_threshold(&mut self, queue: &wgpu::Queue, threshold: f32) {
+        self.threshold = threshold;
+        self.write_blur_params(queue);
+    }
+
+    /// 블러 결과를 장면에 더할 때 곱하는 값
+    pub fn set_intensity(&mut self, queue: &wgpu::Queue, intensity: f32) {
+        self.intensity = intensity;
+        queue.write_buffer(
+            &self.composite_params,
+            0,
+            bytemuck::bytes_of(&CompositeParams::new(intensity)),
+        );
+    }
+
+    /// 블러 커널의 한 변 크기. `MIN_KERNEL_SIZE..=MAX_KERNEL_SIZE` 범위의 홀수로 맞춘다.
+    pub fn set_kernel_size(&mut self, queue: &wgpu::Queue, kernel_size: u32) {
+        self.kernel_size = (kernel_size | 1).clamp(MIN_KERNEL_SIZE, MAX_KERNEL_SIZE);
+        self.write_blur_params(queue);
+    }
+
+    /// 중간 텍스처들을 새 크기로 다시 만든다.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let size = (width.max(1), height.max(1));
+        if size == self.size {
+            return;
+        }
+
+        let (hdr_texture, hdr_view, blur_views) = create_textures(&self.device, size);
+        self.vertical_bind_group = create_blur_bind_group(
+            &self.device,
+            &self.blur_bind_group_layout,
+            &blur_views[0],
+            &blur_views[1],
+            &self.vertical_params,
+        );
+        self.hdr_texture = hdr_texture;
+        self.hdr_view = hdr_view;
+        self.blur_views = blur_views;
+        self.size = size;
+    }
+
+    /// `hdr_view` 의 밝은 부분을 블러해서 더한 결과를 `output_view` 에 그린다.
+    /// `hdr_view` 는 `resize` 에 넘긴 크기와 같아야 한다.
+    pub fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        hdr_view: &wgpu::TextureView,
+        output_view: &wgpu::TextureView,
+    ) {
+        let horizontal_bind_group = create_blur_bind_group(
+            &self.device,
+            &self.blur_bind_group_layout,
+            hdr_view,
+            &self.blur_views[0],
+            &self.horizontal_params,
+        );
+
+        let dispatch = (
+            ComputePass::workgroup_count(self.size.0, WORKGROUP_SIZE),
+            ComputePass::workgroup_count(self.size.1, WORKGROUP_SIZE),
+            1,
+        );
+        ComputePass::run(
+            encoder,
+            &self.blur_pipeline,
+            &horizontal_bind_group,
+            dispatch,
+        );
+        ComputePass::run(
+            encoder,
+            &self.blur_pipeline,
+            &self.vertical_bind_group,
+            dispatch,
+        );
+
+        let composite_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Bloom Composite Bind Group"),
+            layout: &self.composite_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(hdr_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&self.blur_views[1]),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.composite_params.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Bloom Composite Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.composite_pipeline);
+        render_pass.set_bind_group(0, &composite_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    fn write_blur_params(&self, queue: &wgpu::Queue) {
+        let radius = (self.kernel_size / 2) as i32;
+        let weights = gaussian_weights(radius);
+
+        let horizontal = BlurParams {
+            direction: [1, 0],
+            radius,
+            use_threshold: 1,
+            threshold: self.threshold,
+            _padding: [0.0; 3],
+            weights,
+        };
+        let vertical = BlurParams {
+            direction: [0, 1],
+            use_threshold: 0,
+            ..horizontal
+        };
+
+        queue.write_buffer(&self.horizontal_params, 0, bytemuck::bytes_of(&horizontal));
+        queue.write_buffer(&self.vertical_params, 0, bytemuck::bytes_of(&vertical));
+    }
+}
+
+// 가운데 가중치부터 바깥쪽으로, 양쪽을 합친 합이 1 이 되도록 정규화한다
+fn gaussian_weights(radius: i32) -> [[f32; 4]; 3] {
+    let sigma = (radius as f32 / 2.0).max(1.0);
+    let mut weights = [0.0f32; 12];
+    for (i, weight) in weights.iter_mut().enumerate().take(radius as usize + 1) {
+        let x = i as f32;
+        *weight = (-(x * x) / (2.0 * sigma * sigma)).exp();
+    }
+
+    let total = weights[0] + 2.0 * weights[1..].iter().sum::<f32>();
+    let mut packed = [[0.0; 4]; 3];
+    for (i, weight) in weights.iter().enumerate() {
+        packed[i / 4][i % 4] = weight / total;
+    }
+    packed
+}
+
+fn create_params_buffer(device: &wgpu::Device, label: &str) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size: std::mem::size_of::<BlurParams>() as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+fn create_textures(
+    device: &wgpu::Device,
+    size: (u32, u32),
+) -> (wgpu::Texture, wgpu::TextureView, [wgpu::TextureView; 2]) {
+    let create = |label: &str, usage: wgpu::TextureUsages| {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: BloomPass::HDR_FORMAT,
+            usage,
+            view_formats: &[],
+        })
+    };
+
+    let hdr_texture = create(
+        "Bloom HDR Texture",
+        wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+    );
+    let hdr_view = hdr_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let blur_usage = wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING;
+    let blur_views = ["Bloom Blur Texture H", "Bloom Blur Texture V"].map(|label| {
+        create(label, blur_usage).create_view(&wgpu::TextureViewDescriptor::default())
+    });
+
+    (hdr_texture, hdr_view, blur_views)
+}
+
+fn create_blur_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    input: &wgpu::TextureView,
+    output: &wgpu::TextureView,
+    params: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Bloom Blur Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(input),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(output),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: params.as_entire_binding(),
+            },
+        ],
+    })
+}
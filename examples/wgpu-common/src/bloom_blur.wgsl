@@ -0,0 +1,46 @@
+struct BlurParams {
+    direction: vec2<i32>,
+    radius: i32,
+    use_threshold: u32,
+    threshold: f32,
+    weights: array<vec4<f32>, 3>,
+};
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var output_texture: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(2)
+var<uniform> params: BlurParams;
+
+fn weight(i: u32) -> f32 {
+    return params.weights[i / 4u][i % 4u];
+}
+
+// 첫 번째(가로) 패스에서는 threshold 보다 밝은 부분만 남긴다
+fn load_color(coord: vec2<i32>) -> vec3<f32> {
+    let color = textureLoad(input_texture, coord, 0).rgb;
+    if (params.use_threshold == 0u) {
+        return color;
+    }
+
+    let brightness = max(color.r, max(color.g, color.b));
+    return color * max(brightness - params.threshold, 0.0) / max(brightness, 0.0001);
+}
+
+@compute @workgroup_size(8, 8)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = vec2<i32>(textureDimensions(output_texture));
+    let coord = vec2<i32>(id.xy);
+    if (coord.x >= size.x || coord.y >= size.y) {
+        return;
+    }
+
+    var sum = vec3<f32>(0.0);
+    for (var i = -params.radius; i <= params.radius; i++) {
+        let sample_coord = clamp(coord + params.direction * i, vec2<i32>(0), size - 1);
+        sum += load_color(sample_coord) * weight(u32(abs(i)));
+    }
+
+    textureStore(output_texture, coord, vec4<f32>(sum, 1.0));
+}
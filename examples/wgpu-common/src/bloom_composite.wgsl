@@ -0,0 +1,27 @@
+struct CompositeParams {
+    intensity: f32,
+};
+
+@group(0) @binding(0)
+var scene_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var bloom_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var<uniform> params: CompositeParams;
+
+// Vertex shader
+// 버텍스 버퍼 없이 화면을 덮는 삼각형 하나를 만든다
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// Fragment shader
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let coord = vec2<i32>(position.xy);
+    let scene = textureLoad(scene_texture, coord, 0).rgb;
+    let bloom = textureLoad(bloom_texture, coord, 0).rgb;
+    return vec4<f32>(scene + bloom * params.intensity, 1.0);
+}
@@ -0,0 +1,216 @@
+use glam::Vec3;
+use wgpu::util::DeviceExt;
+
+use crate::pbr::{hammersley, importance_sample_ggx};
+use crate::{ComputePass, Texture};
+
+// brdf_lut.wgsl 의 workgroup_size 와 같아야 한다
+const WORKGROUP_SIZE: u32 = 8;
+const CPU_SAMPLE_COUNT: u32 = 128;
+
+/// IBL 스페큘러 항에 쓰는 BRDF LUT 를 만든다.
+///
+/// 스페큘러 적분을 `f0 * scale + bias` 로 나눠서, x 축이 n·v, y 축이 거칠기인 텍스처의
+/// R, G 채널에 (scale, bias) 를 담는다. 빛이나 재질과 무관하므로 시작할 때 한 번만 만든다.
+pub struct BrdfLutGenerator;
+
+impl BrdfLutGenerator {
+    /// `generate` 로 만든 LUT 의 가로, 세로 크기
+    pub const SIZE: u32 = 512;
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg16Float;
+
+    /// 컴퓨트 셰이더에서 GGX 중요도 샘플링으로 적분해서 `SIZE` x `SIZE` LUT 를 만든다.
+    /// 컴퓨트 셰이더가 없는 WebGL 에서는 `generate_cpu` 를 쓴다.
+    pub fn generate(device: &wgpu::Device, queue: &wgpu::Queue) -> Texture {
+        // Rg16Float 는 스토리지 텍스처가 될 수 없어서 버퍼에 묶어 쓴 뒤 복사한다
+        let lut_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("BRDF LUT Buffer"),
+            size: (Self::SIZE * Self::SIZE * 4) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("BRDF LUT Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("BRDF LUT Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: lut_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("BRDF LUT Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("brdf_lut.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("BRDF LUT Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("BRDF LUT Pipeline"),
+            layout: Some(&layout),
+            module: &shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        let texture = device.create_texture(&Self::descriptor(Self::SIZE));
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("BRDF LUT Encoder"),
+        });
+        let groups = ComputePass::workgroup_count(Self::SIZE, WORKGROUP_SIZE);
+        ComputePass::run(&mut encoder, &pipeline, &bind_group, (groups, groups, 1));
+        encoder.copy_buffer_to_texture(
+            wgpu::TexelCopyBufferInfo {
+                buffer: &lut_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(Self::SIZE * 4),
+                    rows_per_image: Some(Self::SIZE),
+                },
+            },
+            texture.as_image_copy(),
+            wgpu::Extent3d {
+                width: Self::SIZE,
+                height: Self::SIZE,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        Texture::from_texture(device, texture, "BRDF LUT")
+    }
+
+    /// 같은 LUT 를 CPU 에서 `size` x `size` 로 만든다. 적분이 느리므로 32 정도로 작게 쓴다.
+    pub fn generate_cpu(device: &wgpu::Device, queue: &wgpu::Queue, size: u32) -> Texture {
+        let data: Vec<u8> = (0..size * size)
+            .flat_map(|i| {
+                let n_dot_v = ((i % size) as f32 + 0.5) / size as f32;
+                let roughness = ((i / size) as f32 + 0.5) / size as f32;
+                let (scale, bias) = integrate_brdf(n_dot_v, roughness);
+                let [s0, s1] = f16_bits(scale).to_le_bytes();
+                let [b0, b1] = f16_bits(bias).to_le_bytes();
+                [s0, s1, b0, b1]
+            })
+            .collect();
+        let texture = device.create_texture_with_data(
+            queue,
+            &Self::descriptor(size),
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &data,
+        );
+
+        Texture::from_texture(device, texture, "BRDF LUT")
+    }
+
+    fn descriptor(size: u32) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("BRDF LUT"),
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }
+    }
+}
+
+// 스페큘러 적분을 f0 * scale + bias 로 나눈 (scale, bias). brdf_lut.wgsl 의 cs_main 과 같다
+fn integrate_brdf(n_dot_v: f32, roughness: f32) -> (f32, f32) {
+    let view = Vec3::new((1.0 - n_dot_v * n_dot_v).sqrt(), 0.0, n_dot_v);
+    // IBL 에서는 k = a / 2 를 쓴다
+    let k = roughness * roughness / 2.0;
+    let geometry = |n_dot_x: f32| n_dot_x / (n_dot_x * (1.0 - k) + k);
+
+    let (mut scale, mut bias) = (0.0, 0.0);
+    for sample in 0..CPU_SAMPLE_COUNT {
+        let half = importance_sample_ggx(hammersley(sample, CPU_SAMPLE_COUNT), Vec3::Z, roughness);
+        let light = 2.0 * view.dot(half) * half - view;
+        let n_dot_l = light.z.max(0.0);
+        let n_dot_h = half.z.max(0.0);
+        let v_dot_h = view.dot(half).max(0.0);
+
+        if n_dot_l > 0.0 {
+            let visibility = geometry(n_dot_v) * geometry(n_dot_l) * v_dot_h / (n_dot_h * n_dot_v);
+            let fresnel = (1.0 - v_dot_h).powi(5);
+            scale += (1.0 - fresnel) * visibility;
+            bias += fresnel * visibility;
+        }
+    }
+    (
+        scale / CPU_SAMPLE_COUNT as f32,
+        bias / CPU_SAMPLE_COUNT as f32,
+    )
+}
+
+// 0 이상의 유한한 값을 half float 비트로 바꾼다. 남는 가수 비트는 버린다
+fn f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent >= 31 {
+        0x7c00
+    } else if exponent <= 0 {
+        // 서브노멀. 숨은 1 을 살려서 가수를 오른쪽으로 민다
+        if exponent < -10 {
+            0
+        } else {
+            ((mantissa | 0x80_0000) >> (14 - exponent)) as u16
+        }
+    } else {
+        ((exponent as u32) << 10 | mantissa >> 13) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brdf_lut_bounds() {
+        // 매끈한 표면을 정면에서 보면 빛이 모두 되돌아온다
+        let (scale, bias) = integrate_brdf(1.0, 0.0);
+        assert!((scale - 1.0).abs() < 1e-3 && bias.abs() < 1e-3);
+
+        // 거칠거나 비스듬할수록 에너지가 줄어들지만 1 을 넘지 않는다
+        for (n_dot_v, roughness) in [(0.1, 0.2), (0.5, 0.5), (0.9, 1.0)] {
+            let (scale, bias) = integrate_brdf(n_dot_v, roughness);
+            assert!(scale >= 0.0 && bias >= 0.0);
+            assert!(scale + bias <= 1.0 + 1e-3);
+        }
+        assert!(integrate_brdf(0.1, 0.2).1 > integrate_brdf(0.9, 0.2).1);
+    }
+
+    #[test]
+    fn half_float_bits() {
+        assert_eq!(f16_bits(0.0), 0);
+        assert_eq!(f16_bits(1.0), 0x3c00);
+        assert_eq!(f16_bits(0.5), 0x3800);
+        assert_eq!(f16_bits(0.333_333_34), 0x3555);
+        // 가장 작은 서브노멀은 2^-24 이다
+        assert_eq!(f16_bits(2.0f32.powi(-24)), 1);
+        assert_eq!(f16_bits(1e9), 0x7c00);
+    }
+}
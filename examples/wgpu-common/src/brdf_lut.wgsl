@@ -0,0 +1,59 @@
+const PI: f32 = 3.14159265;
+// brdf_lut.rs 의 BrdfLutGenerator::SIZE 와 같아야 한다
+const LUT_SIZE: u32 = 512u;
+const SAMPLE_COUNT: u32 = 1024u;
+
+// 텍셀마다 (scale, bias) 를 pack2x16float 로 묶은 값. 그대로 Rg16Float 텍스처에 복사한다
+@group(0) @binding(0)
+var<storage, read_write> lut: array<u32>;
+
+// 0 ~ 1 범위의 저불일치 수열
+fn hammersley(i: u32, count: u32) -> vec2<f32> {
+    return vec2<f32>(f32(i) / f32(count), f32(reverseBits(i)) * 2.3283064e-10);
+}
+
+// 법선이 +z 인 공간에서 GGX 분포를 따르는 하프 벡터
+fn importance_sample_ggx(xi: vec2<f32>, roughness: f32) -> vec3<f32> {
+    let a = roughness * roughness;
+    let phi = 2.0 * PI * xi.x;
+    let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (a * a - 1.0) * xi.y));
+    let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+    return vec3<f32>(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+}
+
+fn geometry_schlick_ggx(n_dot_x: f32, k: f32) -> f32 {
+    return n_dot_x / (n_dot_x * (1.0 - k) + k);
+}
+
+// x 축이 n·v, y 축이 거칠기다
+@compute @workgroup_size(8, 8)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= LUT_SIZE || id.y >= LUT_SIZE) {
+        return;
+    }
+
+    let n_dot_v = (f32(id.x) + 0.5) / f32(LUT_SIZE);
+    let roughness = (f32(id.y) + 0.5) / f32(LUT_SIZE);
+    let v = vec3<f32>(sqrt(1.0 - n_dot_v * n_dot_v), 0.0, n_dot_v);
+    // IBL 에서는 k = a / 2 를 쓴다
+    let k = roughness * roughness / 2.0;
+
+    var scale = 0.0;
+    var bias = 0.0;
+    for (var i = 0u; i < SAMPLE_COUNT; i++) {
+        let h = importance_sample_ggx(hammersley(i, SAMPLE_COUNT), roughness);
+        let v_dot_h = max(dot(v, h), 0.0);
+        let l = 2.0 * dot(v, h) * h - v;
+        let n_dot_l = l.z;
+
+        if (n_dot_l > 0.0) {
+            let visibility = geometry_schlick_ggx(n_dot_v, k) * geometry_schlick_ggx(n_dot_l, k)
+                * v_dot_h / (h.z * n_dot_v);
+            let fresnel = pow(1.0 - v_dot_h, 5.0);
+            scale += (1.0 - fresnel) * visibility;
+            bias += fresnel * visibility;
+        }
+    }
+
+    lut[id.y * LUT_SIZE + id.x] = pack2x16float(vec2<f32>(scale, bias) / f32(SAMPLE_COUNT));
+}
@@ -0,0 +1,292 @@
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::Aabb;
+
+// 리프 하나에 넣는 최대 오브젝트 수
+const MAX_LEAF_SIZE: usize = 2;
+
+/// `BvhTree` 에 넣은 오브젝트를 구분하는 ID
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EntityId(pub u32);
+
+/// `origin` 에서 `direction` 으로 뻗는 반직선
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self { origin, direction }
+    }
+
+    /// 화면 좌표(왼쪽 위가 원점)를 뷰-투영 행렬의 역행렬로 되돌려서
+    /// 가까운 평면에서 먼 평면으로 향하는 월드 공간 광선을 만든다.
+    pub fn from_screen(position: Vec2, screen_size: Vec2, view_proj: Mat4) -> Self {
+        let ndc = Vec2::new(
+            position.x / screen_size.x * 2.0 - 1.0,
+            1.0 - position.y / screen_size.y * 2.0,
+        );
+        let inverse = view_proj.inverse();
+        let near = inverse.project_point3(ndc.extend(0.0));
+        let far = inverse.project_point3(ndc.extend(1.0));
+
+        Self::new(near, (far - near).normalize())
+    }
+
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// 박스에 들어가는 지점까지의 거리 `t`. 시작점이 박스 안이면 0 이다.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        // 슬랩 방식: 축마다 두 평면 사이에 있는 구간의 교집합을 구한다
+        let inverse = self.direction.recip();
+        let t0 = (aabb.min - self.origin) * inverse;
+        let t1 = (aabb.max - self.origin) * inverse;
+        let t_enter = t0.min(t1).max_element().max(0.0);
+        let t_exit = t0.max(t1).min_element();
+
+        (t_enter <= t_exit).then_some(t_enter)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct BvhNode {
+    bounds: Aabb,
+    // 리프면 entries[first..first + count], 내부 노드면 count 가 0 이고
+    // 왼쪽 자식은 바로 다음 노드, 오른쪽 자식은 nodes[first] 이다
+    first: usize,
+    count: usize,
+}
+
+/// 광선 피킹용 바운딩 볼륨 계층.
+///
+/// 노드는 깊이 우선 순서로 `Vec` 하나에 담긴다.
+/// 각 노드는 표면적 휴리스틱(SAH)으로 비용이 가장 작은 축과 위치에서 둘로 나눈다.
+#[derive(Clone, Debug, Default)]
+pub struct BvhTree {
+    nodes: Vec<BvhNode>,
+    entries: Vec<(EntityId, Aabb)>,
+}
+
+impl BvhTree {
+    pub fn new(entries: &[(EntityId, Aabb)]) -> Self {
+        let mut tree = Self {
+            nodes: Vec::with_capacity(entries.len() * 2),
+            entries: entries.to_vec(),
+        };
+        if !entries.is_empty() {
+            tree.build_node(0, entries.len());
+        }
+        tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 광선이 처음 부딪히는 박스의 ID
+    pub fn cast_ray(&self, ray: Ray) -> Option<EntityId> {
+        let mut nearest: Option<(EntityId, f32)> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            // 이미 찾은 것보다 먼 노드는 볼 필요가 없다
+            match ray.intersect_aabb(&node.bounds) {
+                Some(t) if nearest.is_none_or(|(_, nearest_t)| t < nearest_t) => {}
+                _ => continue,
+            }
+
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(index + 1);
+                continue;
+            }
+
+            for (id, aabb) in &self.entries[node.first..node.first + node.count] {
+                if let Some(t) = ray.intersect_aabb(aabb)
+                    && nearest.is_none_or(|(_, nearest_t)| t < nearest_t)
+                {
+                    nearest = Some((*id, t));
+                }
+            }
+        }
+
+        nearest.map(|(id, _)| id)
+    }
+
+    // entries[start..end] 를 담는 노드를 만들고 인덱스를 반환한다
+    fn build_node(&mut self, start: usize, end: usize) -> usize {
+        let bounds = union_all(&self.entries[start..end]);
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounds,
+            first: start,
+            count: end - start,
+        });
+
+        if end - start <= MAX_LEAF_SIZE {
+            return index;
+        }
+        let Some((axis, split)) = best_split(&mut self.entries[start..end], &bounds) else {
+            return index;
+        };
+
+        sort_by_centroid(&mut self.entries[start..end], axis);
+        self.build_node(start, start + split);
+        let right = self.build_node(start + split, end);
+        self.nodes[index].first = right;
+        self.nodes[index].count = 0;
+        index
+    }
+}
+
+fn union_all(entries: &[(EntityId, Aabb)]) -> Aabb {
+    entries
+        .iter()
+        .map(|(_, aabb)| *aabb)
+        .reduce(|a, b| a.union(&b))
+        .expect("node has entries")
+}
+
+fn sort_by_centroid(entries: &mut [(EntityId, Aabb)], axis: usize) {
+    entries.sort_by(|(_, a), (_, b)| a.center()[axis].total_cmp(&b.center()[axis]));
+}
+
+// 축마다 중심 순서로 정렬해서 앞쪽 i 개와 나머지로 나누는 모든 경우의
+// 비용 (왼쪽 표면적 * 개수 + 오른쪽 표면적 * 개수) 을 구하고, 나누지 않을 때보다 싼 것을 고른다
+fn best_split(entries: &mut [(EntityId, Aabb)], bounds: &Aabb) -> Option<(usize, usize)> {
+    let count = entries.len();
+    let mut best: Option<(f32, usize, usize)> = None;
+    let mut right_areas = vec![0.0; count];
+
+    for axis in 0..3 {
+        sort_by_centroid(entries, axis);
+
+        let mut right = entries[count - 1].1;
+        for i in (1..count).rev() {
+            right = right.union(&entries[i].1);
+            right_areas[i] = right.surface_area();
+        }
+
+        let mut left = entries[0].1;
+        for split in 1..count {
+            left = left.union(&entries[split - 1].1);
+            let cost =
+                left.surface_area() * split as f32 + right_areas[split] * (count - split) as f32;
+            if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                best = Some((cost, axis, split));
+            }
+        }
+    }
+
+    let leaf_cost = bounds.surface_area() * count as f32;
+    best.filter(|(cost, _, _)| *cost < leaf_cost)
+        .map(|(_, axis, split)| (axis, split))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_box(min: Vec3) -> Aabb {
+        Aabb::new(min, min + Vec3::ONE)
+    }
+
+    // x 축으로 멀리 떨어진 두 무리와 위쪽에 떨어진 박스 하나
+    fn scene() -> Vec<(EntityId, Aabb)> {
+        [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(6.0, 0.0, 0.0),
+            Vec3::new(100.0, 0.0, 0.0),
+            Vec3::new(102.0, 0.0, 0.0),
+            Vec3::new(104.0, 0.0, 0.0),
+            Vec3::new(3.0, 10.0, 0.0),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, min)| (EntityId(i as u32), unit_box(min)))
+        .collect()
+    }
+
+    fn contains(outer: &Aabb, inner: &Aabb) -> bool {
+        outer.min.cmple(inner.min).all() && inner.max.cmple(outer.max).all()
+    }
+
+    #[test]
+    fn every_entity_lands_in_exactly_one_leaf() {
+        let entries = scene();
+        let tree = BvhTree::new(&entries);
+        assert_eq!(tree.len(), entries.len());
+        assert!(tree.nodes[0].count == 0, "root should be split");
+
+        let mut seen = vec![0; entries.len()];
+        for node in tree.nodes.iter().filter(|node| node.count > 0) {
+            assert!(node.count <= MAX_LEAF_SIZE);
+            for (id, _) in &tree.entries[node.first..node.first + node.count] {
+                seen[id.0 as usize] += 1;
+            }
+        }
+        assert!(seen.iter().all(|&count| count == 1), "{:?}", seen);
+    }
+
+    #[test]
+    fn parent_bounds_contain_children() {
+        let tree = BvhTree::new(&scene());
+        for (index, node) in tree.nodes.iter().enumerate() {
+            if node.count == 0 {
+                for child in [index + 1, node.first] {
+                    assert!(contains(&node.bounds, &tree.nodes[child].bounds));
+                }
+            } else {
+                for (_, aabb) in &tree.entries[node.first..node.first + node.count] {
+                    assert!(contains(&node.bounds, aabb));
+                }
+            }
+        }
+
+        // SAH 는 멀리 떨어진 두 무리를 먼저 나눈다
+        let (left, right) = (&tree.nodes[1], &tree.nodes[tree.nodes[0].first]);
+        let far_side = if left.bounds.min.x > 50.0 {
+            left
+        } else {
+            right
+        };
+        assert_eq!(far_side.bounds.min.x, 100.0);
+        assert_eq!(far_side.bounds.max.x, 105.0);
+    }
+
+    #[test]
+    fn cast_ray_returns_nearest_hit() {
+        let tree = BvhTree::new(&scene());
+        let hit = |origin: Vec3, direction: Vec3| tree.cast_ray(Ray::new(origin, direction));
+
+        assert_eq!(hit(Vec3::new(-5.0, 0.5, 0.5), Vec3::X), Some(EntityId(0)));
+        assert_eq!(
+            hit(Vec3::new(200.0, 0.5, 0.5), Vec3::NEG_X),
+            Some(EntityId(6))
+        );
+        // 아래 줄의 틈을 지나서 위쪽 박스에 맞는다
+        assert_eq!(hit(Vec3::new(3.5, -5.0, 0.5), Vec3::Y), Some(EntityId(7)));
+        // 시작점이 박스 안이면 그 박스다
+        assert_eq!(hit(Vec3::new(4.5, 0.5, 0.5), Vec3::X), Some(EntityId(2)));
+        assert_eq!(hit(Vec3::new(50.0, 0.5, 0.5), Vec3::Y), None);
+        assert_eq!(hit(Vec3::new(-5.0, 0.5, 0.5), Vec3::NEG_X), None);
+        assert_eq!(
+            BvhTree::new(&[]).cast_ray(Ray::new(Vec3::ZERO, Vec3::X)),
+            None
+        );
+    }
+}
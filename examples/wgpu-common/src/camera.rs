@@ -0,0 +1,198 @@
+use glam::{Mat4, Vec2, Vec3};
+
+/// `Camera::zoom` 으로 줄이거나 늘릴 수 있는 세로 시야각 범위 (라디안)
+pub const MIN_FOV_Y: f32 = 15.0 * std::f32::consts::PI / 180.0;
+pub const MAX_FOV_Y: f32 = 90.0 * std::f32::consts::PI / 180.0;
+
+/// 원근 투영 카메라
+#[derive(Clone, Copy, Debug)]
+pub struct Camera {
+    pub eye: Vec3,
+    pub target: Vec3,
+    pub up: Vec3,
+    /// 세로 시야각 (라디안)
+    pub fov_y: f32,
+    /// 가로 / 세로
+    pub aspect: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Camera {
+    pub fn new(eye: Vec3, target: Vec3, aspect: f32) -> Self {
+        Self {
+            eye,
+            target,
+            up: Vec3::Y,
+            fov_y: 45f32.to_radians(),
+            aspect,
+            near: 0.1,
+            far: 100.0,
+        }
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at_rh(self.eye, self.target, self.up)
+    }
+
+    /// 시야각을 `scale` 로 나눠서 확대한다. 핀치의 `pinch_scale_delta` 를 그대로 넘기면
+    /// 손가락을 벌릴 때 가까워진다. 결과는 `MIN_FOV_Y` ~ `MAX_FOV_Y` 로 제한된다.
+    pub fn zoom(&mut self, scale: f32) {
+        if scale > 0.0 {
+            self.fov_y = (self.fov_y / scale).clamp(MIN_FOV_Y, MAX_FOV_Y);
+        }
+    }
+
+    /// wgpu 의 클립 공간 깊이 범위 [0, 1] 에 맞는 투영 행렬
+    pub fn projection_matrix(&self) -> Mat4 {
+        Mat4::perspective_rh(self.fov_y, self.aspect, self.near, self.far)
+    }
+
+    pub fn view_projection(&self) -> Mat4 {
+        self.projection_matrix() * self.view_matrix()
+    }
+}
+
+/// 2D 장면용 직교 투영 카메라
+#[derive(Clone, Copy, Debug)]
+pub struct OrthoCamera {
+    pub eye: Vec3,
+    pub target: Vec3,
+    pub up: Vec3,
+    pub left: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub top: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl OrthoCamera {
+    /// 원점을 -Z 방향으로 바라보는 직교 카메라
+    pub fn new(left: f32, right: f32, bottom: f32, top: f32) -> Self {
+        Self {
+            eye: Vec3::new(0.0, 0.0, 1.0),
+            target: Vec3::ZERO,
+            up: Vec3::Y,
+            left,
+            right,
+            bottom,
+            top,
+            near: 0.0,
+            far: 2.0,
+        }
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at_rh(self.eye, self.target, self.up)
+    }
+
+    pub fn projection_matrix(&self) -> Mat4 {
+        Mat4::orthographic_rh(
+            self.left,
+            self.right,
+            self.bottom,
+            self.top,
+            self.near,
+            self.far,
+        )
+    }
+
+    pub fn view_projection(&self) -> Mat4 {
+        self.projection_matrix() * self.view_matrix()
+    }
+}
+
+/// 셰이더의 `CameraUniform { view_proj: mat4x4<f32> }` 와 대응된다.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct CameraUniform {
+    pub view_proj: [[f32; 4]; 4],
+}
+
+impl CameraUniform {
+    pub fn new(view_proj: Mat4) -> Self {
+        Self {
+            view_proj: view_proj.to_cols_array_2d(),
+        }
+    }
+}
+
+impl From<&Camera> for CameraUniform {
+    fn from(camera: &Camera) -> Self {
+        Self::new(camera.view_projection())
+    }
+}
+
+impl From<&OrthoCamera> for CameraUniform {
+    fn from(camera: &OrthoCamera) -> Self {
+        Self::new(camera.view_projection())
+    }
+}
+
+/// 월드 좌표 `world` 를 캔버스 왼쪽 위가 (0, 0) 인 픽셀 좌표 `(x, y)` 와 깊이 `z` 로 바꾼다.
+///
+/// 카메라 뒤에 있거나 깊이가 near ~ far 를 벗어나 `z` 가 [0, 1] 밖이면 `None` 이다.
+pub fn world_to_screen(view_proj: Mat4, world: Vec3, screen_size: Vec2) -> Option<Vec3> {
+    let clip = view_proj * world.extend(1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+
+    let ndc = clip.truncate() / clip.w;
+    if !(0.0..=1.0).contains(&ndc.z) {
+        return None;
+    }
+    // NDC 는 위쪽이 +y 이고 픽셀 좌표는 아래쪽이 +y 이다
+    Some(Vec3::new(
+        (ndc.x * 0.5 + 0.5) * screen_size.x,
+        (0.5 - ndc.y * 0.5) * screen_size.y,
+        ndc.z,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_clamps_fov() {
+        let mut camera = Camera::new(Vec3::Z, Vec3::ZERO, 1.0);
+        let fov = camera.fov_y;
+
+        camera.zoom(1.5);
+        assert!((camera.fov_y - fov / 1.5).abs() < 1e-6);
+
+        camera.zoom(100.0);
+        assert_eq!(camera.fov_y, MIN_FOV_Y);
+        camera.zoom(0.01);
+        assert_eq!(camera.fov_y, MAX_FOV_Y);
+
+        // 0 이하는 무시한다
+        camera.zoom(0.0);
+        assert_eq!(camera.fov_y, MAX_FOV_Y);
+    }
+
+    #[test]
+    fn world_to_screen_maps_to_pixels_and_culls_depth() {
+        let camera = Camera::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, 2.0);
+        let view_proj = camera.view_projection();
+        let screen = Vec2::new(800.0, 400.0);
+
+        let center = world_to_screen(view_proj, Vec3::ZERO, screen).unwrap();
+        assert!((center.truncate() - Vec2::new(400.0, 200.0)).length() < 1e-3);
+        assert!(center.z > 0.0 && center.z < 1.0);
+
+        // 위쪽에 있는 점은 픽셀 y 가 작다
+        let above = world_to_screen(view_proj, Vec3::Y, screen).unwrap();
+        assert!(above.y < center.y);
+        // 더 멀면 깊이가 크다
+        let behind = world_to_screen(view_proj, Vec3::new(0.0, 0.0, -5.0), screen).unwrap();
+        assert!(behind.z > center.z);
+
+        // 카메라 뒤, near 앞, far 뒤는 버린다
+        assert!(world_to_screen(view_proj, Vec3::new(0.0, 0.0, 10.0), screen).is_none());
+        assert!(world_to_screen(view_proj, Vec3::new(0.0, 0.0, 4.95), screen).is_none());
+        assert!(world_to_screen(view_proj, Vec3::new(0.0, 0.0, -200.0), screen).is_none());
+    }
+}
@@ -0,0 +1,180 @@
+use glam::{Mat4, Vec2};
+
+use crate::{CameraUniform, InputState, OrthoCamera};
+
+/// `OrthographicCamera2D::zoom` 의 범위
+pub const MIN_ZOOM_2D: f32 = 0.05;
+pub const MAX_ZOOM_2D: f32 = 50.0;
+
+// 휠 한 칸(deltaY 100 정도)에 약 1.2 배 확대, 축소한다
+const SCROLL_ZOOM_SPEED: f32 = 0.002;
+// InputState::is_mouse_button_down 의 가운데 버튼
+const MIDDLE_BUTTON: i16 = 1;
+
+/// 끌어서 옮기고 커서를 중심으로 확대하는 2D 카메라.
+///
+/// 화면 좌표는 캔버스 왼쪽 위가 (0, 0) 이고 아래로 갈수록 y 가 커지는 렌더링 픽셀이다.
+/// 월드 좌표는 위쪽이 +y 이다. 처음에는 월드 좌표가 캔버스 왼쪽 아래를 원점으로 하는
+/// 픽셀 좌표와 같도록 `position` 을 화면 가운데에 둔다.
+#[derive(Clone, Copy, Debug)]
+pub struct OrthographicCamera2D {
+    /// 화면 가운데에 보이는 월드 좌표
+    pub position: Vec2,
+    /// 월드 1 단위가 화면에서 차지하는 픽셀 수
+    pub zoom: f32,
+    viewport: Vec2,
+    // 가운데 버튼으로 끄는 중일 때 마지막으로 본 커서 위치
+    drag_cursor: Option<Vec2>,
+}
+
+impl OrthographicCamera2D {
+    pub fn new(viewport_size: (u32, u32)) -> Self {
+        let viewport = Vec2::new(viewport_size.0 as f32, viewport_size.1 as f32);
+        Self {
+            position: viewport / 2.0,
+            zoom: 1.0,
+            viewport,
+            drag_cursor: None,
+        }
+    }
+
+    /// 캔버스 크기가 바뀌면 호출한다. 화면 가운데에 보이던 위치는 그대로 둔다.
+    pub fn resize(&mut self, viewport_size: (u32, u32)) {
+        self.viewport = Vec2::new(viewport_size.0 as f32, viewport_size.1 as f32);
+    }
+
+    /// 화면을 `delta` 픽셀만큼 끈다. 커서로 끌면 커서 아래의 장면이 커서를 따라온다.
+    pub fn pan(&mut self, delta: Vec2) {
+        self.position += Vec2::new(-delta.x, delta.y) / self.zoom;
+    }
+
+    /// `factor` 배 확대한다. `screen_point` 아래의 월드 좌표는 확대 전후에 그대로 있다.
+    /// 결과는 `MIN_ZOOM_2D` ~ `MAX_ZOOM_2D` 로 제한되고, 0 이하는 무시한다.
+    pub fn zoom_at(&mut self, factor: f32, screen_point: Vec2) {
+        if factor <= 0.0 {
+            return;
+        }
+
+        let anchor = self.screen_to_world(screen_point);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM_2D, MAX_ZOOM_2D);
+        self.position += anchor - self.screen_to_world(screen_point);
+    }
+
+    pub fn world_to_screen(&self, world: Vec2) -> Vec2 {
+        let offset = (world - self.position) * self.zoom;
+        Vec2::new(offset.x, -offset.y) + self.viewport / 2.0
+    }
+
+    pub fn screen_to_world(&self, screen: Vec2) -> Vec2 {
+        let offset = screen - self.viewport / 2.0;
+        self.position + Vec2::new(offset.x, -offset.y) / self.zoom
+    }
+
+    /// 가운데 버튼으로 끌면 옮기고, 휠을 굴리면 커서를 중심으로 확대, 축소한다.
+    ///
+    /// `InputState` 의 마우스 좌표는 CSS 픽셀이므로 `pixel_ratio` (보통 `devicePixelRatio`)
+    /// 를 곱해서 렌더링 픽셀로 바꾼다. 휠 이동량을 비우는 `end_frame` 은 호출하는 쪽에서 부른다.
+    pub fn handle_input(&mut self, input: &InputState, pixel_ratio: f32) {
+        let cursor = Vec2::from(input.mouse_position()) * pixel_ratio;
+
+        if input.is_mouse_button_down(MIDDLE_BUTTON) {
+            if let Some(previous) = self.drag_cursor {
+                self.pan(cursor - previous);
+            }
+            self.drag_cursor = Some(cursor);
+        } else {
+            self.drag_cursor = None;
+        }
+
+        let scroll = input.scroll_delta().1;
+        if scroll != 0.0 {
+            self.zoom_at((-scroll * SCROLL_ZOOM_SPEED).exp(), cursor);
+        }
+    }
+
+    /// 지금 보이는 월드 영역을 담는 직교 카메라
+    pub fn ortho_camera(&self) -> OrthoCamera {
+        let half = self.viewport / (2.0 * self.zoom);
+        OrthoCamera::new(
+            self.position.x - half.x,
+            self.position.x + half.x,
+            self.position.y - half.y,
+            self.position.y + half.y,
+        )
+    }
+
+    pub fn view_projection(&self) -> Mat4 {
+        self.ortho_camera().view_projection()
+    }
+}
+
+impl From<&OrthographicCamera2D> for CameraUniform {
+    fn from(camera: &OrthographicCamera2D) -> Self {
+        Self::new(camera.view_projection())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).length() < 1e-3, "{a} != {b}");
+    }
+
+    #[test]
+    fn starts_as_pixel_coordinates() {
+        let camera = OrthographicCamera2D::new((800, 600));
+        assert_near(camera.screen_to_world(Vec2::new(0.0, 600.0)), Vec2::ZERO);
+        assert_near(
+            camera.screen_to_world(Vec2::new(800.0, 0.0)),
+            Vec2::new(800.0, 600.0),
+        );
+
+        // 화면 좌표를 월드로 바꾼 뒤 NDC 로 보내면 화면 위치와 맞아야 한다
+        let world = camera.screen_to_world(Vec2::new(200.0, 150.0));
+        let ndc = camera.view_projection().project_point3(world.extend(0.0));
+        assert_near(ndc.truncate(), Vec2::new(-0.5, 0.5));
+    }
+
+    #[test]
+    fn world_and_screen_round_trip() {
+        let mut camera = OrthographicCamera2D::new((640, 480));
+        camera.position = Vec2::new(-30.0, 12.0);
+        camera.zoom = 2.5;
+        let screen = Vec2::new(100.0, 400.0);
+        assert_near(
+            camera.world_to_screen(camera.screen_to_world(screen)),
+            screen,
+        );
+    }
+
+    #[test]
+    fn pan_moves_the_scene_with_the_cursor() {
+        let mut camera = OrthographicCamera2D::new((640, 480));
+        camera.zoom = 2.0;
+        let world = Vec2::new(100.0, 100.0);
+        let before = camera.world_to_screen(world);
+        camera.pan(Vec2::new(30.0, -20.0));
+        assert_near(
+            camera.world_to_screen(world),
+            before + Vec2::new(30.0, -20.0),
+        );
+    }
+
+    #[test]
+    fn zoom_keeps_the_point_under_the_cursor() {
+        let mut camera = OrthographicCamera2D::new((640, 480));
+        let cursor = Vec2::new(500.0, 90.0);
+        let world = camera.screen_to_world(cursor);
+
+        camera.zoom_at(3.0, cursor);
+        assert_eq!(camera.zoom, 3.0);
+        assert_near(camera.screen_to_world(cursor), world);
+
+        camera.zoom_at(1000.0, cursor);
+        assert_eq!(camera.zoom, MAX_ZOOM_2D);
+        camera.zoom_at(0.0, cursor);
+        assert_eq!(camera.zoom, MAX_ZOOM_2D);
+    }
+}
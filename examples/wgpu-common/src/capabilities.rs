@@ -0,0 +1,144 @@
+use crate::limits::limit_values;
+
+/// 어댑터 정보와 지원하는 기능, 한도를 사람이 읽을 수 있는 표로 만든다.
+///
+/// 이슈를 올릴 때 그대로 붙여 넣을 수 있도록 이름, 벤더, 디바이스 ID, 백엔드, 디바이스 종류를
+/// 먼저 쓰고, 그 아래에 모든 `Features` 의 지원 여부와 `Limits` 값을 쓴다.
+pub fn capabilities_report(adapter: &wgpu::Adapter) -> String {
+    format_report(&adapter.get_info(), adapter.features(), &adapter.limits())
+}
+
+/// 디바이스를 만들기 전에도 부를 수 있도록 어댑터만 따로 요청해서 `capabilities_report` 를 돌려준다.
+/// 초기화가 실패했을 때 JS 에서 불러서 콘솔에 남긴다.
+///
+/// ```js
+/// console.error(await wasmModule.get_gpu_report());
+/// ```
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub async fn get_gpu_report() -> String {
+    use wasm_bindgen::JsCast;
+
+    // WebGL 은 캔버스가 있어야 어댑터를 만들 수 있으므로 화면에 붙이지 않은 캔버스를 쓴다
+    let canvas = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("canvas").ok())
+        .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok());
+    let Some(canvas) = canvas else {
+        return "GPU report unavailable: no document to create a canvas".to_string();
+    };
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let surface = match instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas)) {
+        Ok(surface) => surface,
+        Err(e) => return format!("GPU report unavailable: failed to create surface: {}", e),
+    };
+    match instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            compatible_surface: Some(&surface),
+            ..Default::default()
+        })
+        .await
+    {
+        Ok(adapter) => capabilities_report(&adapter),
+        Err(e) => format!("GPU report unavailable: {}", e),
+    }
+}
+
+fn format_report(
+    info: &wgpu::AdapterInfo,
+    features: wgpu::Features,
+    limits: &wgpu::Limits,
+) -> String {
+    let mut report = String::new();
+    report.push_str(&format!("name:        {}\n", info.name));
+    report.push_str(&format!(
+        "vendor:      {:#06x} ({})\n",
+        info.vendor,
+        vendor_name(info.vendor)
+    ));
+    report.push_str(&format!("device id:   {:#06x}\n", info.device));
+    report.push_str(&format!("backend:     {}\n", info.backend));
+    report.push_str(&format!("device type: {:?}\n", info.device_type));
+    if !info.driver.is_empty() {
+        report.push_str(&format!(
+            "driver:      {} {}\n",
+            info.driver, info.driver_info
+        ));
+    }
+
+    let names: Vec<(&str, bool)> = wgpu::Features::all()
+        .iter_names()
+        .map(|(name, flag)| (name, features.contains(flag)))
+        .collect();
+    let supported = names.iter().filter(|(_, supported)| *supported).count();
+    report.push_str(&format!("\nfeatures ({}/{}):\n", supported, names.len()));
+    for (name, supported) in names {
+        report.push_str(&format!(
+            "  [{}] {}\n",
+            if supported { "x" } else { " " },
+            name
+        ));
+    }
+
+    let values = limit_values(limits);
+    let width = values.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    report.push_str("\nlimits:\n");
+    for (name, value) in values {
+        report.push_str(&format!("  {:<width$} {:>12}\n", name, value));
+    }
+
+    report
+}
+
+// PCI 벤더 ID 로 알려진 회사 이름. WebGL 처럼 ID 를 주지 않으면 0 이다
+fn vendor_name(vendor: u32) -> &'static str {
+    match vendor {
+        0x1002 => "AMD",
+        0x106b => "Apple",
+        0x10de => "NVIDIA",
+        0x13b5 => "ARM",
+        0x5143 => "Qualcomm",
+        0x8086 => "Intel",
+        0x1010 => "Imagination",
+        0 => "unknown",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_info_features_and_limits() {
+        let info = wgpu::AdapterInfo {
+            name: "Test GPU".to_string(),
+            vendor: 0x10de,
+            device: 0x2684,
+            device_type: wgpu::DeviceType::DiscreteGpu,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend: wgpu::Backend::Vulkan,
+        };
+        let limits = wgpu::Limits::default();
+        let report = format_report(&info, wgpu::Features::DEPTH_CLIP_CONTROL, &limits);
+
+        assert!(report.contains("name:        Test GPU"));
+        assert!(report.contains("vendor:      0x10de (NVIDIA)"));
+        assert!(report.contains("device id:   0x2684"));
+        assert!(report.contains("backend:     vulkan"));
+        assert!(report.contains("device type: DiscreteGpu"));
+        assert!(!report.contains("driver:"));
+
+        assert!(report.contains("[x] DEPTH_CLIP_CONTROL"));
+        assert!(report.contains("[ ] PUSH_CONSTANTS"));
+        assert!(report.contains("features (1/"));
+
+        let line = report
+            .lines()
+            .find(|line| line.trim_start().starts_with("max_texture_dimension_2d"))
+            .expect("limit row");
+        assert!(line.ends_with(&limits.max_texture_dimension_2d.to_string()));
+    }
+}
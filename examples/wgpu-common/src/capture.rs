@@ -0,0 +1,166 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::error::CaptureError;
+
+/// 텍스처를 CPU 로 읽어 오기 위한 `MAP_READ | COPY_DST` 버퍼.
+///
+/// `copy_from` 으로 복사 명령을 기록하고 제출한 뒤 `read` 를 기다리면
+/// 행 패딩이 제거된 RGBA8 바이트를 얻는다. 서피스 텍스처는 `present` 전에
+/// 복사를 기록해야 하지만, `read` 는 `present` 이후에 기다려도 된다.
+pub struct TextureReadback {
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    bgra: bool,
+}
+
+impl TextureReadback {
+    /// `texture` 전체를 버퍼로 복사하는 명령을 기록한다.
+    /// 텍스처는 `COPY_SRC` 용도로 만들어져 있어야 한다.
+    pub fn copy_from(
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+    ) -> Result<Self, CaptureError> {
+        let format = texture.format();
+        let bgra = match format {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            _ => return Err(CaptureError::UnsupportedFormat(format)),
+        };
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(CaptureError::CopyNotSupported);
+        }
+
+        let size = texture.size();
+        // 버퍼로 복사할 때 한 행의 바이트 수는 256 의 배수여야 한다
+        let padded_bytes_per_row =
+            (size.width * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback Buffer"),
+            size: padded_bytes_per_row as wgpu::BufferAddress * size.height as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            wgpu::Extent3d {
+                depth_or_array_layers: 1,
+                ..size
+            },
+        );
+
+        Ok(Self {
+            buffer,
+            width: size.width,
+            height: size.height,
+            padded_bytes_per_row,
+            bgra,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 복사 명령을 제출한 뒤 호출한다. 매핑이 끝나면 `width * height * 4` 바이트의
+    /// RGBA 데이터를 반환한다.
+    pub async fn read(self, device: &wgpu::Device) -> Result<Vec<u8>, CaptureError> {
+        let slot = Arc::new(Mutex::new(MapSlot::default()));
+        let callback_slot = Arc::clone(&slot);
+        self.buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let mut slot = callback_slot.lock().unwrap();
+                slot.result = Some(result);
+                if let Some(waker) = slot.waker.take() {
+                    waker.wake();
+                }
+            });
+
+        // 브라우저는 이벤트 루프에서 매핑을 끝내지만 네이티브는 직접 기다려야 한다
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = device.poll(wgpu::PollType::Wait);
+        #[cfg(target_arch = "wasm32")]
+        let _ = device;
+
+        MapFuture { slot }.await?;
+
+        let row_bytes = self.width as usize * 4;
+        let mut pixels = Vec::with_capacity(row_bytes * self.height as usize);
+        {
+            let mapped = self.buffer.slice(..).get_mapped_range();
+            for row in mapped.chunks_exact(self.padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..row_bytes]);
+            }
+        }
+        self.buffer.unmap();
+
+        if self.bgra {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Ok(pixels)
+    }
+}
+
+/// RGBA8 바이트를 PNG 파일 데이터로 인코딩한다.
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>, CaptureError> {
+    use image::ImageEncoder;
+
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png)
+        .write_image(rgba, width, height, image::ExtendedColorType::Rgba8)
+        .map_err(CaptureError::EncodeFailed)?;
+    Ok(png)
+}
+
+#[derive(Default)]
+struct MapSlot {
+    result: Option<Result<(), wgpu::BufferAsyncError>>,
+    waker: Option<Waker>,
+}
+
+// map_async 콜백이 호출될 때까지 기다리는 퓨처
+struct MapFuture {
+    slot: Arc<Mutex<MapSlot>>,
+}
+
+impl Future for MapFuture {
+    type Output = Result<(), wgpu::BufferAsyncError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use glam::{Vec2, Vec3};
+
+use crate::{GpuContext, Mesh, Vertex};
+
+/// xz 평면을 `chunk_size` 간격으로 나눈 청크의 번호. 청크 `(x, z)` 는
+/// `x * chunk_size ..= (x + 1) * chunk_size` 범위를 덮는다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChunkCoord {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl ChunkCoord {
+    pub fn new(x: i32, z: i32) -> Self {
+        Self { x, z }
+    }
+
+    /// 월드 좌표 `position` 이 들어 있는 청크. 높이(y)는 무시한다.
+    pub fn containing(position: Vec3, chunk_size: f32) -> Self {
+        Self::new(
+            (position.x / chunk_size).floor() as i32,
+            (position.z / chunk_size).floor() as i32,
+        )
+    }
+
+    /// 청크가 시작하는 모서리의 월드 좌표 (y = 0)
+    pub fn origin(self, chunk_size: f32) -> Vec3 {
+        Vec3::new(self.x as f32 * chunk_size, 0.0, self.z as f32 * chunk_size)
+    }
+
+    /// 청크 가운데의 xz 좌표
+    pub fn center(self, chunk_size: f32) -> Vec2 {
+        (Vec2::new(self.x as f32, self.z as f32) + 0.5) * chunk_size
+    }
+}
+
+/// 로더가 CPU 에서 만든 청크 하나의 지오메트리. `ChunkStreamer::update` 가 GPU 에 올린다.
+pub struct ChunkGeometry<V> {
+    pub vertices: Vec<V>,
+    pub indices: Vec<u32>,
+}
+
+/// `spawn_local` 로 띄운 작업의 결과를 받는 자리.
+///
+/// 웹에는 스레드가 없으므로 작업은 메인 스레드의 이벤트 루프에서 돌고, 끝나면 결과를 여기에 넣는다.
+/// 핸들을 버려도 작업은 멈추지 않고 결과만 버려진다.
+pub struct JoinHandle<T> {
+    result: Rc<RefCell<Option<T>>>,
+}
+
+impl<T: 'static> JoinHandle<T> {
+    pub fn spawn(future: impl Future<Output = T> + 'static) -> Self {
+        let result = Rc::new(RefCell::new(None));
+        let slot = result.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let value = future.await;
+            *slot.borrow_mut() = Some(value);
+        });
+        Self { result }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.result.borrow().is_some()
+    }
+
+    /// 작업이 끝났으면 결과를 꺼낸다. 한 번 꺼내면 다시 `None` 이다.
+    pub fn try_take(&self) -> Option<T> {
+        self.result.borrow_mut().take()
+    }
+}
+
+pub enum ChunkState<V> {
+    /// 보이는 범위에 들어왔지만 아직 로드를 시작하지 않음
+    Unloaded,
+    Loading(JoinHandle<ChunkGeometry<V>>),
+    Loaded(Mesh),
+}
+
+type ChunkLoader<V> = Box<dyn Fn(ChunkCoord) -> Pin<Box<dyn Future<Output = ChunkGeometry<V>>>>>;
+
+/// 카메라 주변의 청크만 불러와서 GPU 에 올리고, 멀어진 청크의 메시는 버린다.
+///
+/// 매 프레임 `update` 를 부르면 가운데가 카메라에서 `view_distance` 안에 있는 청크를
+/// 가까운 순서로 로더에 맡긴다. 동시에 로드하는 청크는 `max_concurrent_loads` 개로 제한한다.
+/// 그릴 때는 `loaded` 로 올라간 메시를 돌면서 그린다.
+pub struct ChunkStreamer<V> {
+    chunk_size: f32,
+    view_distance: f32,
+    max_concurrent_loads: usize,
+    loader: ChunkLoader<V>,
+    chunks: HashMap<ChunkCoord, ChunkState<V>>,
+}
+
+impl<V: Vertex> ChunkStreamer<V> {
+    /// `loader` 는 청크 번호로 지오메트리를 만드는 비동기 함수다. 웹에서는 `fetch_bytes` 로
+    /// 받아서 디코딩하거나, 절차적으로 만들어도 된다.
+    pub fn new<F, Fut>(chunk_size: f32, view_distance: f32, loader: F) -> Self
+    where
+        F: Fn(ChunkCoord) -> Fut + 'static,
+        Fut: Future<Output = ChunkGeometry<V>> + 'static,
+    {
+        Self {
+            chunk_size,
+            view_distance,
+            max_concurrent_loads: 4,
+            loader: Box::new(move |coord| Box::pin(loader(coord))),
+            chunks: HashMap::new(),
+        }
+    }
+
+    pub fn chunk_size(&self) -> f32 {
+        self.chunk_size
+    }
+
+    pub fn view_distance(&self) -> f32 {
+        self.view_distance
+    }
+
+    /// 다음 `update` 부터 적용된다.
+    pub fn set_view_distance(&mut self, view_distance: f32) {
+        self.view_distance = view_distance;
+    }
+
+    pub fn set_max_concurrent_loads(&mut self, count: usize) {
+        self.max_concurrent_loads = count.max(1);
+    }
+
+    pub fn state(&self, coord: ChunkCoord) -> Option<&ChunkState<V>> {
+        self.chunks.get(&coord)
+    }
+
+    /// 로드가 끝난 청크와 메시
+    pub fn loaded(&self) -> impl Iterator<Item = (ChunkCoord, &Mesh)> {
+        self.chunks
+            .iter()
+            .filter_map(|(&coord, state)| match state {
+                ChunkState::Loaded(mesh) => Some((coord, mesh)),
+                _ => None,
+            })
+    }
+
+    /// 로드 중인 청크 수
+    pub fn loading_count(&self) -> usize {
+        self.chunks
+            .values()
+            .filter(|state| matches!(state, ChunkState::Loading(_)))
+            .count()
+    }
+
+    /// 범위를 벗어난 청크를 버리고, 끝난 로드를 GPU 에 올리고, 새로 보이는 청크의 로드를 시작한다.
+    pub fn update(&mut self, gpu: &GpuContext, camera_position: Vec3) {
+        let visible = chunks_in_range(camera_position, self.chunk_size, self.view_distance);
+
+        // 범위를 벗어나면 메시를 버린다. 로드 중이던 결과는 도착해도 버려진다
+        let in_range: HashSet<ChunkCoord> = visible.iter().copied().collect();
+        self.chunks.retain(|coord, _| in_range.contains(coord));
+        for &coord in &visible {
+            self.chunks.entry(coord).or_insert(ChunkState::Unloaded);
+        }
+
+        for state in self.chunks.values_mut() {
+            if let ChunkState::Loading(handle) = state
+                && let Some(geometry) = handle.try_take()
+            {
+                *state = ChunkState::Loaded(Mesh::new(gpu, &geometry.vertices, &geometry.indices));
+            }
+        }
+
+        // visible 이 가까운 순서이므로 카메라 가까이부터 채운다
+        let mut free = self
+            .max_concurrent_loads
+            .saturating_sub(self.loading_count());
+        for coord in visible {
+            if free == 0 {
+                break;
+            }
+            let state = self
+                .chunks
+                .get_mut(&coord)
+                .expect("visible chunk is tracked");
+            if matches!(state, ChunkState::Unloaded) {
+                *state = ChunkState::Loading(JoinHandle::spawn((self.loader)(coord)));
+                free -= 1;
+            }
+        }
+    }
+
+    /// 올라간 청크를 모두 그린다. 파이프라인과 바인드 그룹은 미리 설정한다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        for (_, mesh) in self.loaded() {
+            mesh.draw(render_pass);
+        }
+    }
+}
+
+// 가운데가 `position` 에서 xz 거리 `view_distance` 안에 있는 청크를 가까운 순서로
+fn chunks_in_range(position: Vec3, chunk_size: f32, view_distance: f32) -> Vec<ChunkCoord> {
+    let center = ChunkCoord::containing(position, chunk_size);
+    let eye = Vec2::new(position.x, position.z);
+    let radius = (view_distance / chunk_size).ceil() as i32 + 1;
+
+    let mut chunks: Vec<(f32, ChunkCoord)> = (-radius..=radius)
+        .flat_map(|dz| (-radius..=radius).map(move |dx| (dx, dz)))
+        .map(|(dx, dz)| ChunkCoord::new(center.x + dx, center.z + dz))
+        .map(|coord| (coord.center(chunk_size).distance(eye), coord))
+        .filter(|&(distance, _)| distance <= view_distance)
+        .collect();
+    chunks.sort_by(|a, b| a.0.total_cmp(&b.0));
+    chunks.into_iter().map(|(_, coord)| coord).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coord_containing_position() {
+        assert_eq!(
+            ChunkCoord::containing(Vec3::new(5.0, 100.0, 15.0), 10.0),
+            ChunkCoord::new(0, 1)
+        );
+        // 음수 쪽은 0 이 아니라 -1 청크다
+        assert_eq!(
+            ChunkCoord::containing(Vec3::new(-0.5, 0.0, -10.0), 10.0),
+            ChunkCoord::new(-1, -1)
+        );
+        assert_eq!(ChunkCoord::new(-1, 2).center(10.0), Vec2::new(-5.0, 25.0));
+    }
+
+    #[test]
+    fn chunks_in_range_are_sorted_and_bounded() {
+        let position = Vec3::new(5.0, 0.0, 5.0);
+        let chunks = chunks_in_range(position, 10.0, 25.0);
+
+        assert_eq!(chunks[0], ChunkCoord::new(0, 0));
+        let distances: Vec<f32> = chunks
+            .iter()
+            .map(|coord| coord.center(10.0).distance(Vec2::new(5.0, 5.0)))
+            .collect();
+        assert!(distances.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(distances.iter().all(|&distance| distance <= 25.0));
+        // 가운데에서 두 칸 떨어진 청크까지 들어오고 대각선 두 칸은 빠진다
+        assert!(chunks.contains(&ChunkCoord::new(2, 0)));
+        assert!(!chunks.contains(&ChunkCoord::new(2, 2)));
+        assert!(!chunks.contains(&ChunkCoord::new(3, 0)));
+    }
+}
@@ -0,0 +1,76 @@
+use glam::Vec3;
+
+/// `color.wgsl` 의 같은 이름 함수를 셰이더에서 쓰려면 소스 앞에 붙인다.
+pub(crate) const COLOR_WGSL: &str = include_str!("color.wgsl");
+
+/// 셰이더가 쓴 선형 색을 GPU 가 sRGB 로 인코딩하게 할지 여부
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SrgbMode {
+    /// 렌더 타깃과 색 텍스처를 sRGB 포맷으로 만든다.
+    /// 셰이더는 항상 선형 값을 읽고 쓰며, 저장할 때와 샘플링할 때 GPU 가 변환한다.
+    #[default]
+    Enabled,
+    /// sRGB 가 아닌 포맷을 사용한다. 텍스처의 값과 셰이더가 쓴 값이 변환 없이 화면에 나간다.
+    Disabled,
+}
+
+impl SrgbMode {
+    /// `format` 을 이 모드의 sRGB 변형으로 바꾼다. sRGB 변형이 없는 포맷은 그대로 둔다.
+    pub fn apply(self, format: wgpu::TextureFormat) -> wgpu::TextureFormat {
+        match self {
+            SrgbMode::Enabled => format.add_srgb_suffix(),
+            SrgbMode::Disabled => format.remove_srgb_suffix(),
+        }
+    }
+
+    /// 이미지 파일에서 읽은 RGBA8 텍스처의 포맷
+    pub fn texture_format(self) -> wgpu::TextureFormat {
+        self.apply(wgpu::TextureFormat::Rgba8Unorm)
+    }
+}
+
+/// 선형 색을 모니터에 보낼 sRGB 값으로 인코딩한다. 각 성분은 [0, 1] 범위로 가정한다.
+pub fn linear_to_srgb(c: Vec3) -> Vec3 {
+    let encode = |c: f32| {
+        if c <= 0.003_130_8 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    Vec3::new(encode(c.x), encode(c.y), encode(c.z))
+}
+
+/// 색 선택기나 이미지의 sRGB 값을 셰이더가 계산에 쓰는 선형 색으로 디코딩한다.
+pub fn srgb_to_linear(c: Vec3) -> Vec3 {
+    let decode = |c: f32| {
+        if c <= 0.040_45 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    Vec3::new(decode(c.x), decode(c.y), decode(c.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_known_values() {
+        let encoded = linear_to_srgb(Vec3::new(0.0, 0.214_041, 1.0));
+        assert!(encoded.abs_diff_eq(Vec3::new(0.0, 0.5, 1.0), 1e-4));
+
+        let decoded = srgb_to_linear(Vec3::splat(0.5));
+        assert!(decoded.abs_diff_eq(Vec3::splat(0.214_041), 1e-4));
+    }
+
+    #[test]
+    fn round_trips() {
+        for i in 0..=100 {
+            let c = Vec3::splat(i as f32 / 100.0);
+            assert!(srgb_to_linear(linear_to_srgb(c)).abs_diff_eq(c, 1e-5));
+        }
+    }
+}
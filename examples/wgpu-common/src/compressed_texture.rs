@@ -0,0 +1,351 @@
+use crate::{DdsError, GpuContext, SrgbMode, Texture};
+
+// "DDS " 매직 넘버와 DDS_HEADER 의 크기
+const MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_SIZE: usize = 124;
+// DX10 확장 헤더 (DDS_HEADER_DXT10) 의 크기
+const DX10_HEADER_SIZE: usize = 20;
+// DDS_HEADER.dwFlags 의 DDSD_MIPMAPCOUNT
+const FLAG_MIPMAP_COUNT: u32 = 0x20000;
+// DDS_PIXELFORMAT.dwFlags 의 DDPF_FOURCC
+const PIXEL_FORMAT_FOURCC: u32 = 0x4;
+
+/// 지원하는 BC 블록 압축 포맷. 둘 다 4x4 픽셀을 한 블록으로 압축한다
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BcFormat {
+    /// DXT1. 블록당 8 바이트이고 알파는 켜짐/꺼짐만 있다
+    Bc1,
+    /// DXT5. 블록당 16 바이트이고 알파를 따로 보간한다
+    Bc3,
+}
+
+impl BcFormat {
+    /// 4x4 블록 하나의 바이트 수
+    pub fn block_size(self) -> usize {
+        match self {
+            BcFormat::Bc1 => 8,
+            BcFormat::Bc3 => 16,
+        }
+    }
+
+    /// `width` x `height` 이미지를 담는 데 필요한 바이트 수. 가장자리는 블록 단위로 올림한다
+    pub fn compressed_size(self, width: u32, height: u32) -> usize {
+        width.div_ceil(4) as usize * height.div_ceil(4) as usize * self.block_size()
+    }
+
+    pub fn texture_format(self, mode: SrgbMode) -> wgpu::TextureFormat {
+        match (self, mode) {
+            (BcFormat::Bc1, SrgbMode::Enabled) => wgpu::TextureFormat::Bc1RgbaUnormSrgb,
+            (BcFormat::Bc1, SrgbMode::Disabled) => wgpu::TextureFormat::Bc1RgbaUnorm,
+            (BcFormat::Bc3, SrgbMode::Enabled) => wgpu::TextureFormat::Bc3RgbaUnormSrgb,
+            (BcFormat::Bc3, SrgbMode::Disabled) => wgpu::TextureFormat::Bc3RgbaUnorm,
+        }
+    }
+
+    fn squish_format(self) -> squish::Format {
+        match self {
+            BcFormat::Bc1 => squish::Format::Bc1,
+            BcFormat::Bc3 => squish::Format::Bc3,
+        }
+    }
+
+    // 레거시 헤더의 FourCC
+    fn from_four_cc(four_cc: &[u8]) -> Option<Self> {
+        match four_cc {
+            b"DXT1" => Some(BcFormat::Bc1),
+            b"DXT5" => Some(BcFormat::Bc3),
+            _ => None,
+        }
+    }
+
+    // DX10 헤더의 DXGI_FORMAT. sRGB 여부는 로더에 넘기는 SrgbMode 가 정한다
+    fn from_dxgi_format(format: u32) -> Option<Self> {
+        match format {
+            // DXGI_FORMAT_BC1_UNORM, DXGI_FORMAT_BC1_UNORM_SRGB
+            71 | 72 => Some(BcFormat::Bc1),
+            // DXGI_FORMAT_BC3_UNORM, DXGI_FORMAT_BC3_UNORM_SRGB
+            77 | 78 => Some(BcFormat::Bc3),
+            _ => None,
+        }
+    }
+}
+
+/// DDS 파일의 헤더와 밉 레벨 데이터.
+///
+/// 2D 텍스처만 읽으며, 밉 레벨은 큰 것부터 빈틈없이 이어져 있다고 본다.
+#[derive(Clone, Copy, Debug)]
+pub struct DdsImage<'a> {
+    pub width: u32,
+    pub height: u32,
+    pub mip_level_count: u32,
+    pub format: BcFormat,
+    data: &'a [u8],
+}
+
+impl<'a> DdsImage<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, DdsError> {
+        if bytes.len() < MAGIC.len() + HEADER_SIZE || &bytes[..4] != MAGIC {
+            return Err(DdsError::InvalidHeader);
+        }
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("4 bytes"))
+        };
+        if read_u32(4) as usize != HEADER_SIZE {
+            return Err(DdsError::InvalidHeader);
+        }
+
+        let flags = read_u32(8);
+        let height = read_u32(12);
+        let width = read_u32(16);
+        let mip_level_count = if flags & FLAG_MIPMAP_COUNT != 0 {
+            read_u32(28).max(1)
+        } else {
+            1
+        };
+        if width == 0 || height == 0 {
+            return Err(DdsError::InvalidHeader);
+        }
+
+        // DDS_PIXELFORMAT 은 오프셋 76 에서 시작한다
+        let pixel_flags = read_u32(80);
+        let four_cc = &bytes[84..88];
+        if pixel_flags & PIXEL_FORMAT_FOURCC == 0 {
+            return Err(DdsError::UnsupportedFormat("uncompressed".to_string()));
+        }
+
+        let mut data_offset = MAGIC.len() + HEADER_SIZE;
+        let format = if four_cc == b"DX10" {
+            if bytes.len() < data_offset + DX10_HEADER_SIZE {
+                return Err(DdsError::InvalidHeader);
+            }
+            let dxgi_format = read_u32(data_offset);
+            data_offset += DX10_HEADER_SIZE;
+            BcFormat::from_dxgi_format(dxgi_format)
+                .ok_or_else(|| DdsError::UnsupportedFormat(format!("DXGI {}", dxgi_format)))?
+        } else {
+            BcFormat::from_four_cc(four_cc).ok_or_else(|| {
+                DdsError::UnsupportedFormat(String::from_utf8_lossy(four_cc).into_owned())
+            })?
+        };
+
+        let data = &bytes[data_offset..];
+        let expected = (0..mip_level_count)
+            .map(|level| {
+                let (width, height) = mip_size(width, height, level);
+                format.compressed_size(width, height)
+            })
+            .sum();
+        if data.len() < expected {
+            return Err(DdsError::Truncated {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            mip_level_count,
+            format,
+            data,
+        })
+    }
+
+    /// 밉 레벨마다 (너비, 높이, 압축된 데이터) 를 큰 것부터 돌려준다.
+    pub fn mip_levels(&self) -> impl Iterator<Item = (u32, u32, &'a [u8])> + '_ {
+        let mut offset = 0;
+        (0..self.mip_level_count).map(move |level| {
+            let (width, height) = mip_size(self.width, self.height, level);
+            let size = self.format.compressed_size(width, height);
+            let data = &self.data[offset..offset + size];
+            offset += size;
+            (width, height, data)
+        })
+    }
+
+    /// 밉 레벨 하나를 `Rgba8` 픽셀로 푼다.
+    pub fn decompress(format: BcFormat, width: u32, height: u32, data: &[u8]) -> Vec<u8> {
+        let mut rgba = vec![0; width as usize * height as usize * 4];
+        format
+            .squish_format()
+            .decompress(data, width as usize, height as usize, &mut rgba);
+        rgba
+    }
+}
+
+fn mip_size(width: u32, height: u32, level: u32) -> (u32, u32) {
+    ((width >> level).max(1), (height >> level).max(1))
+}
+
+/// DDS 파일의 BC1(DXT1), BC3(DXT5) 텍스처를 올린다.
+///
+/// 디바이스에 `Features::TEXTURE_COMPRESSION_BC` 가 있으면 압축된 블록을 그대로 올려서
+/// 비디오 메모리와 대역폭을 아끼고, 없으면 CPU 에서 `Rgba8` 로 풀어서 올린다.
+/// 디바이스를 요청할 때 `adapter.features() & CompressedTextureLoader::FEATURES` 를 넘기면
+/// 어댑터가 지원하는 경우에만 기능이 켜진다.
+///
+/// BC 포맷은 데스크톱 GPU 에서만 지원되며, WebGPU 호환 모드와 WebGL2 백엔드에서는
+/// 쓸 수 없으므로 웹에서는 대부분 압축을 푸는 쪽으로 간다.
+pub struct CompressedTextureLoader {
+    supported: bool,
+}
+
+impl CompressedTextureLoader {
+    /// 디바이스를 요청할 때 `required_features` 에 포함해야 하는 기능
+    pub const FEATURES: wgpu::Features = wgpu::Features::TEXTURE_COMPRESSION_BC;
+
+    pub fn new(gpu: &GpuContext) -> Self {
+        Self {
+            supported: gpu.device().features().contains(Self::FEATURES),
+        }
+    }
+
+    /// 압축된 블록을 그대로 올릴 수 있으면 true
+    pub fn is_supported(&self) -> bool {
+        self.supported
+    }
+
+    /// DDS 파일을 읽어서 밉 레벨을 모두 담은 텍스처를 만든다.
+    /// BC 텍스처는 첫 밉 레벨의 크기가 4 의 배수여야 하므로 그렇지 않으면 압축을 푼다.
+    pub fn load(
+        &self,
+        gpu: &GpuContext,
+        bytes: &[u8],
+        label: &str,
+        mode: SrgbMode,
+    ) -> Result<Texture, DdsError> {
+        let image = DdsImage::parse(bytes)?;
+        let aligned = image.width % 4 == 0 && image.height % 4 == 0;
+        let format = if self.supported && aligned {
+            image.format.texture_format(mode)
+        } else {
+            mode.texture_format()
+        };
+
+        let device = gpu.device();
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: image.width,
+                height: image.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: image.mip_level_count,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        for (level, (width, height, data)) in image.mip_levels().enumerate() {
+            let size = wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            };
+            let destination = wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: level as u32,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            };
+
+            if format.is_compressed() {
+                // 4 보다 작은 밉 레벨도 블록 하나를 통째로 복사한다
+                let blocks = (width.div_ceil(4), height.div_ceil(4));
+                gpu.queue().write_texture(
+                    destination,
+                    data,
+                    wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(blocks.0 * image.format.block_size() as u32),
+                        rows_per_image: Some(blocks.1),
+                    },
+                    size.physical_size(format),
+                );
+            } else {
+                let rgba = DdsImage::decompress(image.format, width, height, data);
+                gpu.queue().write_texture(
+                    destination,
+                    &rgba,
+                    wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(4 * width),
+                        rows_per_image: Some(height),
+                    },
+                    size,
+                );
+            }
+        }
+
+        Ok(Texture::from_texture(device, texture, label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 레거시 헤더를 가진 DDS 파일
+    fn dds_bytes(four_cc: &[u8; 4], width: u32, height: u32, mips: u32, data: &[u8]) -> Vec<u8> {
+        let mut header = [0u32; HEADER_SIZE / 4];
+        header[0] = HEADER_SIZE as u32;
+        header[1] = FLAG_MIPMAP_COUNT;
+        header[2] = height;
+        header[3] = width;
+        header[6] = mips;
+        header[18] = 32;
+        header[19] = PIXEL_FORMAT_FOURCC;
+        header[20] = u32::from_le_bytes(*four_cc);
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(header.iter().flat_map(|value| value.to_le_bytes()));
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    // 색 0 이 빨강(565), 모든 픽셀이 색 0 을 가리키는 BC1 블록
+    const RED_BC1_BLOCK: [u8; 8] = [0x00, 0xF8, 0x00, 0x00, 0, 0, 0, 0];
+
+    #[test]
+    fn parses_mip_chain() {
+        // 8x4 -> 4x2 -> 2x1 -> 1x1 은 블록이 2, 1, 1, 1 개
+        let data = RED_BC1_BLOCK.repeat(5);
+        let bytes = dds_bytes(b"DXT1", 8, 4, 4, &data);
+        let image = DdsImage::parse(&bytes).unwrap();
+
+        assert_eq!((image.width, image.height), (8, 4));
+        assert_eq!(image.format, BcFormat::Bc1);
+        let sizes: Vec<_> = image
+            .mip_levels()
+            .map(|(width, height, data)| (width, height, data.len()))
+            .collect();
+        assert_eq!(sizes, [(8, 4, 16), (4, 2, 8), (2, 1, 8), (1, 1, 8)]);
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        assert!(matches!(
+            DdsImage::parse(b"PNG"),
+            Err(DdsError::InvalidHeader)
+        ));
+        assert!(matches!(
+            DdsImage::parse(&dds_bytes(b"ATI2", 4, 4, 1, &[0; 16])),
+            Err(DdsError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            DdsImage::parse(&dds_bytes(b"DXT5", 4, 4, 1, &[0; 8])),
+            Err(DdsError::Truncated {
+                expected: 16,
+                actual: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn decompresses_to_rgba() {
+        let rgba = DdsImage::decompress(BcFormat::Bc1, 2, 2, &RED_BC1_BLOCK);
+        assert_eq!(rgba, [255, 0, 0, 255].repeat(4));
+    }
+}
@@ -0,0 +1,47 @@
+/// 컴퓨트 파이프라인 하나를 바인드 그룹과 함께 디스패치하는 컴퓨트 패스
+#[derive(Clone)]
+pub struct ComputePass {
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    dispatch: (u32, u32, u32),
+}
+
+impl ComputePass {
+    /// `dispatch` 는 워크그룹 개수 `(x, y, z)` 이다.
+    pub fn new(
+        pipeline: &wgpu::ComputePipeline,
+        bind_group: &wgpu::BindGroup,
+        dispatch: (u32, u32, u32),
+    ) -> Self {
+        Self {
+            pipeline: pipeline.clone(),
+            bind_group: bind_group.clone(),
+            dispatch,
+        }
+    }
+
+    pub fn encode(&self, encoder: &mut wgpu::CommandEncoder) {
+        Self::run(encoder, &self.pipeline, &self.bind_group, self.dispatch);
+    }
+
+    /// 컴퓨트 패스를 열고 바인드 그룹을 0번 그룹에 바인딩한 뒤 디스패치한다.
+    pub fn run(
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::ComputePipeline,
+        bind_group: &wgpu::BindGroup,
+        dispatch: (u32, u32, u32),
+    ) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Compute Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.set_bind_group(0, bind_group, &[]);
+        compute_pass.dispatch_workgroups(dispatch.0, dispatch.1, dispatch.2);
+    }
+
+    /// `count` 개의 항목을 `workgroup_size` 크기 워크그룹으로 처리할 때 필요한 워크그룹 수
+    pub fn workgroup_count(count: u32, workgroup_size: u32) -> u32 {
+        count.div_ceil(workgroup_size)
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+/// 디바이스와 큐를 한 곳에 묶은 것.
+///
+/// `Arc<GpuContext>` 로 들고 있다가 백그라운드 업로드 작업처럼 GPU 리소스를 만드는 곳에
+/// 복제해서 넘긴다. 디바이스와 큐를 모두 쓰는 헬퍼(`Texture`, `Mesh`, `UniformBuffer` 등)는
+/// `&GpuContext` 를 받고, 레이아웃이나 샘플러처럼 디바이스만 필요한 헬퍼는 `device()` 를 받는다.
+#[derive(Clone, Debug)]
+pub struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+impl GpuContext {
+    pub fn new(device: wgpu::Device, queue: wgpu::Queue) -> Self {
+        Self { device, queue }
+    }
+
+    /// `adapter` 에서 디바이스와 큐를 요청해서 공유할 수 있는 컨텍스트로 돌려준다.
+    // wasm 에서는 wgpu 타입이 Send 가 아니지만 스레드가 하나뿐이므로 Arc 로 묶어도 문제없다
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub async fn request(
+        adapter: &wgpu::Adapter,
+        descriptor: &wgpu::DeviceDescriptor<'_>,
+    ) -> Result<Arc<Self>, wgpu::RequestDeviceError> {
+        let (device, queue) = adapter.request_device(descriptor).await?;
+        Ok(Arc::new(Self::new(device, queue)))
+    }
+
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+}
@@ -0,0 +1,27 @@
+/// 어느 쪽을 향한 면을 그리지 않을지
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CullMode {
+    /// 앞면과 뒷면을 모두 그린다
+    #[default]
+    None,
+    Back,
+    Front,
+}
+
+impl CullMode {
+    /// 모드별로 파이프라인을 미리 만들어 둘 때 사용하는 순서. `index` 와 같다.
+    pub const ALL: [CullMode; 3] = [CullMode::None, CullMode::Back, CullMode::Front];
+
+    pub fn face(self) -> Option<wgpu::Face> {
+        match self {
+            CullMode::None => None,
+            CullMode::Back => Some(wgpu::Face::Back),
+            CullMode::Front => Some(wgpu::Face::Front),
+        }
+    }
+
+    /// `ALL` 안에서의 위치
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
@@ -0,0 +1,81 @@
+/// 처리되지 않은 GPU 에러를 패닉 대신 콘솔 에러로 출력한다.
+///
+/// wgpu 는 에러 스코프 밖에서 생긴 검증 에러를 기본으로 패닉으로 바꾸는데, wasm 에서는
+/// 패닉 메시지가 긴 스택 트레이스에 묻혀서 원인을 찾기 어렵다. `install` 후에는 에러 종류와
+/// 메시지를 한 덩어리로 `console.error` 에 출력하고 계속 실행한다.
+/// 디버그 빌드에서는 에러를 받은 시점의 JS 스택 트레이스를 함께 출력한다.
+pub struct WgpuDebugLayer;
+
+impl WgpuDebugLayer {
+    /// 디바이스를 만든 직후에 한 번 호출한다. 이미 설치된 핸들러는 교체된다.
+    pub fn install(device: &wgpu::Device) {
+        device.on_uncaptured_error(Box::new(|error| {
+            let mut message = describe_error(&error);
+            if cfg!(debug_assertions)
+                && let Some(stack) = js_stack()
+            {
+                message.push_str("\n\n");
+                message.push_str(&stack);
+            }
+            log_error(&message);
+        }));
+    }
+}
+
+fn describe_error(error: &wgpu::Error) -> String {
+    match error {
+        wgpu::Error::Validation { description, .. } => {
+            format!("[wgpu] Validation error\n{}", description.trim_end())
+        }
+        wgpu::Error::OutOfMemory { source } => format!("[wgpu] Out of memory\n{}", source),
+        wgpu::Error::Internal { description, .. } => {
+            format!("[wgpu] Internal error\n{}", description.trim_end())
+        }
+    }
+}
+
+// js_sys::Error 에는 stack 바인딩이 없으므로 속성으로 읽는다
+#[cfg(target_arch = "wasm32")]
+fn js_stack() -> Option<String> {
+    let error = js_sys::Error::new("");
+    js_sys::Reflect::get(&error, &"stack".into())
+        .ok()?
+        .as_string()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn js_stack() -> Option<String> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+fn log_error(message: &str) {
+    web_sys::console::error_1(&message.into());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_error(message: &str) {
+    eprintln!("{}", message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_error_kind_and_message() {
+        let validation = wgpu::Error::Validation {
+            source: Box::new(std::fmt::Error),
+            description: "Buffer is too small\n".to_string(),
+        };
+        assert_eq!(
+            describe_error(&validation),
+            "[wgpu] Validation error\nBuffer is too small"
+        );
+
+        let out_of_memory = wgpu::Error::OutOfMemory {
+            source: Box::new(std::fmt::Error),
+        };
+        assert!(describe_error(&out_of_memory).starts_with("[wgpu] Out of memory\n"));
+    }
+}
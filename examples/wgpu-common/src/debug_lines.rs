@@ -0,0 +1,224 @@
+use glam::Vec3;
+
+use crate::{Aabb, DepthTexture, GpuContext, Vertex, VertexBuffer};
+
+// add_sphere 가 원 하나를 나누는 선분 수
+const SPHERE_SEGMENTS: usize = 32;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct DebugLineVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+}
+
+impl Vertex for DebugLineVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x4];
+}
+
+/// 충돌체, 법선, 경로 같은 디버그용 선분을 모아서 그린다.
+///
+/// 한 프레임 동안 `add_*` 로 선분을 쌓고, 메인 패스가 끝난 뒤 `flush` 로
+/// 같은 컬러 타깃 위에 `LineList` 로 덧그린다. 그린 선분은 비워진다.
+/// 셰이더는 0번 그룹의 `CameraUniform` 으로 월드 좌표를 변환한다.
+pub struct DebugLines {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    vertices: Vec<DebugLineVertex>,
+    vertex_buffer: VertexBuffer<DebugLineVertex>,
+    // GPU 버퍼에 담을 수 있는 버텍스 수
+    capacity: usize,
+    pipeline: wgpu::RenderPipeline,
+    depth_pipeline: wgpu::RenderPipeline,
+    // Some 이면 이 깊이 텍스처로 가려진 선분을 그리지 않는다
+    depth_view: Option<wgpu::TextureView>,
+}
+
+impl DebugLines {
+    /// `camera_layout` 은 `flush` 에 넘길 카메라 바인드 그룹의 레이아웃이다.
+    pub fn new(
+        gpu: &GpuContext,
+        color_format: wgpu::TextureFormat,
+        camera_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let capacity = 256;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Debug Lines Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("debug_lines.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Debug Lines Pipeline Layout"),
+            bind_group_layouts: &[camera_layout],
+            push_constant_ranges: &[],
+        });
+
+        let create_pipeline = |depth_stencil| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Debug Lines Pipeline"),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[DebugLineVertex::layout()],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::LineList,
+                    ..Default::default()
+                },
+                depth_stencil,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let pipeline = create_pipeline(None);
+        // 메인 패스의 깊이를 읽기만 하고, 표면 위에 놓인 선분도 보이도록 같은 깊이는 통과시킨다
+        let depth_pipeline = create_pipeline(Some(wgpu::DepthStencilState {
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            ..DepthTexture::depth_stencil_state()
+        }));
+
+        Self {
+            device: device.clone(),
+            queue: queue.clone(),
+            vertices: Vec::with_capacity(capacity),
+            vertex_buffer: create_vertex_buffer(device, capacity),
+            capacity,
+            pipeline,
+            depth_pipeline,
+            depth_view: None,
+        }
+    }
+
+    /// `Some` 이면 `flush` 가 이 깊이 텍스처(`DepthTexture::FORMAT`, 샘플 1개)로 깊이 테스트를 한다.
+    /// `None` 이면 선분이 항상 모든 물체 위에 그려진다.
+    pub fn set_depth_test(&mut self, depth_view: Option<&wgpu::TextureView>) {
+        self.depth_view = depth_view.cloned();
+    }
+
+    /// 이번 프레임에 쌓인 선분 수
+    pub fn len(&self) -> usize {
+        self.vertices.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn add_line(&mut self, a: Vec3, b: Vec3, color: [f32; 4]) {
+        self.vertices.push(DebugLineVertex {
+            position: a.into(),
+            color,
+        });
+        self.vertices.push(DebugLineVertex {
+            position: b.into(),
+            color,
+        });
+    }
+
+    /// 박스의 모서리 12개
+    pub fn add_aabb(&mut self, aabb: &Aabb, color: [f32; 4]) {
+        let corner = |i: usize| {
+            Vec3::new(
+                if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+            )
+        };
+
+        // 꼭짓점 번호의 비트 하나만 다른 쌍이 모서리다
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    self.add_line(corner(i), corner(i | bit), color);
+                }
+            }
+        }
+    }
+
+    /// XY, YZ, ZX 평면의 원 세 개로 구를 표시한다.
+    pub fn add_sphere(&mut self, center: Vec3, radius: f32, color: [f32; 4]) {
+        for (u, v) in [(Vec3::X, Vec3::Y), (Vec3::Y, Vec3::Z), (Vec3::Z, Vec3::X)] {
+            let point = |i: usize| {
+                let angle = i as f32 / SPHERE_SEGMENTS as f32 * std::f32::consts::TAU;
+                center + (u * angle.cos() + v * angle.sin()) * radius
+            };
+            for i in 0..SPHERE_SEGMENTS {
+                self.add_line(point(i), point(i + 1), color);
+            }
+        }
+    }
+
+    /// 쌓인 선분을 올려서 `view` 의 기존 내용 위에 그리고 비운다.
+    /// 용량이 부족하면 버텍스 버퍼를 두 배씩 키워서 다시 만든다.
+    pub fn flush(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        camera_bind_group: &wgpu::BindGroup,
+    ) {
+        if self.vertices.is_empty() {
+            return;
+        }
+
+        if self.vertices.len() > self.capacity {
+            self.capacity = self.vertices.len().next_power_of_two();
+            self.vertex_buffer = create_vertex_buffer(&self.device, self.capacity);
+        }
+        self.vertex_buffer.write(&self.queue, &self.vertices);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Debug Lines Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: self.depth_view.as_ref().map(|view| {
+                wgpu::RenderPassDepthStencilAttachment {
+                    view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        let pipeline = match self.depth_view {
+            Some(_) => &self.depth_pipeline,
+            None => &self.pipeline,
+        };
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, camera_bind_group, &[]);
+        self.vertex_buffer.bind(&mut render_pass, 0);
+        render_pass.draw(0..self.vertices.len() as u32, 0..1);
+        drop(render_pass);
+
+        self.vertices.clear();
+    }
+}
+
+fn create_vertex_buffer(device: &wgpu::Device, capacity: usize) -> VertexBuffer<DebugLineVertex> {
+    VertexBuffer::writable(device, &vec![bytemuck::Zeroable::zeroed(); capacity])
+}
@@ -0,0 +1,95 @@
+/// 깊이 테스트에 사용되는 깊이 텍스처
+pub struct DepthTexture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+impl DepthTexture {
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+    /// 스텐실 버퍼가 함께 필요할 때 쓰는 포맷
+    pub const STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
+
+    pub fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        Self::with_sample_count(device, width, height, 1)
+    }
+
+    /// `STENCIL_FORMAT` 으로 만든다. 파이프라인의 `depth_stencil` 포맷도 맞춰야 한다.
+    pub fn with_stencil(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        Self::create(device, width, height, 1, Self::STENCIL_FORMAT)
+    }
+
+    /// 멀티 샘플 렌더 패스에서는 컬러 어태치먼트와 샘플 수가 같아야 한다.
+    pub fn with_sample_count(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    ) -> Self {
+        Self::create(device, width, height, sample_count, Self::FORMAT)
+    }
+
+    fn create(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Depth Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self { texture, view }
+    }
+
+    /// 렌더 파이프라인의 `depth_stencil` 에 넘길 상태.
+    /// 더 가까운(깊이 값이 작은) 프래그먼트만 통과시킨다.
+    pub fn depth_stencil_state() -> wgpu::DepthStencilState {
+        wgpu::DepthStencilState {
+            format: Self::FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }
+    }
+
+    /// 매 프레임 깊이를 1.0 으로, 스텐실이 있으면 0 으로 지우는 렌더 패스 어태치먼트
+    pub fn attachment(&self) -> wgpu::RenderPassDepthStencilAttachment<'_> {
+        wgpu::RenderPassDepthStencilAttachment {
+            view: &self.view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: self
+                .texture
+                .format()
+                .has_stencil_aspect()
+                .then_some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0),
+                    store: wgpu::StoreOp::Store,
+                }),
+        }
+    }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+}
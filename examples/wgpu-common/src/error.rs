@@ -0,0 +1,466 @@
+use std::fmt;
+
+use wasm_bindgen::JsValue;
+
+/// `State` 초기화 중 발생할 수 있는 에러
+#[derive(Debug)]
+pub enum InitError {
+    /// 주어진 id 의 캔버스를 찾지 못함
+    CanvasNotFound(String),
+    AdapterNotFound(wgpu::RequestAdapterError),
+    /// 어댑터가 예제에 필요한 기능을 지원하지 않음
+    AdapterUnsupported(String),
+    DeviceRequestFailed(wgpu::RequestDeviceError),
+    SurfaceCreationFailed(wgpu::CreateSurfaceError),
+    /// 셰이더 검증 에러 메시지
+    ShaderCompilationFailed(String),
+    /// 텍스처, 모델, 폰트 같은 에셋을 불러오지 못한 에러 메시지
+    AssetLoadFailed(String),
+    /// 입력 이벤트 리스너를 등록하지 못한 JS 에러 메시지
+    EventListenerFailed(String),
+    RenderGraphInvalid(RenderGraphError),
+}
+
+impl InitError {
+    /// JS 쪽 `Error.name` 으로 전달되는 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            InitError::CanvasNotFound(_) => "CanvasNotFound",
+            InitError::AdapterNotFound(_) => "AdapterNotFound",
+            InitError::AdapterUnsupported(_) => "AdapterUnsupported",
+            InitError::DeviceRequestFailed(_) => "DeviceRequestFailed",
+            InitError::SurfaceCreationFailed(_) => "SurfaceCreationFailed",
+            InitError::ShaderCompilationFailed(_) => "ShaderCompilationFailed",
+            InitError::AssetLoadFailed(_) => "AssetLoadFailed",
+            InitError::EventListenerFailed(_) => "EventListenerFailed",
+            InitError::RenderGraphInvalid(_) => "RenderGraphInvalid",
+        }
+    }
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::CanvasNotFound(canvas_id) => {
+                write!(f, "Canvas with id '{}' not found", canvas_id)
+            }
+            InitError::AdapterNotFound(e) => write!(f, "Failed to find a GPU adapter: {}", e),
+            InitError::AdapterUnsupported(message) => {
+                write!(f, "GPU adapter is not supported: {}", message)
+            }
+            InitError::DeviceRequestFailed(e) => write!(f, "Failed to request device: {}", e),
+            InitError::SurfaceCreationFailed(e) => write!(f, "Failed to create surface: {}", e),
+            InitError::ShaderCompilationFailed(message) => {
+                write!(f, "Failed to compile shader: {}", message)
+            }
+            InitError::AssetLoadFailed(message) => write!(f, "Failed to load asset: {}", message),
+            InitError::EventListenerFailed(message) => {
+                write!(f, "Failed to register event listener: {}", message)
+            }
+            InitError::RenderGraphInvalid(e) => write!(f, "Invalid render graph: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for InitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InitError::AdapterNotFound(e) => Some(e),
+            InitError::DeviceRequestFailed(e) => Some(e),
+            InitError::SurfaceCreationFailed(e) => Some(e),
+            InitError::RenderGraphInvalid(e) => Some(e),
+            InitError::CanvasNotFound(_)
+            | InitError::AdapterUnsupported(_)
+            | InitError::ShaderCompilationFailed(_)
+            | InitError::AssetLoadFailed(_)
+            | InitError::EventListenerFailed(_) => None,
+        }
+    }
+}
+
+impl From<wgpu::RequestAdapterError> for InitError {
+    fn from(e: wgpu::RequestAdapterError) -> Self {
+        InitError::AdapterNotFound(e)
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for InitError {
+    fn from(e: wgpu::RequestDeviceError) -> Self {
+        InitError::DeviceRequestFailed(e)
+    }
+}
+
+impl From<wgpu::CreateSurfaceError> for InitError {
+    fn from(e: wgpu::CreateSurfaceError) -> Self {
+        InitError::SurfaceCreationFailed(e)
+    }
+}
+
+impl From<AssetError> for InitError {
+    fn from(e: AssetError) -> Self {
+        InitError::AssetLoadFailed(e.to_string())
+    }
+}
+
+impl From<image::ImageError> for InitError {
+    fn from(e: image::ImageError) -> Self {
+        InitError::AssetLoadFailed(e.to_string())
+    }
+}
+
+impl From<DdsError> for InitError {
+    fn from(e: DdsError) -> Self {
+        InitError::AssetLoadFailed(e.to_string())
+    }
+}
+
+impl From<GltfError> for InitError {
+    fn from(e: GltfError) -> Self {
+        InitError::AssetLoadFailed(e.to_string())
+    }
+}
+
+impl From<RenderGraphError> for InitError {
+    fn from(e: RenderGraphError) -> Self {
+        InitError::RenderGraphInvalid(e)
+    }
+}
+
+/// 프레임 캡처 중 발생할 수 있는 에러
+#[derive(Debug)]
+pub enum CaptureError {
+    SurfaceError(wgpu::SurfaceError),
+    /// 서피스가 `COPY_SRC` 용도를 지원하지 않음
+    CopyNotSupported,
+    /// RGBA8/BGRA8 이 아닌 텍스처 포맷
+    UnsupportedFormat(wgpu::TextureFormat),
+    MapFailed(wgpu::BufferAsyncError),
+    EncodeFailed(image::ImageError),
+}
+
+impl CaptureError {
+    /// JS 쪽 `Error.name` 으로 전달되는 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            CaptureError::SurfaceError(_) => "SurfaceError",
+            CaptureError::CopyNotSupported => "CopyNotSupported",
+            CaptureError::UnsupportedFormat(_) => "UnsupportedFormat",
+            CaptureError::MapFailed(_) => "MapFailed",
+            CaptureError::EncodeFailed(_) => "EncodeFailed",
+        }
+    }
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::SurfaceError(e) => write!(f, "Failed to acquire surface texture: {}", e),
+            CaptureError::CopyNotSupported => {
+                write!(f, "Surface does not support copying its texture")
+            }
+            CaptureError::UnsupportedFormat(format) => {
+                write!(f, "Cannot capture texture format {:?}", format)
+            }
+            CaptureError::MapFailed(e) => write!(f, "Failed to map readback buffer: {}", e),
+            CaptureError::EncodeFailed(e) => write!(f, "Failed to encode PNG: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CaptureError::SurfaceError(e) => Some(e),
+            CaptureError::MapFailed(e) => Some(e),
+            CaptureError::EncodeFailed(e) => Some(e),
+            CaptureError::CopyNotSupported | CaptureError::UnsupportedFormat(_) => None,
+        }
+    }
+}
+
+impl From<wgpu::SurfaceError> for CaptureError {
+    fn from(e: wgpu::SurfaceError) -> Self {
+        CaptureError::SurfaceError(e)
+    }
+}
+
+impl From<wgpu::BufferAsyncError> for CaptureError {
+    fn from(e: wgpu::BufferAsyncError) -> Self {
+        CaptureError::MapFailed(e)
+    }
+}
+
+/// 에셋을 불러오는 중 발생할 수 있는 에러
+#[derive(Debug)]
+pub enum AssetError {
+    /// `fetch` 나 응답 읽기가 실패했을 때의 JS 에러 메시지
+    Fetch(String),
+    /// 2xx 가 아닌 HTTP 상태 코드
+    Http(u16),
+    Decode(image::ImageError),
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::Fetch(message) => write!(f, "Failed to fetch asset: {}", message),
+            AssetError::Http(status) => write!(f, "Asset request failed with status {}", status),
+            AssetError::Decode(e) => write!(f, "Failed to decode asset: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AssetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AssetError::Decode(e) => Some(e),
+            AssetError::Fetch(_) | AssetError::Http(_) => None,
+        }
+    }
+}
+
+impl From<image::ImageError> for AssetError {
+    fn from(e: image::ImageError) -> Self {
+        AssetError::Decode(e)
+    }
+}
+
+impl From<JsValue> for AssetError {
+    fn from(e: JsValue) -> Self {
+        AssetError::Fetch(format!("{:?}", e))
+    }
+}
+
+impl From<AssetError> for JsValue {
+    fn from(e: AssetError) -> Self {
+        js_sys::Error::new(&e.to_string()).into()
+    }
+}
+
+/// 파일에서 셰이더를 불러오는 중 발생할 수 있는 에러
+#[derive(Debug)]
+pub enum ShaderError {
+    Io(std::io::Error),
+    /// 셰이더 검증 에러 메시지
+    Compile(String),
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShaderError::Io(e) => write!(f, "Failed to read shader file: {}", e),
+            ShaderError::Compile(message) => write!(f, "Failed to compile shader: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ShaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ShaderError::Io(e) => Some(e),
+            ShaderError::Compile(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ShaderError {
+    fn from(e: std::io::Error) -> Self {
+        ShaderError::Io(e)
+    }
+}
+
+/// DDS 파일을 읽는 중 발생할 수 있는 에러
+#[derive(Debug)]
+pub enum DdsError {
+    /// `DDS ` 매직 넘버나 헤더 크기가 맞지 않음
+    InvalidHeader,
+    /// BC1(DXT1), BC3(DXT5) 가 아닌 픽셀 포맷의 FourCC 또는 DXGI 포맷 번호
+    UnsupportedFormat(String),
+    /// 헤더가 말하는 밉 레벨을 모두 담기에 데이터가 모자람
+    Truncated { expected: usize, actual: usize },
+}
+
+impl fmt::Display for DdsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DdsError::InvalidHeader => write!(f, "Not a DDS file"),
+            DdsError::UnsupportedFormat(format) => {
+                write!(f, "Unsupported DDS pixel format {}", format)
+            }
+            DdsError::Truncated { expected, actual } => write!(
+                f,
+                "DDS data is truncated: expected {} bytes, found {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DdsError {}
+
+/// glTF 모델을 불러오는 중 발생할 수 있는 에러
+#[derive(Debug)]
+pub enum GltfError {
+    /// URL 에서 파일을 받지 못함
+    Fetch(AssetError),
+    /// 파일 파싱이나 버퍼, 이미지 디코딩 실패
+    Import(gltf::Error),
+    /// 위치 속성이 없는 프리미티브가 있는 메시의 인덱스
+    MissingPositions(usize),
+    /// RGBA8 로 바꿀 수 없는 텍스처 포맷
+    UnsupportedImageFormat(gltf::image::Format),
+}
+
+impl fmt::Display for GltfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GltfError::Fetch(e) => write!(f, "Failed to fetch glTF file: {}", e),
+            GltfError::Import(e) => write!(f, "Failed to import glTF: {}", e),
+            GltfError::MissingPositions(mesh) => {
+                write!(f, "Mesh {} has a primitive without positions", mesh)
+            }
+            GltfError::UnsupportedImageFormat(format) => {
+                write!(f, "Cannot load glTF image format {:?}", format)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GltfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GltfError::Fetch(e) => Some(e),
+            GltfError::Import(e) => Some(e),
+            GltfError::MissingPositions(_) | GltfError::UnsupportedImageFormat(_) => None,
+        }
+    }
+}
+
+impl From<AssetError> for GltfError {
+    fn from(e: AssetError) -> Self {
+        GltfError::Fetch(e)
+    }
+}
+
+impl From<gltf::Error> for GltfError {
+    fn from(e: gltf::Error) -> Self {
+        GltfError::Import(e)
+    }
+}
+
+impl From<GltfError> for JsValue {
+    fn from(e: GltfError) -> Self {
+        js_sys::Error::new(&e.to_string()).into()
+    }
+}
+
+/// 렌더 그래프의 노드를 정렬하는 중 발견한 잘못된 연결
+#[derive(Debug)]
+pub enum RenderGraphError {
+    /// 어떤 노드도 출력하지 않는 텍스처를 입력으로 사용함
+    MissingProducer { node: String, resource: String },
+    /// 한 텍스처를 두 노드가 출력함
+    MultipleProducers {
+        resource: String,
+        first: String,
+        second: String,
+    },
+    /// 순환 의존 때문에 정렬할 수 없는 노드들
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for RenderGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderGraphError::MissingProducer { node, resource } => write!(
+                f,
+                "Node '{}' reads texture '{}' that no node writes",
+                node, resource
+            ),
+            RenderGraphError::MultipleProducers {
+                resource,
+                first,
+                second,
+            } => write!(
+                f,
+                "Texture '{}' is written by both '{}' and '{}'",
+                resource, first, second
+            ),
+            RenderGraphError::Cycle(nodes) => {
+                write!(f, "Render graph has a cycle among: {}", nodes.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderGraphError {}
+
+/// 푸시 상수를 쓸 수 없는 디바이스
+#[derive(Debug)]
+pub enum PushConstantsError {
+    /// 디바이스를 만들 때 `Features::PUSH_CONSTANTS` 를 요청하지 않았거나 백엔드가 지원하지 않음
+    FeatureNotEnabled,
+    /// 범위의 끝이 `Limits::max_push_constant_size` 를 넘음
+    ExceedsLimit { end: u32, max: u32 },
+}
+
+impl fmt::Display for PushConstantsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushConstantsError::FeatureNotEnabled => write!(
+                f,
+                "Push constants require wgpu::Features::PUSH_CONSTANTS, which is not enabled on this device (unavailable on WebGPU and WebGL2)"
+            ),
+            PushConstantsError::ExceedsLimit { end, max } => write!(
+                f,
+                "Push constant range ends at {} bytes, but the device limit is {} bytes",
+                end, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PushConstantsError {}
+
+/// 텍스처 바인딩 배열을 쓸 수 없는 디바이스
+#[derive(Debug)]
+pub enum BindlessTextureError {
+    /// 디바이스를 만들 때 `Features::TEXTURE_BINDING_ARRAY` 를 요청하지 않았거나 백엔드가 지원하지 않음
+    FeatureNotEnabled,
+    /// 배열 크기가 `Limits::max_binding_array_elements_per_shader_stage` 를 넘음
+    ExceedsLimit { count: u32, max: u32 },
+}
+
+impl fmt::Display for BindlessTextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindlessTextureError::FeatureNotEnabled => write!(
+                f,
+                "Bindless textures require wgpu::Features::TEXTURE_BINDING_ARRAY, which is not enabled on this device (unavailable on WebGPU and WebGL2)"
+            ),
+            BindlessTextureError::ExceedsLimit { count, max } => write!(
+                f,
+                "Texture binding array has {} elements, but the device limit is {}",
+                count, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BindlessTextureError {}
+
+impl From<InitError> for JsValue {
+    fn from(e: InitError) -> Self {
+        let error = js_sys::Error::new(&e.to_string());
+        error.set_name(e.name());
+        error.into()
+    }
+}
+
+impl From<CaptureError> for JsValue {
+    fn from(e: CaptureError) -> Self {
+        let error = js_sys::Error::new(&e.to_string());
+        error.set_name(e.name());
+        error.into()
+    }
+}
@@ -0,0 +1,183 @@
+use wgpu::TextureFormat;
+
+use crate::SrgbMode;
+use crate::log::log;
+
+/// `FormatSelector::default` 의 우선순위. 셰이더가 선형 색을 쓰면 GPU 가 sRGB 로 인코딩하는 포맷이다
+pub const SRGB_SURFACE_FORMATS: &[TextureFormat] =
+    &[TextureFormat::Bgra8UnormSrgb, TextureFormat::Rgba8UnormSrgb];
+
+// for_srgb_mode 의 우선순위. 원하는 변형이 없으면 반대 변형을 골라서 뷰 포맷만 바꾼다
+const SRGB_FIRST_FORMATS: &[TextureFormat] = &[
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Rgba8Unorm,
+];
+const LINEAR_FIRST_FORMATS: &[TextureFormat] = &[
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::Rgba8UnormSrgb,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormatSelectionResult {
+    pub chosen: TextureFormat,
+    /// 우선순위 목록의 첫 번째 포맷을 골랐는지
+    pub was_first_choice: bool,
+}
+
+/// 서피스가 지원하는 포맷 중에서 우선순위 목록의 앞쪽에 있는 포맷을 고른다.
+///
+/// 목록의 포맷을 하나도 지원하지 않으면 지원 목록의 첫 번째 포맷을 쓰되, 색이 틀릴 수 있다고
+/// 경고를 남긴다. 고른 포맷은 항상 콘솔에 남긴다.
+///
+/// ```ignore
+/// let surface_format = FormatSelector::default()
+///     .select(&surface_caps.formats)
+///     .chosen;
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FormatSelector<'a> {
+    priorities: &'a [TextureFormat],
+}
+
+impl Default for FormatSelector<'static> {
+    fn default() -> Self {
+        Self::new(SRGB_SURFACE_FORMATS)
+    }
+}
+
+impl FormatSelector<'static> {
+    /// `mode` 의 변형을 먼저 고르고, 없으면 반대 변형의 8비트 포맷을 고른다.
+    ///
+    /// WebGPU 캔버스처럼 `Bgra8Unorm` 만 지원하면 서피스는 그대로 구성하고 렌더 타깃으로 쓸 뷰만
+    /// `mode.apply(chosen)` 으로 만든다. 이때 뷰 포맷을 `SurfaceConfiguration::view_formats` 에 넣고
+    /// 서피스 텍스처의 뷰를 그 포맷으로 만들어야 한다.
+    pub fn for_srgb_mode(mode: SrgbMode) -> Self {
+        match mode {
+            SrgbMode::Enabled => Self::new(SRGB_FIRST_FORMATS),
+            SrgbMode::Disabled => Self::new(LINEAR_FIRST_FORMATS),
+        }
+    }
+}
+
+impl<'a> FormatSelector<'a> {
+    /// `priorities` 는 원하는 순서대로 나열한 포맷이다.
+    pub fn new(priorities: &'a [TextureFormat]) -> Self {
+        Self { priorities }
+    }
+
+    /// `available` 은 `SurfaceCapabilities::formats` 이다.
+    ///
+    /// # Panics
+    ///
+    /// `available` 이 비어 있으면, 즉 서피스를 이 어댑터로 구성할 수 없으면 panic 한다.
+    pub fn select(&self, available: &[TextureFormat]) -> FormatSelectionResult {
+        let result = self.rank(available);
+        match self.priorities.iter().position(|&f| f == result.chosen) {
+            Some(rank) => log(
+                &format!(
+                    "Surface format: {:?} (priority {} of {})",
+                    result.chosen,
+                    rank + 1,
+                    self.priorities.len()
+                ),
+                false,
+            ),
+            None => log(
+                &format!(
+                    "None of the preferred surface formats {:?} are supported by {:?}; \
+                     falling back to {:?}, colors may be wrong",
+                    self.priorities, available, result.chosen
+                ),
+                true,
+            ),
+        }
+        result
+    }
+
+    fn rank(&self, available: &[TextureFormat]) -> FormatSelectionResult {
+        assert!(!available.is_empty(), "surface supports no formats");
+
+        let chosen = self
+            .priorities
+            .iter()
+            .copied()
+            .find(|format| available.contains(format))
+            .unwrap_or(available[0]);
+        FormatSelectionResult {
+            chosen,
+            was_first_choice: self.priorities.first() == Some(&chosen),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_highest_priority_available_format() {
+        let available = [
+            TextureFormat::Rgba16Float,
+            TextureFormat::Rgba8UnormSrgb,
+            TextureFormat::Bgra8UnormSrgb,
+        ];
+        assert_eq!(
+            FormatSelector::default().rank(&available),
+            FormatSelectionResult {
+                chosen: TextureFormat::Bgra8UnormSrgb,
+                was_first_choice: true,
+            }
+        );
+
+        // 순서는 지원 목록이 아니라 우선순위 목록을 따른다
+        let selector = FormatSelector::new(&[
+            TextureFormat::Rgb10a2Unorm,
+            TextureFormat::Rgba8UnormSrgb,
+            TextureFormat::Bgra8UnormSrgb,
+        ]);
+        assert_eq!(
+            selector.rank(&available),
+            FormatSelectionResult {
+                chosen: TextureFormat::Rgba8UnormSrgb,
+                was_first_choice: false,
+            }
+        );
+    }
+
+    #[test]
+    fn falls_back_to_first_available_format() {
+        // WebGPU 캔버스는 sRGB 포맷을 지원하지 않는다
+        let available = [TextureFormat::Bgra8Unorm, TextureFormat::Rgba8Unorm];
+        let result = FormatSelector::default().select(&available);
+        assert_eq!(result.chosen, TextureFormat::Bgra8Unorm);
+        assert!(!result.was_first_choice);
+
+        // 빈 우선순위 목록도 지원 목록의 첫 번째를 고른다
+        let result = FormatSelector::new(&[]).rank(&available);
+        assert_eq!(result.chosen, TextureFormat::Bgra8Unorm);
+        assert!(!result.was_first_choice);
+    }
+
+    #[test]
+    fn srgb_mode_prefers_matching_variant() {
+        let available = [TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb];
+        let chosen = |mode| FormatSelector::for_srgb_mode(mode).rank(&available).chosen;
+        assert_eq!(chosen(SrgbMode::Enabled), TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(chosen(SrgbMode::Disabled), TextureFormat::Bgra8Unorm);
+    }
+
+    #[test]
+    fn srgb_mode_falls_back_to_srgb_view_format() {
+        let available = [TextureFormat::Bgra8Unorm];
+        let result = FormatSelector::for_srgb_mode(SrgbMode::Enabled).rank(&available);
+        assert_eq!(result.chosen, TextureFormat::Bgra8Unorm);
+        assert_eq!(
+            SrgbMode::Enabled.apply(result.chosen),
+            TextureFormat::Bgra8UnormSrgb
+        );
+    }
+}
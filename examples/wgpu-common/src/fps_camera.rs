@@ -0,0 +1,147 @@
+use glam::{Mat4, Vec3};
+
+use crate::{Camera, InputState};
+
+/// `FpsCamera::pitch` 의 한계 (라디안). 90° 가 되면 위쪽 벡터와 시선이 겹쳐서 ±89° 로 막는다
+pub const MAX_PITCH: f32 = 89.0 * std::f32::consts::PI / 180.0;
+
+// 마우스 1 CSS 픽셀에 도는 각도 (라디안)
+const DEFAULT_LOOK_SENSITIVITY: f32 = 0.002;
+
+/// 마우스로 둘러보고 WASD 로 걷는 1인칭 카메라.
+///
+/// `yaw` 가 0 이면 -z 를 보고, 양수면 왼쪽(위에서 봤을 때 반시계 방향)으로 돈다.
+/// `pitch` 가 양수면 위를 본다. 앞뒤, 좌우 이동은 바닥(xz 평면) 위에서만 하고
+/// E 와 Q 로 오르내린다.
+#[derive(Clone, Copy, Debug)]
+pub struct FpsCamera {
+    pub position: Vec3,
+    /// 라디안
+    pub yaw: f32,
+    /// 라디안. `MAX_PITCH` 로 제한된다
+    pub pitch: f32,
+    /// 초당 이동 거리
+    pub move_speed: f32,
+    /// 마우스 1 CSS 픽셀에 도는 각도 (라디안)
+    pub look_sensitivity: f32,
+}
+
+impl FpsCamera {
+    pub fn new(position: Vec3, yaw: f32, pitch: f32) -> Self {
+        Self {
+            position,
+            yaw,
+            pitch: pitch.clamp(-MAX_PITCH, MAX_PITCH),
+            move_speed: 3.0,
+            look_sensitivity: DEFAULT_LOOK_SENSITIVITY,
+        }
+    }
+
+    /// 바라보는 방향의 단위 벡터
+    pub fn forward(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        Vec3::new(-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch)
+    }
+
+    /// 바닥 위에서 오른쪽 방향의 단위 벡터
+    pub fn right(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        Vec3::new(cos_yaw, 0.0, -sin_yaw)
+    }
+
+    /// 마우스 이동량(CSS 픽셀)만큼 돌린다. 오른쪽, 아래로 움직이면 오른쪽, 아래를 본다.
+    pub fn look(&mut self, delta: (f32, f32)) {
+        self.yaw -= delta.0 * self.look_sensitivity;
+        self.pitch = (self.pitch - delta.1 * self.look_sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// 카메라 기준 (오른쪽, 위, 앞) 방향으로 `amount` 만큼 옮긴다. 앞은 바닥 위의 방향이다.
+    pub fn translate_local(&mut self, amount: Vec3) {
+        let ahead = Vec3::Y.cross(self.right());
+        self.position += self.right() * amount.x + Vec3::Y * amount.y + ahead * amount.z;
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_to_rh(self.position, self.forward(), Vec3::Y)
+    }
+
+    /// 포인터 락이 걸려 있으면 마우스 이동량으로 둘러보고, 눌린 키로 `dt` 초만큼 걷는다.
+    /// 뷰 행렬을 반환한다. 이동량을 비우는 `end_frame` 은 호출하는 쪽에서 부른다.
+    pub fn update(&mut self, dt: f32, input: &InputState) -> Mat4 {
+        if input.is_pointer_locked() {
+            self.look(input.mouse_delta());
+        }
+
+        let axis = |positive: &str, negative: &str| {
+            input.is_key_down(positive) as i32 as f32 - input.is_key_down(negative) as i32 as f32
+        };
+        let direction = Vec3::new(
+            axis("KeyD", "KeyA"),
+            axis("KeyE", "KeyQ"),
+            axis("KeyW", "KeyS"),
+        );
+        // 대각선으로 더 빨리 가지 않도록 정규화한다
+        self.translate_local(direction.normalize_or_zero() * self.move_speed * dt);
+
+        self.view_matrix()
+    }
+
+    /// 같은 위치와 방향의 원근 카메라. `CameraUniform::from` 에 넘길 때 쓴다
+    pub fn camera(&self, aspect: f32) -> Camera {
+        Camera::new(self.position, self.position + self.forward(), aspect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{a} != {b}");
+    }
+
+    #[test]
+    fn yaw_zero_looks_down_negative_z() {
+        let camera = FpsCamera::new(Vec3::ZERO, 0.0, 0.0);
+        assert_near(camera.forward(), Vec3::NEG_Z);
+        assert_near(camera.right(), Vec3::X);
+
+        let turned = FpsCamera::new(Vec3::ZERO, std::f32::consts::FRAC_PI_2, 0.0);
+        assert_near(turned.forward(), Vec3::NEG_X);
+    }
+
+    #[test]
+    fn pitch_is_clamped() {
+        let mut camera = FpsCamera::new(Vec3::ZERO, 0.0, 0.0);
+        camera.look((0.0, -100_000.0));
+        assert_eq!(camera.pitch, MAX_PITCH);
+        camera.look((0.0, 100_000.0));
+        assert_eq!(camera.pitch, -MAX_PITCH);
+
+        // 마우스를 오른쪽으로 움직이면 오른쪽(-yaw)을 본다
+        camera.look((10.0, 0.0));
+        assert!(camera.yaw < 0.0);
+    }
+
+    #[test]
+    fn walking_stays_on_the_floor() {
+        let mut camera = FpsCamera::new(Vec3::new(0.0, 1.7, 0.0), 0.0, 0.5);
+        camera.translate_local(Vec3::new(0.0, 0.0, 2.0));
+        assert_near(camera.position, Vec3::new(0.0, 1.7, -2.0));
+        camera.translate_local(Vec3::new(1.0, 0.5, 0.0));
+        assert_near(camera.position, Vec3::new(1.0, 2.2, -2.0));
+    }
+
+    #[test]
+    fn view_matrix_matches_camera() {
+        let camera = FpsCamera::new(Vec3::new(2.0, 1.0, 5.0), 0.7, -0.3);
+        let view = camera.view_matrix();
+        assert_near(view.transform_point3(camera.position), Vec3::ZERO);
+        assert_near(view.transform_vector3(camera.forward()), Vec3::NEG_Z);
+        assert!(
+            view.abs_diff_eq(camera.camera(1.0).view_matrix(), 1e-5),
+            "{view}"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use web_sys::console;
+
+/// 평균 FPS 를 계산할 때 사용하는 최근 프레임 수
+pub const SAMPLE_COUNT: usize = 60;
+/// 디버그 로그를 남기는 간격 (밀리초)
+const LOG_INTERVAL_MS: f64 = 5000.0;
+
+/// `performance.now()` (네이티브에서는 `Instant`) 로 프레임 시간과 FPS 를 측정한다.
+///
+/// 매 프레임 시작에 `begin_frame`, 끝에 `end_frame` 을 호출한다.
+pub struct FrameTimer {
+    frame_start: Option<f64>,
+    // 마지막 프레임의 시작부터 끝까지 걸린 시간 (밀리초)
+    frame_time_ms: f64,
+    delta_secs: f32,
+    samples: [f32; SAMPLE_COUNT],
+    sample_index: usize,
+    sample_len: usize,
+    frame_count: u64,
+    debug: bool,
+    last_log: f64,
+}
+
+impl Default for FrameTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameTimer {
+    pub fn new() -> Self {
+        Self {
+            frame_start: None,
+            frame_time_ms: 0.0,
+            delta_secs: 0.0,
+            samples: [0.0; SAMPLE_COUNT],
+            sample_index: 0,
+            sample_len: 0,
+            frame_count: 0,
+            debug: false,
+            last_log: now_ms(),
+        }
+    }
+
+    /// 켜져 있으면 5초마다 콘솔에 FPS 를 출력한다.
+    pub fn set_debug(&mut self, enabled: bool) {
+        self.debug = enabled;
+    }
+
+    pub fn begin_frame(&mut self) {
+        let now = now_ms();
+
+        // 이전 프레임 시작 시각과의 차이가 이번 프레임의 델타 시간이다
+        if let Some(previous) = self.frame_start {
+            self.delta_secs = ((now - previous) / 1000.0) as f32;
+            self.samples[self.sample_index] = self.delta_secs;
+            self.sample_index = (self.sample_index + 1) % SAMPLE_COUNT;
+            self.sample_len = (self.sample_len + 1).min(SAMPLE_COUNT);
+        }
+
+        self.frame_start = Some(now);
+    }
+
+    pub fn end_frame(&mut self) {
+        let now = now_ms();
+        if let Some(start) = self.frame_start {
+            self.frame_time_ms = now - start;
+        }
+        self.frame_count += 1;
+
+        if self.debug && now - self.last_log >= LOG_INTERVAL_MS {
+            self.last_log = now;
+            console::log_1(
+                &format!(
+                    "FPS: {:.1} (frame time {:.2} ms)",
+                    self.fps(),
+                    self.frame_time_ms
+                )
+                .into(),
+            );
+        }
+    }
+
+    /// 직전 프레임과의 시간 차이 (초)
+    pub fn delta_secs(&self) -> f32 {
+        self.delta_secs
+    }
+
+    /// 최근 `SAMPLE_COUNT` 프레임의 평균 델타 시간 (초)
+    pub fn average_delta_secs(&self) -> f32 {
+        if self.sample_len == 0 {
+            return 0.0;
+        }
+        self.samples[..self.sample_len].iter().sum::<f32>() / self.sample_len as f32
+    }
+
+    /// 평균 델타 시간으로 계산한 FPS
+    pub fn fps(&self) -> f32 {
+        let average = self.average_delta_secs();
+        if average > 0.0 { 1.0 / average } else { 0.0 }
+    }
+
+    /// `begin_frame` 부터 `end_frame` 까지 걸린 시간 (밀리초)
+    pub fn frame_time_ms(&self) -> f64 {
+        self.frame_time_ms
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now_ms() -> f64 {
+    web_sys::window().unwrap().performance().unwrap().now()
+}
+
+// 네이티브에는 performance 가 없으므로 처음 호출한 시점부터의 경과 시간을 쓴다
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now_ms() -> f64 {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    START
+        .get_or_init(std::time::Instant::now)
+        .elapsed()
+        .as_secs_f64()
+        * 1000.0
+}
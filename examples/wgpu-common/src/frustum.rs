@@ -0,0 +1,97 @@
+use glam::{Mat4, Vec3, Vec4};
+
+/// 월드 공간 축 정렬 바운딩 박스
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// 점들을 모두 감싸는 가장 작은 박스. 점이 없으면 `None`.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |aabb, point| {
+            Self::new(aabb.min.min(point), aabb.max.max(point))
+        }))
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// 두 박스를 모두 감싸는 가장 작은 박스
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        let size = (self.max - self.min).max(Vec3::ZERO);
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+}
+
+/// `normal · p + distance >= 0` 인 쪽을 안쪽으로 보는 평면
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+impl Plane {
+    // (a, b, c, d) 를 법선 길이가 1 이 되도록 정규화한다
+    fn from_vec4(v: Vec4) -> Self {
+        let length = v.truncate().length();
+        Self {
+            normal: v.truncate() / length,
+            distance: v.w / length,
+        }
+    }
+
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) + self.distance
+    }
+}
+
+/// 뷰-투영 행렬에서 뽑아낸 6개의 절두체 평면 (왼쪽, 오른쪽, 아래, 위, 가까운, 먼)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// 클립 공간 깊이 범위가 [0, 1] 인 wgpu 의 투영 행렬을 기준으로 한다.
+    pub fn from_view_projection(view_proj: Mat4) -> Self {
+        let (r0, r1, r2, r3) = (
+            view_proj.row(0),
+            view_proj.row(1),
+            view_proj.row(2),
+            view_proj.row(3),
+        );
+
+        Self {
+            planes: [
+                Plane::from_vec4(r3 + r0),
+                Plane::from_vec4(r3 - r0),
+                Plane::from_vec4(r3 + r1),
+                Plane::from_vec4(r3 - r1),
+                Plane::from_vec4(r2),
+                Plane::from_vec4(r3 - r2),
+            ],
+        }
+    }
+
+    /// 박스가 절두체와 조금이라도 겹치면 `true`.
+    /// 평면마다 법선 방향으로 가장 먼 꼭짓점만 검사하므로 보수적으로 `true` 가 나올 수 있다.
+    pub fn test_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            let farthest = Vec3::select(plane.normal.cmpge(Vec3::ZERO), aabb.max, aabb.min);
+            plane.signed_distance(farthest) >= 0.0
+        })
+    }
+}
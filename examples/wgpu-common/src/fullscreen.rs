@@ -0,0 +1,32 @@
+/// 뷰포트 전체를 덮는 삼각형 하나를 그린다.
+///
+/// 후처리 패스의 프래그먼트 셰이더는 `@builtin(position)` 과 `@location(0) uv` 를
+/// 입력으로 받으면 된다. 버텍스 버퍼 없이 `vertex_index` 로 꼭짓점을 만든다.
+pub struct FullscreenTriangle {
+    shader: wgpu::ShaderModule,
+}
+
+impl FullscreenTriangle {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Fullscreen Triangle Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("fullscreen.wgsl").into()),
+        });
+
+        Self { shader }
+    }
+
+    /// 렌더 파이프라인의 `vertex` 에 그대로 넣는다.
+    pub fn vertex_state(&self) -> wgpu::VertexState<'_> {
+        wgpu::VertexState {
+            module: &self.shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }
+    }
+
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,16 @@
+struct FullscreenOutput {
+    @builtin(position) position: vec4<f32>,
+    // 왼쪽 위가 (0, 0) 인 텍스처 좌표
+    @location(0) uv: vec2<f32>,
+};
+
+// 버텍스 버퍼 없이 화면을 덮는 삼각형 하나를 만든다.
+// 꼭짓점은 (-1, -1), (3, -1), (-1, 3) 이므로 [-1, 1] 범위를 두 배로 덮는다
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    var out: FullscreenOutput;
+    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return out;
+}
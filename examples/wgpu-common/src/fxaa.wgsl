@@ -0,0 +1,78 @@
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+// 버텍스 셰이더는 FullscreenTriangle 이 제공한다
+struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// 주변 밝기 차이가 이 값보다 작으면 경계로 보지 않는다
+const EDGE_THRESHOLD_MIN: f32 = 0.0312;
+// 밝은 곳일수록 큰 차이가 있어야 경계로 본다
+const EDGE_THRESHOLD_MAX: f32 = 0.125;
+// 경계 방향으로 샘플링하는 최대 거리 (텍셀)
+const SPAN_MAX: f32 = 8.0;
+const REDUCE_MUL: f32 = 0.125;
+const REDUCE_MIN: f32 = 0.0078125;
+
+// HDR 입력에서도 경계를 찾을 수 있도록 Reinhard 로 [0, 1] 범위에 넣은 뒤 밝기를 구한다
+fn luma(color: vec3<f32>) -> f32 {
+    let mapped = color / (1.0 + color);
+    return dot(mapped, vec3<f32>(0.299, 0.587, 0.114));
+}
+
+// 일찍 반환하는 분기 뒤에서도 읽을 수 있도록 textureSample 대신 textureSampleLevel 을 쓴다
+fn sample(uv: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(input_texture, input_sampler, uv, 0.0);
+}
+
+// Fragment shader
+// 대각선 네 텍셀의 밝기로 경계 방향을 구하고, 그 방향을 따라 여러 번 읽어서 평균을 낸다
+@fragment
+fn fs_main(in: FragmentInput) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(input_texture));
+    let center = sample(in.uv);
+
+    let luma_m = luma(center.rgb);
+    let luma_nw = luma(sample(in.uv + vec2<f32>(-1.0, -1.0) * texel).rgb);
+    let luma_ne = luma(sample(in.uv + vec2<f32>(1.0, -1.0) * texel).rgb);
+    let luma_sw = luma(sample(in.uv + vec2<f32>(-1.0, 1.0) * texel).rgb);
+    let luma_se = luma(sample(in.uv + vec2<f32>(1.0, 1.0) * texel).rgb);
+
+    let luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    let luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+    if luma_max - luma_min < max(EDGE_THRESHOLD_MIN, luma_max * EDGE_THRESHOLD_MAX) {
+        return center;
+    }
+
+    var direction = vec2<f32>(
+        -((luma_nw + luma_ne) - (luma_sw + luma_se)),
+        (luma_nw + luma_sw) - (luma_ne + luma_se),
+    );
+    let direction_reduce = max(
+        (luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * REDUCE_MUL,
+        REDUCE_MIN,
+    );
+    let scale = 1.0 / (min(abs(direction.x), abs(direction.y)) + direction_reduce);
+    direction = clamp(direction * scale, vec2<f32>(-SPAN_MAX), vec2<f32>(SPAN_MAX)) * texel;
+
+    // 가까운 두 점의 평균과, 먼 두 점까지 더한 평균
+    let near = 0.5 * (
+        sample(in.uv + direction * (1.0 / 3.0 - 0.5)).rgb +
+        sample(in.uv + direction * (2.0 / 3.0 - 0.5)).rgb
+    );
+    let far = near * 0.5 + 0.25 * (
+        sample(in.uv - direction * 0.5).rgb +
+        sample(in.uv + direction * 0.5).rgb
+    );
+
+    // 먼 점이 다른 물체에 걸려서 범위를 벗어나면 가까운 평균을 쓴다
+    let luma_far = luma(far);
+    if luma_far < luma_min || luma_far > luma_max {
+        return vec4<f32>(near, center.a);
+    }
+    return vec4<f32>(far, center.a);
+}
@@ -0,0 +1,346 @@
+use glam::{Mat3, Mat4, Vec3};
+
+use crate::error::GltfError;
+use crate::{GpuContext, Mesh, TangentVertex, Texture, Vertex};
+
+/// glTF 프리미티브에서 읽은 버텍스
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GltfVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub tex_coords: [f32; 2],
+    /// u 가 커지는 방향. w 는 바이탄젠트 방향(+1 또는 -1)이다
+    pub tangent: [f32; 4],
+}
+
+impl Vertex for GltfVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &wgpu::vertex_attr_array![
+        0 => Float32x3,
+        1 => Float32x3,
+        2 => Float32x2,
+        3 => Float32x4,
+    ];
+}
+
+impl TangentVertex for GltfVertex {
+    fn position(&self) -> [f32; 3] {
+        self.position
+    }
+
+    fn normal(&self) -> [f32; 3] {
+        self.normal
+    }
+
+    fn tex_coords(&self) -> [f32; 2] {
+        self.tex_coords
+    }
+
+    fn set_tangent(&mut self, tangent: [f32; 4]) {
+        self.tangent = tangent;
+    }
+}
+
+/// GPU 에 올린 프리미티브 하나와 그 재질의 기본 색
+#[derive(Clone)]
+pub struct GltfMesh {
+    pub mesh: Mesh,
+    /// 텍스처 색에 곱하는 선형 RGBA
+    pub base_color_factor: [f32; 4],
+    /// `TEXCOORD_0` 으로 샘플링하는 sRGB 텍스처
+    pub base_color_texture: Option<Texture>,
+}
+
+// GPU 에 올리기 전의 프리미티브
+#[derive(Debug)]
+struct Primitive {
+    vertices: Vec<GltfVertex>,
+    indices: Vec<u32>,
+    base_color_factor: [f32; 4],
+    // 베이스 컬러 텍스처가 가리키는 이미지 인덱스
+    base_color_image: Option<usize>,
+}
+
+/// `.gltf` 나 `.glb` 파일의 메시를 `Mesh` 로 올린다.
+///
+/// 기본 씬(없으면 첫 번째 씬)의 노드를 따라가면서 노드 변환을 버텍스에 미리 곱해 두므로
+/// 반환된 메시는 모두 모델 공간 기준으로 그리면 된다. 삼각형이 아닌 프리미티브는 건너뛴다.
+pub struct GltfLoader;
+
+impl GltfLoader {
+    /// 외부 파일을 참조하지 않는 glTF(`.glb` 나 data URI 를 쓰는 `.gltf`)를 불러온다.
+    pub fn load(bytes: &[u8], gpu: &GpuContext) -> Result<Vec<GltfMesh>, GltfError> {
+        let (document, buffers, images) = gltf::import_slice(bytes)?;
+        upload(&document, &buffers, &images, gpu)
+    }
+
+    /// 파일 옆의 `.bin` 이나 이미지 파일도 함께 읽는다.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_file(
+        path: impl AsRef<std::path::Path>,
+        gpu: &GpuContext,
+    ) -> Result<Vec<GltfMesh>, GltfError> {
+        let (document, buffers, images) = gltf::import(path)?;
+        upload(&document, &buffers, &images, gpu)
+    }
+
+    /// `fetch` 로 받은 파일을 `load` 로 불러온다.
+    pub async fn load_url(url: &str, gpu: &GpuContext) -> Result<Vec<GltfMesh>, GltfError> {
+        let bytes = crate::asset::fetch_bytes(url).await?;
+        Self::load(&bytes, gpu)
+    }
+}
+
+fn upload(
+    document: &gltf::Document,
+    buffers: &[gltf::buffer::Data],
+    images: &[gltf::image::Data],
+    gpu: &GpuContext,
+) -> Result<Vec<GltfMesh>, GltfError> {
+    // 여러 재질이 같은 이미지를 쓰면 텍스처도 하나만 만든다
+    let mut textures: Vec<Option<Texture>> = vec![None; images.len()];
+
+    read_primitives(document, buffers)?
+        .into_iter()
+        .map(|primitive| {
+            let base_color_texture = match primitive.base_color_image {
+                Some(index) => {
+                    if textures[index].is_none() {
+                        let image = to_rgba_image(&images[index])?;
+                        let label = format!("glTF Image {}", index);
+                        textures[index] = Some(Texture::from_image(
+                            gpu,
+                            &image::DynamicImage::ImageRgba8(image),
+                            &label,
+                        ));
+                    }
+                    textures[index].clone()
+                }
+                None => None,
+            };
+
+            Ok(GltfMesh {
+                mesh: Mesh::new(gpu, &primitive.vertices, &primitive.indices),
+                base_color_factor: primitive.base_color_factor,
+                base_color_texture,
+            })
+        })
+        .collect()
+}
+
+fn read_primitives(
+    document: &gltf::Document,
+    buffers: &[gltf::buffer::Data],
+) -> Result<Vec<Primitive>, GltfError> {
+    let mut primitives = Vec::new();
+    let Some(scene) = document
+        .default_scene()
+        .or_else(|| document.scenes().next())
+    else {
+        return Ok(primitives);
+    };
+
+    let mut stack: Vec<(gltf::Node, Mat4)> =
+        scene.nodes().map(|node| (node, Mat4::IDENTITY)).collect();
+    while let Some((node, parent)) = stack.pop() {
+        let world = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+        if let Some(mesh) = node.mesh() {
+            for primitive in mesh.primitives() {
+                if primitive.mode() == gltf::mesh::Mode::Triangles {
+                    primitives.push(read_primitive(&mesh, &primitive, buffers, world)?);
+                }
+            }
+        }
+        stack.extend(node.children().map(|child| (child, world)));
+    }
+
+    Ok(primitives)
+}
+
+fn read_primitive(
+    mesh: &gltf::Mesh,
+    primitive: &gltf::Primitive,
+    buffers: &[gltf::buffer::Data],
+    world: Mat4,
+) -> Result<Primitive, GltfError> {
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|data| &data[..]));
+
+    let positions: Vec<Vec3> = reader
+        .read_positions()
+        .ok_or(GltfError::MissingPositions(mesh.index()))?
+        .map(|position| world.transform_point3(Vec3::from(position)))
+        .collect();
+    let mut indices: Vec<u32> = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect(),
+        None => (0..positions.len() as u32).collect(),
+    };
+    // 음수 스케일이 들어간 노드는 앞면 방향이 뒤집히므로 감는 순서를 되돌린다
+    if world.determinant() < 0.0 {
+        for triangle in indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+
+    let normal_matrix = Mat3::from_mat4(world).inverse().transpose();
+    let normals: Vec<Vec3> = match reader.read_normals() {
+        Some(normals) => normals
+            .map(|normal| (normal_matrix * Vec3::from(normal)).normalize_or_zero())
+            .collect(),
+        None => smooth_normals(&positions, &indices),
+    };
+    let tex_coords: Vec<[f32; 2]> = match reader.read_tex_coords(0) {
+        Some(tex_coords) => tex_coords.into_f32().collect(),
+        None => vec![[0.0, 0.0]; positions.len()],
+    };
+
+    let mut vertices: Vec<GltfVertex> = positions
+        .iter()
+        .zip(&normals)
+        .zip(&tex_coords)
+        .map(|((position, normal), tex_coords)| GltfVertex {
+            position: (*position).into(),
+            normal: (*normal).into(),
+            tex_coords: *tex_coords,
+            tangent: [1.0, 0.0, 0.0, 1.0],
+        })
+        .collect();
+
+    // 탄젠트가 없으면 노멀 맵을 구운 도구와 같은 MikkTSpace 로 만든다
+    match reader.read_tangents() {
+        Some(tangents) => {
+            // 음수 스케일은 바이탄젠트 방향도 뒤집는다
+            let handedness = world.determinant().signum();
+            let rotation = Mat3::from_mat4(world);
+            for (vertex, tangent) in vertices.iter_mut().zip(tangents) {
+                let direction = (rotation * Vec3::from_slice(&tangent)).normalize_or_zero();
+                vertex.tangent = direction.extend(tangent[3] * handedness).into();
+            }
+        }
+        None => {
+            crate::generate_tangents(&mut vertices, &indices);
+        }
+    }
+
+    let pbr = primitive.material().pbr_metallic_roughness();
+    Ok(Primitive {
+        vertices,
+        indices,
+        base_color_factor: pbr.base_color_factor(),
+        base_color_image: pbr
+            .base_color_texture()
+            .map(|info| info.texture().source().index()),
+    })
+}
+
+// 법선이 없으면 정점을 공유하는 삼각형들의 면 법선을 면적 가중치로 더해서 만든다
+fn smooth_normals(positions: &[Vec3], indices: &[u32]) -> Vec<Vec3> {
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| triangle[i] as usize);
+        let face = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+        for i in [a, b, c] {
+            normals[i] += face;
+        }
+    }
+    normals
+        .iter()
+        .map(|normal| normal.normalize_or_zero())
+        .collect()
+}
+
+fn to_rgba_image(data: &gltf::image::Data) -> Result<image::RgbaImage, GltfError> {
+    use gltf::image::Format;
+
+    let pixels = match data.format {
+        Format::R8G8B8A8 => data.pixels.clone(),
+        Format::R8G8B8 => data
+            .pixels
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+            .collect(),
+        Format::R8 => data.pixels.iter().flat_map(|&r| [r, r, r, 255]).collect(),
+        format => return Err(GltfError::UnsupportedImageFormat(format)),
+    };
+
+    image::RgbaImage::from_raw(data.width, data.height, pixels)
+        .ok_or(GltfError::UnsupportedImageFormat(data.format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (0,0,0), (1,0,0), (0,1,0) 삼각형 하나. 법선과 인덱스가 없고 노드가 x 로 2 만큼 이동한다
+    const TRIANGLE_GLTF: &str = r#"{
+        "asset": { "version": "2.0" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0, "translation": [2.0, 0.0, 0.0] }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }],
+        "accessors": [{
+            "bufferView": 0,
+            "componentType": 5126,
+            "count": 3,
+            "type": "VEC3",
+            "min": [0.0, 0.0, 0.0],
+            "max": [1.0, 1.0, 0.0]
+        }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "buffers": [{
+            "byteLength": 36,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAA"
+        }]
+    }"#;
+
+    #[test]
+    fn reads_triangle_with_node_transform() {
+        let (document, buffers, _) = gltf::import_slice(TRIANGLE_GLTF.as_bytes()).unwrap();
+        let primitives = read_primitives(&document, &buffers).unwrap();
+
+        assert_eq!(primitives.len(), 1);
+        let primitive = &primitives[0];
+        assert_eq!(primitive.indices, vec![0, 1, 2]);
+        let positions: Vec<_> = primitive.vertices.iter().map(|v| v.position).collect();
+        assert_eq!(
+            positions,
+            vec![[2.0, 0.0, 0.0], [3.0, 0.0, 0.0], [2.0, 1.0, 0.0]]
+        );
+        // 기본 재질은 흰색이고 텍스처가 없다
+        assert_eq!(primitive.base_color_factor, [1.0; 4]);
+        assert_eq!(primitive.base_color_image, None);
+    }
+
+    #[test]
+    fn generates_normals_when_missing() {
+        let (document, buffers, _) = gltf::import_slice(TRIANGLE_GLTF.as_bytes()).unwrap();
+        let primitives = read_primitives(&document, &buffers).unwrap();
+
+        for vertex in &primitives[0].vertices {
+            assert_eq!(vertex.normal, [0.0, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn generates_tangents_when_missing() {
+        let (document, buffers, _) = gltf::import_slice(TRIANGLE_GLTF.as_bytes()).unwrap();
+        let primitives = read_primitives(&document, &buffers).unwrap();
+
+        // UV 가 모두 0 이라 방향은 정할 수 없지만 바이탄젠트 방향은 ±1 이어야 한다
+        for vertex in &primitives[0].vertices {
+            assert_eq!(vertex.tangent[3].abs(), 1.0);
+        }
+    }
+
+    #[test]
+    fn expands_rgb_images_to_rgba() {
+        let data = gltf::image::Data {
+            pixels: vec![10, 20, 30, 40, 50, 60],
+            format: gltf::image::Format::R8G8B8,
+            width: 2,
+            height: 1,
+        };
+
+        let image = to_rgba_image(&data).unwrap();
+        assert_eq!(image.into_raw(), vec![10, 20, 30, 255, 40, 50, 60, 255]);
+    }
+}
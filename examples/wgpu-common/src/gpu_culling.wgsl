@@ -0,0 +1,63 @@
+// gpu_driven.rs 의 CullParams 와 같은 레이아웃
+struct CullParams {
+    // normal.xyz, distance
+    planes: array<vec4<f32>, 6>,
+    instance_count: u32,
+};
+
+struct Bounds {
+    min: vec4<f32>,
+    max: vec4<f32>,
+};
+
+struct Instance {
+    model: mat4x4<f32>,
+};
+
+// wgpu::util::DrawIndexedIndirectArgs 와 같은 레이아웃
+struct DrawIndexedArgs {
+    index_count: u32,
+    instance_count: atomic<u32>,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> params: CullParams;
+
+@group(0) @binding(1)
+var<storage, read> instances: array<Instance>;
+
+@group(0) @binding(2)
+var<storage, read> bounds: array<Bounds>;
+
+@group(0) @binding(3)
+var<storage, read_write> visible_instances: array<Instance>;
+
+@group(0) @binding(4)
+var<storage, read_write> draw_args: DrawIndexedArgs;
+
+// Frustum::test_aabb 와 같다. 평면마다 법선 방향으로 가장 먼 꼭짓점만 검사한다
+fn is_visible(aabb: Bounds) -> bool {
+    for (var i = 0u; i < 6u; i++) {
+        let plane = params.planes[i];
+        let farthest = select(aabb.min.xyz, aabb.max.xyz, plane.xyz >= vec3<f32>(0.0));
+        if dot(plane.xyz, farthest) + plane.w < 0.0 {
+            return false;
+        }
+    }
+    return true;
+}
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= params.instance_count || !is_visible(bounds[index]) {
+        return;
+    }
+
+    // 보이는 인스턴스만 앞에서부터 채우고 그 수를 그리기 인자에 더한다
+    let slot = atomicAdd(&draw_args.instance_count, 1u);
+    visible_instances[slot] = instances[index];
+}
@@ -0,0 +1,215 @@
+use wgpu::util::DeviceExt;
+
+use crate::{Aabb, BindGroupBuilder, ComputePass, Frustum, InstanceRaw, Mesh, Transform};
+
+// gpu_culling.wgsl 의 @workgroup_size 와 같아야 한다
+const WORKGROUP_SIZE: u32 = 64;
+
+// gpu_culling.wgsl 의 CullParams 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct CullParams {
+    planes: [[f32; 4]; 6],
+    instance_count: u32,
+    _padding: [u32; 3],
+}
+
+impl CullParams {
+    fn new(frustum: &Frustum, instance_count: u32) -> Self {
+        Self {
+            planes: frustum
+                .planes
+                .map(|plane| plane.normal.extend(plane.distance).into()),
+            instance_count,
+            _padding: [0; 3],
+        }
+    }
+}
+
+// gpu_culling.wgsl 의 Bounds. vec3 은 16 바이트로 정렬되므로 vec4 로 올린다
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuBounds {
+    min: [f32; 4],
+    max: [f32; 4],
+}
+
+impl From<&Aabb> for GpuBounds {
+    fn from(aabb: &Aabb) -> Self {
+        Self {
+            min: aabb.min.extend(0.0).into(),
+            max: aabb.max.extend(0.0).into(),
+        }
+    }
+}
+
+/// 같은 메시의 인스턴스를 컴퓨트 셰이더로 절두체 컬링하고 간접 그리기 한 번으로 그린다.
+///
+/// 모든 인스턴스의 모델 행렬과 월드 AABB 를 스토리지 버퍼에 올려 두고, `cull` 이 인스턴스마다
+/// 스레드 하나로 `Frustum::test_aabb` 와 같은 검사를 한다. 보이는 인스턴스는 출력 버퍼의 앞쪽에
+/// 모이고 그 수는 `DrawIndexedIndirectArgs::instance_count` 에 바로 더해지므로,
+/// CPU 는 인스턴스 수와 상관없이 `draw` 에서 `draw_indexed_indirect` 한 번만 기록한다.
+///
+/// 출력 버퍼는 `InstanceBuffer::layout()` 과 같은 레이아웃으로 1번 슬롯에 바인딩된다.
+/// 컴퓨트 셰이더가 필요하므로 WebGL2 에서는 동작하지 않는다.
+pub struct GpuDrivenRenderer {
+    mesh: Mesh,
+    instance_count: u32,
+    params_buffer: wgpu::Buffer,
+    visible_buffer: wgpu::Buffer,
+    indirect_buffer: wgpu::Buffer,
+    compute_pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+}
+
+impl GpuDrivenRenderer {
+    /// `instances` 는 인스턴스마다 변환과 그 변환을 적용한 월드 공간 바운딩 박스다.
+    pub fn new(device: &wgpu::Device, mesh: &Mesh, instances: &[(Transform, Aabb)]) -> Self {
+        let instance_count = instances.len() as u32;
+        // 빈 스토리지 버퍼는 바인딩할 수 없으므로 최소 하나는 만든다
+        let capacity = instances.len().max(1);
+
+        let mut transforms: Vec<InstanceRaw> = instances
+            .iter()
+            .map(|(transform, _)| InstanceRaw::from(transform))
+            .collect();
+        let mut bounds: Vec<GpuBounds> = instances
+            .iter()
+            .map(|(_, aabb)| GpuBounds::from(aabb))
+            .collect();
+        transforms.resize(capacity, bytemuck::Zeroable::zeroed());
+        bounds.resize(capacity, bytemuck::Zeroable::zeroed());
+
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Cull Params Buffer"),
+            size: std::mem::size_of::<CullParams>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GPU Cull Instance Buffer"),
+            contents: bytemuck::cast_slice(&transforms),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let bounds_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GPU Cull Bounds Buffer"),
+            contents: bytemuck::cast_slice(&bounds),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let visible_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Cull Visible Instance Buffer"),
+            size: (capacity * std::mem::size_of::<InstanceRaw>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::VERTEX,
+            mapped_at_creation: false,
+        });
+        let indirect_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Cull Indirect Buffer"),
+            size: std::mem::size_of::<wgpu::util::DrawIndexedIndirectArgs>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let (bind_group_layout, bind_group) = BindGroupBuilder::new()
+            .uniform_buffer(0, &params_buffer, wgpu::ShaderStages::COMPUTE)
+            .storage_buffer(1, &instance_buffer, true, wgpu::ShaderStages::COMPUTE)
+            .storage_buffer(2, &bounds_buffer, true, wgpu::ShaderStages::COMPUTE)
+            .storage_buffer(3, &visible_buffer, false, wgpu::ShaderStages::COMPUTE)
+            .storage_buffer(4, &indirect_buffer, false, wgpu::ShaderStages::COMPUTE)
+            .build(device, "GPU Cull Bind Group");
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("GPU Cull Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("gpu_culling.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("GPU Cull Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("GPU Cull Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        Self {
+            mesh: mesh.clone(),
+            instance_count,
+            params_buffer,
+            visible_buffer,
+            indirect_buffer,
+            compute_pipeline,
+            bind_group,
+        }
+    }
+
+    pub fn instance_count(&self) -> u32 {
+        self.instance_count
+    }
+
+    /// `frustum` 안의 인스턴스를 모으는 컴퓨트 패스를 기록한다.
+    /// 간접 그리기 인자의 인스턴스 수는 여기서 0 으로 되돌린 뒤 컴퓨트 셰이더가 다시 센다.
+    pub fn cull(&self, encoder: &mut wgpu::CommandEncoder, queue: &wgpu::Queue, frustum: &Frustum) {
+        let params = CullParams::new(frustum, self.instance_count);
+        let draw_args = wgpu::util::DrawIndexedIndirectArgs {
+            index_count: self.mesh.index_count(),
+            instance_count: 0,
+            first_index: 0,
+            base_vertex: 0,
+            first_instance: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+        queue.write_buffer(&self.indirect_buffer, 0, draw_args.as_bytes());
+
+        let dispatch = (
+            ComputePass::workgroup_count(self.instance_count.max(1), WORKGROUP_SIZE),
+            1,
+            1,
+        );
+        ComputePass::run(encoder, &self.compute_pipeline, &self.bind_group, dispatch);
+    }
+
+    /// 메시를 0번, 보이는 인스턴스를 1번 슬롯에 바인딩하고 간접 그리기를 한다.
+    /// 파이프라인과 바인드 그룹은 호출하기 전에 설정해 둔다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_vertex_buffer(0, self.mesh.vertex_buffer().slice(..));
+        render_pass.set_vertex_buffer(1, self.visible_buffer.slice(..));
+        render_pass.set_index_buffer(self.mesh.index_buffer().slice(..), self.mesh.index_format());
+        render_pass.draw_indexed_indirect(&self.indirect_buffer, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Mat4, Vec3};
+
+    use super::*;
+
+    #[test]
+    fn layouts_match_shader() {
+        // planes 6개 + instance_count 를 16 바이트로 채운 크기
+        assert_eq!(std::mem::size_of::<CullParams>(), 6 * 16 + 16);
+        assert_eq!(std::mem::size_of::<GpuBounds>(), 32);
+        assert_eq!(
+            std::mem::size_of::<wgpu::util::DrawIndexedIndirectArgs>(),
+            20
+        );
+    }
+
+    #[test]
+    fn packs_planes_as_normal_and_distance() {
+        let frustum =
+            Frustum::from_view_projection(Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0));
+        let params = CullParams::new(&frustum, 3);
+        assert_eq!(params.instance_count, 3);
+        for (packed, plane) in params.planes.iter().zip(&frustum.planes) {
+            assert_eq!(Vec3::from_slice(packed), plane.normal);
+            assert_eq!(packed[3], plane.distance);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::GpuContext;
+
+const TIMESTAMP_SIZE: wgpu::BufferAddress = std::mem::size_of::<u64>() as wgpu::BufferAddress;
+
+/// 타임스탬프 쿼리로 렌더/컴퓨트 패스의 GPU 실행 시간을 잰다.
+///
+/// 한 프레임의 사용 순서는 다음과 같다.
+/// 1. `begin_frame` 으로 이전 프레임의 패스 목록을 비운다
+/// 2. 패스마다 `render_timestamp_writes(label)` 를 패스 디스크립터에 넘긴다
+/// 3. 제출 전에 `resolve` 로 결과를 리드백 버퍼에 복사한다
+/// 4. 제출 후 `read_back` 으로 매핑을 요청하면 완료될 때 `last_pass_micros` 가 갱신된다
+///
+/// `Features::TIMESTAMP_QUERY` 를 지원하는 어댑터에서만 만들 수 있다.
+pub struct GpuTimer {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    max_passes: u32,
+    // 이번 프레임에 타임스탬프를 기록한 패스 이름, 쿼리 인덱스 순서
+    labels: Vec<String>,
+    // resolve 로 리드백 버퍼에 복사한 패스 이름
+    resolved_labels: Vec<String>,
+    // 리드백 버퍼가 매핑 중이면 복사할 수 없으므로 다음 프레임은 건너뛴다
+    mapping: Arc<AtomicBool>,
+    results: Arc<Mutex<HashMap<String, u64>>>,
+    // 타임스탬프 한 틱의 나노초
+    period: f32,
+}
+
+impl GpuTimer {
+    /// 디바이스를 요청할 때 `required_features` 에 포함해야 하는 기능
+    pub const FEATURES: wgpu::Features = wgpu::Features::TIMESTAMP_QUERY;
+
+    /// 디바이스에 `FEATURES` 가 없으면 `None` 을 반환한다.
+    pub fn new(gpu: &GpuContext, max_passes: u32) -> Option<Self> {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        if !device.features().contains(Self::FEATURES) {
+            return None;
+        }
+
+        let max_passes = max_passes.max(1);
+        let query_count = max_passes * 2;
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("GPU Timer Query Set"),
+            ty: wgpu::QueryType::Timestamp,
+            count: query_count,
+        });
+
+        let size = query_count as wgpu::BufferAddress * TIMESTAMP_SIZE;
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Timer Resolve Buffer"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Timer Readback Buffer"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            max_passes,
+            labels: Vec::with_capacity(max_passes as usize),
+            resolved_labels: Vec::new(),
+            mapping: Arc::new(AtomicBool::new(false)),
+            results: Arc::new(Mutex::new(HashMap::new())),
+            period: queue.get_timestamp_period(),
+        })
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.labels.clear();
+    }
+
+    /// `label` 패스의 시작과 끝에 타임스탬프를 기록하도록 할당한다.
+    /// 한 프레임에 `max_passes` 개를 넘으면 `None` 을 반환한다.
+    pub fn render_timestamp_writes(
+        &mut self,
+        label: &str,
+    ) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        let begin = self.allocate(label)?;
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(begin),
+            end_of_pass_write_index: Some(begin + 1),
+        })
+    }
+
+    pub fn compute_timestamp_writes(
+        &mut self,
+        label: &str,
+    ) -> Option<wgpu::ComputePassTimestampWrites<'_>> {
+        let begin = self.allocate(label)?;
+        Some(wgpu::ComputePassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(begin),
+            end_of_pass_write_index: Some(begin + 1),
+        })
+    }
+
+    /// 이번 프레임의 타임스탬프를 리드백 버퍼로 복사하는 명령을 기록한다.
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if self.labels.is_empty() || self.mapping.load(Ordering::Acquire) {
+            return;
+        }
+
+        let query_count = self.labels.len() as u32 * 2;
+        encoder.resolve_query_set(&self.query_set, 0..query_count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            query_count as wgpu::BufferAddress * TIMESTAMP_SIZE,
+        );
+        self.resolved_labels = self.labels.clone();
+    }
+
+    /// 제출한 뒤 호출한다. 매핑이 끝나면 패스별 시간이 갱신된다.
+    pub fn read_back(&mut self) {
+        if self.resolved_labels.is_empty() || self.mapping.load(Ordering::Acquire) {
+            return;
+        }
+
+        self.mapping.store(true, Ordering::Release);
+        let labels = std::mem::take(&mut self.resolved_labels);
+        let mapping = Arc::clone(&self.mapping);
+        let results = Arc::clone(&self.results);
+        let buffer = self.readback_buffer.clone();
+        let period = self.period;
+
+        self.readback_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                if result.is_ok() {
+                    {
+                        let mapped = buffer.slice(..).get_mapped_range();
+                        let timestamps: &[u64] = bytemuck::cast_slice(&mapped);
+                        let mut results = results.lock().unwrap();
+                        for (label, pair) in labels.into_iter().zip(timestamps.chunks_exact(2)) {
+                            let ticks = pair[1].saturating_sub(pair[0]);
+                            let micros = (ticks as f64 * period as f64 / 1000.0) as u64;
+                            results.insert(label, micros);
+                        }
+                    }
+                    buffer.unmap();
+                }
+                mapping.store(false, Ordering::Release);
+            });
+    }
+
+    /// 마지막으로 읽어 온 `label` 패스의 GPU 실행 시간 (마이크로초)
+    pub fn last_pass_micros(&self, label: &str) -> Option<u64> {
+        self.results.lock().unwrap().get(label).copied()
+    }
+
+    fn allocate(&mut self, label: &str) -> Option<u32> {
+        if self.labels.len() as u32 >= self.max_passes {
+            return None;
+        }
+
+        let begin = self.labels.len() as u32 * 2;
+        self.labels.push(label.to_string());
+        Some(begin)
+    }
+}
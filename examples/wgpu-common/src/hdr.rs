@@ -0,0 +1,389 @@
+use crate::color::COLOR_WGSL;
+use crate::{BindGroupBuilder, FullscreenTriangle, GpuContext, SamplerBuilder, UniformBuffer};
+
+// tonemap.wgsl 의 ToneMapParams 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ToneMapParams {
+    exposure: f32,
+    _padding: [f32; 3],
+}
+
+impl ToneMapParams {
+    fn new(exposure: f32) -> Self {
+        Self {
+            exposure,
+            _padding: [0.0; 3],
+        }
+    }
+}
+
+/// 장면을 서피스 대신 그리는 `Rgba16Float` 텍스처.
+/// 1.0 보다 큰 색을 잘리지 않고 담으며, 셰이더에서 읽을 수 있도록 바인드 그룹도 함께 만든다.
+pub struct HdrRenderTarget {
+    view: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    size: (u32, u32),
+}
+
+impl HdrRenderTarget {
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+    pub fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let sampler = SamplerBuilder::linear().label("HDR Sampler").build(device);
+        let (view, bind_group_layout, bind_group) =
+            Self::create_texture(device, &sampler, width, height);
+
+        Self {
+            view,
+            sampler,
+            bind_group_layout,
+            bind_group,
+            size: (width, height),
+        }
+    }
+
+    /// 텍스처와 바인드 그룹을 새 크기로 다시 만든다.
+    /// 레이아웃의 항목은 그대로이므로 이 레이아웃으로 만든 파이프라인은 계속 사용할 수 있다.
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        let (view, bind_group_layout, bind_group) =
+            Self::create_texture(device, &self.sampler, width, height);
+        self.view = view;
+        self.bind_group_layout = bind_group_layout;
+        self.bind_group = bind_group;
+        self.size = (width, height);
+    }
+
+    /// 장면을 그리는 렌더 패스의 컬러 어태치먼트로 사용한다.
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// 0번이 텍스처, 1번이 샘플러인 바인드 그룹 레이아웃
+    pub fn bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    fn create_texture(
+        device: &wgpu::Device,
+        sampler: &wgpu::Sampler,
+        width: u32,
+        height: u32,
+    ) -> (wgpu::TextureView, wgpu::BindGroupLayout, wgpu::BindGroup) {
+        let view = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("HDR Texture"),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: Self::FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let (bind_group_layout, bind_group) = BindGroupBuilder::new()
+            .texture(0, &view, wgpu::ShaderStages::FRAGMENT)
+            .sampler(1, sampler, wgpu::ShaderStages::FRAGMENT)
+            .build(device, "HDR Bind Group");
+
+        (view, bind_group_layout, bind_group)
+    }
+}
+
+/// HDR 색을 [0, 1] 범위로 줄이는 방법. 모두 `tonemap_operators.wgsl` 의 함수 하나에 대응한다.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToneMapOperator {
+    #[default]
+    Reinhard,
+    /// 영화용 ACES 커브의 근사. 대비가 강하고 밝은 부분이 부드럽게 하얘진다
+    Aces,
+    /// Uncharted 2 에서 쓰인 Hable 필름 커브
+    Uncharted2,
+    /// 톤 매핑 없이 1.0 에서 자른다
+    Clamp,
+}
+
+impl ToneMapOperator {
+    pub const ALL: [ToneMapOperator; 4] = [
+        ToneMapOperator::Reinhard,
+        ToneMapOperator::Aces,
+        ToneMapOperator::Uncharted2,
+        ToneMapOperator::Clamp,
+    ];
+
+    /// `"reinhard"`, `"aces"`, `"uncharted2"`, `"clamp"`
+    pub fn name(self) -> &'static str {
+        match self {
+            ToneMapOperator::Reinhard => "reinhard",
+            ToneMapOperator::Aces => "aces",
+            ToneMapOperator::Uncharted2 => "uncharted2",
+            ToneMapOperator::Clamp => "clamp",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|operator| operator.name() == name)
+    }
+
+    fn function_name(self) -> &'static str {
+        match self {
+            ToneMapOperator::Reinhard => "tonemap_reinhard",
+            ToneMapOperator::Aces => "tonemap_aces",
+            ToneMapOperator::Uncharted2 => "tonemap_uncharted2",
+            ToneMapOperator::Clamp => "tonemap_clamp",
+        }
+    }
+
+    // 색 변환 함수와 연산자 라이브러리 뒤에 fs_main 이 부를 함수 이름을 바꿔 넣은 tonemap.wgsl 을 붙인다.
+    // sRGB 변형이 있는데 sRGB 가 아닌 출력 포맷(Bgra8Unorm 등)은 GPU 가 인코딩하지 않으므로
+    // 셰이더에서 linear_to_srgb 를 적용한다
+    fn shader_source(self, output_format: wgpu::TextureFormat) -> String {
+        let encoding = if output_format.add_srgb_suffix() != output_format {
+            "linear_to_srgb"
+        } else {
+            ""
+        };
+        format!(
+            "{}\n{}\n{}",
+            COLOR_WGSL,
+            include_str!("tonemap_operators.wgsl"),
+            include_str!("tonemap.wgsl")
+                .replace("TONEMAP_OPERATOR", self.function_name())
+                .replace("OUTPUT_ENCODING", encoding)
+        )
+    }
+}
+
+/// HDR 텍스처를 읽어서 톤 매핑을 적용해 출력 텍스처에 그리는 후처리 패스.
+/// 연산자는 셰이더를 컴파일할 때 정해지므로 바꾸려면 패스를 새로 만든다.
+pub struct ToneMapPass {
+    device: wgpu::Device,
+    operator: ToneMapOperator,
+    pipeline: wgpu::RenderPipeline,
+    hdr_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    params: UniformBuffer<ToneMapParams>,
+    fullscreen: FullscreenTriangle,
+}
+
+impl ToneMapPass {
+    pub const DEFAULT_EXPOSURE: f32 = 1.0;
+
+    /// `output_format` 은 톤 매핑 결과를 그릴 텍스처(보통 서피스)의 포맷이다.
+    pub fn new(
+        gpu: &GpuContext,
+        output_format: wgpu::TextureFormat,
+        operator: ToneMapOperator,
+    ) -> Self {
+        let device = gpu.device();
+        let params = UniformBuffer::new(
+            gpu,
+            &ToneMapParams::new(Self::DEFAULT_EXPOSURE),
+            0,
+            wgpu::ShaderStages::FRAGMENT,
+        );
+        let fullscreen = FullscreenTriangle::new(device);
+        let hdr_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Tone Map HDR Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Tone Map Shader"),
+            source: wgpu::ShaderSource::Wgsl(operator.shader_source(output_format).into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Tone Map Pipeline Layout"),
+            bind_group_layouts: &[&hdr_layout, params.bind_group_layout()],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Tone Map Pipeline"),
+            layout: Some(&layout),
+            vertex: fullscreen.vertex_state(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            device: device.clone(),
+            operator,
+            pipeline,
+            hdr_layout,
+            sampler: SamplerBuilder::linear()
+                .label("Tone Map Sampler")
+                .build(device),
+            params,
+            fullscreen,
+        }
+    }
+
+    pub fn operator(&self) -> ToneMapOperator {
+        self.operator
+    }
+
+    /// 톤 매핑 전에 HDR 색에 곱하는 값
+    pub fn set_exposure(&self, queue: &wgpu::Queue, exposure: f32) {
+        self.params.update(queue, &ToneMapParams::new(exposure));
+    }
+
+    /// `hdr_view` 전체를 톤 매핑해서 `ldr_view` 를 덮어쓴다.
+    pub fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        hdr_view: &wgpu::TextureView,
+        ldr_view: &wgpu::TextureView,
+    ) {
+        self.encode(encoder, hdr_view, ldr_view, None);
+    }
+
+    /// `ldr_view` 의 (x, y, width, height) 픽셀 영역에만 `hdr_view` 전체를 줄여서 그린다.
+    /// 나머지 영역은 그대로 두므로 여러 결과를 한 화면에 나란히 놓을 때 쓴다.
+    pub fn apply_viewport(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        hdr_view: &wgpu::TextureView,
+        ldr_view: &wgpu::TextureView,
+        viewport: [f32; 4],
+    ) {
+        self.encode(encoder, hdr_view, ldr_view, Some(viewport));
+    }
+
+    fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        hdr_view: &wgpu::TextureView,
+        ldr_view: &wgpu::TextureView,
+        viewport: Option<[f32; 4]>,
+    ) {
+        // 입력 텍스처는 리사이즈 등으로 바뀔 수 있어서 바인드 그룹을 매번 만든다
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Tone Map HDR Bind Group"),
+            layout: &self.hdr_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(hdr_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        let load = match viewport {
+            Some(_) => wgpu::LoadOp::Load,
+            None => wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+        };
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Tone Map Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: ldr_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        if let Some([x, y, width, height]) = viewport {
+            render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+        }
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_bind_group(1, self.params.bind_group(), &[]);
+        self.fullscreen.draw(&mut render_pass);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_operator_produces_valid_wgsl() {
+        let formats = [
+            wgpu::TextureFormat::Bgra8UnormSrgb,
+            wgpu::TextureFormat::Bgra8Unorm,
+        ];
+        for (operator, format) in ToneMapOperator::ALL
+            .into_iter()
+            .flat_map(|operator| formats.map(|format| (operator, format)))
+        {
+            let source = operator.shader_source(format);
+            assert!(!source.contains("TONEMAP_OPERATOR"));
+            assert_eq!(source.contains("linear_to_srgb(mapped)"), !format.is_srgb());
+
+            let module = naga::front::wgsl::parse_str(&source)
+                .unwrap_or_else(|e| panic!("{:?}: {}", operator, e.emit_to_string(&source)));
+            naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::empty(),
+            )
+            .validate(&module)
+            .unwrap_or_else(|e| panic!("{:?}: {:?}", operator, e));
+        }
+    }
+
+    #[test]
+    fn operator_names_round_trip() {
+        for operator in ToneMapOperator::ALL {
+            assert_eq!(ToneMapOperator::from_name(operator.name()), Some(operator));
+        }
+        assert_eq!(ToneMapOperator::from_name("filmic"), None);
+    }
+}
@@ -0,0 +1,92 @@
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use std::time::SystemTime;
+
+use crate::error::ShaderError;
+
+/// 파일 시스템의 WGSL 파일로 만든 셰이더 모듈.
+///
+/// 네이티브 개발 중에 바이너리를 다시 빌드하지 않고 셰이더를 고칠 수 있도록
+/// `poll_reload` 가 파일의 수정 시간을 보고 바뀌었으면 다시 컴파일한다.
+pub struct HotShader {
+    path: PathBuf,
+    module: RefCell<wgpu::ShaderModule>,
+    // 마지막으로 읽은 파일의 수정 시간
+    modified: Cell<Option<SystemTime>>,
+}
+
+impl HotShader {
+    pub fn load(path: &Path, device: &wgpu::Device) -> Result<Self, ShaderError> {
+        let modified = modified_time(path);
+        let module = compile(path, device)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            module: RefCell::new(module),
+            modified: Cell::new(modified),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 현재 셰이더 모듈. 다시 불러오면 다른 모듈이 되므로 파이프라인을 만들 때마다 가져온다.
+    pub fn module(&self) -> wgpu::ShaderModule {
+        self.module.borrow().clone()
+    }
+
+    /// 파일이 바뀌었으면 다시 컴파일하고 `true` 를 반환한다.
+    /// 컴파일에 실패하면 에러를 출력하고 이전 모듈을 그대로 사용한다.
+    pub fn poll_reload(&self, device: &wgpu::Device) -> bool {
+        let modified = modified_time(&self.path);
+        if modified.is_none() || modified == self.modified.get() {
+            return false;
+        }
+        // 실패한 파일을 매 프레임 다시 컴파일하지 않도록 시간은 먼저 기록한다
+        self.modified.set(modified);
+
+        match compile(&self.path, device) {
+            Ok(module) => {
+                *self.module.borrow_mut() = module;
+                true
+            }
+            Err(error) => {
+                eprintln!("{}: {}", self.path.display(), error);
+                false
+            }
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+// 검증 에러가 기본 에러 핸들러로 가서 패닉하지 않도록 에러 스코프 안에서 만든다
+fn compile(path: &Path, device: &wgpu::Device) -> Result<wgpu::ShaderModule, ShaderError> {
+    let source = std::fs::read_to_string(path)?;
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: path.to_str(),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    match poll_ready(device.pop_error_scope()) {
+        Some(Some(error)) => Err(ShaderError::Compile(error.to_string())),
+        _ => Ok(module),
+    }
+}
+
+// 네이티브 백엔드에서 pop_error_scope 는 바로 끝나므로 한 번만 폴링한다
+fn poll_ready<F: Future>(future: F) -> Option<F::Output> {
+    let future = pin!(future);
+    match future.poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => Some(output),
+        Poll::Pending => None,
+    }
+}
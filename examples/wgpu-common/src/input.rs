@@ -0,0 +1,401 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    Element, Event, EventTarget, HtmlCanvasElement, KeyboardEvent, MouseEvent, TouchEvent,
+    WheelEvent,
+};
+
+/// 동시에 추적하는 터치 수
+pub const MAX_TOUCHES: usize = 10;
+
+/// 화면에 닿아 있는 손가락 하나
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Touch {
+    /// `Touch.identifier`. 손가락을 뗄 때까지 같은 값을 유지한다
+    pub id: i32,
+    /// 캔버스 왼쪽 위 기준 CSS 픽셀 좌표
+    pub position: (f32, f32),
+}
+
+/// 터치 상태와 두 손가락 핀치 제스처
+#[derive(Debug)]
+pub struct TouchState {
+    touches: Vec<Touch>,
+    // 두 손가락이 닿아 있을 때 마지막으로 본 손가락 사이 거리
+    pinch_distance: Option<f32>,
+    pinch_scale_delta: f32,
+}
+
+impl Default for TouchState {
+    fn default() -> Self {
+        Self {
+            touches: Vec::with_capacity(MAX_TOUCHES),
+            pinch_distance: None,
+            pinch_scale_delta: 1.0,
+        }
+    }
+}
+
+impl TouchState {
+    /// 닿은 순서대로 정렬된 현재 터치
+    pub fn touches(&self) -> &[Touch] {
+        &self.touches
+    }
+
+    /// 마지막 `end_frame` 이후 두 손가락 사이 거리가 변한 비율.
+    /// 손가락을 벌리면 1 보다 크고, 오므리면 1 보다 작고, 핀치 중이 아니면 1 이다.
+    pub fn pinch_scale_delta(&self) -> f32 {
+        self.pinch_scale_delta
+    }
+
+    /// 프레임마다 누적되는 값을 초기화한다. 매 프레임 끝에 호출한다.
+    pub fn end_frame(&mut self) {
+        self.pinch_scale_delta = 1.0;
+    }
+
+    fn start(&mut self, touch: Touch) {
+        if self.touches.len() < MAX_TOUCHES && !self.touches.iter().any(|t| t.id == touch.id) {
+            self.touches.push(touch);
+        }
+        self.reset_pinch();
+    }
+
+    fn moved(&mut self, touch: Touch) {
+        if let Some(existing) = self.touches.iter_mut().find(|t| t.id == touch.id) {
+            existing.position = touch.position;
+        }
+
+        if let (Some(previous), Some(distance)) = (self.pinch_distance, self.two_finger_distance())
+        {
+            if previous > 0.0 {
+                self.pinch_scale_delta *= distance / previous;
+            }
+            self.pinch_distance = Some(distance);
+        }
+    }
+
+    fn end(&mut self, id: i32) {
+        self.touches.retain(|t| t.id != id);
+        self.reset_pinch();
+    }
+
+    fn clear(&mut self) {
+        self.touches.clear();
+        self.reset_pinch();
+    }
+
+    // 손가락 수가 바뀌면 이전 거리와 비교하지 않도록 기준 거리를 다시 잡는다
+    fn reset_pinch(&mut self) {
+        self.pinch_distance = self.two_finger_distance();
+    }
+
+    fn two_finger_distance(&self) -> Option<f32> {
+        match self.touches.as_slice() {
+            [a, b] => {
+                let (dx, dy) = (a.position.0 - b.position.0, a.position.1 - b.position.1);
+                Some((dx * dx + dy * dy).sqrt())
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_event(&mut self, event: &TouchEvent) {
+        // 브라우저의 스크롤과 확대를 막는다
+        event.prevent_default();
+
+        let origin = event
+            .current_target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+            .map(|element| {
+                let rect = element.get_bounding_client_rect();
+                (rect.left() as f32, rect.top() as f32)
+            })
+            .unwrap_or_default();
+
+        let changed = event.changed_touches();
+        for touch in (0..changed.length()).filter_map(|i| changed.get(i)) {
+            let touch = Touch {
+                id: touch.identifier(),
+                position: (
+                    touch.client_x() as f32 - origin.0,
+                    touch.client_y() as f32 - origin.1,
+                ),
+            };
+            match event.type_().as_str() {
+                "touchstart" => self.start(touch),
+                "touchmove" => self.moved(touch),
+                _ => self.end(touch.id),
+            }
+        }
+    }
+}
+
+/// 키보드, 마우스, 터치의 현재 상태
+#[derive(Debug, Default)]
+pub struct InputState {
+    keys_down: HashSet<String>,
+    mouse_position: (f32, f32),
+    mouse_delta: (f32, f32),
+    pointer_locked: bool,
+    mouse_buttons: u16,
+    scroll_delta: (f32, f32),
+    touch: TouchState,
+}
+
+impl InputState {
+    /// `code` 는 `KeyboardEvent.code` 값(`"KeyW"`, `"ArrowUp"` 등)이다.
+    /// 키보드 배열과 관계없이 물리적인 키 위치로 비교한다.
+    pub fn is_key_down(&self, code: &str) -> bool {
+        self.keys_down.contains(code)
+    }
+
+    /// 캔버스 왼쪽 위 기준 CSS 픽셀 좌표
+    pub fn mouse_position(&self) -> (f32, f32) {
+        self.mouse_position
+    }
+
+    /// 마지막 `end_frame` 이후 누적된 `movementX/Y` (CSS 픽셀).
+    /// 포인터 락 중에는 커서가 캔버스 끝에 닿아도 계속 늘어난다.
+    pub fn mouse_delta(&self) -> (f32, f32) {
+        self.mouse_delta
+    }
+
+    /// 마지막 마우스 이벤트 때 포인터 락이 걸려 있었는지
+    pub fn is_pointer_locked(&self) -> bool {
+        self.pointer_locked
+    }
+
+    /// `button` 은 `MouseEvent.button` 값(0: 왼쪽, 1: 가운데, 2: 오른쪽)이다.
+    pub fn is_mouse_button_down(&self, button: i16) -> bool {
+        self.mouse_buttons & button_mask(button) != 0
+    }
+
+    /// 마지막 `end_frame` 이후 누적된 휠 이동량
+    pub fn scroll_delta(&self) -> (f32, f32) {
+        self.scroll_delta
+    }
+
+    pub fn touch(&self) -> &TouchState {
+        &self.touch
+    }
+
+    /// 프레임마다 누적되는 값을 초기화한다. 매 프레임 끝에 호출한다.
+    pub fn end_frame(&mut self) {
+        self.scroll_delta = (0.0, 0.0);
+        self.mouse_delta = (0.0, 0.0);
+        self.touch.end_frame();
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        match event.type_().as_str() {
+            "keydown" => {
+                if let Some(event) = event.dyn_ref::<KeyboardEvent>() {
+                    self.keys_down.insert(event.code());
+                }
+            }
+            "keyup" => {
+                if let Some(event) = event.dyn_ref::<KeyboardEvent>() {
+                    self.keys_down.remove(&event.code());
+                }
+            }
+            "mousemove" => {
+                if let Some(event) = event.dyn_ref::<MouseEvent>() {
+                    self.mouse_position = (event.offset_x() as f32, event.offset_y() as f32);
+                    self.mouse_delta.0 += event.movement_x() as f32;
+                    self.mouse_delta.1 += event.movement_y() as f32;
+                    self.pointer_locked = is_pointer_locked();
+                }
+            }
+            "mousedown" => {
+                if let Some(event) = event.dyn_ref::<MouseEvent>() {
+                    // 가운데 버튼으로 끌 때 브라우저의 자동 스크롤이 켜지지 않도록 막는다
+                    if event.button() == 1 {
+                        event.prevent_default();
+                    }
+                    self.mouse_buttons |= button_mask(event.button());
+                }
+            }
+            "mouseup" => {
+                if let Some(event) = event.dyn_ref::<MouseEvent>() {
+                    self.mouse_buttons &= !button_mask(event.button());
+                }
+            }
+            "wheel" => {
+                if let Some(event) = event.dyn_ref::<WheelEvent>() {
+                    // 페이지가 같이 스크롤되지 않도록 막는다
+                    event.prevent_default();
+                    self.scroll_delta.0 += event.delta_x() as f32;
+                    self.scroll_delta.1 += event.delta_y() as f32;
+                }
+            }
+            "touchstart" | "touchmove" | "touchend" | "touchcancel" => {
+                if let Some(event) = event.dyn_ref::<TouchEvent>() {
+                    self.touch.handle_event(event);
+                }
+            }
+            "blur" => {
+                // 포커스를 잃으면 keyup 을 받지 못하므로 눌린 상태를 모두 해제한다
+                self.keys_down.clear();
+                self.mouse_buttons = 0;
+                self.pointer_locked = false;
+                self.touch.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_pointer_locked() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .is_some_and(|document| document.pointer_lock_element().is_some())
+}
+
+fn button_mask(button: i16) -> u16 {
+    if (0..16).contains(&button) {
+        1 << button
+    } else {
+        0
+    }
+}
+
+const EVENTS: &[&str] = &[
+    "keydown",
+    "keyup",
+    "mousemove",
+    "mousedown",
+    "mouseup",
+    "wheel",
+    "touchstart",
+    "touchmove",
+    "touchend",
+    "touchcancel",
+    "blur",
+];
+
+/// 캔버스에 등록된 입력 이벤트 리스너. drop 되면 리스너를 해제한다.
+pub struct InputListeners {
+    target: EventTarget,
+    callback: Closure<dyn FnMut(Event)>,
+}
+
+impl InputListeners {
+    /// 캔버스에 이벤트 리스너를 등록해서 `input` 을 갱신한다.
+    /// 키 입력을 받을 수 있도록 캔버스에 `tabindex` 를 지정하고,
+    /// 터치로 페이지가 움직이지 않도록 `touch-action: none` 을 지정한다.
+    pub fn register(
+        canvas: &HtmlCanvasElement,
+        input: Rc<RefCell<InputState>>,
+    ) -> Result<Self, JsValue> {
+        if canvas.tab_index() < 0 {
+            canvas.set_tab_index(0);
+        }
+        canvas.style().set_property("touch-action", "none")?;
+
+        let callback = Closure::wrap(Box::new(move |event: Event| {
+            input.borrow_mut().handle_event(&event);
+        }) as Box<dyn FnMut(Event)>);
+
+        let target: EventTarget = canvas.clone().into();
+        for event in EVENTS {
+            target.add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())?;
+        }
+
+        Ok(Self { target, callback })
+    }
+}
+
+impl Drop for InputListeners {
+    fn drop(&mut self) {
+        for event in EVENTS {
+            let _ = self
+                .target
+                .remove_event_listener_with_callback(event, self.callback.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// 캔버스를 클릭하면 `requestPointerLock` 으로 커서를 가둔다. drop 되면 리스너를 해제한다.
+///
+/// 포인터 락 중에는 커서가 보이지 않고 `InputState::mouse_delta` 로 움직임만 받는다.
+/// 사용자가 Esc 를 누르면 브라우저가 락을 푼다.
+pub struct PointerLock {
+    canvas: HtmlCanvasElement,
+    callback: Closure<dyn FnMut()>,
+}
+
+impl PointerLock {
+    pub fn new(canvas: &HtmlCanvasElement) -> Result<Self, JsValue> {
+        let callback = {
+            let canvas = canvas.clone();
+            Closure::wrap(Box::new(move || canvas.request_pointer_lock()) as Box<dyn FnMut()>)
+        };
+        canvas.add_event_listener_with_callback("click", callback.as_ref().unchecked_ref())?;
+
+        Ok(Self {
+            canvas: canvas.clone(),
+            callback,
+        })
+    }
+}
+
+impl Drop for PointerLock {
+    fn drop(&mut self) {
+        let _ = self
+            .canvas
+            .remove_event_listener_with_callback("click", self.callback.as_ref().unchecked_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: i32, x: f32, y: f32) -> Touch {
+        Touch {
+            id,
+            position: (x, y),
+        }
+    }
+
+    #[test]
+    fn tracks_touches_by_id() {
+        let mut state = TouchState::default();
+        for id in 0..12 {
+            state.start(touch(id, 0.0, 0.0));
+        }
+        assert_eq!(state.touches().len(), MAX_TOUCHES);
+
+        state.moved(touch(3, 5.0, 6.0));
+        assert_eq!(state.touches()[3].position, (5.0, 6.0));
+
+        state.end(3);
+        assert!(state.touches().iter().all(|t| t.id != 3));
+    }
+
+    #[test]
+    fn pinch_scale_follows_finger_distance() {
+        let mut state = TouchState::default();
+        state.start(touch(0, 0.0, 0.0));
+        state.moved(touch(0, 10.0, 0.0));
+        assert_eq!(state.pinch_scale_delta(), 1.0);
+
+        state.start(touch(1, 110.0, 0.0));
+        state.moved(touch(1, 160.0, 0.0));
+        state.moved(touch(1, 210.0, 0.0));
+        assert!((state.pinch_scale_delta() - 2.0).abs() < 1e-6);
+
+        state.end_frame();
+        state.moved(touch(0, 110.0, 0.0));
+        assert!((state.pinch_scale_delta() - 0.5).abs() < 1e-6);
+
+        // 손가락 하나를 떼면 핀치가 끝난다
+        state.end_frame();
+        state.end(1);
+        state.moved(touch(0, 0.0, 0.0));
+        assert_eq!(state.pinch_scale_delta(), 1.0);
+    }
+}
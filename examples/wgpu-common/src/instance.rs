@@ -0,0 +1,97 @@
+use wgpu::util::DeviceExt;
+
+use crate::Transform;
+
+/// 인스턴스 하나의 모델 행렬. 셰이더에서는 `@location(5)` 부터 네 개의 열로 받는다.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct InstanceRaw {
+    model: [[f32; 4]; 4],
+}
+
+impl InstanceRaw {
+    /// 버텍스 속성과 겹치지 않도록 5번부터 사용한다
+    pub const ATTRIBUTES: [wgpu::VertexAttribute; 4] = wgpu::vertex_attr_array![
+        5 => Float32x4,
+        6 => Float32x4,
+        7 => Float32x4,
+        8 => Float32x4,
+    ];
+}
+
+impl From<&Transform> for InstanceRaw {
+    fn from(transform: &Transform) -> Self {
+        Self {
+            model: transform.matrix().to_cols_array_2d(),
+        }
+    }
+}
+
+/// 인스턴스마다 `Transform` 하나를 모델 행렬로 담는 버텍스 버퍼.
+///
+/// `transforms_mut` 로 값을 바꾸면 다음 `update` 때 GPU 버퍼에 반영된다.
+/// `Mesh::draw_instanced` 와 함께 `slot` 1 에 바인딩해서 사용한다.
+pub struct InstanceBuffer {
+    buffer: wgpu::Buffer,
+    transforms: Vec<Transform>,
+    dirty: bool,
+}
+
+impl InstanceBuffer {
+    pub fn new(device: &wgpu::Device, transforms: &[Transform]) -> Self {
+        let raw: Vec<InstanceRaw> = transforms.iter().map(InstanceRaw::from).collect();
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Instance Buffer"),
+            contents: bytemuck::cast_slice(&raw),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Self {
+            buffer,
+            transforms: transforms.to_vec(),
+            dirty: false,
+        }
+    }
+
+    /// `wgpu::VertexState::buffers` 에 넘길 인스턴스 단위 레이아웃
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &InstanceRaw::ATTRIBUTES,
+        }
+    }
+
+    pub fn transforms(&self) -> &[Transform] {
+        &self.transforms
+    }
+
+    /// 인스턴스 수는 바꿀 수 없고 각 값만 바꿀 수 있다.
+    pub fn transforms_mut(&mut self) -> &mut [Transform] {
+        self.dirty = true;
+        &mut self.transforms
+    }
+
+    /// 바뀐 변환이 있으면 모델 행렬을 다시 계산해서 GPU 버퍼에 쓴다.
+    pub fn update(&mut self, queue: &wgpu::Queue) {
+        if !self.dirty {
+            return;
+        }
+
+        let raw: Vec<InstanceRaw> = self.transforms.iter().map(InstanceRaw::from).collect();
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(&raw));
+        self.dirty = false;
+    }
+
+    pub fn len(&self) -> u32 {
+        self.transforms.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+
+    pub fn bind(&self, render_pass: &mut wgpu::RenderPass<'_>, slot: u32) {
+        render_pass.set_vertex_buffer(slot, self.buffer.slice(..));
+    }
+}
@@ -0,0 +1,188 @@
+pub mod anti_alias;
+pub mod asset;
+pub mod atlas;
+pub mod bind_group;
+pub mod bindless_textures;
+pub mod blend_mode;
+pub mod bloom;
+pub mod brdf_lut;
+pub mod bvh;
+pub mod camera;
+pub mod camera_2d;
+pub mod capabilities;
+pub mod capture;
+pub mod chunk_streamer;
+pub mod color;
+pub mod compressed_texture;
+pub mod compute;
+pub mod context;
+pub mod cull_mode;
+pub mod debug_layer;
+pub mod debug_lines;
+pub mod depth;
+pub mod error;
+pub mod format_selector;
+pub mod fps_camera;
+pub mod frame_timer;
+pub mod frustum;
+pub mod fullscreen;
+pub mod gltf_loader;
+pub mod gpu_driven;
+pub mod gpu_timer;
+pub mod hdr;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod hot_shader;
+pub mod input;
+pub mod instance;
+pub mod limits;
+mod log;
+pub mod memory_stats;
+pub mod mesh;
+pub mod mipmap;
+pub mod msaa;
+pub mod noise;
+pub mod occlusion;
+pub mod offscreen;
+pub mod outline;
+pub mod particle;
+pub mod pass_profiler;
+pub mod pbr;
+pub mod perf_counter;
+pub mod phong;
+pub mod physics_debug;
+pub mod pipeline_cache;
+pub mod pipeline_stats;
+pub mod post_process;
+pub mod push_constants;
+pub mod render_graph;
+pub mod render_mode;
+pub mod render_pass;
+pub mod render_pass_2d;
+pub mod sampler;
+pub mod scene;
+pub mod sdf;
+pub mod shader;
+#[cfg(feature = "shader-cache")]
+pub mod shader_cache;
+pub mod shadow;
+pub mod skeleton;
+pub mod skybox;
+pub mod smoothed_lines;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod spirv_shader;
+pub mod spline;
+pub mod sprite;
+pub mod sprite_animation;
+pub mod ssao;
+pub mod ssr;
+pub mod staging;
+pub mod static_scene;
+pub mod storage;
+pub mod taa;
+pub mod tangent;
+pub mod terrain;
+#[cfg(test)]
+mod test_gpu;
+pub mod texture;
+pub mod textured_cube;
+pub mod tilemap;
+pub mod transform;
+pub mod uniform;
+pub mod vertex;
+pub mod video_texture;
+pub mod web;
+
+pub use anti_alias::AntiAliasMode;
+pub use asset::AssetLoader;
+pub use atlas::{AtlasRect, TextureAtlas};
+pub use bind_group::BindGroupBuilder;
+pub use bindless_textures::{BindlessTextureArray, MAX_TEXTURES};
+pub use blend_mode::BlendMode;
+pub use bloom::BloomPass;
+pub use brdf_lut::BrdfLutGenerator;
+pub use bvh::{BvhTree, EntityId, Ray};
+pub use camera::{Camera, CameraUniform, OrthoCamera, world_to_screen};
+pub use camera_2d::{MAX_ZOOM_2D, MIN_ZOOM_2D, OrthographicCamera2D};
+pub use capabilities::capabilities_report;
+pub use capture::TextureReadback;
+pub use chunk_streamer::{ChunkCoord, ChunkGeometry, ChunkState, ChunkStreamer, JoinHandle};
+pub use color::{SrgbMode, linear_to_srgb, srgb_to_linear};
+pub use compressed_texture::{BcFormat, CompressedTextureLoader, DdsImage};
+pub use compute::ComputePass;
+pub use context::GpuContext;
+pub use cull_mode::CullMode;
+pub use debug_layer::WgpuDebugLayer;
+pub use debug_lines::{DebugLineVertex, DebugLines};
+pub use depth::DepthTexture;
+pub use error::{
+    AssetError, BindlessTextureError, CaptureError, DdsError, GltfError, InitError,
+    PushConstantsError, RenderGraphError, ShaderError,
+};
+pub use format_selector::{FormatSelectionResult, FormatSelector, SRGB_SURFACE_FORMATS};
+pub use fps_camera::{FpsCamera, MAX_PITCH};
+pub use frame_timer::FrameTimer;
+pub use frustum::{Aabb, Frustum, Plane};
+pub use fullscreen::FullscreenTriangle;
+pub use gltf_loader::{GltfLoader, GltfMesh, GltfVertex};
+pub use gpu_driven::GpuDrivenRenderer;
+pub use gpu_timer::GpuTimer;
+pub use hdr::{HdrRenderTarget, ToneMapOperator, ToneMapPass};
+#[cfg(not(target_arch = "wasm32"))]
+pub use hot_shader::HotShader;
+pub use input::{InputListeners, InputState, PointerLock, Touch, TouchState};
+pub use instance::{InstanceBuffer, InstanceRaw};
+pub use limits::{LimitDiff, UseCase, limits_diff, print_limits_diff, suggest_limits};
+pub use memory_stats::MemoryStats;
+pub use mesh::{Index, Mesh};
+pub use mipmap::MipmapGenerator;
+pub use msaa::MsaaConfig;
+pub use noise::{NoiseTextureBuilder, NoiseType};
+pub use occlusion::OcclusionQueryPool;
+pub use offscreen::{CanvasOffscreenRenderer, CommandRing, OffscreenCommand, OffscreenCommands};
+pub use outline::StencilOutlinePass;
+pub use particle::{ParticleInstance, ParticleSystem};
+pub use pass_profiler::{DEFAULT_PROFILE_FRAMES, PassProfile, RenderPassProfiler};
+pub use pbr::{PbrEnvironment, PbrMaterial, PbrMaterialBinding, PbrPipeline, PbrVertex};
+pub use perf_counter::{FrameStats, PerfCounter};
+pub use phong::{DirLight, PhongMaterial, PhongPipeline, PhongVertex};
+pub use physics_debug::{DrawShape, PhysicsDebugDraw};
+pub use pipeline_cache::{PipelineCache, PipelineKey, PipelineLayoutCache};
+pub use pipeline_stats::{PipelineStats, PipelineStatsQuery};
+pub use post_process::{
+    FullscreenEffect, FxaaEffect, PostProcessEffect, PostProcessStack, VignetteEffect,
+};
+pub use push_constants::{PushConstants, RenderPassPushConstantsExt};
+pub use render_graph::{RenderGraph, RenderGraphContext, RenderGraphNode, ResourceId};
+pub use render_mode::RenderMode;
+pub use render_pass::{DrawCommand, DrawRecorder, RenderPassBuilder, RenderTarget};
+pub use render_pass_2d::{RenderPass2D, TextureHandle, Vertex2D};
+pub use sampler::SamplerBuilder;
+pub use scene::{NodeId, SceneGraph, SceneNode};
+pub use sdf::signed_distance_field;
+pub use shader::ShaderLoader;
+#[cfg(feature = "shader-cache")]
+pub use shader_cache::ShaderCache;
+pub use shadow::ShadowMap;
+pub use skeleton::{BonePalette, BoneTransform, MAX_BONES, Skeleton, SkinnedVertex};
+pub use skybox::Skybox;
+pub use smoothed_lines::SmoothedLines;
+#[cfg(not(target_arch = "wasm32"))]
+pub use spirv_shader::SpirVShader;
+pub use spline::{BezierSpline, CameraPath};
+pub use sprite::{Sprite, SpriteBatch, SpriteVertex};
+pub use sprite_animation::{LoopMode, SpriteAnimator};
+pub use ssao::SsaoPass;
+pub use ssr::SsrPass;
+pub use staging::StagingBuffer;
+pub use static_scene::StaticScene;
+pub use storage::GrowableStorageBuffer;
+pub use taa::TaaPass;
+pub use tangent::{TangentVertex, generate_tangents};
+pub use terrain::{Terrain, TerrainVertex};
+pub use texture::Texture;
+pub use textured_cube::{PositionNormalUvVertex, TexturedCube};
+pub use tilemap::Tilemap;
+pub use transform::Transform;
+pub use uniform::UniformBuffer;
+pub use vertex::{Vertex, VertexBuffer};
+pub use video_texture::VideoTexture;
@@ -0,0 +1,263 @@
+use wgpu::Limits;
+
+use crate::log::log;
+
+// 요구값이 어댑터 값보다 크면 안 되는 항목과, 작으면 안 되는 항목(정렬, 최소 서브그룹 크기)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LimitKind {
+    Max,
+    Min,
+}
+
+struct LimitField {
+    name: &'static str,
+    kind: LimitKind,
+    get: fn(&Limits) -> u64,
+    set: fn(&mut Limits, u64),
+}
+
+macro_rules! limit_fields {
+    ($($kind:ident $name:ident),* $(,)?) => {
+        const LIMIT_FIELDS: &[LimitField] = &[$(
+            LimitField {
+                name: stringify!($name),
+                kind: LimitKind::$kind,
+                get: |limits| limits.$name as u64,
+                set: |limits, value| limits.$name = value as _,
+            },
+        )*];
+    };
+}
+
+// wgpu::Limits::check_limits_with_fail_fn 과 같은 순서
+limit_fields! {
+    Max max_texture_dimension_1d,
+    Max max_texture_dimension_2d,
+    Max max_texture_dimension_3d,
+    Max max_texture_array_layers,
+    Max max_bind_groups,
+    Max max_bindings_per_bind_group,
+    Max max_dynamic_uniform_buffers_per_pipeline_layout,
+    Max max_dynamic_storage_buffers_per_pipeline_layout,
+    Max max_sampled_textures_per_shader_stage,
+    Max max_samplers_per_shader_stage,
+    Max max_storage_buffers_per_shader_stage,
+    Max max_storage_textures_per_shader_stage,
+    Max max_uniform_buffers_per_shader_stage,
+    Max max_binding_array_elements_per_shader_stage,
+    Max max_uniform_buffer_binding_size,
+    Max max_storage_buffer_binding_size,
+    Max max_vertex_buffers,
+    Max max_buffer_size,
+    Max max_vertex_attributes,
+    Max max_vertex_buffer_array_stride,
+    Min min_uniform_buffer_offset_alignment,
+    Min min_storage_buffer_offset_alignment,
+    Max max_inter_stage_shader_components,
+    Max max_color_attachments,
+    Max max_color_attachment_bytes_per_sample,
+    Max max_compute_workgroup_storage_size,
+    Max max_compute_invocations_per_workgroup,
+    Max max_compute_workgroup_size_x,
+    Max max_compute_workgroup_size_y,
+    Max max_compute_workgroup_size_z,
+    Max max_compute_workgroups_per_dimension,
+    Min min_subgroup_size,
+    Max max_subgroup_size,
+    Max max_push_constant_size,
+    Max max_non_sampler_bindings,
+}
+
+/// `limits_diff` 가 비교한 항목 하나
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimitDiff {
+    pub name: &'static str,
+    pub adapter: u64,
+    pub required: u64,
+    /// 어댑터가 이 요구값을 만족하지 못하면 `true`.
+    /// `max_*` 는 요구값이 더 클 때, 정렬 같은 `min_*` 는 요구값이 더 작을 때다.
+    pub exceeded: bool,
+}
+
+/// 예제들이 사용하는 렌더링 기능. `suggest_limits` 가 각 기능에 필요한 한도를 더한다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseCase {
+    /// 버텍스/프래그먼트 셰이더와 유니폼, 텍스처만 쓰는 렌더링. WebGL2 에서도 동작한다
+    Basic,
+    /// 컴퓨트 셰이더와 스토리지 버퍼
+    Compute,
+    /// 컴퓨트 셰이더에서 쓰는 스토리지 텍스처
+    StorageTextures,
+    /// 한 변이 2048 보다 큰 텍스처나 렌더 타깃
+    LargeTextures,
+    /// 16 KiB 보다 큰 유니폼 버퍼 바인딩
+    LargeUniforms,
+    /// 컬러 타깃을 4개보다 많이 쓰는 패스
+    MultipleRenderTargets,
+}
+
+impl UseCase {
+    fn apply(self, limits: &mut Limits) {
+        let defaults = Limits::default();
+        match self {
+            UseCase::Basic => {}
+            UseCase::Compute => {
+                let downlevel = Limits::downlevel_defaults();
+                limits.max_storage_buffers_per_shader_stage =
+                    downlevel.max_storage_buffers_per_shader_stage;
+                limits.max_storage_buffer_binding_size = downlevel.max_storage_buffer_binding_size;
+                limits.max_compute_workgroup_storage_size =
+                    downlevel.max_compute_workgroup_storage_size;
+                limits.max_compute_invocations_per_workgroup =
+                    downlevel.max_compute_invocations_per_workgroup;
+                limits.max_compute_workgroup_size_x = downlevel.max_compute_workgroup_size_x;
+                limits.max_compute_workgroup_size_y = downlevel.max_compute_workgroup_size_y;
+                limits.max_compute_workgroup_size_z = downlevel.max_compute_workgroup_size_z;
+                limits.max_compute_workgroups_per_dimension =
+                    downlevel.max_compute_workgroups_per_dimension;
+            }
+            UseCase::StorageTextures => {
+                limits.max_storage_textures_per_shader_stage =
+                    defaults.max_storage_textures_per_shader_stage;
+            }
+            UseCase::LargeTextures => {
+                limits.max_texture_dimension_1d = defaults.max_texture_dimension_1d;
+                limits.max_texture_dimension_2d = defaults.max_texture_dimension_2d;
+            }
+            UseCase::LargeUniforms => {
+                limits.max_uniform_buffer_binding_size = defaults.max_uniform_buffer_binding_size;
+            }
+            UseCase::MultipleRenderTargets => {
+                limits.max_color_attachments = defaults.max_color_attachments;
+            }
+        }
+    }
+}
+
+/// 모든 항목을 비교한 결과를 `wgpu::Limits` 의 필드 순서대로 반환한다.
+pub fn limits_diff(adapter_limits: &Limits, required_limits: &Limits) -> Vec<LimitDiff> {
+    LIMIT_FIELDS
+        .iter()
+        .map(|field| {
+            let adapter = (field.get)(adapter_limits);
+            let required = (field.get)(required_limits);
+            let exceeded = match field.kind {
+                LimitKind::Max => required > adapter,
+                LimitKind::Min => required < adapter,
+            };
+            LimitDiff {
+                name: field.name,
+                adapter,
+                required,
+                exceeded,
+            }
+        })
+        .collect()
+}
+
+/// 어댑터 한도와 요구 한도를 표로 출력하고, 어댑터가 만족하지 못하는 항목에 `<<` 를 붙인다.
+/// 웹에서는 브라우저 콘솔에, 네이티브에서는 표준 출력에 쓴다.
+pub fn print_limits_diff(adapter_limits: &Limits, required_limits: &Limits) {
+    let diffs = limits_diff(adapter_limits, required_limits);
+    let exceeded = diffs.iter().filter(|diff| diff.exceeded).count();
+
+    let width = diffs.iter().map(|diff| diff.name.len()).max().unwrap_or(0);
+    let mut table = format!("{:<width$} {:>12} {:>12}\n", "limit", "adapter", "required");
+    for diff in &diffs {
+        table.push_str(&format!(
+            "{:<width$} {:>12} {:>12}{}\n",
+            diff.name,
+            diff.adapter,
+            diff.required,
+            if diff.exceeded { "  <<" } else { "" }
+        ));
+    }
+    table.push_str(&format!(
+        "{} limit(s) not supported by the adapter",
+        exceeded
+    ));
+
+    log(&table, exceeded > 0);
+}
+
+// `wgpu::Limits` 의 필드 순서대로 (이름, 값)
+pub(crate) fn limit_values(limits: &Limits) -> Vec<(&'static str, u64)> {
+    LIMIT_FIELDS
+        .iter()
+        .map(|field| (field.name, (field.get)(limits)))
+        .collect()
+}
+
+/// `use_cases` 를 모두 쓰는 데 필요한 가장 낮은 한도.
+/// `Limits::downlevel_webgl2_defaults` 에서 시작해서 각 기능이 필요한 항목만 올린다.
+pub fn suggest_limits(use_cases: &[UseCase]) -> Limits {
+    let mut limits = Limits::downlevel_webgl2_defaults();
+    for use_case in use_cases {
+        let mut needed = limits.clone();
+        use_case.apply(&mut needed);
+        // 여러 기능이 같은 항목을 올리면 더 엄격한 쪽을 남긴다
+        for field in LIMIT_FIELDS {
+            let (current, value) = ((field.get)(&limits), (field.get)(&needed));
+            let merged = match field.kind {
+                LimitKind::Max => current.max(value),
+                LimitKind::Min => current.min(value),
+            };
+            (field.set)(&mut limits, merged);
+        }
+    }
+    limits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_every_field() {
+        let diffs = limits_diff(&Limits::default(), &Limits::default());
+        assert_eq!(diffs.len(), LIMIT_FIELDS.len());
+        assert!(diffs.iter().all(|diff| !diff.exceeded));
+    }
+
+    #[test]
+    fn alignment_is_exceeded_when_smaller() {
+        let adapter = Limits::default();
+        let required = Limits {
+            max_texture_dimension_2d: adapter.max_texture_dimension_2d * 2,
+            min_uniform_buffer_offset_alignment: adapter.min_uniform_buffer_offset_alignment / 2,
+            ..Limits::default()
+        };
+
+        let exceeded: Vec<_> = limits_diff(&adapter, &required)
+            .into_iter()
+            .filter(|diff| diff.exceeded)
+            .map(|diff| diff.name)
+            .collect();
+        assert_eq!(
+            exceeded,
+            vec![
+                "max_texture_dimension_2d",
+                "min_uniform_buffer_offset_alignment"
+            ]
+        );
+    }
+
+    #[test]
+    fn suggested_limits_fit_webgl2_for_basic_rendering() {
+        let limits = suggest_limits(&[UseCase::Basic]);
+        assert_eq!(limits, Limits::downlevel_webgl2_defaults());
+    }
+
+    #[test]
+    fn suggested_limits_combine_use_cases() {
+        let limits = suggest_limits(&[UseCase::Compute, UseCase::LargeTextures]);
+        assert!(limits.max_compute_invocations_per_workgroup > 0);
+        assert!(limits.max_storage_buffers_per_shader_stage > 0);
+        assert_eq!(
+            limits.max_texture_dimension_2d,
+            Limits::default().max_texture_dimension_2d
+        );
+        // 필요한 만큼만 올렸으므로 기본 한도를 넘지 않는다
+        assert!(limits.check_limits(&Limits::default()));
+    }
+}
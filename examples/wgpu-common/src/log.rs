@@ -0,0 +1,18 @@
+// 웹에서는 브라우저 콘솔, 네이티브에서는 표준 출력에 남긴다. warn 이면 경고(표준 에러)로 남긴다
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn log(message: &str, warn: bool) {
+    if warn {
+        web_sys::console::warn_1(&message.into());
+    } else {
+        web_sys::console::log_1(&message.into());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn log(message: &str, warn: bool) {
+    if warn {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
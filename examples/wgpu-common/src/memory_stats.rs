@@ -0,0 +1,55 @@
+use wasm_bindgen::JsValue;
+
+/// 디바이스가 할당한 버퍼/텍스처 메모리와 바인드 그룹 수.
+///
+/// wgpu 의 내부 카운터에서 읽으므로 백엔드가 세지 않는 값은 0 으로 남는다.
+/// 브라우저의 WebGPU 백엔드는 어떤 값도 제공하지 않고, WebGL2 는 바인드 그룹 수만 센다.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    buffer_bytes: u64,
+    texture_bytes: u64,
+    bind_group_count: u32,
+}
+
+impl MemoryStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 끝난 작업의 자원을 정리한 뒤 카운터를 다시 읽는다. 매 프레임 제출 후에 호출한다.
+    pub fn update(&mut self, device: &wgpu::Device) {
+        let _ = device.poll(wgpu::PollType::Poll);
+
+        let counters = device.get_internal_counters().hal;
+        self.buffer_bytes = counters.buffer_memory.read().max(0) as u64;
+        self.texture_bytes = counters.texture_memory.read().max(0) as u64;
+        self.bind_group_count = counters.bind_groups.read().clamp(0, u32::MAX as isize) as u32;
+    }
+
+    pub fn buffer_bytes(&self) -> u64 {
+        self.buffer_bytes
+    }
+
+    pub fn texture_bytes(&self) -> u64 {
+        self.texture_bytes
+    }
+
+    pub fn bind_group_count(&self) -> u32 {
+        self.bind_group_count
+    }
+
+    /// `{ bufferBytes, textureBytes, bindGroupCount }` 형태의 JS 객체
+    pub fn to_js(&self) -> JsValue {
+        let object = js_sys::Object::new();
+        let fields = [
+            ("bufferBytes", self.buffer_bytes as f64),
+            ("textureBytes", self.texture_bytes as f64),
+            ("bindGroupCount", self.bind_group_count as f64),
+        ];
+        for (key, value) in fields {
+            // 일반 객체에 속성을 추가하는 것은 실패하지 않는다
+            let _ = js_sys::Reflect::set(&object, &key.into(), &value.into());
+        }
+        object.into()
+    }
+}
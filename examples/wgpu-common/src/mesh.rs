@@ -0,0 +1,101 @@
+use std::ops::Range;
+
+use wgpu::util::DeviceExt;
+
+use crate::{GpuContext, TangentVertex, Vertex};
+
+/// 인덱스 버퍼에 쓸 수 있는 정수 타입
+pub trait Index: bytemuck::Pod {
+    const FORMAT: wgpu::IndexFormat;
+}
+
+impl Index for u16 {
+    const FORMAT: wgpu::IndexFormat = wgpu::IndexFormat::Uint16;
+}
+
+impl Index for u32 {
+    const FORMAT: wgpu::IndexFormat = wgpu::IndexFormat::Uint32;
+}
+
+/// 버텍스 버퍼와 인덱스 버퍼를 함께 들고 있는 메시
+#[derive(Clone)]
+pub struct Mesh {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_format: wgpu::IndexFormat,
+    index_count: u32,
+}
+
+impl Mesh {
+    /// 버텍스와 인덱스를 바로 GPU 에 올린다.
+    /// 인덱스 포맷은 `indices` 의 타입(`u16` 또는 `u32`)으로 정해진다.
+    pub fn new<V: Vertex, I: Index>(gpu: &GpuContext, vertices: &[V], indices: &[I]) -> Self {
+        let device = gpu.device();
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Vertex Buffer"),
+            contents: bytemuck::cast_slice(vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Index Buffer"),
+            contents: bytemuck::cast_slice(indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        Self {
+            vertex_buffer,
+            index_buffer,
+            index_format: I::FORMAT,
+            index_count: indices.len() as u32,
+        }
+    }
+
+    /// `generate_tangents` 로 탄젠트를 채운 다음 `new` 와 같이 올린다.
+    /// 탄젠트를 만들 수 없으면 버텍스에 들어 있던 값을 그대로 쓴다.
+    pub fn new_with_tangents<V, I>(gpu: &GpuContext, vertices: &mut [V], indices: &[I]) -> Self
+    where
+        V: Vertex + TangentVertex,
+        I: Index + Into<u32>,
+    {
+        crate::generate_tangents(vertices, indices);
+        Self::new(gpu, vertices, indices)
+    }
+
+    pub fn vertex_buffer(&self) -> &wgpu::Buffer {
+        &self.vertex_buffer
+    }
+
+    pub fn index_buffer(&self) -> &wgpu::Buffer {
+        &self.index_buffer
+    }
+
+    pub fn index_format(&self) -> wgpu::IndexFormat {
+        self.index_format
+    }
+
+    pub fn index_count(&self) -> u32 {
+        self.index_count
+    }
+
+    /// 버퍼를 바인딩하고 `draw_indexed` 를 호출한다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), self.index_format);
+        render_pass.draw_indexed(0..self.index_count, 0, 0..1);
+    }
+
+    /// `draw` 와 같은 명령을 렌더 번들에 기록한다.
+    pub fn draw_bundle<'a>(&'a self, bundle: &mut wgpu::RenderBundleEncoder<'a>) {
+        bundle.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        bundle.set_index_buffer(self.index_buffer.slice(..), self.index_format);
+        bundle.draw_indexed(0..self.index_count, 0, 0..1);
+    }
+
+    /// 인스턴스 버퍼는 호출하기 전에 1번 슬롯에 바인딩해 둔다.
+    pub fn draw_instanced(&self, render_pass: &mut wgpu::RenderPass<'_>, instances: Range<u32>) {
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), self.index_format);
+        render_pass.draw_indexed(0..self.index_count, 0, instances);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::ComputePass;
+
+// mipmap.wgsl 의 @workgroup_size 와 같아야 한다
+const WORKGROUP_SIZE: u32 = 8;
+
+/// wgpu 는 밉맵을 자동으로 만들어 주지 않으므로, 컴퓨트 셰이더로 밉 레벨마다
+/// 이전 레벨을 2x2 박스 필터로 줄여서 채운다.
+///
+/// 텍스처는 `FORMAT` 이어야 하고 `TEXTURE_BINDING | STORAGE_BINDING` 용도가 필요하다.
+/// sRGB 포맷은 스토리지 텍스처로 쓸 수 없으므로 인코딩된 값 그대로 평균을 낸다.
+/// 컴퓨트 셰이더가 필요하므로 WebGL2 에서는 `downsample_rgba8` 로 CPU 에서 만든다.
+pub struct MipmapGenerator {
+    device: wgpu::Device,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl MipmapGenerator {
+    /// 밉맵을 만들 수 있는 텍스처 포맷
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+    pub fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Mipmap Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: Self::FORMAT,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Mipmap Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("mipmap.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Mipmap Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Mipmap Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        Self {
+            device: device.clone(),
+            bind_group_layout,
+            pipeline,
+        }
+    }
+
+    /// `width` x `height` 에서 1x1 까지 내려가는 밉 레벨 수
+    pub fn mip_level_count(width: u32, height: u32) -> u32 {
+        u32::BITS - width.max(height).max(1).leading_zeros()
+    }
+
+    /// `level` 번째 밉 레벨의 크기. 한 단계마다 반으로 줄고 1 보다 작아지지 않는다
+    pub fn mip_size(width: u32, height: u32, level: u32) -> (u32, u32) {
+        ((width >> level).max(1), (height >> level).max(1))
+    }
+
+    /// 0번 밉 레벨로부터 1 ~ `mip_count - 1` 번 레벨을 채우는 디스패치를 기록한다.
+    pub fn generate(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+        mip_count: u32,
+    ) {
+        assert_eq!(texture.format(), Self::FORMAT, "unsupported mipmap format");
+        assert!(
+            mip_count <= texture.mip_level_count(),
+            "texture has only {} mip levels",
+            texture.mip_level_count()
+        );
+
+        let level_view = |level: u32| {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                label: Some("Mipmap Level View"),
+                base_mip_level: level,
+                mip_level_count: Some(1),
+                ..Default::default()
+            })
+        };
+
+        for level in 1..mip_count {
+            let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Mipmap Bind Group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&level_view(level - 1)),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&level_view(level)),
+                    },
+                ],
+            });
+
+            let (width, height) = Self::mip_size(texture.width(), texture.height(), level);
+            let dispatch = (
+                ComputePass::workgroup_count(width, WORKGROUP_SIZE),
+                ComputePass::workgroup_count(height, WORKGROUP_SIZE),
+                1,
+            );
+            ComputePass::run(encoder, &self.pipeline, &bind_group, dispatch);
+        }
+    }
+
+    /// `generate` 와 같은 필터로 `Rgba8` 픽셀을 한 단계 줄인다.
+    /// 홀수 크기에서는 마지막 행과 열이 남는 한 줄까지 포함해서 평균을 낸다.
+    pub fn downsample_rgba8(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+        let (output_width, output_height) = Self::mip_size(width, height, 1);
+        let mut output = Vec::with_capacity((output_width * output_height * 4) as usize);
+
+        for y in 0..output_height {
+            let y_range = source_range(y, output_height, height);
+            for x in 0..output_width {
+                let x_range = source_range(x, output_width, width);
+
+                let mut sum = [0u32; 4];
+                for sy in y_range.clone() {
+                    for sx in x_range.clone() {
+                        let index = ((sy * width + sx) * 4) as usize;
+                        for (channel, value) in sum.iter_mut().zip(&pixels[index..index + 4]) {
+                            *channel += *value as u32;
+                        }
+                    }
+                }
+
+                let count = x_range.len() as u32 * y_range.len() as u32;
+                output.extend(sum.map(|channel| ((channel + count / 2) / count) as u8));
+            }
+        }
+
+        output
+    }
+}
+
+// mipmap.wgsl 의 source_range 와 같다
+fn source_range(coord: u32, output_size: u32, source_size: u32) -> std::ops::Range<u32> {
+    let start = coord * 2;
+    let end = if coord == output_size - 1 {
+        source_size
+    } else {
+        start + 2
+    };
+    start..end.min(source_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard(size: u32) -> Vec<u8> {
+        (0..size * size)
+            .flat_map(|i| {
+                let value = if (i % size + i / size).is_multiple_of(2) {
+                    255
+                } else {
+                    0
+                };
+                [value, value, value, 255]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn checkerboard_mip_chain() {
+        let mut size = (256, 256);
+        let mut pixels = checkerboard(256);
+        let mip_count = MipmapGenerator::mip_level_count(size.0, size.1);
+        assert_eq!(mip_count, 9);
+
+        for level in 1..mip_count {
+            pixels = MipmapGenerator::downsample_rgba8(&pixels, size.0, size.1);
+            let next = MipmapGenerator::mip_size(256, 256, level);
+            assert_eq!(next, (size.0 / 2, size.1 / 2));
+            assert_eq!(pixels.len(), (next.0 * next.1 * 4) as usize);
+            size = next;
+
+            // 1 픽셀 체커는 첫 단계부터 회색이 된다
+            assert!(pixels.chunks(4).all(|pixel| pixel == [128, 128, 128, 255]));
+        }
+        assert_eq!(size, (1, 1));
+    }
+
+    #[test]
+    fn odd_sizes_cover_every_texel() {
+        assert_eq!(MipmapGenerator::mip_level_count(5, 3), 3);
+        assert_eq!(MipmapGenerator::mip_size(5, 3, 1), (2, 1));
+        assert_eq!(MipmapGenerator::mip_size(5, 3, 2), (1, 1));
+
+        // 마지막 열만 밝으면 마지막 출력 텍셀에만 섞여야 한다
+        let pixels: Vec<u8> = (0..5 * 3)
+            .flat_map(|i| if i % 5 == 4 { [255; 4] } else { [0; 4] })
+            .collect();
+        let output = MipmapGenerator::downsample_rgba8(&pixels, 5, 3);
+        assert_eq!(output, [[0; 4], [85; 4]].concat());
+    }
+}
@@ -0,0 +1,38 @@
+@group(0) @binding(0)
+var source_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var output_texture: texture_storage_2d<rgba8unorm, write>;
+
+// 출력 텍셀 하나가 덮는 원본 텍셀 범위 [start, end).
+// 원본 크기가 홀수이면 마지막 출력 텍셀이 남는 한 줄까지 맡아서 3 개를 평균낸다
+fn source_range(coord: i32, output_size: i32, source_size: i32) -> vec2<i32> {
+    let start = coord * 2;
+    var end = start + 2;
+    if (coord == output_size - 1) {
+        end = source_size;
+    }
+    return vec2<i32>(start, min(end, source_size));
+}
+
+// 이전 밉 레벨을 2x2 박스 필터로 줄여서 다음 밉 레벨에 쓴다
+@compute @workgroup_size(8, 8)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let output_size = vec2<i32>(textureDimensions(output_texture));
+    let coord = vec2<i32>(id.xy);
+    if (coord.x >= output_size.x || coord.y >= output_size.y) {
+        return;
+    }
+
+    let source_size = vec2<i32>(textureDimensions(source_texture));
+    let x_range = source_range(coord.x, output_size.x, source_size.x);
+    let y_range = source_range(coord.y, output_size.y, source_size.y);
+
+    var sum = vec4<f32>(0.0);
+    for (var y = y_range.x; y < y_range.y; y++) {
+        for (var x = x_range.x; x < x_range.y; x++) {
+            sum += textureLoad(source_texture, vec2<i32>(x, y), 0);
+        }
+    }
+    let count = f32((x_range.y - x_range.x) * (y_range.y - y_range.x));
+    textureStore(output_texture, coord, sum / count);
+}
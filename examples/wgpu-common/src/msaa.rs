@@ -0,0 +1,213 @@
+/// 멀티 샘플 안티 에일리어싱 샘플 수
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MsaaConfig {
+    #[default]
+    One,
+    Two,
+    Four,
+    Eight,
+}
+
+impl MsaaConfig {
+    pub fn sample_count(self) -> u32 {
+        match self {
+            MsaaConfig::One => 1,
+            MsaaConfig::Two => 2,
+            MsaaConfig::Four => 4,
+            MsaaConfig::Eight => 8,
+        }
+    }
+
+    pub fn from_sample_count(count: u32) -> Option<Self> {
+        match count {
+            1 => Some(MsaaConfig::One),
+            2 => Some(MsaaConfig::Two),
+            4 => Some(MsaaConfig::Four),
+            8 => Some(MsaaConfig::Eight),
+            _ => None,
+        }
+    }
+
+    /// 어댑터가 `formats` 모두에 대해 이 샘플 수를 지원하는지 확인한다.
+    /// WebGPU 는 1 과 4 만 보장한다.
+    pub fn is_supported(self, adapter: &wgpu::Adapter, formats: &[wgpu::TextureFormat]) -> bool {
+        formats.iter().all(|format| {
+            adapter
+                .get_texture_format_features(*format)
+                .flags
+                .sample_count_supported(self.sample_count())
+        })
+    }
+
+    pub fn multisample_state(self) -> wgpu::MultisampleState {
+        wgpu::MultisampleState {
+            count: self.sample_count(),
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        }
+    }
+
+    /// 샘플 수가 1 보다 크면 서피스 대신 그려질 멀티 샘플 텍스처 뷰를 만든다.
+    /// 이 뷰를 `color_attachments[0].view` 로, 서피스 텍스처를 `resolve_target` 으로 사용한다.
+    pub fn create_color_view(
+        self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Option<wgpu::TextureView> {
+        if self == MsaaConfig::One {
+            return None;
+        }
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Multisampled Color Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: self.sample_count(),
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextureReadback;
+    use crate::test_gpu::{block_on, device};
+
+    const SIZE: u32 = 32;
+    // 모든 변이 비스듬한 흰 삼각형
+    const SHADER: &str = r#"
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    var pos = array<vec2<f32>, 3>(vec2(-0.8, -0.7), vec2(0.9, -0.3), vec2(-0.2, 0.85));
+    return vec4<f32>(pos[index], 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+"#;
+
+    // 검은 배경에 삼각형을 그려서 읽어 온 RGBA 바이트
+    fn render_triangle(device: &wgpu::Device, queue: &wgpu::Queue, msaa: MsaaConfig) -> Vec<u8> {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(format.into())],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: msaa.multisample_state(),
+            multiview: None,
+            cache: None,
+        });
+
+        let target = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let target_view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        // 렌더러와 같이 멀티 샘플 텍스처에 그리고 target 으로 리졸브한다
+        let msaa_view = msaa.create_color_view(device, format, SIZE, SIZE);
+        let (view, resolve_target) = match &msaa_view {
+            Some(msaa_view) => (msaa_view, Some(&target_view)),
+            None => (&target_view, None),
+        };
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            render_pass.set_pipeline(&pipeline);
+            render_pass.draw(0..3, 0..1);
+        }
+        let readback = TextureReadback::copy_from(device, &mut encoder, &target).unwrap();
+        queue.submit(std::iter::once(encoder.finish()));
+        block_on(readback.read(device)).unwrap()
+    }
+
+    // 빨강 채널이 (배경, 삼각형) 사이인 경계 픽셀 수와 삼각형으로 꽉 찬 픽셀 수
+    fn count_pixels(rgba: &[u8]) -> (usize, usize) {
+        let red = rgba.chunks_exact(4).map(|pixel| pixel[0]);
+        let partial = red.clone().filter(|&r| r != 0 && r != 255).count();
+        let full = red.filter(|&r| r == 255).count();
+        (partial, full)
+    }
+
+    #[test]
+    #[cfg_attr(not(wgpu_tests), ignore = "needs a GPU adapter, set WGPU_TESTS=1")]
+    fn four_samples_smooth_triangle_edges() {
+        let Some((device, queue)) = device() else {
+            return;
+        };
+        let aliased = render_triangle(&device, &queue, MsaaConfig::One);
+        let smoothed = render_triangle(&device, &queue, MsaaConfig::Four);
+
+        // 샘플이 하나면 픽셀은 배경이거나 삼각형이고, 4 개면 경계 픽셀이 중간 값이 된다.
+        // 세 변이 세로로 SIZE 행 넘게 걸쳐 있으므로 행마다 하나 이상은 섞인다
+        let (aliased_partial, aliased_full) = count_pixels(&aliased);
+        let (smoothed_partial, smoothed_full) = count_pixels(&smoothed);
+        assert_eq!(aliased_partial, 0);
+        assert!(aliased_full > 0);
+        assert!(
+            smoothed_partial >= SIZE as usize,
+            "only {} edge pixels were blended",
+            smoothed_partial
+        );
+
+        // 안쪽은 그대로 꽉 찬다
+        assert!(smoothed_full > aliased_full / 2);
+        let center = ((SIZE / 2 * SIZE + SIZE / 2) * 4) as usize;
+        assert_eq!(aliased[center], 255);
+        assert_eq!(smoothed[center], 255);
+    }
+}
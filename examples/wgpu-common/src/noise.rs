@@ -0,0 +1,354 @@
+use crate::Texture;
+
+// Perlin 이 격자 꼭짓점에 쓰는 기울기. 대각선 넷과 축 방향 넷
+const GRADIENTS: [(f32, f32); 8] = [
+    (1.0, 1.0),
+    (-1.0, 1.0),
+    (1.0, -1.0),
+    (-1.0, -1.0),
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NoiseType {
+    /// 격자 꼭짓점의 기울기를 보간하는 그래디언트 노이즈
+    #[default]
+    Perlin,
+    /// 삼각형 격자를 써서 Perlin 보다 축 방향 무늬가 덜 보이는 그래디언트 노이즈
+    Simplex,
+    /// 칸마다 하나씩 뿌린 점 중 가장 가까운 점까지의 거리. 세포나 돌 무늬에 쓴다.
+    Worley,
+}
+
+/// 구름이나 지형 디테일에 쓸 노이즈를 CPU 에서 만들어 `R8Unorm` 텍스처로 올린다.
+///
+/// ```ignore
+/// let clouds = NoiseTextureBuilder::new()
+///     .size(512, 512)
+///     .noise_type(NoiseType::Simplex)
+///     .frequency(8.0)
+///     .octaves(5)
+///     .seed(42)
+///     .build(device, queue);
+/// ```
+///
+/// `frequency` 는 텍스처 한 변에 들어가는 격자 칸 수이고, 옥타브마다 두 배가 된다.
+/// Perlin, Simplex 는 -1..1 의 값을 0..1 로 옮겨 담고, Worley 는 거리를 그대로 담는다.
+#[derive(Clone, Debug)]
+pub struct NoiseTextureBuilder {
+    width: u32,
+    height: u32,
+    noise_type: NoiseType,
+    frequency: f32,
+    octaves: u8,
+    seed: u64,
+}
+
+impl Default for NoiseTextureBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoiseTextureBuilder {
+    /// 256x256 Perlin, 주파수 4, 옥타브 1
+    pub fn new() -> Self {
+        Self {
+            width: 256,
+            height: 256,
+            noise_type: NoiseType::default(),
+            frequency: 4.0,
+            octaves: 1,
+            seed: 0,
+        }
+    }
+
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = height.max(1);
+        self
+    }
+
+    pub fn size(self, width: u32, height: u32) -> Self {
+        self.width(width).height(height)
+    }
+
+    pub fn noise_type(mut self, noise_type: NoiseType) -> Self {
+        self.noise_type = noise_type;
+        self
+    }
+
+    pub fn frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// 겹칠 옥타브 수. 한 옥타브 올라갈 때마다 주파수는 두 배, 세기는 절반이 된다.
+    pub fn octaves(mut self, octaves: u8) -> Self {
+        self.octaves = octaves.max(1);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// 텍셀 값을 행 우선으로 만든다. Perlin, Simplex 는 대략 -1..1, Worley 는 0..1 이다.
+    pub fn generate(&self) -> Vec<f32> {
+        let noise = Noise::new(self.seed);
+        let (width, height) = (self.width, self.height);
+        let mut values = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let u = x as f32 / width as f32 * self.frequency;
+                let v = y as f32 / height as f32 * self.frequency;
+                values.push(noise.fbm(self.noise_type, u, v, self.octaves));
+            }
+        }
+        values
+    }
+
+    pub fn build(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Texture {
+        let texels: Vec<u8> = self
+            .generate()
+            .into_iter()
+            .map(|value| {
+                let value = match self.noise_type {
+                    NoiseType::Perlin | NoiseType::Simplex => value * 0.5 + 0.5,
+                    NoiseType::Worley => value,
+                };
+                (value.clamp(0.0, 1.0) * 255.0).round() as u8
+            })
+            .collect();
+
+        let size = wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Noise Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &texels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(self.width),
+                rows_per_image: Some(self.height),
+            },
+            size,
+        );
+
+        Texture::from_texture(device, texture, "Noise Texture")
+    }
+}
+
+// 시드로 섞은 순열 표. 격자 좌표를 해시하는 데 쓴다
+struct Noise {
+    permutation: [u8; 512],
+}
+
+impl Noise {
+    fn new(seed: u64) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        // splitmix64 로 만든 난수로 Fisher-Yates 셔플
+        let mut state = seed;
+        for i in (1..table.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+
+        Self {
+            permutation: std::array::from_fn(|i| table[i % 256]),
+        }
+    }
+
+    fn hash(&self, x: i32, y: i32) -> usize {
+        let x = self.permutation[(x & 255) as usize] as usize;
+        self.permutation[x + (y & 255) as usize] as usize
+    }
+
+    fn fbm(&self, noise_type: NoiseType, x: f32, y: f32, octaves: u8) -> f32 {
+        let (mut sum, mut amplitude, mut total) = (0.0, 1.0, 0.0);
+        let mut frequency = 1.0;
+        for _ in 0..octaves {
+            let (x, y) = (x * frequency, y * frequency);
+            sum += amplitude
+                * match noise_type {
+                    NoiseType::Perlin => self.perlin(x, y),
+                    NoiseType::Simplex => self.simplex(x, y),
+                    NoiseType::Worley => self.worley(x, y),
+                };
+            total += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        sum / total
+    }
+
+    fn gradient(&self, ix: i32, iy: i32, dx: f32, dy: f32) -> f32 {
+        let (gx, gy) = GRADIENTS[self.hash(ix, iy) & 7];
+        gx * dx + gy * dy
+    }
+
+    fn perlin(&self, x: f32, y: f32) -> f32 {
+        let (x0, y0) = (x.floor(), y.floor());
+        let (ix, iy) = (x0 as i32, y0 as i32);
+        let (dx, dy) = (x - x0, y - y0);
+
+        let n00 = self.gradient(ix, iy, dx, dy);
+        let n10 = self.gradient(ix + 1, iy, dx - 1.0, dy);
+        let n01 = self.gradient(ix, iy + 1, dx, dy - 1.0);
+        let n11 = self.gradient(ix + 1, iy + 1, dx - 1.0, dy - 1.0);
+
+        let (u, v) = (fade(dx), fade(dy));
+        let nx0 = n00 + (n10 - n00) * u;
+        let nx1 = n01 + (n11 - n01) * u;
+        nx0 + (nx1 - nx0) * v
+    }
+
+    fn simplex(&self, x: f32, y: f32) -> f32 {
+        // 정사각형 격자를 기울여 정삼각형 격자로 보는 비율
+        let skew = (3.0_f32.sqrt() - 1.0) / 2.0;
+        let unskew = (3.0 - 3.0_f32.sqrt()) / 6.0;
+
+        let s = (x + y) * skew;
+        let (i, j) = ((x + s).floor(), (y + s).floor());
+        let t = (i + j) * unskew;
+        let (x0, y0) = (x - (i - t), y - (j - t));
+
+        // 칸을 나눈 두 삼각형 중 어느 쪽인지
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+        let (x1, y1) = (x0 - i1 as f32 + unskew, y0 - j1 as f32 + unskew);
+        let (x2, y2) = (x0 - 1.0 + 2.0 * unskew, y0 - 1.0 + 2.0 * unskew);
+
+        let (ii, jj) = (i as i32, j as i32);
+        let corner = |ix: i32, iy: i32, dx: f32, dy: f32| {
+            let falloff = 0.5 - dx * dx - dy * dy;
+            if falloff < 0.0 {
+                0.0
+            } else {
+                falloff.powi(4) * self.gradient(ix, iy, dx, dy)
+            }
+        };
+        let sum = corner(ii, jj, x0, y0)
+            + corner(ii + i1, jj + j1, x1, y1)
+            + corner(ii + 1, jj + 1, x2, y2);
+        // 결과를 대략 -1..1 로 맞추는 배율
+        70.0 * sum
+    }
+
+    fn worley(&self, x: f32, y: f32) -> f32 {
+        let (cx, cy) = (x.floor() as i32, y.floor() as i32);
+        let mut nearest = f32::MAX;
+        for oy in -1..=1 {
+            for ox in -1..=1 {
+                let (ix, iy) = (cx + ox, cy + oy);
+                let h = self.hash(ix, iy);
+                let px = ix as f32 + self.permutation[h] as f32 / 255.0;
+                let py = iy as f32 + self.permutation[h + 1] as f32 / 255.0;
+                nearest = nearest.min((px - x).powi(2) + (py - y).powi(2));
+            }
+        }
+        // 드물게 1 을 넘는 거리는 잘라서 0..1 로 맞춘다
+        nearest.sqrt().min(1.0)
+    }
+}
+
+// 6t^5 - 15t^4 + 10t^3. 격자 경계에서 1, 2차 도함수가 0 이 된다
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean_and_variance(values: &[f32]) -> (f32, f32) {
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32;
+        (mean, variance)
+    }
+
+    fn generate(noise_type: NoiseType, octaves: u8) -> Vec<f32> {
+        NoiseTextureBuilder::new()
+            .size(256, 256)
+            .noise_type(noise_type)
+            .frequency(32.0)
+            .octaves(octaves)
+            .seed(7)
+            .generate()
+    }
+
+    #[test]
+    fn perlin_is_zero_mean_with_expected_variance() {
+        let values = generate(NoiseType::Perlin, 1);
+        let (mean, variance) = mean_and_variance(&values);
+        assert!(mean.abs() < 0.02, "mean {}", mean);
+        assert!((0.03..0.12).contains(&variance), "variance {}", variance);
+        assert!(values.iter().all(|v| v.abs() <= 1.0));
+    }
+
+    #[test]
+    fn simplex_is_zero_mean_with_expected_variance() {
+        let values = generate(NoiseType::Simplex, 1);
+        let (mean, variance) = mean_and_variance(&values);
+        assert!(mean.abs() < 0.02, "mean {}", mean);
+        assert!((0.05..0.25).contains(&variance), "variance {}", variance);
+        assert!(values.iter().all(|v| v.abs() <= 1.0));
+    }
+
+    #[test]
+    fn octaves_reduce_variance() {
+        // 세기를 정규화하므로 독립적인 옥타브가 겹칠수록 분산이 줄어든다
+        let (_, one) = mean_and_variance(&generate(NoiseType::Perlin, 1));
+        let (_, four) = mean_and_variance(&generate(NoiseType::Perlin, 4));
+        assert!(four < one);
+    }
+
+    #[test]
+    fn worley_stays_in_unit_range() {
+        let values = generate(NoiseType::Worley, 1);
+        assert!(values.iter().all(|v| (0.0..=1.0).contains(v)));
+        let (mean, _) = mean_and_variance(&values);
+        assert!((0.2..0.6).contains(&mean), "mean {}", mean);
+    }
+
+    #[test]
+    fn same_seed_gives_same_noise() {
+        let a = NoiseTextureBuilder::new().seed(3).size(16, 16).generate();
+        let b = NoiseTextureBuilder::new().seed(3).size(16, 16).generate();
+        let c = NoiseTextureBuilder::new().seed(4).size(16, 16).generate();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
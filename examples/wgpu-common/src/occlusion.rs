@@ -0,0 +1,198 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+const RESULT_SIZE: wgpu::BufferAddress = std::mem::size_of::<u64>() as wgpu::BufferAddress;
+
+// 매핑이 끝난 쿼리 결과. counts[i] 는 indices[i] 번 물체가 통과한 샘플 수다
+struct QueryResults {
+    indices: Vec<u32>,
+    counts: Vec<u64>,
+}
+
+/// 오클루전 쿼리로 물체가 다른 물체에 완전히 가려졌는지 확인해서 다음 프레임의 그리기를 건너뛴다.
+///
+/// 한 프레임의 사용 순서는 다음과 같다.
+/// 1. `begin_frame` 으로 지난번에 읽어 온 결과를 `visibility` 에 반영한다
+/// 2. 렌더 패스 디스크립터의 `occlusion_query_set` 에 `query_set()` 을 넘긴다
+/// 3. 물체마다 `query` 안에서 그린다. `visibility` 가 `false` 인 물체는 진짜 메시 대신
+///    색과 깊이를 쓰지 않는 파이프라인으로 같은 메시나 바운딩 박스를 그려서 다시 보이는지 확인한다
+/// 4. 패스가 끝나면 `resolve`, 제출한 뒤 `read_back` 을 호출한다
+///
+/// 결과는 GPU 에서 읽어 와야 하므로 한 프레임 이상 늦게 반영된다. 아직 결과가 없는 물체는 보인다고 본다.
+/// 가려진 물체의 쿼리는 앞의 물체들이 깊이를 채운 뒤에 해야 하므로 보이는 물체를 먼저 그린다.
+pub struct OcclusionQueryPool {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    capacity: u32,
+    // 이번 프레임에 쿼리한 물체 번호, 쿼리 인덱스 순서
+    queried: Vec<u32>,
+    // resolve 로 리드백 버퍼에 복사한 물체 번호
+    resolved: Vec<u32>,
+    // 리드백 버퍼가 매핑 중이면 복사할 수 없으므로 다음 프레임은 건너뛴다
+    mapping: Arc<AtomicBool>,
+    results: Arc<Mutex<Option<QueryResults>>>,
+    visible: Vec<bool>,
+}
+
+impl OcclusionQueryPool {
+    /// 물체 번호 `0..capacity` 를 쿼리할 수 있는 풀
+    pub fn new(device: &wgpu::Device, capacity: u32) -> Self {
+        let capacity = capacity.max(1);
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Occlusion Query Set"),
+            ty: wgpu::QueryType::Occlusion,
+            count: capacity,
+        });
+
+        let size = capacity as wgpu::BufferAddress * RESULT_SIZE;
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Occlusion Resolve Buffer"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Occlusion Readback Buffer"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            capacity,
+            queried: Vec::with_capacity(capacity as usize),
+            resolved: Vec::new(),
+            mapping: Arc::new(AtomicBool::new(false)),
+            results: Arc::new(Mutex::new(None)),
+            visible: vec![true; capacity as usize],
+        }
+    }
+
+    /// 렌더 패스 디스크립터의 `occlusion_query_set` 에 넘긴다.
+    pub fn query_set(&self) -> &wgpu::QuerySet {
+        &self.query_set
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.queried.clear();
+        if let Some(results) = self.results.lock().unwrap().take() {
+            update_visibility(&mut self.visible, &results.indices, &results.counts);
+        }
+    }
+
+    /// `draw` 에서 그린 프래그먼트가 깊이 테스트를 통과하는지 `index` 번 물체의 결과로 기록한다.
+    /// 한 프레임에 `capacity` 개를 넘으면 쿼리 없이 그리기만 한다.
+    pub fn query(
+        &mut self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        index: u32,
+        draw: impl FnOnce(&mut wgpu::RenderPass<'_>),
+    ) {
+        if index >= self.capacity || self.queried.len() as u32 >= self.capacity {
+            draw(render_pass);
+            return;
+        }
+
+        render_pass.begin_occlusion_query(self.queried.len() as u32);
+        draw(render_pass);
+        render_pass.end_occlusion_query();
+        self.queried.push(index);
+    }
+
+    /// 이번 프레임의 쿼리 결과를 리드백 버퍼로 복사하는 명령을 기록한다.
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if self.queried.is_empty() || self.mapping.load(Ordering::Acquire) {
+            return;
+        }
+
+        let query_count = self.queried.len() as u32;
+        encoder.resolve_query_set(&self.query_set, 0..query_count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            query_count as wgpu::BufferAddress * RESULT_SIZE,
+        );
+        self.resolved = self.queried.clone();
+    }
+
+    /// 제출한 뒤 호출한다. 매핑이 끝나면 다음 `begin_frame` 에서 `visibility` 가 갱신된다.
+    pub fn read_back(&mut self) {
+        if self.resolved.is_empty() || self.mapping.load(Ordering::Acquire) {
+            return;
+        }
+
+        self.mapping.store(true, Ordering::Release);
+        let indices = std::mem::take(&mut self.resolved);
+        let mapping = Arc::clone(&self.mapping);
+        let results = Arc::clone(&self.results);
+        let buffer = self.readback_buffer.clone();
+        let size = indices.len() as wgpu::BufferAddress * RESULT_SIZE;
+
+        self.readback_buffer
+            .slice(..size)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                if result.is_ok() {
+                    let counts = {
+                        let mapped = buffer.slice(..size).get_mapped_range();
+                        bytemuck::cast_slice::<u8, u64>(&mapped).to_vec()
+                    };
+                    buffer.unmap();
+                    *results.lock().unwrap() = Some(QueryResults { indices, counts });
+                }
+                mapping.store(false, Ordering::Release);
+            });
+    }
+
+    /// 물체 번호마다 마지막으로 읽어 온 결과. `false` 면 완전히 가려져 있었다
+    pub fn visibility(&self) -> &[bool] {
+        &self.visible
+    }
+
+    /// `capacity` 밖의 번호는 항상 보인다고 본다
+    pub fn is_visible(&self, index: u32) -> bool {
+        self.visible.get(index as usize).copied().unwrap_or(true)
+    }
+
+    /// 모든 물체를 다시 보인다고 본다. 카메라가 순간 이동했을 때처럼 결과를 믿을 수 없을 때 쓴다
+    pub fn reset(&mut self) {
+        self.visible.fill(true);
+    }
+}
+
+// 한 샘플이라도 깊이 테스트를 통과했으면 보인다. 이번에 쿼리하지 않은 물체는 그대로 둔다
+fn update_visibility(visible: &mut [bool], indices: &[u32], counts: &[u64]) {
+    for (&index, &count) in indices.iter().zip(counts) {
+        if let Some(visible) = visible.get_mut(index as usize) {
+            *visible = count > 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_only_queried_objects() {
+        let mut visible = vec![true; 4];
+        update_visibility(&mut visible, &[2, 0], &[0, 17]);
+        assert_eq!(visible, [true, true, false, true]);
+
+        // 가려졌던 물체도 샘플이 통과하면 다시 보인다
+        update_visibility(&mut visible, &[2], &[1]);
+        assert_eq!(visible, [true; 4]);
+    }
+
+    #[test]
+    fn ignores_out_of_range_indices() {
+        let mut visible = vec![true; 2];
+        update_visibility(&mut visible, &[5, 1], &[0, 0]);
+        assert_eq!(visible, [true, false]);
+    }
+}
@@ -0,0 +1,346 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    Blob, BlobPropertyBag, DedicatedWorkerGlobalScope, HtmlCanvasElement, MessageEvent, Url,
+    Worker, WorkerOptions, WorkerType,
+};
+
+// 명령 하나가 차지하는 i32 개수. 종류 + 값 세 개
+const COMMAND_WORDS: u32 = 4;
+// 링 버퍼 앞쪽의 쓰기 위치와 읽기 위치
+const HEADER_WORDS: u32 = 2;
+const WRITE_INDEX: u32 = 0;
+const READ_INDEX: u32 = 1;
+// 워커가 한 프레임 동안 밀리더라도 넘치지 않을 만큼
+const DEFAULT_CAPACITY: u32 = 256;
+
+/// 메인 스레드가 렌더링 워커로 보내는 명령
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OffscreenCommand {
+    /// 캔버스의 렌더링 픽셀 크기
+    Resize { width: u32, height: u32 },
+    /// 캔버스 기준 CSS 픽셀 좌표와 `MouseEvent::buttons`
+    Pointer { x: f32, y: f32, buttons: u32 },
+}
+
+impl OffscreenCommand {
+    fn encode(&self) -> [i32; COMMAND_WORDS as usize] {
+        match *self {
+            OffscreenCommand::Resize { width, height } => [1, width as i32, height as i32, 0],
+            OffscreenCommand::Pointer { x, y, buttons } => {
+                [2, x.to_bits() as i32, y.to_bits() as i32, buttons as i32]
+            }
+        }
+    }
+
+    fn decode(words: [i32; COMMAND_WORDS as usize]) -> Option<Self> {
+        match words[0] {
+            1 => Some(OffscreenCommand::Resize {
+                width: words[1] as u32,
+                height: words[2] as u32,
+            }),
+            2 => Some(OffscreenCommand::Pointer {
+                x: f32::from_bits(words[1] as u32),
+                y: f32::from_bits(words[2] as u32),
+                buttons: words[3] as u32,
+            }),
+            _ => None,
+        }
+    }
+}
+
+// 쓰기 위치와 읽기 위치는 계속 증가하다가 u32 범위에서 돌아간다
+fn ring_len(write: u32, read: u32) -> u32 {
+    write.wrapping_sub(read)
+}
+
+// position 번째 명령의 첫 word 위치
+fn slot_index(position: u32, capacity: u32) -> u32 {
+    HEADER_WORDS + (position % capacity) * COMMAND_WORDS
+}
+
+/// `SharedArrayBuffer` 위의 단일 생산자, 단일 소비자 명령 링 버퍼.
+///
+/// 메인 스레드가 `push` 하고 워커가 매 프레임 `pop` 한다. 명령을 먼저 쓴 뒤
+/// `Atomics.store` 로 쓰기 위치를 올리므로, 워커는 위치가 올라간 명령만 완전한 상태로 읽는다.
+/// 가득 차면 새 명령을 버린다.
+pub struct CommandRing {
+    words: js_sys::Int32Array,
+    capacity: u32,
+}
+
+impl CommandRing {
+    /// `SharedArrayBuffer` 를 쓸 수 있는 페이지에서만 만들 수 있다. 아니면 `None`.
+    /// `capacity` 는 2 의 거듭제곱으로 올린다.
+    ///
+    /// 페이지가 `Cross-Origin-Opener-Policy: same-origin` 과
+    /// `Cross-Origin-Embedder-Policy: require-corp` 헤더로 격리되어야 한다.
+    pub fn new(capacity: u32) -> Option<Self> {
+        if !is_cross_origin_isolated() {
+            return None;
+        }
+        // 위치가 u32 범위에서 돌아가도 슬롯이 이어지도록 2 의 거듭제곱으로 맞춘다
+        let capacity = capacity.max(1).next_power_of_two();
+        let buffer = js_sys::SharedArrayBuffer::new((HEADER_WORDS + capacity * COMMAND_WORDS) * 4);
+        Some(Self::from_buffer(&buffer))
+    }
+
+    /// 워커에서 메인 스레드가 보낸 버퍼로 같은 링 버퍼를 연다.
+    pub fn from_buffer(buffer: &js_sys::SharedArrayBuffer) -> Self {
+        let words = js_sys::Int32Array::new(buffer);
+        let capacity = (words.length() - HEADER_WORDS) / COMMAND_WORDS;
+        Self { words, capacity }
+    }
+
+    /// `postMessage` 로 워커에 넘길 버퍼
+    pub fn buffer(&self) -> JsValue {
+        self.words.buffer().into()
+    }
+
+    /// 가득 차서 버렸으면 `false`
+    pub fn push(&self, command: OffscreenCommand) -> bool {
+        let write = self.load(WRITE_INDEX);
+        if ring_len(write, self.load(READ_INDEX)) >= self.capacity {
+            return false;
+        }
+
+        let slot = slot_index(write, self.capacity);
+        for (i, word) in command.encode().into_iter().enumerate() {
+            self.words.set_index(slot + i as u32, word);
+        }
+        self.store(WRITE_INDEX, write.wrapping_add(1));
+        true
+    }
+
+    pub fn pop(&self) -> Option<OffscreenCommand> {
+        let read = self.load(READ_INDEX);
+        if ring_len(self.load(WRITE_INDEX), read) == 0 {
+            return None;
+        }
+
+        let slot = slot_index(read, self.capacity);
+        let words = std::array::from_fn(|i| self.words.get_index(slot + i as u32));
+        self.store(READ_INDEX, read.wrapping_add(1));
+        OffscreenCommand::decode(words)
+    }
+
+    fn load(&self, index: u32) -> u32 {
+        js_sys::Atomics::load(&self.words, index).unwrap_or(0) as u32
+    }
+
+    fn store(&self, index: u32, value: u32) {
+        let _ = js_sys::Atomics::store(&self.words, index, value as i32);
+    }
+}
+
+/// 워커가 받는 명령. 격리되지 않은 페이지에서는 `SharedArrayBuffer` 대신
+/// 워커 스크립트가 메시지를 쌓아 둔 JS 배열에서 꺼낸다.
+pub enum OffscreenCommands {
+    Shared(CommandRing),
+    Queue(js_sys::Array),
+}
+
+impl OffscreenCommands {
+    /// `worker_main` 에 넘어온 `commands` 값으로 만든다.
+    pub fn new(commands: JsValue) -> Result<Self, JsValue> {
+        if let Some(buffer) = commands.dyn_ref::<js_sys::SharedArrayBuffer>() {
+            Ok(OffscreenCommands::Shared(CommandRing::from_buffer(buffer)))
+        } else {
+            commands
+                .dyn_into::<js_sys::Array>()
+                .map(OffscreenCommands::Queue)
+                .map_err(|_| JsValue::from_str("Expected SharedArrayBuffer or Array of commands"))
+        }
+    }
+
+    pub fn pop(&self) -> Option<OffscreenCommand> {
+        match self {
+            OffscreenCommands::Shared(ring) => ring.pop(),
+            OffscreenCommands::Queue(queue) => loop {
+                if queue.length() == 0 {
+                    return None;
+                }
+                let words = js_sys::Int32Array::new(&queue.shift());
+                if words.length() != COMMAND_WORDS {
+                    continue;
+                }
+                let words = std::array::from_fn(|i| words.get_index(i as u32));
+                if let Some(command) = OffscreenCommand::decode(words) {
+                    return Some(command);
+                }
+            },
+        }
+    }
+}
+
+/// 캔버스의 렌더링을 웹 워커로 옮긴다.
+///
+/// `transferControlToOffscreen` 으로 얻은 `OffscreenCanvas` 를 워커로 보내고, 워커는
+/// `wgpu::SurfaceTarget::OffscreenCanvas` 로 서피스를 만들어 자기 `requestAnimationFrame` 으로 그린다.
+/// 무거운 프레임이 메인 스레드를 막지 않으므로 스크롤이나 버튼 같은 UI 가 끊기지 않는다.
+///
+/// 옮긴 뒤에는 메인 스레드에서 캔버스 크기를 바꿀 수 없으므로 크기와 입력은 `send` 로 보낸다.
+/// 명령은 `CommandRing` 으로 전달하고, `SharedArrayBuffer` 를 쓸 수 없는 페이지에서는
+/// 명령마다 `postMessage` 한다.
+pub struct CanvasOffscreenRenderer {
+    worker: Worker,
+    // 워커가 스크립트를 불러오기 전에 해제하면 안 되므로 drop 할 때 해제한다
+    script_url: String,
+    commands: Option<CommandRing>,
+    _on_message: Option<Closure<dyn FnMut(MessageEvent)>>,
+}
+
+impl CanvasOffscreenRenderer {
+    /// `module_url` 은 wasm-pack(`--target web`)이 만든 JS 모듈의 절대 URL 이다.
+    /// 그 모듈은 `worker_main(canvas: OffscreenCanvas, commands: JsValue)` 를 export 해야 하고,
+    /// `commands` 는 `OffscreenCommands::new` 에 넘긴다.
+    pub fn spawn(canvas: &HtmlCanvasElement, module_url: &str) -> Result<Self, JsValue> {
+        let offscreen = canvas.transfer_control_to_offscreen()?;
+        let commands = CommandRing::new(DEFAULT_CAPACITY);
+
+        // 같은 wasm 모듈을 워커에서 다시 초기화하는 모듈 워커 스크립트.
+        // wasm 을 불러오는 동안 도착한 명령은 배열에 쌓아 두었다가 함께 넘긴다
+        let script = format!(
+            "import init, {{ worker_main }} from '{module_url}';\n\
+             self.onmessage = async (event) => {{\n\
+             \x20   const {{ canvas, commands }} = event.data;\n\
+             \x20   const queue = [];\n\
+             \x20   self.onmessage = (event) => queue.push(event.data);\n\
+             \x20   await init();\n\
+             \x20   await worker_main(canvas, commands ?? queue);\n\
+             }};\n"
+        );
+        let options = BlobPropertyBag::new();
+        options.set_type("text/javascript");
+        let blob =
+            Blob::new_with_str_sequence_and_options(&js_sys::Array::of1(&script.into()), &options)?;
+        let url = Url::create_object_url_with_blob(&blob)?;
+
+        let worker_options = WorkerOptions::new();
+        worker_options.set_type(WorkerType::Module);
+        let worker = Worker::new_with_options(&url, &worker_options)?;
+
+        let message = js_sys::Object::new();
+        js_sys::Reflect::set(&message, &"canvas".into(), &offscreen)?;
+        js_sys::Reflect::set(
+            &message,
+            &"commands".into(),
+            &commands.as_ref().map_or(JsValue::NULL, CommandRing::buffer),
+        )?;
+        worker.post_message_with_transfer(&message, &js_sys::Array::of1(&offscreen))?;
+
+        Ok(Self {
+            worker,
+            script_url: url,
+            commands,
+            _on_message: None,
+        })
+    }
+
+    /// 명령을 공유 링 버퍼나 메시지로 보낸다. 링 버퍼가 가득 차서 버렸으면 `false`
+    pub fn send(&self, command: OffscreenCommand) -> bool {
+        match &self.commands {
+            Some(ring) => ring.push(command),
+            None => {
+                let words = js_sys::Int32Array::from(&command.encode()[..]);
+                self.worker.post_message(&words).is_ok()
+            }
+        }
+    }
+
+    /// `SharedArrayBuffer` 로 명령을 보내고 있는지
+    pub fn is_shared(&self) -> bool {
+        self.commands.is_some()
+    }
+
+    /// 워커가 `post_to_main` 으로 보낸 값을 받는다. 이전 핸들러는 교체된다.
+    pub fn set_on_message(&mut self, mut on_message: impl FnMut(JsValue) + 'static) {
+        let callback = Closure::wrap(Box::new(move |event: MessageEvent| {
+            on_message(event.data());
+        }) as Box<dyn FnMut(MessageEvent)>);
+        self.worker
+            .set_onmessage(Some(callback.as_ref().unchecked_ref()));
+        self._on_message = Some(callback);
+    }
+}
+
+impl Drop for CanvasOffscreenRenderer {
+    fn drop(&mut self) {
+        self.worker.set_onmessage(None);
+        self.worker.terminate();
+        let _ = Url::revoke_object_url(&self.script_url);
+    }
+}
+
+/// 워커에서 메인 스레드의 `CanvasOffscreenRenderer::set_on_message` 로 값을 보낸다.
+pub fn post_to_main(value: &JsValue) -> Result<(), JsValue> {
+    worker_scope()?.post_message(value)
+}
+
+/// `web::start_animation_loop` 의 워커 버전. 워커의 `requestAnimationFrame` 으로 매 프레임 `frame` 을
+/// 호출하고, `frame` 이 `false` 를 반환하면 멈춘다.
+pub fn start_worker_animation_loop(mut frame: impl FnMut() -> bool + 'static) {
+    let scope = worker_scope().unwrap();
+    let f = Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>));
+    let g = Rc::clone(&f);
+
+    let loop_scope = scope.clone();
+    *g.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        if !frame() {
+            return;
+        }
+        loop_scope
+            .request_animation_frame(f.borrow().as_ref().unwrap().as_ref().unchecked_ref())
+            .expect("Failed to request animation frame");
+    }) as Box<dyn FnMut()>));
+
+    scope
+        .request_animation_frame(g.borrow().as_ref().unwrap().as_ref().unchecked_ref())
+        .expect("Failed to request animation frame");
+}
+
+fn worker_scope() -> Result<DedicatedWorkerGlobalScope, JsValue> {
+    js_sys::global()
+        .dyn_into::<DedicatedWorkerGlobalScope>()
+        .map_err(|_| JsValue::from_str("Not running in a dedicated worker"))
+}
+
+// SharedArrayBuffer 는 교차 출처 격리된 페이지에서만 정의된다
+fn is_cross_origin_isolated() -> bool {
+    js_sys::Reflect::get(&js_sys::global(), &"crossOriginIsolated".into())
+        .is_ok_and(|value| value.is_truthy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_round_trip() {
+        let commands = [
+            OffscreenCommand::Resize {
+                width: 1920,
+                height: 1080,
+            },
+            OffscreenCommand::Pointer {
+                x: 12.5,
+                y: -3.25,
+                buttons: 1,
+            },
+        ];
+        for command in commands {
+            assert_eq!(OffscreenCommand::decode(command.encode()), Some(command));
+        }
+        assert_eq!(OffscreenCommand::decode([0; 4]), None);
+    }
+
+    #[test]
+    fn ring_positions_wrap() {
+        assert_eq!(ring_len(5, 2), 3);
+        assert_eq!(ring_len(1, u32::MAX), 2);
+        assert_eq!(slot_index(0, 4), HEADER_WORDS);
+        assert_eq!(slot_index(5, 4), HEADER_WORDS + COMMAND_WORDS);
+    }
+}
@@ -0,0 +1,233 @@
+use glam::Mat4;
+use wgpu::util::DeviceExt;
+
+use crate::{Mesh, Vertex};
+
+// outline.wgsl 의 OutlineUniform 과 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct OutlineUniform {
+    view_proj: [[f32; 4]; 4],
+    model: [[f32; 4]; 4],
+    color: [f32; 4],
+    thickness: f32,
+    _padding: [f32; 3],
+}
+
+/// 선택된 메시 둘레에 단색 외곽선을 그린다.
+///
+/// 한 렌더 패스에서 메시를 두 번 그린다. 먼저 색은 쓰지 않고 실루엣에 스텐실 1 을 쓰고(`Replace`),
+/// 그다음 `thickness` 만큼 키운 메시를 스텐실이 1 이 아닌 곳(`NotEqual`)에만 칠해서 테두리만 남긴다.
+/// 깊이 스텐실 텍스처는 `DepthTexture::with_stencil` 처럼 스텐실이 있는 포맷이어야 하고,
+/// 메인 패스의 깊이가 남아 있어서 다른 물체에 가려진 테두리는 그리지 않는다.
+///
+/// 메시 버텍스의 `@location(0)` 은 `Float32x3` 위치여야 한다.
+pub struct StencilOutlinePass {
+    device: wgpu::Device,
+    bind_group_layout: wgpu::BindGroupLayout,
+    mask_pipeline: wgpu::RenderPipeline,
+    outline_pipeline: wgpu::RenderPipeline,
+    view_proj: Mat4,
+    model: Mat4,
+}
+
+impl StencilOutlinePass {
+    /// `V` 는 `render` 에 넘길 메시의 버텍스 타입이다.
+    pub fn new<V: Vertex>(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        depth_stencil_format: wgpu::TextureFormat,
+    ) -> Self {
+        assert!(
+            depth_stencil_format.has_stencil_aspect(),
+            "{:?} has no stencil aspect",
+            depth_stencil_format
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Outline Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Outline Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("outline.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Outline Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        // 위치만 읽으므로 버텍스 타입의 stride 에 0번 속성만 넣는다
+        let attributes = [wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x3,
+            offset: 0,
+            shader_location: 0,
+        }];
+        let vertex_buffers = [wgpu::VertexBufferLayout {
+            attributes: &attributes,
+            ..V::layout()
+        }];
+
+        let create_pipeline = |label, entry_point, write_mask, stencil_face, depth_compare| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    buffers: &vertex_buffers,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                // 메시의 감는 순서를 모르므로 컬링하지 않는다
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: depth_stencil_format,
+                    depth_write_enabled: false,
+                    depth_compare,
+                    stencil: wgpu::StencilState {
+                        front: stencil_face,
+                        back: stencil_face,
+                        read_mask: 0xff,
+                        write_mask: 0xff,
+                    },
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        // 가려진 부분까지 실루엣 전체를 표시해야 테두리가 메시 안쪽을 칠하지 않는다
+        let mask_pipeline = create_pipeline(
+            "Outline Mask Pipeline",
+            "vs_mask",
+            wgpu::ColorWrites::empty(),
+            wgpu::StencilFaceState {
+                compare: wgpu::CompareFunction::Always,
+                fail_op: wgpu::StencilOperation::Keep,
+                depth_fail_op: wgpu::StencilOperation::Keep,
+                pass_op: wgpu::StencilOperation::Replace,
+            },
+            wgpu::CompareFunction::Always,
+        );
+        let outline_pipeline = create_pipeline(
+            "Outline Pipeline",
+            "vs_outline",
+            wgpu::ColorWrites::ALL,
+            wgpu::StencilFaceState {
+                compare: wgpu::CompareFunction::NotEqual,
+                fail_op: wgpu::StencilOperation::Keep,
+                depth_fail_op: wgpu::StencilOperation::Keep,
+                pass_op: wgpu::StencilOperation::Keep,
+            },
+            wgpu::CompareFunction::LessEqual,
+        );
+
+        Self {
+            device: device.clone(),
+            bind_group_layout,
+            mask_pipeline,
+            outline_pipeline,
+            view_proj: Mat4::IDENTITY,
+            model: Mat4::IDENTITY,
+        }
+    }
+
+    /// 다음 `render` 에서 쓸 카메라와 메시의 모델 행렬
+    pub fn set_transform(&mut self, view_proj: Mat4, model: Mat4) {
+        self.view_proj = view_proj;
+        self.model = model;
+    }
+
+    /// 메인 패스가 끝난 `view` 와 `depth_view` 위에 `mesh` 의 외곽선을 덧그린다.
+    ///
+    /// `thickness` 는 모델 공간 원점을 기준으로 메시를 키우는 비율이다(0.05 면 5%).
+    /// 호출마다 유니폼 버퍼를 새로 만들므로 한 프레임에 여러 메시를 그려도 된다.
+    /// 스텐실은 호출할 때마다 0 으로 지운다.
+    pub fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+        mesh: &Mesh,
+        color: [f32; 4],
+        thickness: f32,
+    ) {
+        let uniform = OutlineUniform {
+            view_proj: self.view_proj.to_cols_array_2d(),
+            model: self.model.to_cols_array_2d(),
+            color,
+            thickness,
+            _padding: [0.0; 3],
+        };
+        let buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Outline Uniform Buffer"),
+                contents: bytemuck::bytes_of(&uniform),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Outline Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Outline Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0),
+                    store: wgpu::StoreOp::Store,
+                }),
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_stencil_reference(1);
+
+        render_pass.set_pipeline(&self.mask_pipeline);
+        mesh.draw(&mut render_pass);
+        render_pass.set_pipeline(&self.outline_pipeline);
+        mesh.draw(&mut render_pass);
+    }
+}
@@ -0,0 +1,34 @@
+struct OutlineUniform {
+    view_proj: mat4x4<f32>,
+    model: mat4x4<f32>,
+    color: vec4<f32>,
+    // 외곽선 패스에서 메시를 키우는 비율
+    thickness: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> outline: OutlineUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+// Vertex shader
+// 스텐실에 원래 크기의 실루엣을 표시한다
+@vertex
+fn vs_mask(in: VertexInput) -> @builtin(position) vec4<f32> {
+    return outline.view_proj * outline.model * vec4<f32>(in.position, 1.0);
+}
+
+// 모델 공간 원점을 기준으로 키운 메시. 스텐실 밖으로 나온 테두리만 남는다
+@vertex
+fn vs_outline(in: VertexInput) -> @builtin(position) vec4<f32> {
+    let position = in.position * (1.0 + outline.thickness);
+    return outline.view_proj * outline.model * vec4<f32>(position, 1.0);
+}
+
+// Fragment shader
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return outline.color;
+}
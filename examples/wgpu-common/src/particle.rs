@@ -0,0 +1,204 @@
+use glam::Vec3;
+
+use crate::{BindGroupBuilder, ComputePass};
+
+// particle_update.wgsl 의 @workgroup_size 와 같아야 한다
+const WORKGROUP_SIZE: u32 = 64;
+// 파티클 하나를 사각형(삼각형 두 개)으로 그린다
+const VERTICES_PER_PARTICLE: u32 = 6;
+
+// particle_update.wgsl 의 Particle 과 같은 레이아웃. age >= lifetime 이면 죽은 파티클이다
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Particle {
+    position: [f32; 3],
+    lifetime: f32,
+    velocity: [f32; 3],
+    age: f32,
+}
+
+// particle_update.wgsl 의 Params 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    emitter: [f32; 3],
+    dt: f32,
+    seed: u32,
+    burst: u32,
+    max_particles: u32,
+    _padding: u32,
+}
+
+/// 살아 있는 파티클 하나를 그리는 데 필요한 값. 셰이더에서는 인스턴스 속성으로 받는다.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ParticleInstance {
+    pub position: [f32; 3],
+    pub size: f32,
+    pub color: [f32; 4],
+}
+
+impl ParticleInstance {
+    /// `@location(0)` 위치, `@location(1)` 크기, `@location(2)` 색
+    pub const ATTRIBUTES: [wgpu::VertexAttribute; 3] =
+        wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32, 2 => Float32x4];
+}
+
+/// 파티클 상태를 스토리지 버퍼에 두고 컴퓨트 셰이더로 갱신하는 파티클 시스템.
+///
+/// 컴퓨트 셰이더가 살아 있는 파티클만 인스턴스 버퍼에 모으고 그 수를 간접 그리기 인자에
+/// 직접 더하므로, CPU 로 읽어 오지 않고 `draw` 의 `draw_indirect` 로 그린다.
+/// 인스턴스마다 `vertex_index` 0..6 으로 사각형을 만드는 버텍스 셰이더가 필요하다.
+///
+/// 컴퓨트 셰이더가 필요하므로 WebGL2 에서는 동작하지 않는다.
+pub struct ParticleSystem {
+    max_particles: u32,
+    emitter_pos: Vec3,
+    params_buffer: wgpu::Buffer,
+    instance_buffer: wgpu::Buffer,
+    indirect_buffer: wgpu::Buffer,
+    compute_pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    // 다음 update 에서 모든 파티클을 이미터에서 다시 발사한다
+    burst_pending: bool,
+    seed: u32,
+}
+
+impl ParticleSystem {
+    pub fn new(device: &wgpu::Device, max_particles: u32, emitter_pos: Vec3) -> Self {
+        let max_particles = max_particles.max(1);
+
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Params Buffer"),
+            size: std::mem::size_of::<Params>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        // 0 으로 초기화되므로 처음에는 모든 파티클이 죽어 있다
+        let particle_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Buffer"),
+            size: max_particles as wgpu::BufferAddress
+                * std::mem::size_of::<Particle>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Instance Buffer"),
+            size: max_particles as wgpu::BufferAddress
+                * std::mem::size_of::<ParticleInstance>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::VERTEX,
+            mapped_at_creation: false,
+        });
+        let indirect_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Indirect Buffer"),
+            size: std::mem::size_of::<wgpu::util::DrawIndirectArgs>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let (bind_group_layout, bind_group) = BindGroupBuilder::new()
+            .uniform_buffer(0, &params_buffer, wgpu::ShaderStages::COMPUTE)
+            .storage_buffer(1, &particle_buffer, false, wgpu::ShaderStages::COMPUTE)
+            .storage_buffer(2, &instance_buffer, false, wgpu::ShaderStages::COMPUTE)
+            .storage_buffer(3, &indirect_buffer, false, wgpu::ShaderStages::COMPUTE)
+            .build(device, "Particle Bind Group");
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Particle Update Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("particle_update.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Particle Update Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Particle Update Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        Self {
+            max_particles,
+            emitter_pos,
+            params_buffer,
+            instance_buffer,
+            indirect_buffer,
+            compute_pipeline,
+            bind_group,
+            burst_pending: false,
+            seed: 0,
+        }
+    }
+
+    /// `wgpu::VertexState::buffers` 에 넘길 인스턴스 단위 레이아웃
+    pub fn instance_layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<ParticleInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &ParticleInstance::ATTRIBUTES,
+        }
+    }
+
+    pub fn max_particles(&self) -> u32 {
+        self.max_particles
+    }
+
+    pub fn emitter_pos(&self) -> Vec3 {
+        self.emitter_pos
+    }
+
+    /// 다음 `burst` 부터 새 위치에서 발사한다.
+    pub fn set_emitter_pos(&mut self, emitter_pos: Vec3) {
+        self.emitter_pos = emitter_pos;
+    }
+
+    /// 다음 `update` 에서 모든 파티클을 이미터 위치에서 무작위 방향으로 다시 발사한다.
+    pub fn burst(&mut self) {
+        self.burst_pending = true;
+    }
+
+    /// 파티클을 `dt` 초만큼 움직이는 컴퓨트 패스를 기록한다.
+    /// 간접 그리기 인자의 인스턴스 수는 여기서 0 으로 되돌린 뒤 컴퓨트 셰이더가 다시 센다.
+    pub fn update(&mut self, encoder: &mut wgpu::CommandEncoder, queue: &wgpu::Queue, dt: f32) {
+        let params = Params {
+            emitter: self.emitter_pos.into(),
+            dt,
+            seed: self.seed,
+            burst: self.burst_pending as u32,
+            max_particles: self.max_particles,
+            _padding: 0,
+        };
+        if self.burst_pending {
+            self.burst_pending = false;
+            self.seed = self.seed.wrapping_add(1);
+        }
+
+        let draw_args = wgpu::util::DrawIndirectArgs {
+            vertex_count: VERTICES_PER_PARTICLE,
+            instance_count: 0,
+            first_vertex: 0,
+            first_instance: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+        queue.write_buffer(&self.indirect_buffer, 0, draw_args.as_bytes());
+
+        let dispatch = (
+            ComputePass::workgroup_count(self.max_particles, WORKGROUP_SIZE),
+            1,
+            1,
+        );
+        ComputePass::run(encoder, &self.compute_pipeline, &self.bind_group, dispatch);
+    }
+
+    /// 인스턴스 버퍼를 `slot` 0 에 바인딩하고 살아 있는 파티클 수만큼 간접 그리기를 한다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+        render_pass.draw_indirect(&self.indirect_buffer, 0);
+    }
+}
@@ -0,0 +1,93 @@
+struct Particle {
+    position: vec3<f32>,
+    lifetime: f32,
+    velocity: vec3<f32>,
+    age: f32,
+};
+
+struct ParticleInstance {
+    position: vec3<f32>,
+    size: f32,
+    color: vec4<f32>,
+};
+
+struct Params {
+    emitter: vec3<f32>,
+    dt: f32,
+    seed: u32,
+    burst: u32,
+    max_particles: u32,
+};
+
+// wgpu::util::DrawIndirectArgs 와 같은 레이아웃
+struct DrawArgs {
+    vertex_count: u32,
+    instance_count: atomic<u32>,
+    first_vertex: u32,
+    first_instance: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var<storage, read_write> particles: array<Particle>;
+@group(0) @binding(2)
+var<storage, read_write> instances: array<ParticleInstance>;
+@group(0) @binding(3)
+var<storage, read_write> draw_args: DrawArgs;
+
+const GRAVITY = vec3<f32>(0.0, -2.0, 0.0);
+// 초당 속도가 줄어드는 비율
+const DRAG = 1.5;
+const TAU = 6.2831853;
+
+// PCG 해시
+fn hash(value: u32) -> u32 {
+    let state = value * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+// [0, 1) 범위의 난수
+fn random(state: ptr<function, u32>) -> f32 {
+    *state = hash(*state);
+    return f32(*state) / 4294967296.0;
+}
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if (index >= params.max_particles) {
+        return;
+    }
+
+    var particle = particles[index];
+    if (params.burst != 0u) {
+        // 단위 구 위에 고르게 퍼진 방향으로 발사한다
+        var seed = hash(index ^ hash(params.seed));
+        let z = random(&seed) * 2.0 - 1.0;
+        let angle = random(&seed) * TAU;
+        let r = sqrt(1.0 - z * z);
+        let direction = vec3<f32>(r * cos(angle), r * sin(angle), z);
+
+        particle.position = params.emitter;
+        particle.velocity = direction * mix(1.0, 5.0, random(&seed));
+        particle.lifetime = mix(1.0, 2.5, random(&seed));
+        particle.age = 0.0;
+    } else if (particle.age < particle.lifetime) {
+        particle.velocity = particle.velocity * exp(-DRAG * params.dt) + GRAVITY * params.dt;
+        particle.position += particle.velocity * params.dt;
+        particle.age += params.dt;
+    }
+    particles[index] = particle;
+
+    if (particle.age >= particle.lifetime) {
+        return;
+    }
+
+    // 살아 있는 파티클만 앞에서부터 채우고 그 수를 간접 그리기 인자에 더한다
+    let t = particle.age / particle.lifetime;
+    let slot = atomicAdd(&draw_args.instance_count, 1u);
+    let color = mix(vec3<f32>(1.0, 0.9, 0.4), vec3<f32>(0.9, 0.15, 0.05), t);
+    instances[slot] = ParticleInstance(particle.position, mix(0.08, 0.02, t), vec4<f32>(color, 1.0 - t));
+}
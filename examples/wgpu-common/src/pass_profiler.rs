@@ -0,0 +1,225 @@
+use web_sys::console;
+
+use crate::frame_timer::now_ms;
+
+/// `RenderPassProfiler::new` 에 넘기는 기본 프레임 수
+pub const DEFAULT_PROFILE_FRAMES: usize = 120;
+
+/// 최근 프레임 동안 구간 하나를 인코딩하는 데 걸린 CPU 시간
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassProfile {
+    pub name: String,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+// 구간 하나의 프레임별 시간을 담는 링 버퍼
+#[derive(Debug)]
+struct PassSamples {
+    name: String,
+    samples: Vec<f64>,
+    next: usize,
+    // 이번 프레임에 누적된 시간. 이번 프레임에 실행되지 않았으면 None
+    current_ms: Option<f64>,
+}
+
+/// 이름 붙인 구간의 CPU 인코딩 시간을 프레임마다 재서 최근 N 프레임의 평균과 최댓값을 낸다.
+///
+/// GPU 타임스탬프 쿼리는 웹에서 쓸 수 없지만 명령을 기록하는 데 걸린 CPU 시간은
+/// 어디서나 잴 수 있다. 시간은 `performance.now()` (네이티브에서는 `Instant`) 로 잰다.
+///
+/// 구간은 `begin` 과 `end` 로 감싸고 중첩할 수 있다. 한 프레임에 같은 이름이 여러 번 나오면
+/// 시간을 더한다. 프레임 끝에 `end_frame` 을 호출해야 링 버퍼에 기록된다.
+#[derive(Debug)]
+pub struct RenderPassProfiler {
+    frame_count: usize,
+    // 처음 나온 순서대로 유지한다
+    passes: Vec<PassSamples>,
+    // 열려 있는 구간의 (passes 인덱스, 시작 시각)
+    open: Vec<(usize, f64)>,
+}
+
+impl Default for RenderPassProfiler {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROFILE_FRAMES)
+    }
+}
+
+impl RenderPassProfiler {
+    /// 최근 `frame_count` 프레임의 기록을 유지한다.
+    pub fn new(frame_count: usize) -> Self {
+        Self {
+            frame_count: frame_count.max(1),
+            passes: Vec::new(),
+            open: Vec::new(),
+        }
+    }
+
+    pub fn begin(&mut self, name: &str) {
+        let index = self.pass_index(name);
+        self.open.push((index, now_ms()));
+    }
+
+    /// 가장 최근에 연 구간을 닫는다.
+    pub fn end(&mut self) {
+        if let Some((index, start)) = self.open.pop() {
+            self.add(index, now_ms() - start);
+        }
+    }
+
+    /// `begin` 과 `end` 사이에서 `f` 를 실행한다.
+    pub fn scope<R>(&mut self, name: &str, f: impl FnOnce() -> R) -> R {
+        self.begin(name);
+        let result = f();
+        self.end();
+        result
+    }
+
+    /// 이번 프레임에 잰 시간을 링 버퍼에 넣는다. 매 프레임 끝에 호출한다.
+    pub fn end_frame(&mut self) {
+        // 닫히지 않은 구간은 버린다
+        self.open.clear();
+
+        let frame_count = self.frame_count;
+        for pass in &mut self.passes {
+            let Some(ms) = pass.current_ms.take() else {
+                continue;
+            };
+            if pass.samples.len() < frame_count {
+                pass.samples.push(ms);
+            } else {
+                pass.samples[pass.next] = ms;
+            }
+            pass.next = (pass.next + 1) % frame_count;
+        }
+    }
+
+    /// 구간마다 기록된 프레임의 평균과 최댓값. 처음 나온 순서대로 반환한다.
+    pub fn profiles(&self) -> Vec<PassProfile> {
+        self.passes
+            .iter()
+            .filter(|pass| !pass.samples.is_empty())
+            .map(|pass| PassProfile {
+                name: pass.name.clone(),
+                avg_ms: pass.samples.iter().sum::<f64>() / pass.samples.len() as f64,
+                max_ms: pass.samples.iter().copied().fold(0.0, f64::max),
+            })
+            .collect()
+    }
+
+    /// `profiles` 를 표 형태의 문자열로 만든다.
+    pub fn summary(&self) -> String {
+        let profiles = self.profiles();
+        let name_width = profiles
+            .iter()
+            .map(|profile| profile.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("pass".len());
+
+        let mut table = format!(
+            "{:<name_width$}  {:>9}  {:>9}\n",
+            "pass", "avg ms", "max ms"
+        );
+        for profile in &profiles {
+            table += &format!(
+                "{:<name_width$}  {:>9.3}  {:>9.3}\n",
+                profile.name, profile.avg_ms, profile.max_ms
+            );
+        }
+        table
+    }
+
+    /// `summary` 를 콘솔에 출력한다.
+    pub fn log_summary(&self) {
+        console::log_1(
+            &format!(
+                "CPU encode time (last {} frames)\n{}",
+                self.frame_count,
+                self.summary()
+            )
+            .into(),
+        );
+    }
+
+    fn pass_index(&mut self, name: &str) -> usize {
+        if let Some(index) = self.passes.iter().position(|pass| pass.name == name) {
+            return index;
+        }
+        self.passes.push(PassSamples {
+            name: name.to_string(),
+            samples: Vec::with_capacity(self.frame_count),
+            next: 0,
+            current_ms: None,
+        });
+        self.passes.len() - 1
+    }
+
+    fn add(&mut self, index: usize, ms: f64) {
+        let current = self.passes[index].current_ms.get_or_insert(0.0);
+        *current += ms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(profiler: &mut RenderPassProfiler, name: &str, ms: f64) {
+        let index = profiler.pass_index(name);
+        profiler.add(index, ms);
+    }
+
+    #[test]
+    fn averages_only_the_last_frames() {
+        let mut profiler = RenderPassProfiler::new(3);
+        for ms in [10.0, 1.0, 2.0, 3.0] {
+            record(&mut profiler, "shadow", ms);
+            profiler.end_frame();
+        }
+
+        let profiles = profiler.profiles();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "shadow");
+        assert!((profiles[0].avg_ms - 2.0).abs() < 1e-9);
+        assert_eq!(profiles[0].max_ms, 3.0);
+    }
+
+    #[test]
+    fn sums_repeated_names_within_a_frame() {
+        let mut profiler = RenderPassProfiler::new(4);
+        record(&mut profiler, "geometry", 1.0);
+        record(&mut profiler, "post", 0.5);
+        record(&mut profiler, "geometry", 2.0);
+        profiler.end_frame();
+
+        // 다음 프레임에 실행되지 않은 구간은 평균에 0 으로 들어가지 않는다
+        record(&mut profiler, "geometry", 3.0);
+        profiler.end_frame();
+
+        let profiles = profiler.profiles();
+        assert_eq!(profiles[0].name, "geometry");
+        assert_eq!(profiles[0].avg_ms, 3.0);
+        assert_eq!(profiles[1].name, "post");
+        assert_eq!(profiles[1].avg_ms, 0.5);
+    }
+
+    #[test]
+    fn nested_scopes_are_recorded_separately() {
+        let mut profiler = RenderPassProfiler::default();
+        profiler.scope("frame", || {});
+        profiler.begin("outer");
+        profiler.begin("inner");
+        profiler.end();
+        profiler.end();
+        profiler.end_frame();
+
+        let names: Vec<String> = profiler
+            .profiles()
+            .into_iter()
+            .map(|profile| profile.name)
+            .collect();
+        assert_eq!(names, ["frame", "outer", "inner"]);
+        assert_eq!(profiler.summary().lines().count(), 4);
+    }
+}
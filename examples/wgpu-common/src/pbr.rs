@@ -0,0 +1,629 @@
+use std::f32::consts::PI;
+
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use wgpu::util::DeviceExt;
+
+use crate::phong::SceneUniform;
+use crate::skybox::{cube_face_direction, cube_face_uv, equirectangular_to_faces};
+use crate::{
+    Camera, DirLight, GpuContext, InstanceBuffer, SamplerBuilder, TangentVertex, Texture,
+    UniformBuffer, Vertex, linear_to_srgb, srgb_to_linear,
+};
+
+// 환경 큐브 맵의 밉 레벨 수. pbr.wgsl 의 MAX_LOD 는 이 값보다 1 작아야 한다
+const PREFILTER_MIP_LEVELS: u32 = 5;
+const PREFILTER_SAMPLE_COUNT: u32 = 64;
+
+/// 노멀 맵을 위해 탄젠트까지 가진 버텍스. `PbrPipeline` 의 슬롯 0 에 바인딩한다.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PbrVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+    /// u 가 커지는 방향. w 는 바이탄젠트 방향(+1 또는 -1)이다
+    pub tangent: [f32; 4],
+}
+
+impl Vertex for PbrVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &wgpu::vertex_attr_array![
+        0 => Float32x3,
+        1 => Float32x3,
+        2 => Float32x2,
+        3 => Float32x4,
+    ];
+}
+
+impl TangentVertex for PbrVertex {
+    fn position(&self) -> [f32; 3] {
+        self.position
+    }
+
+    fn normal(&self) -> [f32; 3] {
+        self.normal
+    }
+
+    fn tex_coords(&self) -> [f32; 2] {
+        self.uv
+    }
+
+    fn set_tangent(&mut self, tangent: [f32; 4]) {
+        self.tangent = tangent;
+    }
+}
+
+/// 금속성/거칠기 워크플로의 물리 기반 재질.
+///
+/// 텍스처가 있으면 읽은 값에 계수를 곱하고, 없으면 계수만 쓴다.
+#[derive(Clone)]
+pub struct PbrMaterial {
+    pub base_color: Vec4,
+    /// 0 이면 유전체, 1 이면 금속
+    pub metallic: f32,
+    /// 0 이면 거울처럼 매끈하고 1 이면 완전히 거칠다
+    pub roughness: f32,
+    /// 환경광 차폐. 1 이면 가려지지 않는다
+    pub ao: f32,
+    /// sRGB 로 인코딩된 기본 색
+    pub base_color_texture: Option<Texture>,
+    /// glTF 처럼 G 채널이 거칠기, B 채널이 금속성이다
+    pub metallic_roughness_texture: Option<Texture>,
+    /// 탄젠트 공간 노멀 맵
+    pub normal_map: Option<Texture>,
+}
+
+impl Default for PbrMaterial {
+    fn default() -> Self {
+        Self {
+            base_color: Vec4::ONE,
+            metallic: 0.0,
+            roughness: 0.5,
+            ao: 1.0,
+            base_color_texture: None,
+            metallic_roughness_texture: None,
+            normal_map: None,
+        }
+    }
+}
+
+// pbr.wgsl 의 Material 과 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct MaterialUniform {
+    base_color: [f32; 4],
+    metallic: f32,
+    roughness: f32,
+    ao: f32,
+    _padding: f32,
+}
+
+impl From<&PbrMaterial> for MaterialUniform {
+    fn from(material: &PbrMaterial) -> Self {
+        Self {
+            base_color: material.base_color.into(),
+            metallic: material.metallic,
+            roughness: material.roughness,
+            ao: material.ao,
+            _padding: 0.0,
+        }
+    }
+}
+
+/// `PbrPipeline::create_material` 로 만든 재질의 유니폼 버퍼와 바인드 그룹
+pub struct PbrMaterialBinding {
+    uniform: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl PbrMaterialBinding {
+    /// 계수만 다시 쓴다. 텍스처를 바꾸려면 `create_material` 로 새로 만든다.
+    pub fn update(&self, queue: &wgpu::Queue, material: &PbrMaterial) {
+        queue.write_buffer(
+            &self.uniform,
+            0,
+            bytemuck::bytes_of(&MaterialUniform::from(material)),
+        );
+    }
+
+    /// 1번 바인드 그룹을 설정한다. `PbrPipeline::bind` 뒤에 호출한다.
+    pub fn bind(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_bind_group(1, &self.bind_group, &[]);
+    }
+}
+
+/// 이미지 기반 조명(IBL)에 쓰는 환경 텍스처.
+///
+/// 큐브 맵의 밉 레벨마다 거칠기에 맞춰 GGX 로 흐리게 만든 환경을 CPU 에서 만들어 올린다.
+/// 스페큘러 적분을 미리 계산한 BRDF LUT 는 `BrdfLutGenerator` 로 따로 만든다.
+pub struct PbrEnvironment {
+    prefiltered_view: wgpu::TextureView,
+}
+
+impl PbrEnvironment {
+    /// 환경 큐브 맵 포맷
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// `faces` 는 `Skybox::new` 와 같이 +X, -X, +Y, -Y, +Z, -Z 순서의 sRGB 정사각형 이미지다.
+    ///
+    /// # Panics
+    ///
+    /// 면의 크기가 서로 다르거나, 정사각형이 아니거나, 16 보다 작으면 panic 한다.
+    pub fn new(gpu: &GpuContext, faces: &[image::RgbaImage; 6]) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let face_size = faces[0].width();
+        assert!(
+            faces
+                .iter()
+                .all(|face| face.width() == face_size && face.height() == face_size),
+            "environment faces must be squares of the same size"
+        );
+        assert!(
+            face_size >= 1 << (PREFILTER_MIP_LEVELS - 1),
+            "environment faces must be at least {} pixels",
+            1 << (PREFILTER_MIP_LEVELS - 1)
+        );
+
+        let source = faces.each_ref().map(|face| {
+            face.pixels()
+                .map(|pixel| {
+                    srgb_to_linear(Vec3::new(
+                        pixel[0] as f32 / 255.0,
+                        pixel[1] as f32 / 255.0,
+                        pixel[2] as f32 / 255.0,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        // LayerMajor 순서: 면마다 모든 밉 레벨을 이어 붙인다
+        let levels: Vec<[Vec<Vec3>; 6]> = (0..PREFILTER_MIP_LEVELS)
+            .map(|level| {
+                let roughness = level as f32 / (PREFILTER_MIP_LEVELS - 1) as f32;
+                prefilter(&source, face_size, face_size >> level, roughness)
+            })
+            .collect();
+        let data: Vec<u8> = (0..6)
+            .flat_map(|face| levels.iter().flat_map(move |level| level[face].iter()))
+            .flat_map(|&color| {
+                let encoded = linear_to_srgb(color.clamp(Vec3::ZERO, Vec3::ONE)) * 255.0 + 0.5;
+                [encoded.x as u8, encoded.y as u8, encoded.z as u8, 255]
+            })
+            .collect();
+
+        let prefiltered = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("PBR Prefiltered Environment"),
+                size: wgpu::Extent3d {
+                    width: face_size,
+                    height: face_size,
+                    depth_or_array_layers: 6,
+                },
+                mip_level_count: PREFILTER_MIP_LEVELS,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: Self::FORMAT,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &data,
+        );
+        let prefiltered_view = prefiltered.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("PBR Prefiltered Environment View"),
+            dimension: Some(wgpu::TextureViewDimension::Cube),
+            ..Default::default()
+        });
+
+        Self { prefiltered_view }
+    }
+
+    /// 등장방형 이미지를 `Skybox::from_equirectangular` 와 같은 방법으로 여섯 면으로 바꿔서 만든다.
+    pub fn from_equirectangular(
+        gpu: &GpuContext,
+        image: &image::DynamicImage,
+        face_size: u32,
+    ) -> Self {
+        Self::new(gpu, &equirectangular_to_faces(image, face_size))
+    }
+}
+
+// 0 ~ 1 범위의 저불일치 수열. 무작위 수보다 적은 샘플로 고르게 퍼진다
+pub(crate) fn hammersley(i: u32, count: u32) -> Vec2 {
+    Vec2::new(
+        i as f32 / count as f32,
+        i.reverse_bits() as f32 * 2.328_306_4e-10,
+    )
+}
+
+// GGX 분포를 따르도록 `normal` 주위의 하프 벡터를 고른다
+pub(crate) fn importance_sample_ggx(xi: Vec2, normal: Vec3, roughness: f32) -> Vec3 {
+    let a = roughness * roughness;
+    let phi = 2.0 * PI * xi.x;
+    let cos_theta = ((1.0 - xi.y) / (1.0 + (a * a - 1.0) * xi.y)).sqrt();
+    let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+
+    let up = if normal.z.abs() < 0.999 {
+        Vec3::Z
+    } else {
+        Vec3::X
+    };
+    let tangent = up.cross(normal).normalize();
+    let bitangent = normal.cross(tangent);
+    (tangent * phi.cos() * sin_theta + bitangent * phi.sin() * sin_theta + normal * cos_theta)
+        .normalize()
+}
+
+// 면마다 선형 색이 `size` x `size` 개씩 있는 큐브 맵에서 가장 가까운 텍셀을 읽는다
+fn sample_cube(faces: &[Vec<Vec3>; 6], size: u32, direction: Vec3) -> Vec3 {
+    let (face, u, v) = cube_face_uv(direction);
+    let texel = |c: f32| (((c + 1.0) * 0.5 * size as f32) as u32).min(size - 1);
+    faces[face][(texel(v) * size + texel(u)) as usize]
+}
+
+// 보는 방향과 반사 방향이 법선과 같다고 보고 GGX 로 스페큘러 반사를 적분한다
+fn prefilter(
+    source: &[Vec<Vec3>; 6],
+    source_size: u32,
+    size: u32,
+    roughness: f32,
+) -> [Vec<Vec3>; 6] {
+    std::array::from_fn(|face| {
+        (0..size * size)
+            .map(|i| {
+                let u = 2.0 * ((i % size) as f32 + 0.5) / size as f32 - 1.0;
+                let v = 2.0 * ((i / size) as f32 + 0.5) / size as f32 - 1.0;
+                let normal = cube_face_direction(face, u, v).normalize();
+                if roughness == 0.0 {
+                    return sample_cube(source, source_size, normal);
+                }
+
+                let mut color = Vec3::ZERO;
+                let mut weight = 0.0;
+                for sample in 0..PREFILTER_SAMPLE_COUNT {
+                    let half = importance_sample_ggx(
+                        hammersley(sample, PREFILTER_SAMPLE_COUNT),
+                        normal,
+                        roughness,
+                    );
+                    let light = 2.0 * normal.dot(half) * half - normal;
+                    let n_dot_l = normal.dot(light);
+                    if n_dot_l > 0.0 {
+                        color += sample_cube(source, source_size, light) * n_dot_l;
+                        weight += n_dot_l;
+                    }
+                }
+                color / weight.max(f32::EPSILON)
+            })
+            .collect()
+    })
+}
+
+/// 방향광 하나와 IBL 환경광으로 Cook-Torrance 셰이딩을 하는 렌더 파이프라인.
+///
+/// `PbrVertex` 메시를 슬롯 0, 모델 행렬을 담은 `InstanceBuffer` 를 슬롯 1 에 바인딩해서 그린다.
+/// 재질마다 `create_material` 로 바인드 그룹을 만들고 `bind` 다음에 바인딩한다.
+/// 재질 바인드 그룹에는 `BrdfLutGenerator` 로 만든 BRDF LUT 가 함께 들어간다.
+/// 출력은 선형 HDR 값이므로 밝은 하이라이트는 서피스에서 잘린다.
+pub struct PbrPipeline {
+    pipeline: wgpu::RenderPipeline,
+    scene: UniformBuffer<SceneUniform>,
+    material_layout: wgpu::BindGroupLayout,
+    environment_bind_group: wgpu::BindGroup,
+    material_sampler: wgpu::Sampler,
+    // 텍스처가 없는 슬롯에 대신 바인딩한다
+    white_texture: wgpu::TextureView,
+    flat_normal_texture: wgpu::TextureView,
+    brdf_lut: Texture,
+    view_proj: Mat4,
+    eye: Vec3,
+    light: DirLight,
+}
+
+impl PbrPipeline {
+    pub fn new(
+        gpu: &GpuContext,
+        surface_format: wgpu::TextureFormat,
+        depth_format: wgpu::TextureFormat,
+        environment: &PbrEnvironment,
+        brdf_lut: &Texture,
+    ) -> Self {
+        let device = gpu.device();
+        let light = DirLight::default();
+        let scene = UniformBuffer::new(
+            gpu,
+            &SceneUniform::new(Mat4::IDENTITY, Vec3::ZERO, &light),
+            0,
+            wgpu::ShaderStages::VERTEX_FRAGMENT,
+        );
+
+        let texture_entry =
+            |binding: u32, view_dimension: wgpu::TextureViewDimension| wgpu::BindGroupLayoutEntry {
+                binding,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension,
+                    multisampled: false,
+                },
+                count: None,
+            };
+        let sampler_entry = |binding: u32| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        };
+
+        let material_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("PBR Material Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                texture_entry(1, wgpu::TextureViewDimension::D2),
+                texture_entry(2, wgpu::TextureViewDimension::D2),
+                texture_entry(3, wgpu::TextureViewDimension::D2),
+                sampler_entry(4),
+                texture_entry(5, wgpu::TextureViewDimension::D2),
+                sampler_entry(6),
+            ],
+        });
+
+        let environment_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("PBR Environment Bind Group Layout"),
+                entries: &[
+                    texture_entry(0, wgpu::TextureViewDimension::Cube),
+                    sampler_entry(1),
+                ],
+            });
+        let environment_sampler = SamplerBuilder::linear()
+            .label("PBR Environment Sampler")
+            .build(device);
+        let environment_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("PBR Environment Bind Group"),
+            layout: &environment_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&environment.prefiltered_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&environment_sampler),
+                },
+            ],
+        });
+
+        let material_sampler = SamplerBuilder::linear()
+            .label("PBR Material Sampler")
+            .address_mode(
+                wgpu::AddressMode::Repeat,
+                wgpu::AddressMode::Repeat,
+                wgpu::AddressMode::Repeat,
+            )
+            .build(device);
+        let white_texture = solid_texture(gpu, "PBR White Texture", [255, 255, 255, 255]);
+        // 탄젠트 공간의 (0, 0, 1)
+        let flat_normal_texture =
+            solid_texture(gpu, "PBR Flat Normal Texture", [128, 128, 255, 255]);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("PBR Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("pbr.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("PBR Pipeline Layout"),
+            bind_group_layouts: &[
+                scene.bind_group_layout(),
+                &material_layout,
+                &environment_layout,
+            ],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("PBR Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[PbrVertex::layout(), InstanceBuffer::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: depth_format,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            scene,
+            material_layout,
+            environment_bind_group,
+            material_sampler,
+            white_texture,
+            flat_normal_texture,
+            brdf_lut: brdf_lut.clone(),
+            view_proj: Mat4::IDENTITY,
+            eye: Vec3::ZERO,
+            light,
+        }
+    }
+
+    pub fn light(&self) -> &DirLight {
+        &self.light
+    }
+
+    /// 스페큘러 반사는 카메라 위치에 따라 달라지므로 카메라가 움직일 때마다 호출한다.
+    pub fn set_camera(&mut self, queue: &wgpu::Queue, camera: &Camera) {
+        self.view_proj = camera.view_projection();
+        self.eye = camera.eye;
+        self.write_scene(queue);
+    }
+
+    pub fn set_light(&mut self, queue: &wgpu::Queue, light: DirLight) {
+        self.light = light;
+        self.write_scene(queue);
+    }
+
+    /// 재질의 유니폼 버퍼와 바인드 그룹을 만든다. 없는 텍스처는 기본 텍스처로 채운다.
+    pub fn create_material(&self, gpu: &GpuContext, material: &PbrMaterial) -> PbrMaterialBinding {
+        let device = gpu.device();
+        let uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("PBR Material Uniform Buffer"),
+            contents: bytemuck::bytes_of(&MaterialUniform::from(material)),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("PBR Material Bind Group"),
+            layout: &self.material_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(
+                        material
+                            .base_color_texture
+                            .as_ref()
+                            .map_or(&self.white_texture, Texture::view),
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(
+                        material
+                            .metallic_roughness_texture
+                            .as_ref()
+                            .map_or(&self.white_texture, Texture::view),
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(
+                        material
+                            .normal_map
+                            .as_ref()
+                            .map_or(&self.flat_normal_texture, Texture::view),
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(&self.material_sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::TextureView(self.brdf_lut.view()),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::Sampler(self.brdf_lut.sampler()),
+                },
+            ],
+        });
+
+        PbrMaterialBinding {
+            uniform,
+            bind_group,
+        }
+    }
+
+    /// 파이프라인과 0번(카메라, 빛), 2번(환경) 바인드 그룹을 설정한다.
+    pub fn bind(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, self.scene.bind_group(), &[]);
+        render_pass.set_bind_group(2, &self.environment_bind_group, &[]);
+    }
+
+    fn write_scene(&self, queue: &wgpu::Queue) {
+        self.scene.update(
+            queue,
+            &SceneUniform::new(self.view_proj, self.eye, &self.light),
+        );
+    }
+}
+
+// 한 가지 색으로 채운 1x1 텍스처
+fn solid_texture(gpu: &GpuContext, label: &str, color: [u8; 4]) -> wgpu::TextureView {
+    gpu.device()
+        .create_texture_with_data(
+            gpu.queue(),
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &color,
+        )
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefilter_keeps_uniform_environment() {
+        let color = Vec3::new(0.2, 0.4, 0.8);
+        let source: [Vec<Vec3>; 6] = std::array::from_fn(|_| vec![color; 16]);
+
+        for roughness in [0.0, 0.5, 1.0] {
+            let faces = prefilter(&source, 4, 2, roughness);
+            for texel in faces.iter().flatten() {
+                assert!((*texel - color).length() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn importance_samples_stay_around_normal() {
+        let normal = Vec3::new(1.0, 1.0, 0.0).normalize();
+        for i in 0..16 {
+            let half = importance_sample_ggx(hammersley(i, 16), normal, 0.3);
+            assert!((half.length() - 1.0).abs() < 1e-5);
+            assert!(half.dot(normal) > 0.0);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+const PI: f32 = 3.14159265;
+// 환경 큐브 맵의 마지막 밉 레벨. pbr.rs 의 PREFILTER_MIP_LEVELS - 1 과 같아야 한다
+const MAX_LOD: f32 = 4.0;
+
+struct Scene {
+    view_proj: mat4x4<f32>,
+    eye: vec3<f32>,
+    // 표면에서 빛을 향하는 방향
+    to_light: vec3<f32>,
+    light_color: vec3<f32>,
+};
+
+struct Material {
+    base_color: vec4<f32>,
+    metallic: f32,
+    roughness: f32,
+    ao: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> scene: Scene;
+
+@group(1) @binding(0)
+var<uniform> material: Material;
+@group(1) @binding(1)
+var base_color_texture: texture_2d<f32>;
+// G 채널이 거칠기, B 채널이 금속성 (glTF 와 같다)
+@group(1) @binding(2)
+var metallic_roughness_texture: texture_2d<f32>;
+@group(1) @binding(3)
+var normal_map: texture_2d<f32>;
+@group(1) @binding(4)
+var material_sampler: sampler;
+// BrdfLutGenerator 로 만든 (n·v, 거칠기) 에 대한 스페큘러 적분의 (scale, bias)
+@group(1) @binding(5)
+var brdf_lut: texture_2d<f32>;
+@group(1) @binding(6)
+var brdf_lut_sampler: sampler;
+
+// 거칠기에 따라 밉 레벨마다 미리 흐리게 만든 환경 큐브 맵
+@group(2) @binding(0)
+var prefiltered_map: texture_cube<f32>;
+@group(2) @binding(1)
+var environment_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    // w 는 바이탄젠트 방향 (+1 또는 -1)
+    @location(3) tangent: vec4<f32>,
+};
+
+// InstanceBuffer 가 인스턴스마다 넘겨주는 모델 행렬의 네 열
+struct InstanceInput {
+    @location(5) model_0: vec4<f32>,
+    @location(6) model_1: vec4<f32>,
+    @location(7) model_2: vec4<f32>,
+    @location(8) model_3: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) tangent: vec4<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput, instance: InstanceInput) -> VertexOutput {
+    let model = mat4x4<f32>(instance.model_0, instance.model_1, instance.model_2, instance.model_3);
+    let world_position = model * vec4<f32>(in.position, 1.0);
+    // 균등한 크기만 쓴다고 가정하고 모델 행렬의 회전 부분으로 법선과 탄젠트를 돌린다
+    let rotation = mat3x3<f32>(model[0].xyz, model[1].xyz, model[2].xyz);
+
+    var out: VertexOutput;
+    out.clip_position = scene.view_proj * world_position;
+    out.world_position = world_position.xyz;
+    out.normal = rotation * in.normal;
+    out.uv = in.uv;
+    out.tangent = vec4<f32>(rotation * in.tangent.xyz, in.tangent.w);
+    return out;
+}
+
+// GGX (Trowbridge-Reitz) 법선 분포. 하프 벡터 방향을 향하는 미세면의 비율
+fn distribution_ggx(n_dot_h: f32, roughness: f32) -> f32 {
+    let a = roughness * roughness;
+    let a2 = a * a;
+    let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    return a2 / (PI * d * d);
+}
+
+fn geometry_schlick_ggx(n_dot_x: f32, k: f32) -> f32 {
+    return n_dot_x / (n_dot_x * (1.0 - k) + k);
+}
+
+// Smith 기하 함수. 미세면끼리 가려서 빛이 닿지 않거나 빠져나가지 못하는 비율을 뺀다
+fn geometry_smith(n_dot_v: f32, n_dot_l: f32, roughness: f32) -> f32 {
+    let r = roughness + 1.0;
+    let k = r * r / 8.0;
+    return geometry_schlick_ggx(n_dot_v, k) * geometry_schlick_ggx(n_dot_l, k);
+}
+
+// Schlick 근사 프레넬. 비스듬히 볼수록 반사가 강해진다
+fn fresnel_schlick(cos_theta: f32, f0: vec3<f32>) -> vec3<f32> {
+    return f0 + (1.0 - f0) * pow(clamp(1.0 - cos_theta, 0.0, 1.0), 5.0);
+}
+
+// 환경광은 여러 방향에서 오므로 거칠수록 가장자리 반사를 줄인다
+fn fresnel_schlick_roughness(cos_theta: f32, f0: vec3<f32>, roughness: f32) -> vec3<f32> {
+    return f0 + (max(vec3<f32>(1.0 - roughness), f0) - f0) * pow(clamp(1.0 - cos_theta, 0.0, 1.0), 5.0);
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let base_color = material.base_color * textureSample(base_color_texture, material_sampler, in.uv);
+    let metallic_roughness = textureSample(metallic_roughness_texture, material_sampler, in.uv);
+    let metallic = material.metallic * metallic_roughness.b;
+    // 거칠기가 0 이면 하이라이트가 무한히 작아지므로 조금 남긴다
+    let roughness = clamp(material.roughness * metallic_roughness.g, 0.04, 1.0);
+
+    // 탄젠트 공간 노멀 맵을 TBN 행렬로 월드 공간으로 옮긴다.
+    // MikkTSpace 와 같게 보간된 법선과 탄젠트를 정규화하지 않고 쓰고 결과만 정규화한다
+    let bitangent = in.tangent.w * cross(in.normal, in.tangent.xyz);
+    let tbn = mat3x3<f32>(in.tangent.xyz, bitangent, in.normal);
+    let mapped = textureSample(normal_map, material_sampler, in.uv).xyz * 2.0 - 1.0;
+    let n = normalize(tbn * mapped);
+
+    let v = normalize(scene.eye - in.world_position);
+    let l = normalize(scene.to_light);
+    let h = normalize(v + l);
+    let n_dot_v = max(dot(n, v), 0.0001);
+    let n_dot_l = max(dot(n, l), 0.0);
+
+    // 유전체는 4% 를 반사하고, 금속은 기본 색으로 반사한다
+    let f0 = mix(vec3<f32>(0.04), base_color.rgb, metallic);
+
+    // Cook-Torrance 직접광
+    let f = fresnel_schlick(max(dot(h, v), 0.0), f0);
+    let d = distribution_ggx(max(dot(n, h), 0.0), roughness);
+    let g = geometry_smith(n_dot_v, n_dot_l, roughness);
+    let specular = d * g * f / (4.0 * n_dot_v * n_dot_l + 0.0001);
+    // 반사되지 않은 빛만 산란하고, 금속은 산란하지 않는다
+    let k_d = (1.0 - f) * (1.0 - metallic);
+    let direct = (k_d * base_color.rgb / PI + specular) * scene.light_color * n_dot_l;
+
+    // IBL 환경광. 가장 흐린 밉 레벨을 확산광의 근사로 쓴다
+    let f_ambient = fresnel_schlick_roughness(n_dot_v, f0, roughness);
+    let k_d_ambient = (1.0 - f_ambient) * (1.0 - metallic);
+    let irradiance = textureSampleLevel(prefiltered_map, environment_sampler, n, MAX_LOD).rgb;
+    let reflected = reflect(-v, n);
+    let prefiltered = textureSampleLevel(prefiltered_map, environment_sampler, reflected, roughness * MAX_LOD).rgb;
+    let brdf = textureSampleLevel(brdf_lut, brdf_lut_sampler, vec2<f32>(n_dot_v, roughness), 0.0).rg;
+    let ambient = (k_d_ambient * irradiance * base_color.rgb + prefiltered * (f_ambient * brdf.x + brdf.y)) * material.ao;
+
+    return vec4<f32>(ambient + direct, base_color.a);
+}
@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut, Range};
+
+/// 1% low 를 계산할 때 보관하는 최근 프레임 수
+pub const FRAME_HISTORY: usize = 1000;
+
+/// 프레임 시간, 드로우 호출 수, 버퍼 업로드 크기를 모은다.
+///
+/// 매 프레임 `begin_frame` 으로 카운터를 비우고, 드로우는 `PerfCounter` 로 감싸서 세고,
+/// `queue.write_buffer` 대신 `write_buffer` 로 업로드한 뒤 `end_frame` 에 프레임 시간을 넘긴다.
+#[derive(Clone, Debug)]
+pub struct FrameStats {
+    frame_time_ms: f64,
+    low_1_percent_ms: f64,
+    draw_calls: u32,
+    uploaded_bytes: u64,
+    // 최근 FRAME_HISTORY 프레임의 시간 (밀리초)
+    history: VecDeque<f64>,
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameStats {
+    pub fn new() -> Self {
+        Self {
+            frame_time_ms: 0.0,
+            low_1_percent_ms: 0.0,
+            draw_calls: 0,
+            uploaded_bytes: 0,
+            history: VecDeque::with_capacity(FRAME_HISTORY),
+        }
+    }
+
+    /// 드로우 호출 수와 업로드 크기를 0 으로 되돌린다.
+    pub fn begin_frame(&mut self) {
+        self.draw_calls = 0;
+        self.uploaded_bytes = 0;
+    }
+
+    /// 끝난 프레임의 시간을 기록하고 1% low 를 다시 계산한다.
+    pub fn end_frame(&mut self, frame_time_ms: f64) {
+        if self.history.len() == FRAME_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(frame_time_ms);
+        self.frame_time_ms = frame_time_ms;
+        self.low_1_percent_ms = low_1_percent(self.history.iter().copied());
+    }
+
+    /// `PerfCounter` 를 거치지 않는 드로우 호출을 센다.
+    pub fn record_draw(&mut self) {
+        self.draw_calls += 1;
+    }
+
+    /// `write_buffer` 를 거치지 않는 업로드(`write_texture`, 버퍼 생성 등)를 더한다.
+    pub fn record_upload(&mut self, bytes: u64) {
+        self.uploaded_bytes += bytes;
+    }
+
+    /// `queue.write_buffer` 를 호출하고 크기를 더한다.
+    pub fn write_buffer(
+        &mut self,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        data: &[u8],
+    ) {
+        queue.write_buffer(buffer, offset, data);
+        self.record_upload(data.len() as u64);
+    }
+
+    /// 마지막으로 끝난 프레임의 시간 (밀리초)
+    pub fn frame_time_ms(&self) -> f64 {
+        self.frame_time_ms
+    }
+
+    /// 최근 프레임 중 가장 느린 1% 의 평균 시간 (밀리초). 가끔 생기는 끊김이 여기에 드러난다
+    pub fn low_1_percent_ms(&self) -> f64 {
+        self.low_1_percent_ms
+    }
+
+    /// 이번 프레임에서 지금까지 센 드로우 호출 수
+    pub fn draw_calls(&self) -> u32 {
+        self.draw_calls
+    }
+
+    /// 이번 프레임에서 지금까지 업로드한 바이트 수
+    pub fn uploaded_bytes(&self) -> u64 {
+        self.uploaded_bytes
+    }
+}
+
+/// 렌더 패스를 감싸서 `draw`, `draw_indexed` 를 호출할 때마다 `FrameStats` 의 드로우 수를 올린다.
+///
+/// 나머지 메서드는 `Deref` 로 렌더 패스에 그대로 전달되므로 `render_pass` 자리에 바로 쓸 수 있다.
+pub struct PerfCounter<'a, 'p> {
+    render_pass: &'a mut wgpu::RenderPass<'p>,
+    stats: &'a mut FrameStats,
+}
+
+impl<'a, 'p> PerfCounter<'a, 'p> {
+    pub fn new(render_pass: &'a mut wgpu::RenderPass<'p>, stats: &'a mut FrameStats) -> Self {
+        Self { render_pass, stats }
+    }
+
+    pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        self.stats.record_draw();
+        self.render_pass.draw(vertices, instances);
+    }
+
+    pub fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        self.stats.record_draw();
+        self.render_pass
+            .draw_indexed(indices, base_vertex, instances);
+    }
+
+    /// `Mesh::draw` 처럼 안에서 드로우를 한 번 호출하는 헬퍼를 센다.
+    pub fn draw_with<R>(&mut self, draw: impl FnOnce(&mut wgpu::RenderPass<'p>) -> R) -> R {
+        self.stats.record_draw();
+        draw(self.render_pass)
+    }
+}
+
+impl<'p> Deref for PerfCounter<'_, 'p> {
+    type Target = wgpu::RenderPass<'p>;
+
+    fn deref(&self) -> &Self::Target {
+        self.render_pass
+    }
+}
+
+impl DerefMut for PerfCounter<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.render_pass
+    }
+}
+
+// 느린 순으로 상위 1% (최소 한 프레임) 의 평균
+fn low_1_percent(frame_times: impl Iterator<Item = f64>) -> f64 {
+    let mut frame_times: Vec<f64> = frame_times.collect();
+    if frame_times.is_empty() {
+        return 0.0;
+    }
+    frame_times.sort_by(|a, b| b.total_cmp(a));
+    let count = frame_times.len().div_ceil(100);
+    frame_times[..count].iter().sum::<f64>() / count as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_1_percent_averages_slowest_frames() {
+        // 200 프레임 중 느린 2 프레임
+        let frames = (0..198).map(|_| 16.0).chain([40.0, 60.0]);
+        assert_eq!(low_1_percent(frames), 50.0);
+        assert_eq!(low_1_percent([10.0, 30.0].into_iter()), 30.0);
+        assert_eq!(low_1_percent(std::iter::empty()), 0.0);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut stats = FrameStats::new();
+        stats.end_frame(100.0);
+        for _ in 0..FRAME_HISTORY {
+            stats.end_frame(10.0);
+        }
+        // 100 ms 프레임은 기록에서 밀려났다
+        assert_eq!(stats.low_1_percent_ms(), 10.0);
+        assert_eq!(stats.frame_time_ms(), 10.0);
+    }
+
+    #[test]
+    fn begin_frame_resets_counters() {
+        let mut stats = FrameStats::new();
+        stats.record_draw();
+        stats.record_upload(64);
+        assert_eq!((stats.draw_calls(), stats.uploaded_bytes()), (1, 64));
+
+        stats.begin_frame();
+        assert_eq!((stats.draw_calls(), stats.uploaded_bytes()), (0, 0));
+    }
+}
@@ -0,0 +1,230 @@
+use glam::{Mat4, Vec3};
+
+use crate::{Camera, GpuContext, InstanceBuffer, UniformBuffer, Vertex};
+
+/// 위치와 법선만 가진 버텍스. `PhongPipeline` 의 슬롯 0 에 바인딩한다.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PhongVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+}
+
+impl Vertex for PhongVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+/// 빛의 색에 곱하는 표면의 반사 계수
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhongMaterial {
+    pub ambient: Vec3,
+    pub diffuse: Vec3,
+    pub specular: Vec3,
+    /// 클수록 하이라이트가 작고 날카로워진다
+    pub shininess: f32,
+}
+
+impl Default for PhongMaterial {
+    fn default() -> Self {
+        Self {
+            ambient: Vec3::splat(0.1),
+            diffuse: Vec3::splat(0.7),
+            specular: Vec3::splat(0.5),
+            shininess: 32.0,
+        }
+    }
+}
+
+/// 모든 표면에 같은 방향으로 들어오는 빛
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirLight {
+    /// 빛이 나아가는 방향. 길이는 상관없다
+    pub direction: Vec3,
+    pub color: Vec3,
+}
+
+impl Default for DirLight {
+    fn default() -> Self {
+        Self {
+            direction: Vec3::new(-1.0, -1.0, -1.0),
+            color: Vec3::ONE,
+        }
+    }
+}
+
+// phong.wgsl, pbr.wgsl 의 Scene 과 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct SceneUniform {
+    view_proj: [[f32; 4]; 4],
+    eye: [f32; 3],
+    _padding0: f32,
+    to_light: [f32; 3],
+    _padding1: f32,
+    light_color: [f32; 3],
+    _padding2: f32,
+}
+
+impl SceneUniform {
+    pub(crate) fn new(view_proj: Mat4, eye: Vec3, light: &DirLight) -> Self {
+        Self {
+            view_proj: view_proj.to_cols_array_2d(),
+            eye: eye.into(),
+            _padding0: 0.0,
+            to_light: (-light.direction).normalize_or(Vec3::Y).into(),
+            _padding1: 0.0,
+            light_color: light.color.into(),
+            _padding2: 0.0,
+        }
+    }
+}
+
+// phong.wgsl 의 Material 과 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct MaterialUniform {
+    ambient: [f32; 3],
+    _padding0: f32,
+    diffuse: [f32; 3],
+    _padding1: f32,
+    specular: [f32; 3],
+    shininess: f32,
+}
+
+impl From<&PhongMaterial> for MaterialUniform {
+    fn from(material: &PhongMaterial) -> Self {
+        Self {
+            ambient: material.ambient.into(),
+            _padding0: 0.0,
+            diffuse: material.diffuse.into(),
+            _padding1: 0.0,
+            specular: material.specular.into(),
+            shininess: material.shininess,
+        }
+    }
+}
+
+/// 방향광 하나와 재질 하나로 Phong 셰이딩을 하는 렌더 파이프라인.
+///
+/// `PhongVertex` 메시를 슬롯 0, 모델 행렬을 담은 `InstanceBuffer` 를 슬롯 1 에 바인딩해서 그린다.
+/// 카메라나 빛이 바뀌면 `set_camera` / `set_light` 로 유니폼을 다시 쓴다.
+pub struct PhongPipeline {
+    pipeline: wgpu::RenderPipeline,
+    scene: UniformBuffer<SceneUniform>,
+    material: UniformBuffer<MaterialUniform>,
+    view_proj: Mat4,
+    eye: Vec3,
+    light: DirLight,
+}
+
+impl PhongPipeline {
+    pub fn new(
+        gpu: &GpuContext,
+        surface_format: wgpu::TextureFormat,
+        depth_format: wgpu::TextureFormat,
+    ) -> Self {
+        let device = gpu.device();
+        let light = DirLight::default();
+        let scene = UniformBuffer::new(
+            gpu,
+            &SceneUniform::new(Mat4::IDENTITY, Vec3::ZERO, &light),
+            0,
+            wgpu::ShaderStages::VERTEX_FRAGMENT,
+        );
+        let material = UniformBuffer::new(
+            gpu,
+            &MaterialUniform::from(&PhongMaterial::default()),
+            0,
+            wgpu::ShaderStages::FRAGMENT,
+        );
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Phong Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("phong.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Phong Pipeline Layout"),
+            bind_group_layouts: &[scene.bind_group_layout(), material.bind_group_layout()],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Phong Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[PhongVertex::layout(), InstanceBuffer::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: depth_format,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            scene,
+            material,
+            view_proj: Mat4::IDENTITY,
+            eye: Vec3::ZERO,
+            light,
+        }
+    }
+
+    pub fn light(&self) -> &DirLight {
+        &self.light
+    }
+
+    /// 스페큘러 하이라이트는 카메라 위치에 따라 달라지므로 카메라가 움직일 때마다 호출한다.
+    pub fn set_camera(&mut self, queue: &wgpu::Queue, camera: &Camera) {
+        self.view_proj = camera.view_projection();
+        self.eye = camera.eye;
+        self.write_scene(queue);
+    }
+
+    pub fn set_light(&mut self, queue: &wgpu::Queue, light: DirLight) {
+        self.light = light;
+        self.write_scene(queue);
+    }
+
+    pub fn set_material(&self, queue: &wgpu::Queue, material: &PhongMaterial) {
+        self.material
+            .update(queue, &MaterialUniform::from(material));
+    }
+
+    /// 파이프라인과 0번(카메라, 빛), 1번(재질) 바인드 그룹을 설정한다.
+    pub fn bind(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, self.scene.bind_group(), &[]);
+        render_pass.set_bind_group(1, self.material.bind_group(), &[]);
+    }
+
+    fn write_scene(&self, queue: &wgpu::Queue) {
+        self.scene.update(
+            queue,
+            &SceneUniform::new(self.view_proj, self.eye, &self.light),
+        );
+    }
+}
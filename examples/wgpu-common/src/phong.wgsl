@@ -0,0 +1,72 @@
+struct Scene {
+    view_proj: mat4x4<f32>,
+    eye: vec3<f32>,
+    // 표면에서 빛을 향하는 방향
+    to_light: vec3<f32>,
+    light_color: vec3<f32>,
+};
+
+struct Material {
+    ambient: vec3<f32>,
+    diffuse: vec3<f32>,
+    specular: vec3<f32>,
+    shininess: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> scene: Scene;
+@group(1) @binding(0)
+var<uniform> material: Material;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+// InstanceBuffer 가 인스턴스마다 넘겨주는 모델 행렬의 네 열
+struct InstanceInput {
+    @location(5) model_0: vec4<f32>,
+    @location(6) model_1: vec4<f32>,
+    @location(7) model_2: vec4<f32>,
+    @location(8) model_3: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput, instance: InstanceInput) -> VertexOutput {
+    let model = mat4x4<f32>(instance.model_0, instance.model_1, instance.model_2, instance.model_3);
+    let world_position = model * vec4<f32>(in.position, 1.0);
+
+    var out: VertexOutput;
+    out.clip_position = scene.view_proj * world_position;
+    out.world_position = world_position.xyz;
+    // 균등한 크기만 쓴다고 가정하고 모델 행렬의 회전 부분으로 법선을 돌린다
+    out.normal = mat3x3<f32>(model[0].xyz, model[1].xyz, model[2].xyz) * in.normal;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let normal = normalize(in.normal);
+    let to_light = normalize(scene.to_light);
+    let to_eye = normalize(scene.eye - in.world_position);
+
+    let ambient = material.ambient;
+    let diffuse = material.diffuse * max(dot(normal, to_light), 0.0);
+
+    // 빛이 표면에서 반사된 방향이 눈을 향할수록 밝다
+    let reflected = reflect(-to_light, normal);
+    var specular = vec3<f32>(0.0);
+    if (dot(normal, to_light) > 0.0) {
+        specular = material.specular * pow(max(dot(reflected, to_eye), 0.0), material.shininess);
+    }
+
+    return vec4<f32>((ambient + diffuse + specular) * scene.light_color, 1.0);
+}
@@ -0,0 +1,354 @@
+use glam::Vec2;
+
+use crate::{DebugLineVertex, GpuContext, Vertex, VertexBuffer};
+
+// 원 하나를 나누는 변의 수
+const CIRCLE_SEGMENTS: usize = 24;
+// 채우기 색은 외곽선 색의 알파에 이 값을 곱한다
+const FILL_ALPHA: f32 = 0.5;
+
+/// `PhysicsDebugDraw` 에 쌓는 도형. 좌표는 카메라가 변환하는 2D 월드 좌표다.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawShape {
+    Circle {
+        center: Vec2,
+        radius: f32,
+        color: [f32; 4],
+    },
+    /// 꼭짓점을 순서대로 이은 단순 다각형. 오목해도 되고 방향은 상관없다.
+    Polygon { points: Vec<Vec2>, color: [f32; 4] },
+    /// 채우기 없이 외곽선만 그리는 선분
+    Edge { a: Vec2, b: Vec2, color: [f32; 4] },
+}
+
+/// 물리 엔진의 충돌체를 Box2D 디버그 뷰처럼 반투명한 면과 진한 외곽선으로 덧그린다.
+///
+/// 한 프레임 동안 `add` 로 도형을 쌓고, 불투명한 물체를 다 그린 뒤 `flush` 로
+/// 같은 컬러 타깃 위에 그린다. 면은 `TriangleList`, 외곽선은 `LineList` 로
+/// 드로우 콜 두 번이면 끝난다. 다각형은 귀 자르기(ear clipping)로 삼각형으로 나눈다.
+/// 셰이더는 `DebugLines` 와 같아서 0번 그룹의 `CameraUniform` 을 쓴다.
+pub struct PhysicsDebugDraw {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    fill_vertices: Vec<DebugLineVertex>,
+    line_vertices: Vec<DebugLineVertex>,
+    fill_buffer: VertexBuffer<DebugLineVertex>,
+    line_buffer: VertexBuffer<DebugLineVertex>,
+    // GPU 버퍼에 담을 수 있는 버텍스 수
+    fill_capacity: usize,
+    line_capacity: usize,
+    fill_pipeline: wgpu::RenderPipeline,
+    line_pipeline: wgpu::RenderPipeline,
+}
+
+impl PhysicsDebugDraw {
+    /// `camera_layout` 은 `flush` 에 넘길 카메라 바인드 그룹의 레이아웃이다.
+    pub fn new(
+        gpu: &GpuContext,
+        color_format: wgpu::TextureFormat,
+        camera_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let capacity = 256;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Physics Debug Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("debug_lines.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Physics Debug Pipeline Layout"),
+            bind_group_layouts: &[camera_layout],
+            push_constant_ranges: &[],
+        });
+
+        let create_pipeline = |label, topology| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[DebugLineVertex::layout()],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                // 다각형의 감기 방향을 가리지 않으므로 컬링하지 않는다
+                primitive: wgpu::PrimitiveState {
+                    topology,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let fill_pipeline = create_pipeline(
+            "Physics Debug Fill Pipeline",
+            wgpu::PrimitiveTopology::TriangleList,
+        );
+        let line_pipeline = create_pipeline(
+            "Physics Debug Line Pipeline",
+            wgpu::PrimitiveTopology::LineList,
+        );
+
+        Self {
+            device: device.clone(),
+            queue: queue.clone(),
+            fill_vertices: Vec::with_capacity(capacity),
+            line_vertices: Vec::with_capacity(capacity),
+            fill_buffer: create_vertex_buffer(device, capacity),
+            line_buffer: create_vertex_buffer(device, capacity),
+            fill_capacity: capacity,
+            line_capacity: capacity,
+            fill_pipeline,
+            line_pipeline,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fill_vertices.is_empty() && self.line_vertices.is_empty()
+    }
+
+    pub fn add(&mut self, shape: DrawShape) {
+        match shape {
+            DrawShape::Circle {
+                center,
+                radius,
+                color,
+            } => {
+                let points: Vec<Vec2> = (0..CIRCLE_SEGMENTS)
+                    .map(|i| {
+                        let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+                        center + Vec2::from_angle(angle) * radius
+                    })
+                    .collect();
+                // 원은 볼록하니 귀 자르기 없이 부채꼴로 채운다
+                let triangles: Vec<[usize; 3]> =
+                    (1..CIRCLE_SEGMENTS - 1).map(|i| [0, i, i + 1]).collect();
+                self.push_fill(&points, &triangles, color);
+                self.push_outline(&points, color);
+                // 회전을 알아볼 수 있도록 중심에서 반지름 방향으로 선을 하나 긋는다
+                self.push_line(center, points[0], color);
+            }
+            DrawShape::Polygon { points, color } => {
+                self.push_fill(&points, &triangulate(&points), color);
+                self.push_outline(&points, color);
+            }
+            DrawShape::Edge { a, b, color } => self.push_line(a, b, color),
+        }
+    }
+
+    /// 쌓인 도형을 올려서 `view` 의 기존 내용 위에 그리고 비운다.
+    /// 용량이 부족하면 버텍스 버퍼를 두 배씩 키워서 다시 만든다.
+    pub fn flush(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        camera_bind_group: &wgpu::BindGroup,
+    ) {
+        if self.is_empty() {
+            return;
+        }
+
+        if self.fill_vertices.len() > self.fill_capacity {
+            self.fill_capacity = self.fill_vertices.len().next_power_of_two();
+            self.fill_buffer = create_vertex_buffer(&self.device, self.fill_capacity);
+        }
+        if self.line_vertices.len() > self.line_capacity {
+            self.line_capacity = self.line_vertices.len().next_power_of_two();
+            self.line_buffer = create_vertex_buffer(&self.device, self.line_capacity);
+        }
+        self.fill_buffer.write(&self.queue, &self.fill_vertices);
+        self.line_buffer.write(&self.queue, &self.line_vertices);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Physics Debug Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_bind_group(0, camera_bind_group, &[]);
+
+        // 외곽선이 면에 덮이지 않도록 면을 먼저 그린다
+        if !self.fill_vertices.is_empty() {
+            render_pass.set_pipeline(&self.fill_pipeline);
+            self.fill_buffer.bind(&mut render_pass, 0);
+            render_pass.draw(0..self.fill_vertices.len() as u32, 0..1);
+        }
+        if !self.line_vertices.is_empty() {
+            render_pass.set_pipeline(&self.line_pipeline);
+            self.line_buffer.bind(&mut render_pass, 0);
+            render_pass.draw(0..self.line_vertices.len() as u32, 0..1);
+        }
+        drop(render_pass);
+
+        self.fill_vertices.clear();
+        self.line_vertices.clear();
+    }
+
+    fn push_fill(&mut self, points: &[Vec2], triangles: &[[usize; 3]], color: [f32; 4]) {
+        let color = [color[0], color[1], color[2], color[3] * FILL_ALPHA];
+        self.fill_vertices.extend(
+            triangles
+                .iter()
+                .flatten()
+                .map(|&i| vertex(points[i], color)),
+        );
+    }
+
+    fn push_outline(&mut self, points: &[Vec2], color: [f32; 4]) {
+        for (i, &a) in points.iter().enumerate() {
+            self.push_line(a, points[(i + 1) % points.len()], color);
+        }
+    }
+
+    fn push_line(&mut self, a: Vec2, b: Vec2, color: [f32; 4]) {
+        self.line_vertices.push(vertex(a, color));
+        self.line_vertices.push(vertex(b, color));
+    }
+}
+
+fn vertex(position: Vec2, color: [f32; 4]) -> DebugLineVertex {
+    DebugLineVertex {
+        position: position.extend(0.0).into(),
+        color,
+    }
+}
+
+fn create_vertex_buffer(device: &wgpu::Device, capacity: usize) -> VertexBuffer<DebugLineVertex> {
+    VertexBuffer::writable(device, &vec![bytemuck::Zeroable::zeroed(); capacity])
+}
+
+// 신발끈 공식. 반시계 방향이면 양수
+fn signed_area(points: &[Vec2]) -> f32 {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// 단순 다각형을 귀 자르기로 `points.len() - 2` 개의 삼각형으로 나눈다.
+/// 자기 교차처럼 귀를 찾을 수 없는 입력이면 남은 꼭짓점을 부채꼴로 채운다.
+fn triangulate(points: &[Vec2]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // 반시계 방향 기준으로 볼록한 꼭짓점을 찾는다
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if signed_area(points) < 0.0 {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let (ia, ib, ic) = (
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            );
+            let (a, b, c) = (points[ia], points[ib], points[ic]);
+            if (b - a).perp_dot(c - b) <= 0.0 {
+                return false;
+            }
+            // 다른 꼭짓점이 이 삼각형 안에 있으면 잘라낼 수 없다
+            !remaining
+                .iter()
+                .filter(|&&j| j != ia && j != ib && j != ic)
+                .any(|&j| contains_point(a, b, c, points[j]))
+        });
+
+        let Some(i) = ear else {
+            break;
+        };
+        triangles.push([
+            remaining[(i + n - 1) % n],
+            remaining[i],
+            remaining[(i + 1) % n],
+        ]);
+        remaining.remove(i);
+    }
+
+    for i in 1..remaining.len() - 1 {
+        triangles.push([remaining[0], remaining[i], remaining[i + 1]]);
+    }
+    triangles
+}
+
+// 반시계 방향 삼각형 abc 가 p 를 포함하는지. 경계 위도 포함으로 본다
+fn contains_point(a: Vec2, b: Vec2, c: Vec2, p: Vec2) -> bool {
+    (b - a).perp_dot(p - a) >= 0.0
+        && (c - b).perp_dot(p - b) >= 0.0
+        && (a - c).perp_dot(p - c) >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area_of(points: &[Vec2], triangles: &[[usize; 3]]) -> f32 {
+        triangles
+            .iter()
+            .map(|&[a, b, c]| signed_area(&[points[a], points[b], points[c]]).abs())
+            .sum()
+    }
+
+    #[test]
+    fn triangulates_square_into_two_triangles() {
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        let triangles = triangulate(&square);
+        assert_eq!(triangles.len(), 2);
+        assert!((area_of(&square, &triangles) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn triangulates_concave_polygon_in_either_winding() {
+        // 넓이가 3 인 L 자 모양
+        let mut l_shape = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ];
+        for _ in 0..2 {
+            let triangles = triangulate(&l_shape);
+            assert_eq!(triangles.len(), 4);
+            // 오목한 부분을 덮는 삼각형이 있으면 넓이가 3 보다 커진다
+            assert!((area_of(&l_shape, &triangles) - 3.0).abs() < 1e-6);
+            l_shape.reverse();
+        }
+    }
+
+    #[test]
+    fn degenerate_polygons_produce_no_triangles() {
+        assert!(triangulate(&[Vec2::ZERO, Vec2::X]).is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+/// 렌더 파이프라인을 구분하는 상태. 이 값이 같으면 같은 파이프라인을 재사용한다.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PipelineKey {
+    pub shader_label: String,
+    pub topology: wgpu::PrimitiveTopology,
+    pub polygon_mode: wgpu::PolygonMode,
+    pub blend: Option<wgpu::BlendState>,
+    pub sample_count: u32,
+    pub depth_format: Option<wgpu::TextureFormat>,
+}
+
+/// `device.create_render_pipeline` 호출을 줄이기 위한 파이프라인 캐시
+#[derive(Default)]
+pub struct PipelineCache {
+    pipelines: HashMap<PipelineKey, wgpu::RenderPipeline>,
+}
+
+impl PipelineCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `key` 에 해당하는 파이프라인이 없을 때만 `create` 로 만든다.
+    pub fn get_or_create(
+        &mut self,
+        key: PipelineKey,
+        create: impl FnOnce(&PipelineKey) -> wgpu::RenderPipeline,
+    ) -> &wgpu::RenderPipeline {
+        self.pipelines
+            .entry(key)
+            .or_insert_with_key(|key| create(key))
+    }
+
+    pub fn get(&self, key: &PipelineKey) -> Option<&wgpu::RenderPipeline> {
+        self.pipelines.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pipelines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pipelines.is_empty()
+    }
+
+    /// 셰이더가 바뀌는 등 기존 파이프라인을 모두 버려야 할 때 사용한다.
+    pub fn clear(&mut self) {
+        self.pipelines.clear();
+    }
+}
+
+/// 같은 바인드 그룹 레이아웃 목록을 쓰는 파이프라인끼리 `PipelineLayout` 을 공유하기 위한 캐시.
+///
+/// wgpu 의 `BindGroupLayout` 은 같은 GPU 객체일 때만 같다고 비교되므로,
+/// 키는 레이아웃 객체의 순서 있는 목록과 푸시 상수 범위다.
+#[derive(Default)]
+pub struct PipelineLayoutCache {
+    layouts:
+        HashMap<(Vec<wgpu::BindGroupLayout>, Vec<wgpu::PushConstantRange>), wgpu::PipelineLayout>,
+}
+
+impl PipelineLayoutCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `bind_group_layouts` 순서 그대로의 파이프라인 레이아웃이 없을 때만 새로 만든다.
+    pub fn get_or_create(
+        &mut self,
+        device: &wgpu::Device,
+        bind_group_layouts: &[&wgpu::BindGroupLayout],
+    ) -> &wgpu::PipelineLayout {
+        self.get_or_create_with_push_constants(device, bind_group_layouts, &[])
+    }
+
+    /// `get_or_create` 에 푸시 상수 범위를 더한다. 범위는 `PushConstants::range()` 로 만든다.
+    /// 디바이스에 `Features::PUSH_CONSTANTS` 가 없으면 레이아웃 생성이 실패한다.
+    pub fn get_or_create_with_push_constants(
+        &mut self,
+        device: &wgpu::Device,
+        bind_group_layouts: &[&wgpu::BindGroupLayout],
+        push_constant_ranges: &[wgpu::PushConstantRange],
+    ) -> &wgpu::PipelineLayout {
+        let key = (
+            bind_group_layouts
+                .iter()
+                .map(|&layout| layout.clone())
+                .collect(),
+            push_constant_ranges.to_vec(),
+        );
+        self.layouts.entry(key).or_insert_with(|| {
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Cached Pipeline Layout"),
+                bind_group_layouts,
+                push_constant_ranges,
+            })
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.layouts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layouts.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.layouts.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uniform_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        })
+    }
+
+    #[test]
+    #[cfg_attr(not(wgpu_tests), ignore = "needs a GPU adapter, set WGPU_TESTS=1")]
+    fn same_bind_group_layouts_share_pipeline_layout() {
+        let Some((device, _queue)) = crate::test_gpu::device() else {
+            return;
+        };
+        let (camera, material) = (uniform_layout(&device), uniform_layout(&device));
+        let mut cache = PipelineLayoutCache::new();
+
+        let first = cache.get_or_create(&device, &[&camera, &material]).clone();
+        let second = cache.get_or_create(&device, &[&camera, &material]).clone();
+        assert_eq!(first, second);
+        assert_eq!(cache.len(), 1);
+
+        // 내용이 같아도 다른 레이아웃 객체거나 순서가 다르면 다른 키다
+        let swapped = cache.get_or_create(&device, &[&material, &camera]).clone();
+        assert_ne!(first, swapped);
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::GpuContext;
+
+// 쿼리 하나가 기록하는 통계 수. PipelineStatisticsTypes 의 비트 수와 같다
+const STAT_COUNT: usize = 5;
+const RESULT_SIZE: wgpu::BufferAddress = (STAT_COUNT * std::mem::size_of::<u64>()) as _;
+
+/// 쿼리 하나로 읽은 파이프라인 통계. 필드 순서는 `PipelineStatisticsTypes` 의 비트 순서와 같다.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PipelineStats {
+    pub vertex_shader_invocations: u64,
+    /// 버텍스 셰이더가 내보낸 프리미티브 수
+    pub clipper_invocations: u64,
+    /// 클리핑에서 살아남아 래스터라이즈된 프리미티브 수
+    pub clipper_primitives_out: u64,
+    /// 미분 계산을 위해 2x2 단위로 실행된 것까지 센다
+    pub fragment_shader_invocations: u64,
+    pub compute_shader_invocations: u64,
+}
+
+impl PipelineStats {
+    fn from_words(words: &[u64]) -> Self {
+        Self {
+            vertex_shader_invocations: words[0],
+            clipper_invocations: words[1],
+            clipper_primitives_out: words[2],
+            fragment_shader_invocations: words[3],
+            compute_shader_invocations: words[4],
+        }
+    }
+}
+
+/// 패스 하나에서 실행된 셰이더 호출 수와 프리미티브 수를 센다.
+///
+/// 사용 순서는 `GpuTimer` 와 같다.
+/// 1. 패스 안에서 `begin_render` 와 `end_render` 로 셀 구간을 감싼다
+/// 2. 제출 전에 `resolve` 로 결과를 리드백 버퍼에 복사한다
+/// 3. 제출 후 `read_back` 으로 매핑을 요청하면 완료될 때 `last_*` 가 갱신된다
+///
+/// `Features::PIPELINE_STATISTICS_QUERY` 는 Vulkan, DX12 의 네이티브 전용 기능이라 웹에서는 만들 수 없다.
+pub struct PipelineStatsQuery {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    // 이번 프레임에 쿼리를 기록했는지
+    recorded: bool,
+    resolved: bool,
+    // 리드백 버퍼가 매핑 중이면 복사할 수 없으므로 다음 프레임은 건너뛴다
+    mapping: Arc<AtomicBool>,
+    result: Arc<Mutex<Option<PipelineStats>>>,
+}
+
+impl PipelineStatsQuery {
+    /// 디바이스를 요청할 때 `required_features` 에 포함해야 하는 기능
+    pub const FEATURES: wgpu::Features = wgpu::Features::PIPELINE_STATISTICS_QUERY;
+
+    /// 디바이스에 `FEATURES` 가 없으면 `None` 을 반환한다.
+    pub fn new(gpu: &GpuContext) -> Option<Self> {
+        let device = gpu.device();
+        if !device.features().contains(Self::FEATURES) {
+            return None;
+        }
+
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Pipeline Stats Query Set"),
+            ty: wgpu::QueryType::PipelineStatistics(wgpu::PipelineStatisticsTypes::all()),
+            count: 1,
+        });
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Pipeline Stats Resolve Buffer"),
+            size: RESULT_SIZE,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Pipeline Stats Readback Buffer"),
+            size: RESULT_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            recorded: false,
+            resolved: false,
+            mapping: Arc::new(AtomicBool::new(false)),
+            result: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// 한 프레임에 한 번, 패스 안에서 `end_render` 와 짝을 맞춰 호출한다.
+    pub fn begin_render(&mut self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.begin_pipeline_statistics_query(&self.query_set, 0);
+        self.recorded = true;
+    }
+
+    pub fn end_render(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.end_pipeline_statistics_query();
+    }
+
+    pub fn begin_compute(&mut self, compute_pass: &mut wgpu::ComputePass<'_>) {
+        compute_pass.begin_pipeline_statistics_query(&self.query_set, 0);
+        self.recorded = true;
+    }
+
+    pub fn end_compute(&self, compute_pass: &mut wgpu::ComputePass<'_>) {
+        compute_pass.end_pipeline_statistics_query();
+    }
+
+    /// 이번 프레임의 통계를 리드백 버퍼로 복사하는 명령을 기록한다.
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if !std::mem::take(&mut self.recorded) || self.mapping.load(Ordering::Acquire) {
+            return;
+        }
+
+        encoder.resolve_query_set(&self.query_set, 0..1, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            RESULT_SIZE,
+        );
+        self.resolved = true;
+    }
+
+    /// 제출한 뒤 호출한다. 매핑이 끝나면 `last_*` 가 갱신된다.
+    pub fn read_back(&mut self) {
+        if !self.resolved || self.mapping.load(Ordering::Acquire) {
+            return;
+        }
+
+        self.resolved = false;
+        self.mapping.store(true, Ordering::Release);
+        let mapping = Arc::clone(&self.mapping);
+        let result = Arc::clone(&self.result);
+        let buffer = self.readback_buffer.clone();
+
+        self.readback_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |status| {
+                if status.is_ok() {
+                    {
+                        let mapped = buffer.slice(..).get_mapped_range();
+                        let words: &[u64] = bytemuck::cast_slice(&mapped);
+                        *result.lock().unwrap() = Some(PipelineStats::from_words(words));
+                    }
+                    buffer.unmap();
+                }
+                mapping.store(false, Ordering::Release);
+            });
+    }
+
+    /// 마지막으로 읽어 온 통계. 아직 읽은 적이 없으면 `None`
+    pub fn last_stats(&self) -> Option<PipelineStats> {
+        *self.result.lock().unwrap()
+    }
+
+    pub fn last_vertex_invocations(&self) -> u64 {
+        self.last_stats()
+            .unwrap_or_default()
+            .vertex_shader_invocations
+    }
+
+    pub fn last_clipper_invocations(&self) -> u64 {
+        self.last_stats().unwrap_or_default().clipper_invocations
+    }
+
+    pub fn last_clipper_primitives_out(&self) -> u64 {
+        self.last_stats().unwrap_or_default().clipper_primitives_out
+    }
+
+    pub fn last_fragment_invocations(&self) -> u64 {
+        self.last_stats()
+            .unwrap_or_default()
+            .fragment_shader_invocations
+    }
+
+    pub fn last_compute_invocations(&self) -> u64 {
+        self.last_stats()
+            .unwrap_or_default()
+            .compute_shader_invocations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn result_covers_every_statistics_bit() {
+        assert_eq!(
+            wgpu::PipelineStatisticsTypes::all().bits().count_ones() as usize,
+            STAT_COUNT
+        );
+    }
+
+    #[test]
+    fn words_follow_statistics_bit_order() {
+        let stats = PipelineStats::from_words(&[3, 1, 1, 240, 0]);
+        assert_eq!(stats.vertex_shader_invocations, 3);
+        assert_eq!(stats.clipper_invocations, 1);
+        assert_eq!(stats.clipper_primitives_out, 1);
+        assert_eq!(stats.fragment_shader_invocations, 240);
+        assert_eq!(stats.compute_shader_invocations, 0);
+    }
+}
@@ -0,0 +1,468 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::{
+    BloomPass, FullscreenTriangle, GpuContext, HdrRenderTarget, SamplerBuilder, ToneMapPass,
+    UniformBuffer,
+};
+
+/// `PostProcessStack` 에 넣는 전체 화면 효과 하나.
+///
+/// `input` 을 읽어서 `output` 전체를 덮어쓴다. 스택의 마지막 효과가 아니라면 `output` 은
+/// `PostProcessStack::FORMAT` 텍스처이므로 그 포맷으로 파이프라인을 만든다.
+pub trait PostProcessEffect {
+    fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    );
+}
+
+/// 여러 후처리 효과를 넣은 순서대로 이어서 적용한다.
+///
+/// 효과 사이의 결과는 내부의 `Rgba16Float` 텍스처 두 장에 번갈아 그린다.
+/// 같은 텍스처를 읽으면서 그릴 수는 없으므로 한 효과의 출력이 다음 효과의 입력이 되도록 주고받는다.
+/// 마지막 효과만 `apply` 에 넘긴 `output` 에 그리므로 보통 `ToneMapPass` 를 마지막에 둔다.
+pub struct PostProcessStack {
+    effects: Vec<Box<dyn PostProcessEffect>>,
+    enabled: Vec<bool>,
+    targets: [HdrRenderTarget; 2],
+}
+
+impl PostProcessStack {
+    /// 효과 사이에 쓰는 텍스처의 포맷. 마지막이 아닌 효과는 이 포맷으로 출력해야 한다
+    pub const FORMAT: wgpu::TextureFormat = HdrRenderTarget::FORMAT;
+
+    pub fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        Self {
+            effects: Vec::new(),
+            enabled: Vec::new(),
+            targets: [
+                HdrRenderTarget::new(device, width, height),
+                HdrRenderTarget::new(device, width, height),
+            ],
+        }
+    }
+
+    /// 효과를 맨 뒤에 추가하고 `set_enabled` 에 넘길 위치를 돌려준다.
+    pub fn push(&mut self, effect: impl PostProcessEffect + 'static) -> usize {
+        self.effects.push(Box::new(effect));
+        self.enabled.push(true);
+        self.effects.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// 꺼진 효과는 건너뛰고 앞뒤 효과를 바로 잇는다.
+    ///
+    /// # Panics
+    ///
+    /// `index` 가 `len` 보다 크거나 같으면 panic 한다.
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        self.enabled[index] = enabled;
+    }
+
+    pub fn is_enabled(&self, index: usize) -> bool {
+        self.enabled[index]
+    }
+
+    /// 내부 텍스처를 새 크기로 다시 만든다. `apply` 에 넘길 입력과 같은 크기여야 한다.
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        for target in &mut self.targets {
+            target.resize(device, width, height);
+        }
+    }
+
+    /// 켜진 효과를 순서대로 적용해서 `output` 에 그린다.
+    /// 켜진 효과가 없으면 아무것도 그리지 않는다.
+    pub fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        let effects = self
+            .effects
+            .iter()
+            .zip(&self.enabled)
+            .filter_map(|(effect, &enabled)| enabled.then_some(effect));
+        let steps = ping_pong_steps(self.enabled.iter().filter(|&&enabled| enabled).count());
+
+        for (effect, (source, target)) in effects.zip(steps) {
+            let source = match source {
+                Slot::External => input,
+                Slot::Target(index) => self.targets[index].view(),
+            };
+            let target = match target {
+                Slot::External => output,
+                Slot::Target(index) => self.targets[index].view(),
+            };
+            effect.apply(encoder, source, target);
+        }
+    }
+}
+
+// 효과 하나가 읽거나 쓰는 곳. External 은 입력이면 apply 의 input, 출력이면 output 이다
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Slot {
+    External,
+    Target(usize),
+}
+
+// count 개의 효과가 차례로 읽고 쓰는 곳. 첫 효과는 input 을 읽고 마지막 효과는 output 에 쓰며,
+// 그 사이는 두 텍스처를 번갈아 쓴다
+fn ping_pong_steps(count: usize) -> impl Iterator<Item = (Slot, Slot)> {
+    (0..count).map(move |i| {
+        let source = if i == 0 {
+            Slot::External
+        } else {
+            Slot::Target((i - 1) % 2)
+        };
+        let target = if i == count - 1 {
+            Slot::External
+        } else {
+            Slot::Target(i % 2)
+        };
+        (source, target)
+    })
+}
+
+/// 프래그먼트 셰이더 하나로 입력 텍스처 전체를 출력 텍스처에 다시 그리는 효과.
+///
+/// 셰이더에는 `fs_main` 만 있으면 되고 버텍스 셰이더는 `FullscreenTriangle` 이 제공한다.
+/// 0번 그룹의 0번에 입력 텍스처, 1번에 필터링 샘플러가 있고, 파라미터가 필요하면
+/// `extra_layouts` 로 넘긴 레이아웃이 1번 그룹부터 이어진다. 파라미터가 없는 효과는
+/// 그대로 `PostProcessStack::push` 에 넘길 수 있다.
+pub struct FullscreenEffect {
+    device: wgpu::Device,
+    label: &'static str,
+    pipeline: wgpu::RenderPipeline,
+    input_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+}
+
+impl FullscreenEffect {
+    pub fn new(
+        device: &wgpu::Device,
+        label: &'static str,
+        shader_source: &str,
+        output_format: wgpu::TextureFormat,
+        extra_layouts: &[&wgpu::BindGroupLayout],
+    ) -> Self {
+        let fullscreen = FullscreenTriangle::new(device);
+        let input_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(label),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(label),
+            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+        });
+        let bind_group_layouts: Vec<&wgpu::BindGroupLayout> = std::iter::once(&input_layout)
+            .chain(extra_layouts.iter().copied())
+            .collect();
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: &bind_group_layouts,
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(&layout),
+            vertex: fullscreen.vertex_state(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            device: device.clone(),
+            label,
+            pipeline,
+            input_layout,
+            sampler: SamplerBuilder::linear().label(label).build(device),
+        }
+    }
+
+    /// `extra_bind_groups` 는 `new` 에 넘긴 `extra_layouts` 와 같은 순서로 1번 그룹부터 설정된다.
+    pub fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+        extra_bind_groups: &[&wgpu::BindGroup],
+    ) {
+        // 입력은 스택의 어느 텍스처가 될지 모르므로 바인드 그룹을 매번 만든다
+        let input_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(self.label),
+            layout: &self.input_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(self.label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &input_bind_group, &[]);
+        for (i, bind_group) in extra_bind_groups.iter().enumerate() {
+            render_pass.set_bind_group(i as u32 + 1, *bind_group, &[]);
+        }
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+impl PostProcessEffect for FullscreenEffect {
+    fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        self.encode(encoder, input, output, &[]);
+    }
+}
+
+/// 경계의 계단 현상을 줄이는 FXAA.
+///
+/// 밝기 차이로 경계와 그 방향을 찾아서 경계를 따라 흐리게 한다. MSAA 와 달리 장면을 다시 그리지
+/// 않으므로 싸지만, 글자처럼 가는 선도 조금 흐려진다.
+pub struct FxaaEffect {
+    effect: FullscreenEffect,
+}
+
+impl FxaaEffect {
+    pub fn new(device: &wgpu::Device, output_format: wgpu::TextureFormat) -> Self {
+        Self {
+            effect: FullscreenEffect::new(
+                device,
+                "FXAA",
+                include_str!("fxaa.wgsl"),
+                output_format,
+                &[],
+            ),
+        }
+    }
+}
+
+impl PostProcessEffect for FxaaEffect {
+    fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        self.effect.apply(encoder, input, output);
+    }
+}
+
+// vignette.wgsl 의 VignetteParams 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct VignetteParams {
+    intensity: f32,
+    radius: f32,
+    softness: f32,
+    _padding: f32,
+}
+
+/// 화면 가장자리를 어둡게 만드는 비네트.
+///
+/// 세로 높이를 1 로 본 화면 중심에서의 거리가 `radius` 부터 `radius + softness` 까지
+/// 점점 어두워지고, 가장 어두운 곳은 원래 밝기의 `1 - intensity` 배가 된다.
+/// 설정 메서드는 `&self` 를 받으므로 `Rc` 로 감싸서 스택에 넣은 뒤에도 값을 바꿀 수 있다.
+pub struct VignetteEffect {
+    effect: FullscreenEffect,
+    params: UniformBuffer<VignetteParams>,
+    values: Cell<VignetteParams>,
+}
+
+impl VignetteEffect {
+    pub const DEFAULT_INTENSITY: f32 = 0.5;
+    pub const DEFAULT_RADIUS: f32 = 0.3;
+    pub const DEFAULT_SOFTNESS: f32 = 0.5;
+
+    pub fn new(gpu: &GpuContext, output_format: wgpu::TextureFormat) -> Self {
+        let values = VignetteParams {
+            intensity: Self::DEFAULT_INTENSITY,
+            radius: Self::DEFAULT_RADIUS,
+            softness: Self::DEFAULT_SOFTNESS,
+            _padding: 0.0,
+        };
+        let params = UniformBuffer::new(gpu, &values, 0, wgpu::ShaderStages::FRAGMENT);
+        let effect = FullscreenEffect::new(
+            gpu.device(),
+            "Vignette",
+            include_str!("vignette.wgsl"),
+            output_format,
+            &[params.bind_group_layout()],
+        );
+
+        Self {
+            effect,
+            params,
+            values: Cell::new(values),
+        }
+    }
+
+    pub fn intensity(&self) -> f32 {
+        self.values.get().intensity
+    }
+
+    /// 0 이면 효과가 없고 1 이면 가장자리가 검게 된다
+    pub fn set_intensity(&self, queue: &wgpu::Queue, intensity: f32) {
+        self.update(queue, |values| values.intensity = intensity.clamp(0.0, 1.0));
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.values.get().radius
+    }
+
+    pub fn set_radius(&self, queue: &wgpu::Queue, radius: f32) {
+        self.update(queue, |values| values.radius = radius.max(0.0));
+    }
+
+    pub fn softness(&self) -> f32 {
+        self.values.get().softness
+    }
+
+    pub fn set_softness(&self, queue: &wgpu::Queue, softness: f32) {
+        self.update(queue, |values| values.softness = softness.max(0.0));
+    }
+
+    fn update(&self, queue: &wgpu::Queue, change: impl FnOnce(&mut VignetteParams)) {
+        let mut values = self.values.get();
+        change(&mut values);
+        self.values.set(values);
+        self.params.update(queue, &values);
+    }
+}
+
+impl PostProcessEffect for VignetteEffect {
+    fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        self.effect
+            .encode(encoder, input, output, &[self.params.bind_group()]);
+    }
+}
+
+/// 스택에 넣은 효과의 설정을 나중에 바꾸려면 `Rc` 로 감싸서 복제본을 넣는다.
+impl<T: PostProcessEffect + ?Sized> PostProcessEffect for Rc<T> {
+    fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        (**self).apply(encoder, input, output);
+    }
+}
+
+impl PostProcessEffect for ToneMapPass {
+    fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        ToneMapPass::apply(self, encoder, input, output);
+    }
+}
+
+/// `BloomPass::new` 에 넘긴 크기가 스택의 크기와 같아야 한다.
+impl PostProcessEffect for BloomPass {
+    fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        BloomPass::apply(self, encoder, input, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_effect_reads_input_and_writes_output() {
+        let steps: Vec<_> = ping_pong_steps(1).collect();
+        assert_eq!(steps, [(Slot::External, Slot::External)]);
+        assert_eq!(ping_pong_steps(0).count(), 0);
+    }
+
+    #[test]
+    fn effects_alternate_between_targets() {
+        let steps: Vec<_> = ping_pong_steps(4).collect();
+        assert_eq!(
+            steps,
+            [
+                (Slot::External, Slot::Target(0)),
+                (Slot::Target(0), Slot::Target(1)),
+                (Slot::Target(1), Slot::Target(0)),
+                (Slot::Target(0), Slot::External),
+            ]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::marker::PhantomData;
+
+use crate::PushConstantsError;
+
+/// 셰이더의 `var<push_constant>` 하나에 넣을 `T` 값의 오프셋과 셰이더 단계.
+///
+/// 파이프라인 레이아웃에는 `range()` 를 등록하고, 그리기 전에
+/// `RenderPassPushConstantsExt::set_push_constants_typed` 로 값을 넣는다.
+/// 유니폼 버퍼와 달리 버퍼나 바인드 그룹 없이 그리기 명령마다 작은 값을 바꿀 수 있다.
+///
+/// `Features::PUSH_CONSTANTS` 는 네이티브 백엔드에서만 지원되고 WebGPU, WebGL2 에는 없으므로
+/// 디바이스를 만든 뒤 `check_supported` 로 먼저 확인한다.
+pub struct PushConstants<T: bytemuck::Pod> {
+    offset: u32,
+    stages: wgpu::ShaderStages,
+    _marker: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> Clone for PushConstants<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: bytemuck::Pod> Copy for PushConstants<T> {}
+
+impl<T: bytemuck::Pod> PushConstants<T> {
+    /// `T` 의 바이트 크기. wgpu 는 4 의 배수만 허용한다
+    pub const SIZE: u32 = std::mem::size_of::<T>() as u32;
+
+    /// 오프셋 0 에 놓는다.
+    pub fn new(stages: wgpu::ShaderStages) -> Self {
+        Self::with_offset(0, stages)
+    }
+
+    /// 여러 값을 나눠 넣을 때 쓴다. 오프셋과 `T` 의 크기는 4 의 배수여야 한다.
+    pub fn with_offset(offset: u32, stages: wgpu::ShaderStages) -> Self {
+        assert!(
+            offset.is_multiple_of(wgpu::PUSH_CONSTANT_ALIGNMENT),
+            "push constant offset {} is not a multiple of {}",
+            offset,
+            wgpu::PUSH_CONSTANT_ALIGNMENT
+        );
+        assert!(
+            Self::SIZE.is_multiple_of(wgpu::PUSH_CONSTANT_ALIGNMENT),
+            "push constant size {} is not a multiple of {}",
+            Self::SIZE,
+            wgpu::PUSH_CONSTANT_ALIGNMENT
+        );
+
+        Self {
+            offset,
+            stages,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn stages(&self) -> wgpu::ShaderStages {
+        self.stages
+    }
+
+    /// `PipelineLayoutDescriptor::push_constant_ranges` 에 넣을 범위
+    pub fn range(&self) -> wgpu::PushConstantRange {
+        wgpu::PushConstantRange {
+            stages: self.stages,
+            range: self.offset..self.offset + Self::SIZE,
+        }
+    }
+
+    /// 디바이스에 `Features::PUSH_CONSTANTS` 가 켜져 있고 범위가 한도 안에 있는지 확인한다.
+    pub fn check_supported(&self, device: &wgpu::Device) -> Result<(), PushConstantsError> {
+        check_push_constants(
+            device.features(),
+            &device.limits(),
+            self.offset + Self::SIZE,
+        )
+    }
+}
+
+fn check_push_constants(
+    features: wgpu::Features,
+    limits: &wgpu::Limits,
+    end: u32,
+) -> Result<(), PushConstantsError> {
+    if !features.contains(wgpu::Features::PUSH_CONSTANTS) {
+        return Err(PushConstantsError::FeatureNotEnabled);
+    }
+    if end > limits.max_push_constant_size {
+        return Err(PushConstantsError::ExceedsLimit {
+            end,
+            max: limits.max_push_constant_size,
+        });
+    }
+    Ok(())
+}
+
+/// `wgpu::RenderPass` 에 `PushConstants` 로 타입을 맞춘 값을 넣는다.
+pub trait RenderPassPushConstantsExt {
+    /// 현재 파이프라인의 레이아웃에 `push_constants.range()` 가 등록되어 있어야 한다.
+    fn set_push_constants_typed<T: bytemuck::Pod>(
+        &mut self,
+        push_constants: &PushConstants<T>,
+        value: &T,
+    );
+}
+
+impl RenderPassPushConstantsExt for wgpu::RenderPass<'_> {
+    fn set_push_constants_typed<T: bytemuck::Pod>(
+        &mut self,
+        push_constants: &PushConstants<T>,
+        value: &T,
+    ) {
+        self.set_push_constants(
+            push_constants.stages,
+            push_constants.offset,
+            bytemuck::bytes_of(value),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    struct DrawData {
+        color: [f32; 4],
+        index: u32,
+        _padding: [u32; 3],
+    }
+
+    #[test]
+    fn range_covers_value_at_offset() {
+        let push_constants =
+            PushConstants::<DrawData>::with_offset(16, wgpu::ShaderStages::FRAGMENT);
+        let range = push_constants.range();
+        assert_eq!(range.stages, wgpu::ShaderStages::FRAGMENT);
+        assert_eq!(range.range, 16..48);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a multiple of 4")]
+    fn rejects_unaligned_offset() {
+        PushConstants::<DrawData>::with_offset(2, wgpu::ShaderStages::VERTEX);
+    }
+
+    #[test]
+    fn reports_missing_feature_and_limit() {
+        let limits = wgpu::Limits {
+            max_push_constant_size: 128,
+            ..wgpu::Limits::default()
+        };
+        assert!(matches!(
+            check_push_constants(wgpu::Features::empty(), &limits, 32),
+            Err(PushConstantsError::FeatureNotEnabled)
+        ));
+        assert!(matches!(
+            check_push_constants(wgpu::Features::PUSH_CONSTANTS, &limits, 160),
+            Err(PushConstantsError::ExceedsLimit { end: 160, max: 128 })
+        ));
+        assert!(check_push_constants(wgpu::Features::PUSH_CONSTANTS, &limits, 128).is_ok());
+    }
+}
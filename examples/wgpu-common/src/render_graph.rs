@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+
+use crate::{GpuContext, RenderGraphError, RenderPassProfiler};
+
+/// `RenderGraph` 가 관리하는 텍스처를 가리키는 ID
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResourceId(u32);
+
+/// 렌더 그래프의 패스 하나.
+///
+/// `inputs` 로 읽는 텍스처와 `outputs` 로 쓰는 텍스처를 선언하면
+/// 그래프가 이 관계로 실행 순서를 정한다.
+pub trait RenderGraphNode {
+    fn name(&self) -> &str;
+    fn inputs(&self) -> &[ResourceId];
+    fn outputs(&self) -> &[ResourceId];
+    fn execute(&mut self, ctx: &mut RenderGraphContext<'_>);
+}
+
+/// 노드가 실행될 때 받는 디바이스, 인코더와 그래프의 텍스처들
+pub struct RenderGraphContext<'a> {
+    pub device: &'a wgpu::Device,
+    pub queue: &'a wgpu::Queue,
+    pub encoder: &'a mut wgpu::CommandEncoder,
+    resources: &'a [Resource],
+}
+
+impl<'a> RenderGraphContext<'a> {
+    /// 반환된 뷰는 `ctx` 를 빌리지 않으므로 `encoder` 로 패스를 여는 동안에도 쓸 수 있다.
+    ///
+    /// # Panics
+    ///
+    /// 외부 텍스처인데 `set_external` 로 뷰를 넘기지 않았으면 panic 한다.
+    pub fn texture_view(&self, id: ResourceId) -> &'a wgpu::TextureView {
+        let resource = &self.resources[id.0 as usize];
+        resource
+            .view
+            .as_ref()
+            .unwrap_or_else(|| panic!("texture '{}' has no view", resource.label))
+    }
+}
+
+struct Resource {
+    label: String,
+    // None 이면 서피스처럼 그래프 밖에서 매 프레임 넘겨받는 텍스처
+    format: Option<wgpu::TextureFormat>,
+    view: Option<wgpu::TextureView>,
+}
+
+/// 노드들을 텍스처 의존 관계에 따라 정렬해서 실행하는 렌더 그래프.
+///
+/// `create_texture` 로 만든 텍스처는 그래프 크기로 처음 실행할 때 만들어지고,
+/// `import_texture` 로 만든 텍스처는 매 프레임 `set_external` 로 뷰를 넘겨받는다.
+/// 노드를 모두 추가한 뒤 `build` 로 순서를 정하고 `execute` 로 실행한다.
+pub struct RenderGraph {
+    resources: Vec<Resource>,
+    nodes: Vec<Box<dyn RenderGraphNode>>,
+    // build 로 정한 nodes 의 실행 순서. 노드가 추가되면 비워진다
+    order: Vec<usize>,
+    size: (u32, u32),
+}
+
+impl RenderGraph {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            resources: Vec::new(),
+            nodes: Vec::new(),
+            order: Vec::new(),
+            size: (width.max(1), height.max(1)),
+        }
+    }
+
+    /// 그래프 크기의 2D 텍스처. 렌더 타깃과 셰이더 입력으로 모두 쓸 수 있다.
+    pub fn create_texture(&mut self, label: &str, format: wgpu::TextureFormat) -> ResourceId {
+        self.add_resource(label, Some(format))
+    }
+
+    /// 서피스 텍스처처럼 그래프 밖에서 만든 텍스처
+    pub fn import_texture(&mut self, label: &str) -> ResourceId {
+        self.add_resource(label, None)
+    }
+
+    /// 외부 텍스처의 이번 프레임 뷰
+    pub fn set_external(&mut self, id: ResourceId, view: &wgpu::TextureView) {
+        let resource = &mut self.resources[id.0 as usize];
+        debug_assert!(
+            resource.format.is_none(),
+            "'{}' is not an external texture",
+            resource.label
+        );
+        resource.view = Some(view.clone());
+    }
+
+    pub fn add_node(&mut self, node: impl RenderGraphNode + 'static) {
+        self.nodes.push(Box::new(node));
+        self.order.clear();
+    }
+
+    /// 그래프가 만든 텍스처를 버리고 다음 `execute` 때 새 크기로 다시 만든다.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let size = (width.max(1), height.max(1));
+        if size == self.size {
+            return;
+        }
+
+        self.size = size;
+        for resource in &mut self.resources {
+            if resource.format.is_some() {
+                resource.view = None;
+            }
+        }
+    }
+
+    /// 노드를 위상 정렬해서 실행 순서를 정한다.
+    ///
+    /// 모든 입력은 다른 노드의 출력이거나 외부 텍스처여야 하고,
+    /// 한 텍스처를 두 노드가 출력하거나 의존 관계에 순환이 있으면 에러를 반환한다.
+    /// 서로 의존하지 않는 노드는 추가한 순서를 유지한다.
+    pub fn build(&mut self) -> Result<(), RenderGraphError> {
+        let mut producers: HashMap<ResourceId, usize> = HashMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            for &output in node.outputs() {
+                if let Some(&first) = producers.get(&output) {
+                    return Err(RenderGraphError::MultipleProducers {
+                        resource: self.label(output),
+                        first: self.nodes[first].name().to_string(),
+                        second: node.name().to_string(),
+                    });
+                }
+                producers.insert(output, index);
+            }
+        }
+
+        // 입력을 만드는 노드 -> 입력을 읽는 노드
+        let mut dependents = vec![Vec::new(); self.nodes.len()];
+        let mut in_degree = vec![0; self.nodes.len()];
+        for (index, node) in self.nodes.iter().enumerate() {
+            for &input in node.inputs() {
+                match producers.get(&input) {
+                    Some(&producer) => {
+                        dependents[producer].push(index);
+                        in_degree[index] += 1;
+                    }
+                    None if self.resources[input.0 as usize].format.is_none() => {}
+                    None => {
+                        return Err(RenderGraphError::MissingProducer {
+                            node: node.name().to_string(),
+                            resource: self.label(input),
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut ready: Vec<usize> = (0..self.nodes.len())
+            .filter(|&index| in_degree[index] == 0)
+            .collect();
+        while !ready.is_empty() {
+            // 준비된 노드 중 가장 먼저 추가된 것부터 실행한다
+            let (position, _) = ready
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, index)| **index)
+                .expect("ready is not empty");
+            let index = ready.swap_remove(position);
+            order.push(index);
+
+            for &dependent in &dependents[index] {
+                in_degree[dependent] -= 1;
+                if in_degree[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        if order.len() < self.nodes.len() {
+            let nodes = (0..self.nodes.len())
+                .filter(|&index| in_degree[index] > 0)
+                .map(|index| self.nodes[index].name().to_string())
+                .collect();
+            return Err(RenderGraphError::Cycle(nodes));
+        }
+
+        self.order = order;
+        Ok(())
+    }
+
+    /// `build` 로 정한 노드 이름 순서
+    pub fn execution_order(&self) -> Vec<&str> {
+        self.order
+            .iter()
+            .map(|&index| self.nodes[index].name())
+            .collect()
+    }
+
+    /// 필요한 텍스처를 만들고 노드를 정렬된 순서대로 같은 인코더에 기록한다.
+    ///
+    /// # Panics
+    ///
+    /// `build` 가 성공하기 전이나 그 뒤에 노드를 추가했으면 panic 한다.
+    pub fn execute(&mut self, encoder: &mut wgpu::CommandEncoder, gpu: &GpuContext) {
+        self.execute_nodes(encoder, gpu, None);
+    }
+
+    /// `execute` 와 같지만 노드마다 `execute` 에 걸린 CPU 시간을 노드 이름으로 `profiler` 에 기록한다.
+    pub fn execute_profiled(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        gpu: &GpuContext,
+        profiler: &mut RenderPassProfiler,
+    ) {
+        self.execute_nodes(encoder, gpu, Some(profiler));
+    }
+
+    fn execute_nodes(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        gpu: &GpuContext,
+        mut profiler: Option<&mut RenderPassProfiler>,
+    ) {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        assert!(
+            self.order.len() == self.nodes.len(),
+            "RenderGraph::build must succeed before execute"
+        );
+
+        let (width, height) = self.size;
+        for resource in &mut self.resources {
+            if let (Some(format), None) = (resource.format, &resource.view) {
+                let texture = device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some(&resource.label),
+                    size: wgpu::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                });
+                resource.view = Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+            }
+        }
+
+        let mut ctx = RenderGraphContext {
+            device,
+            queue,
+            encoder,
+            resources: &self.resources,
+        };
+        for &index in &self.order {
+            let node = &mut self.nodes[index];
+            if let Some(profiler) = profiler.as_deref_mut() {
+                profiler.begin(node.name());
+                node.execute(&mut ctx);
+                profiler.end();
+            } else {
+                node.execute(&mut ctx);
+            }
+        }
+    }
+
+    fn add_resource(&mut self, label: &str, format: Option<wgpu::TextureFormat>) -> ResourceId {
+        let id = ResourceId(self.resources.len() as u32);
+        self.resources.push(Resource {
+            label: label.to_string(),
+            format,
+            view: None,
+        });
+        id
+    }
+
+    fn label(&self, id: ResourceId) -> String {
+        self.resources[id.0 as usize].label.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+    struct TestNode {
+        name: &'static str,
+        inputs: Vec<ResourceId>,
+        outputs: Vec<ResourceId>,
+    }
+
+    impl TestNode {
+        fn new(name: &'static str, inputs: &[ResourceId], outputs: &[ResourceId]) -> Self {
+            Self {
+                name,
+                inputs: inputs.to_vec(),
+                outputs: outputs.to_vec(),
+            }
+        }
+    }
+
+    impl RenderGraphNode for TestNode {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn inputs(&self) -> &[ResourceId] {
+            &self.inputs
+        }
+
+        fn outputs(&self) -> &[ResourceId] {
+            &self.outputs
+        }
+
+        fn execute(&mut self, _ctx: &mut RenderGraphContext<'_>) {}
+    }
+
+    #[test]
+    fn orders_three_passes_by_dependency() {
+        let mut graph = RenderGraph::new(4, 4);
+        let shadow_map = graph.create_texture("shadow map", wgpu::TextureFormat::Depth32Float);
+        let scene = graph.create_texture("scene", FORMAT);
+        let surface = graph.import_texture("surface");
+
+        // 실행 순서와 반대로 추가한다
+        graph.add_node(TestNode::new("tonemap", &[scene], &[surface]));
+        graph.add_node(TestNode::new("geometry", &[shadow_map], &[scene]));
+        graph.add_node(TestNode::new("shadow", &[], &[shadow_map]));
+
+        graph.build().unwrap();
+        assert_eq!(graph.execution_order(), ["shadow", "geometry", "tonemap"]);
+    }
+
+    #[test]
+    fn keeps_insertion_order_for_independent_nodes() {
+        let mut graph = RenderGraph::new(4, 4);
+        let a = graph.create_texture("a", FORMAT);
+        let b = graph.create_texture("b", FORMAT);
+
+        graph.add_node(TestNode::new("first", &[], &[a]));
+        graph.add_node(TestNode::new("second", &[], &[b]));
+        graph.add_node(TestNode::new("combine", &[b, a], &[]));
+
+        graph.build().unwrap();
+        assert_eq!(graph.execution_order(), ["first", "second", "combine"]);
+    }
+
+    #[test]
+    fn external_inputs_need_no_producer() {
+        let mut graph = RenderGraph::new(4, 4);
+        let environment = graph.import_texture("environment");
+        let scene = graph.create_texture("scene", FORMAT);
+
+        graph.add_node(TestNode::new("sky", &[environment], &[scene]));
+        graph.build().unwrap();
+    }
+
+    #[test]
+    fn rejects_missing_producer() {
+        let mut graph = RenderGraph::new(4, 4);
+        let scene = graph.create_texture("scene", FORMAT);
+        graph.add_node(TestNode::new("tonemap", &[scene], &[]));
+
+        assert!(matches!(
+            graph.build(),
+            Err(RenderGraphError::MissingProducer { node, resource })
+                if node == "tonemap" && resource == "scene"
+        ));
+    }
+
+    #[test]
+    fn rejects_multiple_producers() {
+        let mut graph = RenderGraph::new(4, 4);
+        let scene = graph.create_texture("scene", FORMAT);
+        graph.add_node(TestNode::new("opaque", &[], &[scene]));
+        graph.add_node(TestNode::new("transparent", &[], &[scene]));
+
+        assert!(matches!(
+            graph.build(),
+            Err(RenderGraphError::MultipleProducers { first, second, .. })
+                if first == "opaque" && second == "transparent"
+        ));
+    }
+
+    #[test]
+    fn rejects_cycle() {
+        let mut graph = RenderGraph::new(4, 4);
+        let a = graph.create_texture("a", FORMAT);
+        let b = graph.create_texture("b", FORMAT);
+        let c = graph.create_texture("c", FORMAT);
+
+        graph.add_node(TestNode::new("source", &[], &[c]));
+        graph.add_node(TestNode::new("ping", &[b, c], &[a]));
+        graph.add_node(TestNode::new("pong", &[a], &[b]));
+
+        match graph.build() {
+            Err(RenderGraphError::Cycle(nodes)) => assert_eq!(nodes, ["ping", "pong"]),
+            other => panic!("expected a cycle, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+/// 메시를 면으로 채워서 그릴지, 선으로 그릴지
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RenderMode {
+    #[default]
+    Solid,
+    /// `wgpu::Features::POLYGON_MODE_LINE` 이 필요하다. WebGPU/WebGL 에서는 지원되지 않는다.
+    Wireframe,
+}
+
+impl RenderMode {
+    /// 와이어프레임 모드에 필요한 기능
+    pub const WIREFRAME_FEATURES: wgpu::Features = wgpu::Features::POLYGON_MODE_LINE;
+
+    pub fn polygon_mode(self) -> wgpu::PolygonMode {
+        match self {
+            RenderMode::Solid => wgpu::PolygonMode::Fill,
+            RenderMode::Wireframe => wgpu::PolygonMode::Line,
+        }
+    }
+
+    /// 디바이스가 이 모드로 파이프라인을 만들 수 있는지 여부
+    pub fn is_supported(self, device: &wgpu::Device) -> bool {
+        match self {
+            RenderMode::Solid => true,
+            RenderMode::Wireframe => device.features().contains(Self::WIREFRAME_FEATURES),
+        }
+    }
+}
@@ -0,0 +1,559 @@
+use crate::{Aabb, Frustum, GpuContext, Mesh};
+
+/// `RenderPassBuilder` 와 `DrawRecorder` 가 렌더 패스 안에서 순서대로 실행하는 명령
+#[derive(Clone)]
+pub enum DrawCommand {
+    SetPipeline(wgpu::RenderPipeline),
+    SetViewport {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        min_depth: f32,
+        max_depth: f32,
+    },
+    /// 바인드 그룹을 0번 그룹에 바인딩하고 메시를 그린다.
+    DrawMesh(Mesh, wgpu::BindGroup),
+    /// 미리 기록한 렌더 번들을 실행한다. 실행 뒤에는 파이프라인과 바인딩이 모두 풀린다.
+    ExecuteBundle(wgpu::RenderBundle),
+}
+
+/// 렌더 패스의 컬러 어태치먼트 하나.
+///
+/// `state` 는 이 타깃에 그리는 파이프라인의 `ColorTargetState` 로도 쓰이므로
+/// 패스와 파이프라인의 타깃 순서(`@location`)를 한 곳에서 맞출 수 있다.
+#[derive(Clone)]
+pub struct RenderTarget {
+    pub view: wgpu::TextureView,
+    pub resolve_target: Option<wgpu::TextureView>,
+    pub state: wgpu::ColorTargetState,
+    pub clear_color: wgpu::Color,
+}
+
+impl RenderTarget {
+    /// 블렌딩 없이 검은색으로 지워지는 타깃
+    pub fn new(view: &wgpu::TextureView, format: wgpu::TextureFormat) -> Self {
+        Self {
+            view: view.clone(),
+            resolve_target: None,
+            state: wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            },
+            clear_color: wgpu::Color::BLACK,
+        }
+    }
+
+    pub fn clear_color(mut self, color: wgpu::Color) -> Self {
+        self.clear_color = color;
+        self
+    }
+
+    pub fn blend(mut self, blend: wgpu::BlendState) -> Self {
+        self.state.blend = Some(blend);
+        self
+    }
+
+    /// `FragmentState::targets` 에 넘길 타깃 상태 목록
+    pub fn color_target_states(targets: &[RenderTarget]) -> Vec<Option<wgpu::ColorTargetState>> {
+        targets
+            .iter()
+            .map(|target| Some(target.state.clone()))
+            .collect()
+    }
+}
+
+// 패스를 기록할 때 필요한 부분만 들고 있는 컬러 어태치먼트
+struct ColorAttachment {
+    view: wgpu::TextureView,
+    resolve_target: Option<wgpu::TextureView>,
+    clear_color: wgpu::Color,
+}
+
+/// 한 프레임의 그리기 명령을 모아 두었다가 하나의 렌더 패스로 실행한다.
+///
+/// 커맨드 인코더를 직접 들고 있으며 `submit` 을 호출하거나 drop 될 때
+/// 렌더 패스를 기록하고 `finish` 한 뒤 큐에 제출한다.
+pub struct RenderPassBuilder {
+    encoder: Option<wgpu::CommandEncoder>,
+    queue: wgpu::Queue,
+    targets: Vec<ColorAttachment>,
+    depth: Option<wgpu::TextureView>,
+    frustum: Option<Frustum>,
+    culled_draws: u32,
+    commands: Vec<DrawCommand>,
+}
+
+impl RenderPassBuilder {
+    pub fn new(gpu: &GpuContext, target: &wgpu::TextureView) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let target = ColorAttachment {
+            view: target.clone(),
+            resolve_target: None,
+            clear_color: wgpu::Color::BLACK,
+        };
+        Self::with_attachments(device, queue, vec![target])
+    }
+
+    /// 여러 컬러 타깃에 동시에 그리는 패스. `targets` 의 순서가 `@location` 번호가 된다.
+    pub fn with_targets(gpu: &GpuContext, targets: &[RenderTarget]) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let targets = targets
+            .iter()
+            .map(|target| ColorAttachment {
+                view: target.view.clone(),
+                resolve_target: target.resolve_target.clone(),
+                clear_color: target.clear_color,
+            })
+            .collect();
+        Self::with_attachments(device, queue, targets)
+    }
+
+    fn with_attachments(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        targets: Vec<ColorAttachment>,
+    ) -> Self {
+        let encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Render Pass Builder Encoder"),
+        });
+
+        Self {
+            encoder: Some(encoder),
+            queue: queue.clone(),
+            targets,
+            depth: None,
+            frustum: None,
+            culled_draws: 0,
+            commands: Vec::new(),
+        }
+    }
+
+    /// 첫 번째 컬러 타깃을 지울 색
+    pub fn clear_color(mut self, color: wgpu::Color) -> Self {
+        if let Some(target) = self.targets.first_mut() {
+            target.clear_color = color;
+        }
+        self
+    }
+
+    /// MSAA 를 사용할 때 첫 번째 컬러 타깃을 리졸브할 텍스처
+    pub fn resolve_target(mut self, view: &wgpu::TextureView) -> Self {
+        if let Some(target) = self.targets.first_mut() {
+            target.resolve_target = Some(view.clone());
+        }
+        self
+    }
+
+    /// 매 패스 1.0 으로 지워지는 깊이 어태치먼트
+    pub fn depth(mut self, view: &wgpu::TextureView) -> Self {
+        self.depth = Some(view.clone());
+        self
+    }
+
+    /// `draw_mesh_bounded` 로 그리는 메시를 이 절두체로 컬링한다.
+    pub fn frustum(mut self, frustum: Frustum) -> Self {
+        self.frustum = Some(frustum);
+        self
+    }
+
+    pub fn push(&mut self, command: DrawCommand) -> &mut Self {
+        self.commands.push(command);
+        self
+    }
+
+    pub fn set_pipeline(&mut self, pipeline: &wgpu::RenderPipeline) -> &mut Self {
+        self.push(DrawCommand::SetPipeline(pipeline.clone()))
+    }
+
+    pub fn set_viewport(&mut self, x: f32, y: f32, width: f32, height: f32) -> &mut Self {
+        self.push(DrawCommand::SetViewport {
+            x,
+            y,
+            width,
+            height,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })
+    }
+
+    pub fn draw_mesh(&mut self, mesh: &Mesh, bind_group: &wgpu::BindGroup) -> &mut Self {
+        self.push(DrawCommand::DrawMesh(mesh.clone(), bind_group.clone()))
+    }
+
+    pub fn execute_bundle(&mut self, bundle: &wgpu::RenderBundle) -> &mut Self {
+        self.push(DrawCommand::ExecuteBundle(bundle.clone()))
+    }
+
+    /// `bounds` 가 절두체 밖에 있으면 그리기 명령을 기록하지 않고 컬링 수만 센다.
+    /// 절두체를 설정하지 않았으면 `draw_mesh` 와 같다.
+    pub fn draw_mesh_bounded(
+        &mut self,
+        mesh: &Mesh,
+        bind_group: &wgpu::BindGroup,
+        bounds: &Aabb,
+    ) -> &mut Self {
+        if let Some(frustum) = &self.frustum
+            && !frustum.test_aabb(bounds)
+        {
+            self.culled_draws += 1;
+            return self;
+        }
+        self.draw_mesh(mesh, bind_group)
+    }
+
+    /// 지금까지 절두체 밖이라서 건너뛴 그리기 수
+    pub fn culled_draws(&self) -> u32 {
+        self.culled_draws
+    }
+
+    /// 명령 버퍼가 제출되기 전에 인코더에 다른 명령(복사, 컴퓨트 패스 등)을 기록할 때 사용한다.
+    /// 여기서 기록한 명령은 렌더 패스보다 먼저 실행된다.
+    pub fn encoder(&mut self) -> &mut wgpu::CommandEncoder {
+        self.encoder.as_mut().expect("encoder already submitted")
+    }
+
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    pub fn submit(mut self) {
+        self.finish_and_submit();
+    }
+
+    fn finish_and_submit(&mut self) {
+        let Some(mut encoder) = self.encoder.take() else {
+            return;
+        };
+
+        {
+            let color_attachments: Vec<_> = self
+                .targets
+                .iter()
+                .map(|target| {
+                    Some(wgpu::RenderPassColorAttachment {
+                        view: &target.view,
+                        resolve_target: target.resolve_target.as_ref(),
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(target.clear_color),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })
+                })
+                .collect();
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass Builder"),
+                color_attachments: &color_attachments,
+                depth_stencil_attachment: self.depth.as_ref().map(|view| {
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(1.0),
+                            store: wgpu::StoreOp::Store,
+                        }),
+                        stencil_ops: None,
+                    }
+                }),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            for command in &self.commands {
+                execute(&mut render_pass, command);
+            }
+        }
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+    }
+}
+
+impl Drop for RenderPassBuilder {
+    fn drop(&mut self) {
+        self.finish_and_submit();
+    }
+}
+
+/// 렌더 패스 없이 `DrawCommand` 를 모아 두었다가 `flush` 때 한 번에 실행한다.
+///
+/// 기록하는 동안 인코더나 렌더 패스를 빌리지 않으므로, 장면의 부분마다 따로 만든
+/// 레코더를 `append` 로 이어 붙여서 하나의 패스로 그릴 수 있다.
+#[derive(Clone, Default)]
+pub struct DrawRecorder {
+    commands: Vec<DrawCommand>,
+}
+
+impl DrawRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, command: DrawCommand) -> &mut Self {
+        self.commands.push(command);
+        self
+    }
+
+    pub fn set_pipeline(&mut self, pipeline: &wgpu::RenderPipeline) -> &mut Self {
+        self.push(DrawCommand::SetPipeline(pipeline.clone()))
+    }
+
+    pub fn set_viewport(&mut self, x: f32, y: f32, width: f32, height: f32) -> &mut Self {
+        self.push(DrawCommand::SetViewport {
+            x,
+            y,
+            width,
+            height,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })
+    }
+
+    pub fn draw_mesh(&mut self, mesh: &Mesh, bind_group: &wgpu::BindGroup) -> &mut Self {
+        self.push(DrawCommand::DrawMesh(mesh.clone(), bind_group.clone()))
+    }
+
+    pub fn execute_bundle(&mut self, bundle: &wgpu::RenderBundle) -> &mut Self {
+        self.push(DrawCommand::ExecuteBundle(bundle.clone()))
+    }
+
+    /// 다른 레코더의 명령을 이 레코더 뒤에 이어 붙인다.
+    pub fn append(&mut self, mut other: DrawRecorder) -> &mut Self {
+        self.commands.append(&mut other.commands);
+        self
+    }
+
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// 같은 파이프라인을 쓰는 그리기끼리 모아서 `SetPipeline` 횟수를 줄인다.
+    ///
+    /// 뷰포트를 바꾸거나 번들을 실행하는 명령은 경계로 남겨 두고 그 사이의 그리기만 정렬한다.
+    /// 같은 파이프라인의 그리기는 기록한 순서를 유지하며, 마지막 그리기 뒤의 상태 변경은 버린다.
+    pub fn sort_by_pipeline(&mut self) {
+        let mut sorted = Vec::with_capacity(self.commands.len());
+        let mut segment: Vec<(Option<wgpu::RenderPipeline>, DrawCommand)> = Vec::new();
+        let mut pipeline = None;
+        let mut bound_pipeline = None;
+
+        for command in self.commands.drain(..) {
+            match command {
+                DrawCommand::SetPipeline(next) => pipeline = Some(next),
+                DrawCommand::SetViewport { .. } => {
+                    flush_segment(&mut segment, &mut bound_pipeline, &mut sorted);
+                    sorted.push(command);
+                }
+                DrawCommand::DrawMesh(..) => segment.push((pipeline.clone(), command)),
+                DrawCommand::ExecuteBundle(_) => {
+                    flush_segment(&mut segment, &mut bound_pipeline, &mut sorted);
+                    sorted.push(command);
+                    // 번들이 끝나면 파이프라인이 풀리므로 다음 그리기 앞에 다시 넣어야 한다
+                    bound_pipeline = None;
+                }
+            }
+        }
+        flush_segment(&mut segment, &mut bound_pipeline, &mut sorted);
+
+        self.commands = sorted;
+    }
+
+    /// 기록한 명령을 이미 시작된 렌더 패스에 실행한다.
+    pub fn replay(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        for command in &self.commands {
+            execute(render_pass, command);
+        }
+    }
+
+    /// `pass_desc` 로 렌더 패스를 시작해서 기록한 명령을 순서대로 실행하고 비운다.
+    pub fn flush(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        pass_desc: &wgpu::RenderPassDescriptor<'_>,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(pass_desc);
+        self.replay(&mut render_pass);
+        self.commands.clear();
+    }
+}
+
+// 정렬한 그리기를 내보내면서 파이프라인이 바뀔 때만 SetPipeline 을 넣는다.
+// 파이프라인 없이 기록된 그리기는 None 이 가장 앞에 정렬되므로 먼저 나온다
+fn flush_segment(
+    segment: &mut Vec<(Option<wgpu::RenderPipeline>, DrawCommand)>,
+    bound_pipeline: &mut Option<wgpu::RenderPipeline>,
+    sorted: &mut Vec<DrawCommand>,
+) {
+    segment.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (pipeline, command) in segment.drain(..) {
+        if let Some(pipeline) = pipeline
+            && bound_pipeline.as_ref() != Some(&pipeline)
+        {
+            sorted.push(DrawCommand::SetPipeline(pipeline.clone()));
+            *bound_pipeline = Some(pipeline);
+        }
+        sorted.push(command);
+    }
+}
+
+fn execute(render_pass: &mut wgpu::RenderPass<'_>, command: &DrawCommand) {
+    match command {
+        DrawCommand::SetPipeline(pipeline) => render_pass.set_pipeline(pipeline),
+        DrawCommand::SetViewport {
+            x,
+            y,
+            width,
+            height,
+            min_depth,
+            max_depth,
+        } => render_pass.set_viewport(*x, *y, *width, *height, *min_depth, *max_depth),
+        DrawCommand::DrawMesh(mesh, bind_group) => {
+            render_pass.set_bind_group(0, bind_group, &[]);
+            mesh.draw(render_pass);
+        }
+        DrawCommand::ExecuteBundle(bundle) => render_pass.execute_bundles(std::iter::once(bundle)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::*;
+    use crate::PositionNormalUvVertex;
+
+    // 비교하기 쉽게 명령을 줄인 것. 그리기는 바인드 그룹의 번호로 구분한다
+    #[derive(Debug, PartialEq)]
+    enum Step {
+        Pipeline(wgpu::RenderPipeline),
+        Viewport,
+        Draw(usize),
+    }
+
+    fn pipeline(device: &wgpu::Device) -> wgpu::RenderPipeline {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(
+                "@vertex fn vs_main() -> @builtin(position) vec4<f32> { return vec4<f32>(0.0); }
+                 @fragment fn fs_main() -> @location(0) vec4<f32> { return vec4<f32>(1.0); }"
+                    .into(),
+            ),
+        });
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        })
+    }
+
+    #[test]
+    #[cfg_attr(not(wgpu_tests), ignore = "needs a GPU adapter, set WGPU_TESTS=1")]
+    fn sort_by_pipeline_groups_draws_and_keeps_order() {
+        let Some((device, queue)) = crate::test_gpu::device() else {
+            return;
+        };
+        let gpu = GpuContext::new(device, queue);
+        let device = gpu.device();
+
+        // 정렬 순서는 파이프라인 객체의 순서를 따르므로 a < b 가 되게 한다
+        let mut pipelines = [pipeline(device), pipeline(device)];
+        pipelines.sort();
+        let [a, b] = pipelines;
+        let mesh = Mesh::new(&gpu, &[PositionNormalUvVertex::zeroed(); 3], &[0u16, 1, 2]);
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[],
+        });
+        let bind_groups: Vec<wgpu::BindGroup> = (0..8)
+            .map(|_| {
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &layout,
+                    entries: &[],
+                })
+            })
+            .collect();
+
+        let mut recorder = DrawRecorder::new();
+        recorder
+            .set_pipeline(&a)
+            .draw_mesh(&mesh, &bind_groups[0])
+            .set_pipeline(&b)
+            .draw_mesh(&mesh, &bind_groups[1])
+            .set_pipeline(&a)
+            .draw_mesh(&mesh, &bind_groups[2])
+            .set_pipeline(&b)
+            .draw_mesh(&mesh, &bind_groups[3])
+            .draw_mesh(&mesh, &bind_groups[4])
+            .set_viewport(0.0, 0.0, 16.0, 16.0)
+            .set_pipeline(&a)
+            .draw_mesh(&mesh, &bind_groups[5])
+            .set_pipeline(&b)
+            .draw_mesh(&mesh, &bind_groups[6])
+            .set_pipeline(&a)
+            .draw_mesh(&mesh, &bind_groups[7])
+            .set_pipeline(&b);
+        recorder.sort_by_pipeline();
+
+        let steps: Vec<Step> = recorder
+            .commands()
+            .iter()
+            .map(|command| match command {
+                DrawCommand::SetPipeline(pipeline) => Step::Pipeline(pipeline.clone()),
+                DrawCommand::SetViewport { .. } => Step::Viewport,
+                DrawCommand::DrawMesh(_, bind_group) => Step::Draw(
+                    bind_groups
+                        .iter()
+                        .position(|other| other == bind_group)
+                        .unwrap(),
+                ),
+                DrawCommand::ExecuteBundle(_) => unreachable!("no bundles were recorded"),
+            })
+            .collect();
+
+        // 뷰포트 앞뒤로 파이프라인별로 묶이고, 묶음 안에서는 기록한 순서를 지킨다.
+        // 마지막 그리기 뒤의 SetPipeline 은 버린다
+        assert_eq!(
+            steps,
+            [
+                Step::Pipeline(a.clone()),
+                Step::Draw(0),
+                Step::Draw(2),
+                Step::Pipeline(b.clone()),
+                Step::Draw(1),
+                Step::Draw(3),
+                Step::Draw(4),
+                Step::Viewport,
+                Step::Pipeline(a),
+                Step::Draw(5),
+                Step::Draw(7),
+                Step::Pipeline(b),
+                Step::Draw(6),
+            ]
+        );
+    }
+}
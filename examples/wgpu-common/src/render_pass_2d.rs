@@ -0,0 +1,346 @@
+use std::ops::Range;
+
+use glam::Mat4;
+
+use crate::{
+    BindGroupBuilder, CameraUniform, GpuContext, Texture, UniformBuffer, Vertex, VertexBuffer,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Vertex2D {
+    /// 왼쪽 위가 (0, 0) 인 픽셀 좌표
+    pub position: [f32; 2],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+}
+
+impl Vertex for Vertex2D {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4];
+}
+
+/// `RenderPass2D::add_texture` 로 등록한 텍스처
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextureHandle(usize);
+
+// 단색 도형이 쓰는 흰색 1x1 텍스처
+const WHITE_TEXTURE: TextureHandle = TextureHandle(0);
+
+/// 행렬을 다루지 않고 픽셀 좌표로 사각형과 원을 그리는 2D 렌더러.
+///
+/// 캔버스 왼쪽 위가 (0, 0), 오른쪽 아래가 (width, height) 이다.
+/// 한 프레임 동안 `draw_*` 로 도형을 쌓고 `flush` 로 버텍스 버퍼에 한 번에 올려서
+/// 기존 내용 위에 알파 블렌딩으로 덧그린다. 드로우 콜은 텍스처가 바뀔 때만 나뉜다.
+pub struct RenderPass2D {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    shapes: Shapes,
+    vertex_buffer: VertexBuffer<Vertex2D>,
+    // GPU 버퍼에 담을 수 있는 버텍스 수
+    capacity: usize,
+    pipeline: wgpu::RenderPipeline,
+    screen: UniformBuffer<CameraUniform>,
+    // TextureHandle 의 번호 순서
+    texture_bind_groups: Vec<wgpu::BindGroup>,
+}
+
+impl RenderPass2D {
+    pub fn new(gpu: &GpuContext, color_format: wgpu::TextureFormat) -> Self {
+        let device = gpu.device();
+        let capacity = 1024;
+        let screen = UniformBuffer::new(
+            gpu,
+            &CameraUniform::new(Mat4::IDENTITY),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        let white = Texture::from_image(
+            gpu,
+            &image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+                1,
+                1,
+                image::Rgba([255; 4]),
+            )),
+            "2D White Texture",
+        );
+        let (texture_layout, white_bind_group) = texture_bind_group(device, &white);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("2D Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("render_pass_2d.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("2D Pipeline Layout"),
+            bind_group_layouts: &[screen.bind_group_layout(), &texture_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("2D Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[Vertex2D::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            // y 축이 아래로 향해서 감는 순서가 뒤집히므로 컬링하지 않는다
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            device: device.clone(),
+            queue: gpu.queue().clone(),
+            shapes: Shapes::default(),
+            vertex_buffer: create_vertex_buffer(device, capacity),
+            capacity,
+            pipeline,
+            screen,
+            texture_bind_groups: vec![white_bind_group],
+        }
+    }
+
+    /// `draw_textured_rect` 에 쓸 텍스처를 등록한다.
+    pub fn add_texture(&mut self, texture: &Texture) -> TextureHandle {
+        let (_, bind_group) = texture_bind_group(&self.device, texture);
+        self.texture_bind_groups.push(bind_group);
+        TextureHandle(self.texture_bind_groups.len() - 1)
+    }
+
+    /// 이번 프레임에 쌓인 버텍스 수
+    pub fn vertex_count(&self) -> usize {
+        self.shapes.vertices.len()
+    }
+
+    /// 왼쪽 위 모서리가 (`x`, `y`) 인 단색 사각형
+    pub fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: [f32; 4]) {
+        self.shapes
+            .push(WHITE_TEXTURE, rect_vertices(x, y, w, h, color));
+    }
+
+    /// 텍스처 전체를 늘려서 채운 사각형
+    pub fn draw_textured_rect(&mut self, x: f32, y: f32, w: f32, h: f32, texture: TextureHandle) {
+        assert!(
+            texture.0 < self.texture_bind_groups.len(),
+            "texture {:?} was not added to this RenderPass2D",
+            texture
+        );
+        self.shapes
+            .push(texture, rect_vertices(x, y, w, h, [1.0; 4]));
+    }
+
+    /// `segments` 개의 삼각형으로 나눈 단색 원. 3 보다 작으면 3 으로 그린다
+    pub fn draw_circle(&mut self, cx: f32, cy: f32, r: f32, color: [f32; 4], segments: u32) {
+        self.shapes
+            .push(WHITE_TEXTURE, circle_vertices(cx, cy, r, color, segments));
+    }
+
+    /// 쌓인 도형을 올려서 `size` 픽셀 크기의 `view` 위에 그리고 비운다.
+    /// 용량이 부족하면 버텍스 버퍼를 두 배씩 키워서 다시 만든다.
+    pub fn flush(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        size: (u32, u32),
+    ) {
+        if self.shapes.vertices.is_empty() {
+            return;
+        }
+
+        let vertices = &self.shapes.vertices;
+        if vertices.len() > self.capacity {
+            self.capacity = vertices.len().next_power_of_two();
+            self.vertex_buffer = create_vertex_buffer(&self.device, self.capacity);
+        }
+        self.vertex_buffer.write(&self.queue, vertices);
+        self.screen.update(
+            &self.queue,
+            &CameraUniform::new(screen_projection(size.0, size.1)),
+        );
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("2D Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, self.screen.bind_group(), &[]);
+        self.vertex_buffer.bind(&mut render_pass, 0);
+        for (texture, range) in &self.shapes.batches {
+            render_pass.set_bind_group(1, &self.texture_bind_groups[texture.0], &[]);
+            render_pass.draw(range.clone(), 0..1);
+        }
+        drop(render_pass);
+
+        self.shapes.clear();
+    }
+}
+
+// 왼쪽 위가 (0, 0), 오른쪽 아래가 (width, height) 인 픽셀 좌표를 클립 공간으로 옮긴다
+fn screen_projection(width: u32, height: u32) -> Mat4 {
+    Mat4::orthographic_rh(0.0, width as f32, height as f32, 0.0, -1.0, 1.0)
+}
+
+// 한 프레임 동안 쌓인 버텍스와 텍스처가 같은 연속 구간
+#[derive(Default)]
+struct Shapes {
+    vertices: Vec<Vertex2D>,
+    batches: Vec<(TextureHandle, Range<u32>)>,
+}
+
+impl Shapes {
+    // 직전 도형과 텍스처가 같으면 드로우 콜을 이어 붙인다
+    fn push(&mut self, texture: TextureHandle, vertices: impl IntoIterator<Item = Vertex2D>) {
+        let start = self.vertices.len() as u32;
+        self.vertices.extend(vertices);
+        let end = self.vertices.len() as u32;
+
+        match self.batches.last_mut() {
+            Some((last, range)) if *last == texture => range.end = end,
+            _ => self.batches.push((texture, start..end)),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.vertices.clear();
+        self.batches.clear();
+    }
+}
+
+fn rect_vertices(x: f32, y: f32, w: f32, h: f32, color: [f32; 4]) -> [Vertex2D; 6] {
+    let corner = |u: f32, v: f32| Vertex2D {
+        position: [x + w * u, y + h * v],
+        uv: [u, v],
+        color,
+    };
+    let [top_left, top_right, bottom_right, bottom_left] = [
+        corner(0.0, 0.0),
+        corner(1.0, 0.0),
+        corner(1.0, 1.0),
+        corner(0.0, 1.0),
+    ];
+    [
+        top_left,
+        bottom_left,
+        bottom_right,
+        bottom_right,
+        top_right,
+        top_left,
+    ]
+}
+
+// 중심과 둘레의 두 점으로 된 삼각형을 segments 개 만든다
+fn circle_vertices(cx: f32, cy: f32, r: f32, color: [f32; 4], segments: u32) -> Vec<Vertex2D> {
+    let segments = segments.max(3);
+    let point = |i: u32| {
+        let angle = i as f32 / segments as f32 * std::f32::consts::TAU;
+        let (sin, cos) = angle.sin_cos();
+        Vertex2D {
+            position: [cx + r * cos, cy + r * sin],
+            uv: [0.5 + 0.5 * cos, 0.5 + 0.5 * sin],
+            color,
+        }
+    };
+    let center = Vertex2D {
+        position: [cx, cy],
+        uv: [0.5, 0.5],
+        color,
+    };
+
+    (0..segments)
+        .flat_map(|i| [center, point(i), point(i + 1)])
+        .collect()
+}
+
+fn texture_bind_group(
+    device: &wgpu::Device,
+    texture: &Texture,
+) -> (wgpu::BindGroupLayout, wgpu::BindGroup) {
+    BindGroupBuilder::new()
+        .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+        .sampler(1, texture.sampler(), wgpu::ShaderStages::FRAGMENT)
+        .build(device, "2D Texture Bind Group")
+}
+
+fn create_vertex_buffer(device: &wgpu::Device, capacity: usize) -> VertexBuffer<Vertex2D> {
+    VertexBuffer::writable(device, &vec![bytemuck::Zeroable::zeroed(); capacity])
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec3;
+
+    use super::*;
+
+    #[test]
+    fn screen_projection_puts_origin_top_left() {
+        let projection = screen_projection(800, 600);
+        let top_left = projection.project_point3(Vec3::ZERO);
+        let bottom_right = projection.project_point3(Vec3::new(800.0, 600.0, 0.0));
+
+        assert!(top_left.abs_diff_eq(Vec3::new(-1.0, 1.0, 0.5), 1e-6));
+        assert!(bottom_right.abs_diff_eq(Vec3::new(1.0, -1.0, 0.5), 1e-6));
+    }
+
+    #[test]
+    fn rect_covers_pixel_bounds() {
+        let vertices = rect_vertices(10.0, 20.0, 30.0, 40.0, [1.0; 4]);
+        let xs = vertices.map(|vertex| vertex.position[0]);
+        let ys = vertices.map(|vertex| vertex.position[1]);
+
+        assert_eq!(xs.iter().cloned().fold(f32::MAX, f32::min), 10.0);
+        assert_eq!(xs.iter().cloned().fold(f32::MIN, f32::max), 40.0);
+        assert_eq!(ys.iter().cloned().fold(f32::MAX, f32::min), 20.0);
+        assert_eq!(ys.iter().cloned().fold(f32::MIN, f32::max), 60.0);
+    }
+
+    #[test]
+    fn circle_clamps_segments() {
+        assert_eq!(circle_vertices(0.0, 0.0, 1.0, [1.0; 4], 16).len(), 48);
+        assert_eq!(circle_vertices(0.0, 0.0, 1.0, [1.0; 4], 1).len(), 9);
+    }
+
+    #[test]
+    fn batches_split_only_on_texture_change() {
+        let mut shapes = Shapes::default();
+        let other = TextureHandle(1);
+        shapes.push(WHITE_TEXTURE, rect_vertices(0.0, 0.0, 1.0, 1.0, [1.0; 4]));
+        shapes.push(WHITE_TEXTURE, circle_vertices(0.0, 0.0, 1.0, [1.0; 4], 4));
+        shapes.push(other, rect_vertices(0.0, 0.0, 1.0, 1.0, [1.0; 4]));
+        shapes.push(WHITE_TEXTURE, rect_vertices(0.0, 0.0, 1.0, 1.0, [1.0; 4]));
+
+        assert_eq!(
+            shapes.batches,
+            vec![
+                (WHITE_TEXTURE, 0..18),
+                (other, 18..24),
+                (WHITE_TEXTURE, 24..30)
+            ]
+        );
+    }
+}
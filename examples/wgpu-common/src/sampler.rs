@@ -0,0 +1,182 @@
+/// `wgpu::SamplerDescriptor` 를 메서드 체인으로 채워서 샘플러를 만든다.
+///
+/// ```ignore
+/// let sampler = SamplerBuilder::linear()
+///     .address_mode(
+///         wgpu::AddressMode::Repeat,
+///         wgpu::AddressMode::Repeat,
+///         wgpu::AddressMode::Repeat,
+///     )
+///     .anisotropy(16)
+///     .build(&device);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SamplerBuilder<'a> {
+    descriptor: wgpu::SamplerDescriptor<'a>,
+}
+
+impl Default for SamplerBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> SamplerBuilder<'a> {
+    /// `wgpu::SamplerDescriptor::default()` 와 같다. 모든 필터가 `Nearest` 이다.
+    pub fn new() -> Self {
+        Self {
+            descriptor: wgpu::SamplerDescriptor::default(),
+        }
+    }
+
+    /// 가장자리에서 늘어나고 밉맵 사이까지 선형 보간하는 일반 텍스처용 샘플러
+    pub fn linear() -> Self {
+        Self::new().filter(
+            wgpu::FilterMode::Linear,
+            wgpu::FilterMode::Linear,
+            wgpu::FilterMode::Linear,
+        )
+    }
+
+    /// 보간 없이 가장 가까운 텍셀을 읽는 픽셀 아트나 데이터 텍스처용 샘플러
+    pub fn nearest() -> Self {
+        Self::new()
+    }
+
+    /// 그림자 맵을 `textureSampleCompare` 로 읽는 비교 샘플러.
+    /// 선형 필터를 쓰므로 주변 텍셀의 비교 결과가 섞여 가장자리가 부드러워진다.
+    pub fn shadow() -> Self {
+        Self::new()
+            .filter(
+                wgpu::FilterMode::Linear,
+                wgpu::FilterMode::Linear,
+                wgpu::FilterMode::Nearest,
+            )
+            .compare(wgpu::CompareFunction::LessEqual)
+    }
+
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.descriptor.label = Some(label);
+        self
+    }
+
+    /// 축소, 확대, 밉맵 사이의 필터
+    pub fn filter(
+        mut self,
+        min: wgpu::FilterMode,
+        mag: wgpu::FilterMode,
+        mip: wgpu::FilterMode,
+    ) -> Self {
+        self.descriptor.min_filter = min;
+        self.descriptor.mag_filter = mag;
+        self.descriptor.mipmap_filter = mip;
+        self
+    }
+
+    pub fn address_mode(
+        mut self,
+        u: wgpu::AddressMode,
+        v: wgpu::AddressMode,
+        w: wgpu::AddressMode,
+    ) -> Self {
+        self.descriptor.address_mode_u = u;
+        self.descriptor.address_mode_v = v;
+        self.descriptor.address_mode_w = w;
+        self
+    }
+
+    /// 비등방성 필터링의 최대 샘플 수. 1 이면 끈다.
+    /// 1 보다 크면 세 필터가 모두 `Linear` 여야 샘플러를 만들 수 있다.
+    pub fn anisotropy(mut self, max: u8) -> Self {
+        self.descriptor.anisotropy_clamp = max.max(1) as u16;
+        self
+    }
+
+    pub fn compare(mut self, func: wgpu::CompareFunction) -> Self {
+        self.descriptor.compare = Some(func);
+        self
+    }
+
+    /// `build` 에 넘길 디스크립터
+    pub fn descriptor(&self) -> &wgpu::SamplerDescriptor<'a> {
+        &self.descriptor
+    }
+
+    pub fn build(&self, device: &wgpu::Device) -> wgpu::Sampler {
+        device.create_sampler(&self.descriptor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_clamped(descriptor: &wgpu::SamplerDescriptor) {
+        assert_eq!(descriptor.address_mode_u, wgpu::AddressMode::ClampToEdge);
+        assert_eq!(descriptor.address_mode_v, wgpu::AddressMode::ClampToEdge);
+        assert_eq!(descriptor.address_mode_w, wgpu::AddressMode::ClampToEdge);
+    }
+
+    #[test]
+    fn linear_filters_everything_linearly() {
+        let builder = SamplerBuilder::linear();
+        let descriptor = builder.descriptor();
+
+        assert_clamped(descriptor);
+        assert_eq!(descriptor.min_filter, wgpu::FilterMode::Linear);
+        assert_eq!(descriptor.mag_filter, wgpu::FilterMode::Linear);
+        assert_eq!(descriptor.mipmap_filter, wgpu::FilterMode::Linear);
+        assert_eq!(descriptor.anisotropy_clamp, 1);
+        assert_eq!(descriptor.compare, None);
+    }
+
+    #[test]
+    fn nearest_filters_everything_nearest() {
+        let builder = SamplerBuilder::nearest();
+        let descriptor = builder.descriptor();
+
+        assert_clamped(descriptor);
+        assert_eq!(descriptor.min_filter, wgpu::FilterMode::Nearest);
+        assert_eq!(descriptor.mag_filter, wgpu::FilterMode::Nearest);
+        assert_eq!(descriptor.mipmap_filter, wgpu::FilterMode::Nearest);
+        assert_eq!(descriptor.anisotropy_clamp, 1);
+        assert_eq!(descriptor.compare, None);
+    }
+
+    #[test]
+    fn shadow_compares_with_less_equal() {
+        let builder = SamplerBuilder::shadow();
+        let descriptor = builder.descriptor();
+
+        assert_clamped(descriptor);
+        assert_eq!(descriptor.min_filter, wgpu::FilterMode::Linear);
+        assert_eq!(descriptor.mag_filter, wgpu::FilterMode::Linear);
+        assert_eq!(descriptor.mipmap_filter, wgpu::FilterMode::Nearest);
+        assert_eq!(descriptor.compare, Some(wgpu::CompareFunction::LessEqual));
+    }
+
+    #[test]
+    fn setters_override_preset() {
+        let builder = SamplerBuilder::linear()
+            .label("Test Sampler")
+            .address_mode(
+                wgpu::AddressMode::Repeat,
+                wgpu::AddressMode::MirrorRepeat,
+                wgpu::AddressMode::ClampToEdge,
+            )
+            .anisotropy(16);
+        let descriptor = builder.descriptor();
+
+        assert_eq!(descriptor.label, Some("Test Sampler"));
+        assert_eq!(descriptor.address_mode_u, wgpu::AddressMode::Repeat);
+        assert_eq!(descriptor.address_mode_v, wgpu::AddressMode::MirrorRepeat);
+        assert_eq!(descriptor.address_mode_w, wgpu::AddressMode::ClampToEdge);
+        assert_eq!(descriptor.anisotropy_clamp, 16);
+    }
+
+    #[test]
+    fn anisotropy_zero_is_disabled() {
+        let builder = SamplerBuilder::linear().anisotropy(0);
+        assert_eq!(builder.descriptor().anisotropy_clamp, 1);
+    }
+}
@@ -0,0 +1,118 @@
+use std::cell::Cell;
+
+use glam::Mat4;
+
+use crate::Transform;
+
+/// `SceneGraph` 안의 노드를 가리키는 인덱스
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// 부모를 기준으로 한 로컬 변환과 부모/자식 연결을 가진 노드
+#[derive(Clone, Debug)]
+pub struct SceneNode {
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    local: Transform,
+    // 마지막으로 계산한 월드 행렬. None 이면 다음 접근 때 다시 계산한다
+    world: Cell<Option<Mat4>>,
+}
+
+impl SceneNode {
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    pub fn local_transform(&self) -> &Transform {
+        &self.local
+    }
+}
+
+/// 노드를 `Vec` 에 모아 두고 `NodeId` 로 부모-자식 관계를 잇는 씬 그래프.
+///
+/// 노드의 로컬 변환을 바꾸면 그 노드와 모든 자손의 월드 행렬이 무효화되고,
+/// `world_transform` 으로 다시 읽을 때 루트까지 올라가며 계산한다.
+#[derive(Clone, Debug, Default)]
+pub struct SceneGraph {
+    nodes: Vec<SceneNode>,
+}
+
+impl SceneGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `parent` 가 `None` 이면 루트 노드가 된다.
+    ///
+    /// # Panics
+    ///
+    /// `parent` 가 이 그래프의 노드가 아니면 panic 한다.
+    pub fn add(&mut self, parent: Option<NodeId>, local: Transform) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        if let Some(parent) = parent {
+            self.nodes[parent.0].children.push(id);
+        }
+
+        self.nodes.push(SceneNode {
+            parent,
+            children: Vec::new(),
+            local,
+            world: Cell::new(None),
+        });
+        id
+    }
+
+    pub fn node(&self, id: NodeId) -> &SceneNode {
+        &self.nodes[id.0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn local_transform(&self, id: NodeId) -> &Transform {
+        &self.nodes[id.0].local
+    }
+
+    /// 로컬 변환을 바꾸고 이 노드와 자손의 월드 행렬을 무효화한다.
+    pub fn set_local_transform(&mut self, id: NodeId, local: Transform) {
+        self.nodes[id.0].local = local;
+        self.invalidate(id);
+    }
+
+    /// 루트부터 이 노드까지의 로컬 변환을 곱한 월드 행렬.
+    /// 무효화되지 않은 조상의 행렬은 다시 계산하지 않는다.
+    pub fn world_transform(&self, id: NodeId) -> Mat4 {
+        let node = &self.nodes[id.0];
+        if let Some(world) = node.world.get() {
+            return world;
+        }
+
+        let local = node.local.matrix();
+        let world = match node.parent {
+            Some(parent) => self.world_transform(parent) * local,
+            None => local,
+        };
+        node.world.set(Some(world));
+        world
+    }
+
+    fn invalidate(&self, id: NodeId) {
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id.0];
+            // 월드 행렬은 조상부터 계산되므로, 캐시가 없는 노드의 자손도 캐시가 없다
+            if node.world.take().is_some() {
+                stack.extend_from_slice(&node.children);
+            }
+        }
+    }
+}
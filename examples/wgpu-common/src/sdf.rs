@@ -0,0 +1,157 @@
+// 아직 가장 가까운 점을 찾지 못한 칸. 제곱해도 i32 를 넘지 않는 크기다
+const FAR: (i32, i32) = (9999, 9999);
+
+/// 커버리지 비트맵으로 부호 있는 거리장(SDF)을 만들어 `R8Unorm` 텍셀로 반환한다.
+///
+/// 커버리지가 절반 이상인 픽셀을 안쪽으로 보고, 8SSEDT(8 이웃 순차 유클리드 거리 변환)로
+/// 안쪽과 바깥쪽 각각에서 가장 가까운 반대편 픽셀까지의 거리를 구한다.
+/// 가장자리가 잘리지 않도록 사방에 `spread` 픽셀씩 여백을 두므로 결과의 크기는
+/// `(width + 2 * spread, height + 2 * spread)` 이다.
+///
+/// 값은 경계가 0.5 (128 근처), 안쪽이 더 크고, 경계에서 `spread` 픽셀 떨어지면 0 또는 1 이 된다.
+pub fn signed_distance_field(coverage: &[u8], width: u32, height: u32, spread: u32) -> Vec<u8> {
+    assert_eq!(coverage.len(), (width * height) as usize);
+
+    let padded_width = (width + 2 * spread) as usize;
+    let padded_height = (height + 2 * spread) as usize;
+
+    let mut inside = vec![false; padded_width * padded_height];
+    for y in 0..height as usize {
+        for x in 0..width as usize {
+            let padded = (y + spread as usize) * padded_width + x + spread as usize;
+            inside[padded] = coverage[y * width as usize + x] >= 128;
+        }
+    }
+
+    // 바깥 픽셀은 가장 가까운 안쪽 픽셀까지, 안쪽 픽셀은 가장 가까운 바깥 픽셀까지의 거리
+    let to_inside = distance_transform(&inside, padded_width, padded_height, true);
+    let to_outside = distance_transform(&inside, padded_width, padded_height, false);
+
+    let range = 2.0 * spread.max(1) as f32;
+    to_inside
+        .iter()
+        .zip(&to_outside)
+        .map(|(outside_distance, inside_distance)| {
+            let signed = inside_distance - outside_distance;
+            let value = 0.5 + signed / range;
+            (value.clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+// mask 가 target 인 칸까지의 거리. 두 번의 순차 패스로 이웃의 오프셋을 물려받는다
+fn distance_transform(mask: &[bool], width: usize, height: usize, target: bool) -> Vec<f32> {
+    let mut grid: Vec<(i32, i32)> = mask
+        .iter()
+        .map(|&value| if value == target { (0, 0) } else { FAR })
+        .collect();
+
+    let compare = |grid: &mut [(i32, i32)], x: usize, y: usize, dx: i32, dy: i32| {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+            return;
+        }
+        let (ox, oy) = grid[ny as usize * width + nx as usize];
+        let candidate = (ox + dx, oy + dy);
+        let current = grid[y * width + x];
+        if length_squared(candidate) < length_squared(current) {
+            grid[y * width + x] = candidate;
+        }
+    };
+
+    // 위에서 아래로: 왼쪽과 윗줄을 보고, 되돌아오며 오른쪽을 본다
+    for y in 0..height {
+        for x in 0..width {
+            compare(&mut grid, x, y, -1, 0);
+            compare(&mut grid, x, y, 0, -1);
+            compare(&mut grid, x, y, -1, -1);
+            compare(&mut grid, x, y, 1, -1);
+        }
+        for x in (0..width).rev() {
+            compare(&mut grid, x, y, 1, 0);
+        }
+    }
+
+    // 아래에서 위로: 오른쪽과 아랫줄을 보고, 되돌아오며 왼쪽을 본다
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            compare(&mut grid, x, y, 1, 0);
+            compare(&mut grid, x, y, 0, 1);
+            compare(&mut grid, x, y, -1, 1);
+            compare(&mut grid, x, y, 1, 1);
+        }
+        for x in 0..width {
+            compare(&mut grid, x, y, -1, 0);
+        }
+    }
+
+    grid.iter()
+        .map(|&offset| (length_squared(offset) as f32).sqrt())
+        .collect()
+}
+
+fn length_squared((x, y): (i32, i32)) -> i32 {
+    x * x + y * y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: u32, from: u32, to: u32) -> Vec<u8> {
+        let mut coverage = vec![0; (size * size) as usize];
+        for y in from..to {
+            for x in from..to {
+                coverage[(y * size + x) as usize] = 255;
+            }
+        }
+        coverage
+    }
+
+    #[test]
+    fn pads_every_side_by_spread() {
+        let sdf = signed_distance_field(&square(8, 2, 6), 8, 8, 4);
+        assert_eq!(sdf.len(), 16 * 16);
+    }
+
+    #[test]
+    fn inside_is_above_half_and_outside_below() {
+        let sdf = signed_distance_field(&square(16, 4, 12), 16, 16, 4);
+        let at = |x: usize, y: usize| sdf[(y + 4) * 24 + x + 4];
+
+        // 가운데는 경계에서 spread 만큼 떨어져 있다
+        assert_eq!(at(8, 8), 255);
+        assert!(at(4, 8) > 128);
+        assert!(at(3, 8) < 128);
+        assert_eq!(sdf[0], 0);
+    }
+
+    #[test]
+    fn matches_brute_force_distance_for_a_disc() {
+        let size = 24;
+        let mut coverage = vec![0u8; size * size];
+        for y in 0..size {
+            for x in 0..size {
+                let (dx, dy) = (x as f32 - 11.5, y as f32 - 11.5);
+                if dx * dx + dy * dy < 49.0 {
+                    coverage[y * size + x] = 255;
+                }
+            }
+        }
+
+        let inside: Vec<bool> = coverage.iter().map(|&value| value >= 128).collect();
+        let distances = distance_transform(&inside, size, size, true);
+        for y in 0..size {
+            for x in 0..size {
+                let nearest = (0..size * size)
+                    .filter(|&i| inside[i])
+                    .map(|i| {
+                        let (dx, dy) = ((i % size) as f32 - x as f32, (i / size) as f32 - y as f32);
+                        (dx * dx + dy * dy).sqrt()
+                    })
+                    .fold(f32::MAX, f32::min);
+                assert!((distances[y * size + x] - nearest).abs() < 0.5);
+            }
+        }
+    }
+}
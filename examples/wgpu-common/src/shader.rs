@@ -0,0 +1,49 @@
+/// 셰이더 모듈을 WGSL 소스나 미리 컴파일한 SPIR-V 로 만든다.
+///
+/// 웹에서는 WGSL 만 사용할 수 있다. 네이티브에서는 빌드 스크립트가 컴파일해 둔
+/// SPIR-V 를 넘기면 실행할 때 WGSL 을 파싱하지 않아도 된다.
+pub struct ShaderLoader {
+    device: wgpu::Device,
+}
+
+impl ShaderLoader {
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            device: device.clone(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    pub fn load_wgsl(&self, label: &str, source: &str) -> wgpu::ShaderModule {
+        self.device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            })
+    }
+
+    /// `include_bytes!` 로 포함한 `.spv` 파일은 `wgpu::util::make_spirv_raw` 로
+    /// `u32` 워드로 바꿔서 넘긴다.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_spirv(&self, label: &str, words: &[u32]) -> wgpu::ShaderModule {
+        self.device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::SpirV(words.into()),
+            })
+    }
+
+    // ShaderCache 가 이미 파싱하고 검증한 naga 모듈을 넘긴다. 웹에서도 쓸 수 있다
+    #[cfg(feature = "shader-cache")]
+    pub(crate) fn load_naga(&self, label: &str, module: naga::Module) -> wgpu::ShaderModule {
+        self.device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(module)),
+            })
+    }
+}
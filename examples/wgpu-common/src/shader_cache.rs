@@ -0,0 +1,357 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha2::{Digest, Sha256};
+
+use crate::ShaderLoader;
+use crate::frame_timer::now_ms;
+use crate::log::log;
+
+// localStorage 키 앞에 붙여서 다른 데이터와 구분한다
+const KEY_PREFIX: &str = "wgpu-shader-cache:";
+// 오래 쓰지 않은 순서로 나열한 해시 목록을 담는 키
+const INDEX_KEY: &str = "wgpu-shader-cache:index";
+
+/// WGSL 을 SPIR-V 로 컴파일한 결과를 `localStorage` 에 저장해 두고
+/// 페이지를 다시 열었을 때 WGSL 파싱 대신 저장된 SPIR-V 로 셰이더 모듈을 만든다.
+///
+/// WGSL 은 naga 로 한 번만 파싱하고 검증해서 그 결과로 모듈과 SPIR-V 를 함께 만든다.
+/// 키는 WGSL 소스의 SHA-256 이므로 소스가 바뀌면 자동으로 새로 컴파일된다.
+/// 항목이 `capacity` 를 넘거나 저장 공간이 부족하면 가장 오래 쓰지 않은 항목부터 지운다.
+/// `localStorage` 가 없는 환경(네이티브 등)에서는 항상 WGSL 로 만든다.
+pub struct ShaderCache {
+    storage: Option<web_sys::Storage>,
+    index: LruIndex,
+    hits: u32,
+    misses: u32,
+}
+
+impl ShaderCache {
+    pub const DEFAULT_CAPACITY: usize = 32;
+
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let storage = local_storage();
+        let index = storage
+            .as_ref()
+            .and_then(|storage| storage.get_item(INDEX_KEY).ok().flatten())
+            .map(|index| LruIndex::parse(&index, capacity))
+            .unwrap_or_else(|| LruIndex::new(capacity));
+
+        Self {
+            storage,
+            index,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// 캐시에 있으면 SPIR-V 로, 없으면 WGSL 로 모듈을 만들고 SPIR-V 를 저장한다.
+    /// 히트면 WGSL 로 만들 때보다 줄어든 시간을 로그로 남긴다.
+    /// 저장된 SPIR-V 가 검증에 실패하면 항목을 지우고 WGSL 로 다시 만든다.
+    pub async fn load(
+        &mut self,
+        device: &wgpu::Device,
+        label: &str,
+        wgsl_source: &str,
+    ) -> wgpu::ShaderModule {
+        let loader = ShaderLoader::new(device);
+        let hash = source_hash(wgsl_source);
+
+        if let Some(entry) = self.get(&hash) {
+            let start = now_ms();
+            if let Some(module) = parse_spirv(&entry.spirv) {
+                device.push_error_scope(wgpu::ErrorFilter::Validation);
+                let shader = loader.load_naga(label, module);
+                if device.pop_error_scope().await.is_none() {
+                    let elapsed = now_ms() - start;
+                    log(
+                        &format!(
+                            "Shader '{}' created from cached SPIR-V in {:.2} ms, {:.2} ms faster than WGSL",
+                            label,
+                            elapsed,
+                            entry.wgsl_ms - elapsed
+                        ),
+                        false,
+                    );
+                    self.hits += 1;
+                    self.touch(&hash);
+                    return shader;
+                }
+            }
+            self.remove(&hash);
+        }
+
+        self.misses += 1;
+        let start = now_ms();
+        // 파싱이나 검증에 실패하면 wgpu 가 WGSL 을 컴파일하면서 같은 에러를 보고한다
+        let Some((module, info)) = compile_wgsl(wgsl_source) else {
+            return loader.load_wgsl(label, wgsl_source);
+        };
+        let spirv = write_spirv(&module, &info);
+        let shader = loader.load_naga(label, module);
+        let wgsl_ms = now_ms() - start;
+        if let Some(spirv) = spirv {
+            self.insert(&hash, wgsl_ms, &spirv);
+        }
+        shader
+    }
+
+    /// 이 캐시로 불러온 셰이더 중 저장된 SPIR-V 를 사용한 수
+    pub fn hits(&self) -> u32 {
+        self.hits
+    }
+
+    /// WGSL 로 컴파일한 수
+    pub fn misses(&self) -> u32 {
+        self.misses
+    }
+
+    /// 저장된 항목을 모두 지운다.
+    pub fn clear(&mut self) {
+        for hash in self.index.entries.clone() {
+            self.remove(&hash);
+        }
+    }
+
+    fn get(&self, hash: &str) -> Option<CacheEntry> {
+        let saved = self.storage.as_ref()?.get_item(&entry_key(hash)).ok()??;
+        CacheEntry::parse(&saved)
+    }
+
+    fn insert(&mut self, hash: &str, wgsl_ms: f64, spirv: &[u32]) {
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let bytes: Vec<u8> = spirv.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let encoded = format!("{}:{}", wgsl_ms, BASE64.encode(bytes));
+
+        // 용량 초과(QuotaExceededError)면 오래된 항목을 하나씩 지우면서 다시 시도한다
+        while storage.set_item(&entry_key(hash), &encoded).is_err() {
+            let Some(oldest) = self.index.entries.first().cloned() else {
+                return;
+            };
+            self.remove(&oldest);
+        }
+        self.touch(hash);
+    }
+
+    fn touch(&mut self, hash: &str) {
+        for evicted in self.index.touch(hash) {
+            self.remove_entry(&evicted);
+        }
+        self.save_index();
+    }
+
+    fn remove(&mut self, hash: &str) {
+        self.index.remove(hash);
+        self.remove_entry(hash);
+        self.save_index();
+    }
+
+    fn remove_entry(&self, hash: &str) {
+        if let Some(storage) = &self.storage {
+            let _ = storage.remove_item(&entry_key(hash));
+        }
+    }
+
+    fn save_index(&self) {
+        if let Some(storage) = &self.storage {
+            let _ = storage.set_item(INDEX_KEY, &self.index.entries.join(","));
+        }
+    }
+}
+
+impl Default for ShaderCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 저장된 항목. WGSL 로 만들 때 걸린 시간과 SPIR-V 바이트를 "시간:base64" 로 저장한다
+struct CacheEntry {
+    wgsl_ms: f64,
+    spirv: Vec<u8>,
+}
+
+impl CacheEntry {
+    fn parse(saved: &str) -> Option<Self> {
+        let (wgsl_ms, encoded) = saved.split_once(':')?;
+        Some(Self {
+            wgsl_ms: wgsl_ms.parse().ok()?,
+            spirv: BASE64.decode(encoded).ok()?,
+        })
+    }
+}
+
+// 오래 쓰지 않은 해시가 앞에 오는 목록
+#[derive(Debug, PartialEq)]
+struct LruIndex {
+    entries: Vec<String>,
+    capacity: usize,
+}
+
+impl LruIndex {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+        }
+    }
+
+    fn parse(index: &str, capacity: usize) -> Self {
+        Self {
+            entries: index
+                .split(',')
+                .filter(|hash| !hash.is_empty())
+                .map(str::to_string)
+                .collect(),
+            capacity,
+        }
+    }
+
+    // hash 를 가장 최근으로 옮기고, 용량을 넘어서 빠진 해시들을 반환한다
+    fn touch(&mut self, hash: &str) -> Vec<String> {
+        self.remove(hash);
+        self.entries.push(hash.to_string());
+        let excess = self.entries.len().saturating_sub(self.capacity);
+        self.entries.drain(..excess).collect()
+    }
+
+    fn remove(&mut self, hash: &str) {
+        self.entries.retain(|entry| entry != hash);
+    }
+}
+
+fn entry_key(hash: &str) -> String {
+    format!("{}{}", KEY_PREFIX, hash)
+}
+
+// WGSL 소스의 SHA-256 을 16진수 문자열로
+fn source_hash(source: &str) -> String {
+    Sha256::digest(source.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn compile_wgsl(source: &str) -> Option<(naga::Module, naga::valid::ModuleInfo)> {
+    let module = naga::front::wgsl::parse_str(source).ok()?;
+    let info = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .ok()?;
+    Some((module, info))
+}
+
+// naga IR 은 wgpu 와 같은 좌표계를 쓰므로 Y 를 뒤집지 않고 쓰고, 읽을 때도 뒤집지 않는다.
+// 한쪽에서만 뒤집으면 정점 위치가 위아래로 뒤집혀서 삼각형이 컬링된다
+fn write_spirv(module: &naga::Module, info: &naga::valid::ModuleInfo) -> Option<Vec<u32>> {
+    let options = naga::back::spv::Options {
+        flags: naga::back::spv::Options::default()
+            .flags
+            .difference(naga::back::spv::WriterFlags::ADJUST_COORDINATE_SPACE),
+        ..Default::default()
+    };
+    naga::back::spv::write_vec(module, info, &options, None).ok()
+}
+
+fn parse_spirv(bytes: &[u8]) -> Option<naga::Module> {
+    let options = naga::front::spv::Options {
+        adjust_coordinate_space: false,
+        ..Default::default()
+    };
+    naga::front::spv::parse_u8_slice(bytes, &options).ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn local_storage() -> Option<web_sys::Storage> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_depends_on_source() {
+        let hash = source_hash("fn main() {}");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, source_hash("fn main() {}"));
+        assert_ne!(hash, source_hash("fn main() { }"));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut index = LruIndex::new(2);
+        assert!(index.touch("a").is_empty());
+        assert!(index.touch("b").is_empty());
+        // a 를 다시 쓰면 b 가 가장 오래된 항목이 된다
+        assert!(index.touch("a").is_empty());
+        assert_eq!(index.touch("c"), vec!["b".to_string()]);
+        assert_eq!(index.entries, vec!["a".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn parses_saved_index() {
+        let index = LruIndex::parse("a,b,", 4);
+        assert_eq!(index.entries, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(LruIndex::parse("", 4), LruIndex::new(4));
+    }
+
+    #[test]
+    fn compiles_valid_wgsl_only() {
+        let (module, info) = compile_wgsl(
+            "@fragment fn fs_main() -> @location(0) vec4<f32> { return vec4<f32>(1.0); }",
+        )
+        .unwrap();
+        let words = write_spirv(&module, &info).unwrap();
+        // SPIR-V 매직 넘버
+        assert_eq!(words[0], 0x0723_0203);
+        assert!(compile_wgsl("fn broken(").is_none());
+    }
+
+    #[test]
+    fn round_trip_keeps_vertex_position() {
+        let (module, info) =
+            compile_wgsl(include_str!("../../wgpu-triangle/src/shader.wgsl")).unwrap();
+        let words = write_spirv(&module, &info).unwrap();
+        let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let module = parse_spirv(&bytes).unwrap();
+
+        // Y 를 뒤집었다면 원래 없던 부호 반전이 생긴다. spv-in 은 진입점 본문을 별도 함수로 옮긴다
+        let negated = module
+            .functions
+            .iter()
+            .map(|(_, function)| function)
+            .chain(module.entry_points.iter().map(|entry| &entry.function))
+            .flat_map(|function| function.expressions.iter())
+            .any(|(_, expression)| {
+                matches!(
+                    expression,
+                    naga::Expression::Unary {
+                        op: naga::UnaryOperator::Negate,
+                        ..
+                    }
+                )
+            });
+        assert!(!negated);
+    }
+
+    #[test]
+    fn parses_saved_entry() {
+        let entry = CacheEntry::parse("12.5:AQIDBA==").unwrap();
+        assert_eq!(entry.wgsl_ms, 12.5);
+        assert_eq!(entry.spirv, vec![1, 2, 3, 4]);
+        assert!(CacheEntry::parse("AQIDBA==").is_none());
+    }
+}
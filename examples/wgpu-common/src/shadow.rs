@@ -0,0 +1,192 @@
+use glam::{Mat4, Vec3};
+use wgpu::util::DeviceExt;
+
+use crate::{BindGroupBuilder, SamplerBuilder};
+
+// 그림자 패스와 메인 패스가 함께 쓰는 유니폼.
+// 셰이더에서는 같은 레이아웃의 ShadowUniform 으로 선언한다
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ShadowUniform {
+    light_view_proj: [[f32; 4]; 4],
+    bias: f32,
+    _padding: [f32; 3],
+}
+
+/// 방향광 하나의 그림자 맵.
+///
+/// 1. `set_light` 로 빛의 뷰-투영 행렬을 정한다
+/// 2. `begin_pass` 로 연 깊이 전용 패스에서 `depth_bind_group` 을 바인딩하고 장면을 그린다
+/// 3. 메인 패스에서 `bind_group` 을 바인딩하고 `textureSampleCompare` 로 그림자를 확인한다
+///
+/// `bind_group` 의 바인딩은 0: `ShadowUniform`, 1: `texture_depth_2d`, 2: `sampler_comparison` 이다.
+pub struct ShadowMap {
+    view: wgpu::TextureView,
+    uniform_buffer: wgpu::Buffer,
+    uniform: ShadowUniform,
+    depth_bind_group_layout: wgpu::BindGroupLayout,
+    depth_bind_group: wgpu::BindGroup,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    size: u32,
+}
+
+impl ShadowMap {
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+    pub const DEFAULT_SIZE: u32 = 2048;
+    /// 그림자 여드름(shadow acne)을 줄이기 위해 비교 전에 빼는 깊이
+    pub const DEFAULT_BIAS: f32 = 0.005;
+
+    pub fn new(device: &wgpu::Device, size: u32) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Shadow Map"),
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // 깊이 값이 저장된 깊이 이하이면 빛을 받는다
+        let sampler = SamplerBuilder::shadow()
+            .label("Shadow Sampler")
+            .build(device);
+
+        let uniform = ShadowUniform {
+            light_view_proj: Mat4::IDENTITY.to_cols_array_2d(),
+            bias: Self::DEFAULT_BIAS,
+            _padding: [0.0; 3],
+        };
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Shadow Uniform Buffer"),
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let (depth_bind_group_layout, depth_bind_group) = BindGroupBuilder::new()
+            .uniform_buffer(0, &uniform_buffer, wgpu::ShaderStages::VERTEX)
+            .build(device, "Shadow Depth Bind Group");
+
+        let (bind_group_layout, bind_group) = BindGroupBuilder::new()
+            .uniform_buffer(
+                0,
+                &uniform_buffer,
+                wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+            )
+            .entry(
+                1,
+                wgpu::ShaderStages::FRAGMENT,
+                wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                wgpu::BindingResource::TextureView(&view),
+            )
+            .entry(
+                2,
+                wgpu::ShaderStages::FRAGMENT,
+                wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                wgpu::BindingResource::Sampler(&sampler),
+            )
+            .build(device, "Shadow Bind Group");
+
+        Self {
+            view,
+            uniform_buffer,
+            uniform,
+            depth_bind_group_layout,
+            depth_bind_group,
+            bind_group_layout,
+            bind_group,
+            size,
+        }
+    }
+
+    /// `center` 를 중심으로 반지름 `radius` 안의 물체를 덮는 직교 투영.
+    /// `direction` 은 표면에서 빛을 향하는 방향이다.
+    pub fn light_view_projection(direction: Vec3, center: Vec3, radius: f32) -> Mat4 {
+        let direction = direction.normalize();
+        let up = if direction.abs().y > 0.99 {
+            Vec3::Z
+        } else {
+            Vec3::Y
+        };
+        let view = Mat4::look_at_rh(center + direction * radius * 2.0, center, up);
+        let projection =
+            Mat4::orthographic_rh(-radius, radius, -radius, radius, radius * 0.5, radius * 4.0);
+        projection * view
+    }
+
+    pub fn set_light(&mut self, queue: &wgpu::Queue, light_view_proj: Mat4) {
+        self.uniform.light_view_proj = light_view_proj.to_cols_array_2d();
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&self.uniform));
+    }
+
+    pub fn bias(&self) -> f32 {
+        self.uniform.bias
+    }
+
+    pub fn set_bias(&mut self, queue: &wgpu::Queue, bias: f32) {
+        self.uniform.bias = bias.max(0.0);
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&self.uniform));
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// 그림자 패스 파이프라인의 `depth_stencil` 에 넘길 상태
+    pub fn depth_stencil_state() -> wgpu::DepthStencilState {
+        wgpu::DepthStencilState {
+            format: Self::FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }
+    }
+
+    /// 그림자 패스에서 0번 그룹에 바인딩하는 빛의 뷰-투영 유니폼
+    pub fn depth_bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.depth_bind_group_layout
+    }
+
+    pub fn depth_bind_group(&self) -> &wgpu::BindGroup {
+        &self.depth_bind_group
+    }
+
+    /// 메인 패스에서 그림자 맵을 읽을 때 쓰는 바인드 그룹
+    pub fn bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    /// 그림자 맵을 1.0 으로 지우고 깊이만 기록하는 렌더 패스를 연다.
+    pub fn begin_pass<'e>(&self, encoder: &'e mut wgpu::CommandEncoder) -> wgpu::RenderPass<'e> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Shadow Pass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        })
+    }
+}
@@ -0,0 +1,216 @@
+use glam::{Mat4, Vec3};
+
+use crate::{Transform, Vertex};
+
+/// 스키닝 셰이더의 뼈 행렬 배열 길이. `Skeleton::palette` 가 이 길이로 채운다
+pub const MAX_BONES: usize = 64;
+
+/// 부모 뼈 기준의 로컬 변환. 부모가 없는 뼈는 메시 공간 기준이다
+pub type BoneTransform = Transform;
+
+/// `UniformBuffer<BonePalette>` 로 올리는 뼈 행렬 배열
+pub type BonePalette = [Mat4; MAX_BONES];
+
+/// 최대 네 개의 뼈에 가중치를 나눠 붙는 버텍스.
+/// 가중치의 합은 1 이어야 하고, 쓰지 않는 자리는 가중치를 0 으로 둔다.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SkinnedVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub bone_indices: [u32; 4],
+    pub bone_weights: [f32; 4],
+}
+
+impl Vertex for SkinnedVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &wgpu::vertex_attr_array![
+        0 => Float32x3, 1 => Float32x3, 2 => Uint32x4, 3 => Float32x4
+    ];
+}
+
+impl SkinnedVertex {
+    /// 뼈 하나에만 붙는 버텍스
+    pub fn rigid(position: [f32; 3], normal: [f32; 3], bone: u32) -> Self {
+        Self {
+            position,
+            normal,
+            bone_indices: [bone, 0, 0, 0],
+            bone_weights: [1.0, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+/// 부모 인덱스로 이어진 뼈 계층과 지금의 포즈.
+///
+/// 뼈는 부모가 항상 자식보다 앞에 오도록 늘어놓는다. 그러면 앞에서부터 한 번만 훑어도
+/// 부모의 월드 행렬이 먼저 계산되어 있다.
+///
+/// 만들 때의 포즈를 바인드 포즈로 삼아 그 역행렬을 저장해 둔다. 메시의 버텍스는
+/// 바인드 포즈 기준 메시 공간 좌표이고, `palette` 는 바인드 포즈에서 지금 포즈로 옮기는
+/// 행렬이므로 바인드 포즈 그대로면 모두 단위 행렬이다.
+#[derive(Clone, Debug)]
+pub struct Skeleton {
+    /// 뼈마다의 지금 로컬 변환. 매 프레임 바꾼 뒤 `palette` 를 올린다
+    pub bones: Vec<BoneTransform>,
+    parents: Vec<Option<usize>>,
+    inverse_bind: Vec<Mat4>,
+}
+
+impl Skeleton {
+    /// `bind_pose` 와 `parents` 는 길이가 같고 `MAX_BONES` 이하여야 하며,
+    /// 부모 인덱스는 자기 인덱스보다 작아야 한다.
+    pub fn new(bind_pose: Vec<BoneTransform>, parents: Vec<Option<usize>>) -> Self {
+        assert_eq!(
+            bind_pose.len(),
+            parents.len(),
+            "every bone needs a parent entry"
+        );
+        assert!(
+            bind_pose.len() <= MAX_BONES,
+            "skeleton has more than {} bones",
+            MAX_BONES
+        );
+        for (index, parent) in parents.iter().enumerate() {
+            if let Some(parent) = parent {
+                assert!(
+                    *parent < index,
+                    "bone {index} must come after its parent {parent}"
+                );
+            }
+        }
+
+        let mut skeleton = Self {
+            bones: bind_pose,
+            parents,
+            inverse_bind: Vec::new(),
+        };
+        skeleton.inverse_bind = skeleton
+            .world_matrices()
+            .iter()
+            .map(Mat4::inverse)
+            .collect();
+        skeleton
+    }
+
+    pub fn len(&self) -> usize {
+        self.bones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bones.is_empty()
+    }
+
+    pub fn parents(&self) -> &[Option<usize>] {
+        &self.parents
+    }
+
+    /// 뼈마다 지금 포즈의 메시 공간 행렬
+    pub fn world_matrices(&self) -> Vec<Mat4> {
+        let mut world: Vec<Mat4> = Vec::with_capacity(self.bones.len());
+        for (bone, parent) in self.bones.iter().zip(&self.parents) {
+            let local = bone.matrix();
+            world.push(match parent {
+                Some(parent) => world[*parent] * local,
+                None => local,
+            });
+        }
+        world
+    }
+
+    /// 바인드 포즈의 버텍스를 지금 포즈로 옮기는 뼈 행렬. 뼈가 없는 자리는 단위 행렬이다
+    pub fn palette(&self) -> BonePalette {
+        let mut palette = [Mat4::IDENTITY; MAX_BONES];
+        for ((matrix, world), inverse_bind) in palette
+            .iter_mut()
+            .zip(self.world_matrices())
+            .zip(&self.inverse_bind)
+        {
+            *matrix = world * *inverse_bind;
+        }
+        palette
+    }
+
+    /// CPU 에서 셰이더와 같은 선형 블렌드 스키닝을 한다. 피킹이나 바운딩 박스 계산에 쓴다
+    pub fn skin_position(palette: &BonePalette, vertex: &SkinnedVertex) -> Vec3 {
+        let position = Vec3::from(vertex.position);
+        vertex
+            .bone_indices
+            .iter()
+            .zip(vertex.bone_weights)
+            .map(|(bone, weight)| palette[*bone as usize].transform_point3(position) * weight)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Quat;
+
+    use super::*;
+
+    fn arm() -> Skeleton {
+        // 어깨에서 +x 로 1 만큼 뻗은 팔꿈치
+        Skeleton::new(
+            vec![
+                BoneTransform::from_translation(Vec3::new(0.0, 2.0, 0.0)),
+                BoneTransform::from_translation(Vec3::new(1.0, 0.0, 0.0)),
+            ],
+            vec![None, Some(0)],
+        )
+    }
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{a} != {b}");
+    }
+
+    #[test]
+    fn bind_pose_palette_is_identity() {
+        let skeleton = arm();
+        for matrix in skeleton.palette() {
+            assert!(matrix.abs_diff_eq(Mat4::IDENTITY, 1e-6));
+        }
+    }
+
+    #[test]
+    fn child_follows_parent_rotation() {
+        let mut skeleton = arm();
+        skeleton.bones[0].rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+
+        let elbow = skeleton.world_matrices()[1].transform_point3(Vec3::ZERO);
+        assert_near(elbow, Vec3::new(0.0, 3.0, 0.0));
+
+        // 팔꿈치에서 +x 로 0.5 떨어진 바인드 포즈의 버텍스도 함께 돈다
+        let vertex = SkinnedVertex::rigid([1.5, 2.0, 0.0], [0.0, 1.0, 0.0], 1);
+        let skinned = Skeleton::skin_position(&skeleton.palette(), &vertex);
+        assert_near(skinned, Vec3::new(0.0, 3.5, 0.0));
+    }
+
+    #[test]
+    fn weights_blend_linearly() {
+        let mut skeleton = arm();
+        skeleton.bones[1].translation += Vec3::new(0.0, 1.0, 0.0);
+
+        let vertex = SkinnedVertex {
+            position: [1.0, 2.0, 0.0],
+            normal: [0.0, 1.0, 0.0],
+            bone_indices: [0, 1, 0, 0],
+            bone_weights: [0.5, 0.5, 0.0, 0.0],
+        };
+        let skinned = Skeleton::skin_position(&skeleton.palette(), &vertex);
+        assert_near(skinned, Vec3::new(1.0, 2.5, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "must come after its parent")]
+    fn rejects_parent_after_child() {
+        Skeleton::new(vec![BoneTransform::IDENTITY; 2], vec![Some(1), None]);
+    }
+
+    #[test]
+    fn vertex_layout_matches_struct() {
+        let layout = SkinnedVertex::layout();
+        assert_eq!(layout.array_stride, 56);
+        assert_eq!(layout.attributes[2].offset, 24);
+        assert_eq!(layout.attributes[3].offset, 40);
+    }
+}
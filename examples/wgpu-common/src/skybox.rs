@@ -0,0 +1,290 @@
+use std::f32::consts::PI;
+
+use glam::{Mat4, Vec3};
+use wgpu::util::DeviceExt;
+
+use crate::{BindGroupBuilder, Camera, DepthTexture, GpuContext, SamplerBuilder};
+
+// skybox.wgsl 의 SkyboxUniform 과 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SkyboxUniform {
+    inv_view_proj: [[f32; 4]; 4],
+}
+
+impl SkyboxUniform {
+    // 하늘은 무한히 멀리 있으므로 카메라 위치는 빼고 회전만 사용한다
+    fn new(camera: &Camera) -> Self {
+        let view = Mat4::look_at_rh(Vec3::ZERO, camera.target - camera.eye, camera.up);
+        Self {
+            inv_view_proj: (camera.projection_matrix() * view)
+                .inverse()
+                .to_cols_array_2d(),
+        }
+    }
+}
+
+/// 큐브 맵을 화면 전체에 그려서 배경을 만든다.
+///
+/// 다른 지오메트리를 그린 같은 렌더 패스에서 `draw` 를 호출하면
+/// 깊이 1.0 에 그려지므로 아무것도 그려지지 않은 픽셀에만 하늘이 남는다.
+pub struct Skybox {
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl Skybox {
+    /// 큐브 맵 텍스처 포맷. 면 이미지는 sRGB 로 인코딩되어 있어야 한다.
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// `faces` 는 +X, -X, +Y, -Y, +Z, -Z 순서의 같은 크기 정사각형 이미지다.
+    /// `format` 은 그려질 컬러 타깃의 포맷이다.
+    ///
+    /// # Panics
+    ///
+    /// 면의 크기가 서로 다르거나 정사각형이 아니면 panic 한다.
+    pub fn new(
+        gpu: &GpuContext,
+        format: wgpu::TextureFormat,
+        faces: &[image::RgbaImage; 6],
+    ) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let face_size = faces[0].width();
+        assert!(
+            faces
+                .iter()
+                .all(|face| face.width() == face_size && face.height() == face_size),
+            "skybox faces must be squares of the same size"
+        );
+
+        // 6 레이어짜리 2D 텍스처를 Cube 뷰로 본다
+        let data: Vec<u8> = faces
+            .iter()
+            .flat_map(|face| face.as_raw().iter().copied())
+            .collect();
+        let texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Skybox Texture"),
+                size: wgpu::Extent3d {
+                    width: face_size,
+                    height: face_size,
+                    depth_or_array_layers: 6,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: Self::FORMAT,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &data,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Skybox View"),
+            dimension: Some(wgpu::TextureViewDimension::Cube),
+            ..Default::default()
+        });
+        let sampler = SamplerBuilder::linear()
+            .label("Skybox Sampler")
+            .build(device);
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Skybox Uniform Buffer"),
+            contents: bytemuck::bytes_of(&SkyboxUniform {
+                inv_view_proj: Mat4::IDENTITY.to_cols_array_2d(),
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let (bind_group_layout, bind_group) = BindGroupBuilder::new()
+            .uniform_buffer(0, &uniform_buffer, wgpu::ShaderStages::FRAGMENT)
+            .entry(
+                1,
+                wgpu::ShaderStages::FRAGMENT,
+                wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::Cube,
+                    multisampled: false,
+                },
+                wgpu::BindingResource::TextureView(&view),
+            )
+            .sampler(2, &sampler, wgpu::ShaderStages::FRAGMENT)
+            .build(device, "Skybox Bind Group");
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Skybox Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("skybox.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Skybox Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Skybox Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            // 깊이 1.0 에서도 통과하도록 LessEqual 로 비교하고, 깊이는 쓰지 않는다.
+            // 바이어스를 0 으로 고정해서 하늘이 항상 다른 지오메트리 뒤에 남게 한다
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DepthTexture::FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState {
+                    constant: 0,
+                    slope_scale: 0.0,
+                    clamp: 0.0,
+                },
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            uniform_buffer,
+            bind_group,
+            pipeline,
+        }
+    }
+
+    /// 등장방형(equirectangular) 이미지를 `face_size` 크기의 여섯 면으로 바꿔서 만든다.
+    /// HDR 이미지는 선형 값으로 보고 Reinhard 톤 매핑 후 sRGB 로 인코딩한다.
+    pub fn from_equirectangular(
+        gpu: &GpuContext,
+        format: wgpu::TextureFormat,
+        image: &image::DynamicImage,
+        face_size: u32,
+    ) -> Self {
+        let faces = equirectangular_to_faces(image, face_size);
+        Self::new(gpu, format, &faces)
+    }
+
+    /// 카메라가 바뀐 뒤 그리기 전에 호출한다.
+    pub fn update(&self, queue: &wgpu::Queue, camera: &Camera) {
+        queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&SkyboxUniform::new(camera)),
+        );
+    }
+
+    /// 깊이 어태치먼트가 있는 렌더 패스에서 호출한다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+pub(crate) fn equirectangular_to_faces(
+    image: &image::DynamicImage,
+    face_size: u32,
+) -> [image::RgbaImage; 6] {
+    let is_hdr = matches!(
+        image,
+        image::DynamicImage::ImageRgb32F(_) | image::DynamicImage::ImageRgba32F(_)
+    );
+    let source = image.to_rgb32f();
+    let (width, height) = source.dimensions();
+
+    std::array::from_fn(|face| {
+        image::RgbaImage::from_fn(face_size, face_size, |x, y| {
+            // 면의 텍셀 중심을 [-1, 1] 로 옮긴다 (v 는 아래쪽이 +)
+            let u = 2.0 * (x as f32 + 0.5) / face_size as f32 - 1.0;
+            let v = 2.0 * (y as f32 + 0.5) / face_size as f32 - 1.0;
+            let direction = cube_face_direction(face, u, v).normalize();
+
+            let longitude = direction.x.atan2(-direction.z);
+            let latitude = direction.y.clamp(-1.0, 1.0).asin();
+            let sx = ((0.5 + longitude / (2.0 * PI)) * width as f32) as u32;
+            let sy = ((0.5 - latitude / PI) * height as f32) as u32;
+            let texel = source.get_pixel(sx.min(width - 1), sy.min(height - 1)).0;
+
+            let encode = |c: f32| {
+                let c = if is_hdr {
+                    (c / (1.0 + c)).powf(1.0 / 2.2)
+                } else {
+                    c
+                };
+                (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+            };
+            image::Rgba([encode(texel[0]), encode(texel[1]), encode(texel[2]), 255])
+        })
+    })
+}
+
+// 큐브 맵 면 좌표 (u, v) 에 해당하는 방향. 면 순서와 축 방향은 WebGPU 의 큐브 맵 규칙을 따른다
+pub(crate) fn cube_face_direction(face: usize, u: f32, v: f32) -> Vec3 {
+    match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    }
+}
+
+// cube_face_direction 의 역. 방향이 가리키는 면과 [-1, 1] 범위의 (u, v)
+pub(crate) fn cube_face_uv(direction: Vec3) -> (usize, f32, f32) {
+    let abs = direction.abs();
+    if abs.x >= abs.y && abs.x >= abs.z {
+        let (u, v) = (direction.z / abs.x, -direction.y / abs.x);
+        if direction.x > 0.0 {
+            (0, -u, v)
+        } else {
+            (1, u, v)
+        }
+    } else if abs.y >= abs.z {
+        let (u, v) = (direction.x / abs.y, direction.z / abs.y);
+        if direction.y > 0.0 {
+            (2, u, v)
+        } else {
+            (3, u, -v)
+        }
+    } else {
+        let (u, v) = (direction.x / abs.z, -direction.y / abs.z);
+        if direction.z > 0.0 {
+            (4, u, v)
+        } else {
+            (5, -u, v)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_face_uv_inverts_direction() {
+        for face in 0..6 {
+            for (u, v) in [(0.0, 0.0), (0.5, -0.25), (-0.75, 0.9)] {
+                let (found, found_u, found_v) = cube_face_uv(cube_face_direction(face, u, v));
+                assert_eq!(found, face);
+                assert!((found_u - u).abs() < 1e-6 && (found_v - v).abs() < 1e-6);
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+struct SkyboxUniform {
+    inv_view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> skybox: SkyboxUniform;
+@group(0) @binding(1)
+var sky_texture: texture_cube<f32>;
+@group(0) @binding(2)
+var sky_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+// 화면을 덮는 삼각형. z = w 로 두어서 깊이가 항상 1.0 (가장 먼 곳) 이 된다
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(ndc, 1.0, 1.0);
+    out.ndc = ndc;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // 먼 평면 위의 점을 월드 공간으로 되돌리면 카메라에서 본 방향이 된다
+    let world = skybox.inv_view_proj * vec4<f32>(in.ndc, 1.0, 1.0);
+    let direction = normalize(world.xyz / world.w);
+    return textureSample(sky_texture, sky_sampler, direction);
+}
@@ -0,0 +1,237 @@
+use glam::Vec3;
+
+use crate::{DepthTexture, GpuContext, UniformBuffer, Vertex, VertexBuffer};
+
+// smoothed_lines.wgsl 의 LineInput 과 같은 레이아웃. 선분 하나가 인스턴스 하나다
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct LineInstance {
+    a: [f32; 3],
+    b: [f32; 3],
+    color: [f32; 4],
+    width: f32,
+}
+
+impl Vertex for LineInstance {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &wgpu::vertex_attr_array![
+        0 => Float32x3,
+        1 => Float32x3,
+        2 => Float32x4,
+        3 => Float32,
+    ];
+}
+
+// smoothed_lines.wgsl 의 ViewportUniform. 유니폼은 16 바이트 단위라서 채운다
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ViewportUniform {
+    size: [f32; 2],
+    _padding: [f32; 2],
+}
+
+impl ViewportUniform {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            size: [width.max(1) as f32, height.max(1) as f32],
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+/// 픽셀 단위 굵기를 가진 안티 에일리어싱된 선분을 그린다.
+///
+/// `LineList` 는 굵기가 항상 1 픽셀이고 경계가 계단처럼 보인다. 여기서는 선분마다
+/// 버텍스 셰이더에서 화면 공간 사각형(`TriangleStrip` 4개 버텍스)으로 늘리고,
+/// 프래그먼트 셰이더가 중심선까지의 거리로 가장자리 1 픽셀의 알파를 줄인다.
+/// 사용법은 `DebugLines` 와 같이 `add` 로 쌓고 `flush` 로 덧그린다.
+///
+/// 카메라 뒤로 넘어가는 선분은 잘라 내지 않으므로 올바르게 그려지지 않는다.
+pub struct SmoothedLines {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    lines: Vec<LineInstance>,
+    instance_buffer: VertexBuffer<LineInstance>,
+    // GPU 버퍼에 담을 수 있는 선분 수
+    capacity: usize,
+    viewport: UniformBuffer<ViewportUniform>,
+    pipeline: wgpu::RenderPipeline,
+    depth_pipeline: wgpu::RenderPipeline,
+    // Some 이면 이 깊이 텍스처로 가려진 선분을 그리지 않는다
+    depth_view: Option<wgpu::TextureView>,
+}
+
+impl SmoothedLines {
+    /// `camera_layout` 은 `flush` 에 넘길 카메라 바인드 그룹의 레이아웃이고,
+    /// `width`, `height` 는 그릴 타깃의 픽셀 크기다.
+    pub fn new(
+        gpu: &GpuContext,
+        color_format: wgpu::TextureFormat,
+        camera_layout: &wgpu::BindGroupLayout,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let capacity = 64;
+        let viewport = UniformBuffer::new(
+            gpu,
+            &ViewportUniform::new(width, height),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Smoothed Lines Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("smoothed_lines.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Smoothed Lines Pipeline Layout"),
+            bind_group_layouts: &[camera_layout, viewport.bind_group_layout()],
+            push_constant_ranges: &[],
+        });
+        let vertex_buffers = [wgpu::VertexBufferLayout {
+            step_mode: wgpu::VertexStepMode::Instance,
+            ..LineInstance::layout()
+        }];
+
+        let create_pipeline = |depth_stencil| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Smoothed Lines Pipeline"),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &vertex_buffers,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: color_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                // 선분 방향에 따라 감는 순서가 바뀌므로 컬링하지 않는다
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let pipeline = create_pipeline(None);
+        // 가장자리가 겹쳐도 뒤의 선분이 지워지지 않도록 깊이는 읽기만 한다
+        let depth_pipeline = create_pipeline(Some(wgpu::DepthStencilState {
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            ..DepthTexture::depth_stencil_state()
+        }));
+
+        Self {
+            device: device.clone(),
+            queue: queue.clone(),
+            lines: Vec::with_capacity(capacity),
+            instance_buffer: create_instance_buffer(device, capacity),
+            capacity,
+            viewport,
+            pipeline,
+            depth_pipeline,
+            depth_view: None,
+        }
+    }
+
+    /// 그릴 타깃의 크기가 바뀌면 호출한다. 픽셀 굵기를 계산하는 데 쓴다
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.viewport
+            .update(&self.queue, &ViewportUniform::new(width, height));
+    }
+
+    /// `DebugLines::set_depth_test` 와 같다.
+    pub fn set_depth_test(&mut self, depth_view: Option<&wgpu::TextureView>) {
+        self.depth_view = depth_view.cloned();
+    }
+
+    /// 이번 프레임에 쌓인 선분 수
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// 월드 좌표 `a` 에서 `b` 까지 화면에서 `width_px` 픽셀 굵기인 선분
+    pub fn add(&mut self, a: Vec3, b: Vec3, color: [f32; 4], width_px: f32) {
+        self.lines.push(LineInstance {
+            a: a.into(),
+            b: b.into(),
+            color,
+            width: width_px.max(0.0),
+        });
+    }
+
+    /// 쌓인 선분을 올려서 `view` 의 기존 내용 위에 그리고 비운다.
+    /// 용량이 부족하면 인스턴스 버퍼를 두 배씩 키워서 다시 만든다.
+    pub fn flush(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        camera_bind_group: &wgpu::BindGroup,
+    ) {
+        if self.lines.is_empty() {
+            return;
+        }
+
+        if self.lines.len() > self.capacity {
+            self.capacity = self.lines.len().next_power_of_two();
+            self.instance_buffer = create_instance_buffer(&self.device, self.capacity);
+        }
+        self.instance_buffer.write(&self.queue, &self.lines);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Smoothed Lines Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: self.depth_view.as_ref().map(|view| {
+                wgpu::RenderPassDepthStencilAttachment {
+                    view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        let pipeline = match self.depth_view {
+            Some(_) => &self.depth_pipeline,
+            None => &self.pipeline,
+        };
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, camera_bind_group, &[]);
+        render_pass.set_bind_group(1, self.viewport.bind_group(), &[]);
+        self.instance_buffer.bind(&mut render_pass, 0);
+        render_pass.draw(0..4, 0..self.lines.len() as u32);
+        drop(render_pass);
+
+        self.lines.clear();
+    }
+}
+
+fn create_instance_buffer(device: &wgpu::Device, capacity: usize) -> VertexBuffer<LineInstance> {
+    VertexBuffer::writable(device, &vec![bytemuck::Zeroable::zeroed(); capacity])
+}
@@ -0,0 +1,74 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+struct ViewportUniform {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var<uniform> viewport: ViewportUniform;
+
+// 선분 하나가 인스턴스 하나다
+struct LineInput {
+    @location(0) a: vec3<f32>,
+    @location(1) b: vec3<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) width: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    // 선분 중심선에서 떨어진 거리(픽셀). 양쪽이 부호로 구분된다
+    @location(1) distance: f32,
+    @location(2) half_width: f32,
+};
+
+// 가장자리를 흐리게 할 여유. 선 굵기보다 이만큼 넓게 사각형을 만든다
+const FEATHER: f32 = 1.0;
+
+// Vertex shader
+// 버텍스 0, 1 은 a 쪽, 2, 3 은 b 쪽이고 짝수는 오른쪽, 홀수는 왼쪽 모서리다(TriangleStrip)
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32, line: LineInput) -> VertexOutput {
+    let clip_a = camera.view_proj * vec4<f32>(line.a, 1.0);
+    let clip_b = camera.view_proj * vec4<f32>(line.b, 1.0);
+
+    // 픽셀 단위 화면 좌표에서 선분 방향과 수직 방향을 구한다
+    let screen_a = clip_a.xy / clip_a.w * viewport.size * 0.5;
+    let screen_b = clip_b.xy / clip_b.w * viewport.size * 0.5;
+    var direction = screen_b - screen_a;
+    if length(direction) < 1e-6 {
+        direction = vec2<f32>(1.0, 0.0);
+    }
+    direction = normalize(direction);
+    let normal = vec2<f32>(-direction.y, direction.x);
+
+    let side = select(-1.0, 1.0, (vertex_index & 1u) == 1u);
+    let end = select(-1.0, 1.0, vertex_index >= 2u);
+    var clip = select(clip_a, clip_b, vertex_index >= 2u);
+
+    // 양 끝도 FEATHER 만큼 늘려서 끝이 잘려 보이지 않게 한다
+    let half_width = line.width * 0.5;
+    let offset = normal * side * (half_width + FEATHER) + direction * end * FEATHER;
+    clip = vec4<f32>(clip.xy + offset / (viewport.size * 0.5) * clip.w, clip.zw);
+
+    var out: VertexOutput;
+    out.clip_position = clip;
+    out.color = line.color;
+    out.distance = side * (half_width + FEATHER);
+    out.half_width = half_width;
+    return out;
+}
+
+// Fragment shader
+// 중심선까지의 거리로 만든 SDF. 가장자리에서 1 픽셀에 걸쳐 알파가 0 으로 줄어든다
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = clamp(in.half_width + 0.5 - abs(in.distance), 0.0, 1.0);
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,93 @@
+use crate::{ShaderError, ShaderLoader};
+
+// SPIR-V 모듈의 첫 워드
+const SPIRV_MAGIC: u32 = 0x0723_0203;
+// 매직 넘버, 버전, 생성기, 바운드, 예약 워드
+const HEADER_WORDS: usize = 5;
+
+/// 셰이더 그래프 편집기 같은 외부 도구가 만든 SPIR-V 를 naga 를 거치지 않고 드라이버에 넘긴다.
+///
+/// `SPIRV_SHADER_PASSTHROUGH` 는 Vulkan 에서만 지원하는 네이티브 전용 기능이다.
+/// 디바이스를 만들 때 `required_features` 에 `required_features` 의 결과를 더해야 켜진다.
+/// 기능이 꺼진 디바이스에서는 `ShaderLoader::load_spirv` 처럼 naga 로 변환해서 만든다.
+pub struct SpirVShader;
+
+impl SpirVShader {
+    pub const FEATURES: wgpu::Features = wgpu::Features::SPIRV_SHADER_PASSTHROUGH;
+
+    /// 어댑터가 지원할 때만 `DeviceDescriptor::required_features` 에 더할 기능
+    pub fn required_features(adapter: &wgpu::Adapter) -> wgpu::Features {
+        adapter.features() & Self::FEATURES
+    }
+
+    /// 헤더가 올바르지 않으면 드라이버에 넘기기 전에 에러를 돌려준다.
+    /// 헤더 말고는 검증하지 않으므로 잘못된 SPIR-V 는 드라이버를 죽일 수도 있다.
+    pub fn from_words(
+        loader: &ShaderLoader,
+        label: &str,
+        words: &[u32],
+    ) -> Result<wgpu::ShaderModule, ShaderError> {
+        check_header(words)?;
+
+        let device = loader.device();
+        if !device.features().contains(Self::FEATURES) {
+            return Ok(loader.load_spirv(label, words));
+        }
+
+        // SAFETY: 헤더만 확인한 SPIR-V 를 그대로 넘긴다. 내용이 올바른지는 호출하는 쪽이 보장한다.
+        let module = unsafe {
+            device.create_shader_module_passthrough(wgpu::ShaderModuleDescriptorPassthrough::SpirV(
+                wgpu::ShaderModuleDescriptorSpirV {
+                    label: Some(label),
+                    source: words.into(),
+                },
+            ))
+        };
+        Ok(module)
+    }
+}
+
+fn check_header(words: &[u32]) -> Result<(), ShaderError> {
+    if words.len() < HEADER_WORDS {
+        return Err(ShaderError::Compile(format!(
+            "SPIR-V module is {} words, shorter than its header",
+            words.len()
+        )));
+    }
+    if words[0] != SPIRV_MAGIC {
+        return Err(ShaderError::Compile(format!(
+            "Invalid SPIR-V magic number {:#010x}",
+            words[0]
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_compiled_triangle_shader() {
+        let source = include_str!("../../wgpu-triangle/src/shader.wgsl");
+        let module = naga::front::wgsl::parse_str(source).unwrap();
+        let info = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .unwrap();
+        let words =
+            naga::back::spv::write_vec(&module, &info, &naga::back::spv::Options::default(), None)
+                .unwrap();
+
+        assert!(check_header(&words).is_ok());
+    }
+
+    #[test]
+    fn rejects_truncated_or_byte_swapped_words() {
+        assert!(check_header(&[SPIRV_MAGIC]).is_err());
+        // 엔디안을 잘못 읽은 바이트열
+        assert!(check_header(&[SPIRV_MAGIC.swap_bytes(), 0, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+use glam::Vec3;
+
+use crate::Camera;
+
+// 호 길이 표를 만들 때 세그먼트 하나를 나누는 수
+const SAMPLES_PER_SEGMENT: usize = 32;
+
+/// 3차 베지어 세그먼트를 이어 붙인 곡선.
+///
+/// 세그먼트 `[P0, P1, P2, P3]` 는 P0 에서 시작해서 P3 에서 끝나고, P1, P2 는 곡선을 당기는 손잡이다.
+/// 다음 세그먼트의 P0 은 앞 세그먼트의 P3 과 같아야 이어진다.
+///
+/// 베지어의 매개변수는 곡선을 따라 고르게 움직이지 않으므로, 세그먼트마다 길이를 미리 재 둔 표로
+/// 전체 매개변수 `t` 를 호 길이에 비례하도록 바꾼다. `t` 가 0 이면 시작, 1 이면 끝이고, 같은 간격으로
+/// `t` 를 늘리면 곡선 위를 같은 속도로 움직인다.
+#[derive(Clone, Debug)]
+pub struct BezierSpline {
+    segments: Vec<[Vec3; 4]>,
+    // 샘플 k 까지의 누적 길이. 샘플 k 는 세그먼트 k / S 의 매개변수 (k % S) / S 이다
+    arc_lengths: Vec<f32>,
+}
+
+impl BezierSpline {
+    /// # Panics
+    ///
+    /// 세그먼트가 없으면 panic 한다.
+    pub fn new(segments: Vec<[Vec3; 4]>) -> Self {
+        assert!(!segments.is_empty(), "spline has no segments");
+
+        let mut arc_lengths = Vec::with_capacity(segments.len() * SAMPLES_PER_SEGMENT + 1);
+        let mut length = 0.0;
+        let mut previous = segments[0][0];
+        arc_lengths.push(0.0);
+        for segment in &segments {
+            for i in 1..=SAMPLES_PER_SEGMENT {
+                let point = bezier(segment, i as f32 / SAMPLES_PER_SEGMENT as f32);
+                length += point.distance(previous);
+                arc_lengths.push(length);
+                previous = point;
+            }
+        }
+
+        Self {
+            segments,
+            arc_lengths,
+        }
+    }
+
+    /// `points` 를 모두 지나서 처음으로 돌아오는 닫힌 곡선을 만든다.
+    ///
+    /// 손잡이는 Catmull-Rom 처럼 이웃 점을 잇는 방향으로 놓아서 이음매에서 방향이 꺾이지 않는다.
+    ///
+    /// # Panics
+    ///
+    /// 점이 3개보다 적으면 panic 한다.
+    pub fn closed_through(points: &[Vec3]) -> Self {
+        assert!(points.len() >= 3, "closed spline needs at least 3 points");
+
+        let n = points.len();
+        let point = |i: usize| points[i % n];
+        let segments = (0..n)
+            .map(|i| {
+                let (prev, start, end, next) =
+                    (point(i + n - 1), point(i), point(i + 1), point(i + 2));
+                [
+                    start,
+                    start + (end - prev) / 6.0,
+                    end - (next - start) / 6.0,
+                    end,
+                ]
+            })
+            .collect();
+        Self::new(segments)
+    }
+
+    pub fn segments(&self) -> &[[Vec3; 4]] {
+        &self.segments
+    }
+
+    /// 곡선 전체의 길이 (근삿값)
+    pub fn length(&self) -> f32 {
+        *self.arc_lengths.last().unwrap()
+    }
+
+    /// 호 길이 기준 `t` (0 ~ 1) 위치의 점. 범위를 벗어난 `t` 는 잘라 낸다.
+    pub fn evaluate(&self, t: f32) -> Vec3 {
+        let (segment, u) = self.locate(t);
+        bezier(&self.segments[segment], u)
+    }
+
+    /// `t` 위치에서 진행 방향의 단위 벡터. 손잡이가 끝점과 겹쳐 미분이 0 이면 0 벡터다.
+    pub fn tangent(&self, t: f32) -> Vec3 {
+        let (segment, u) = self.locate(t);
+        bezier_derivative(&self.segments[segment], u).normalize_or_zero()
+    }
+
+    // 호 길이 기준 t 를 (세그먼트 번호, 세그먼트 안의 매개변수) 로 바꾼다
+    fn locate(&self, t: f32) -> (usize, f32) {
+        let distance = t.clamp(0.0, 1.0) * self.length();
+        let last = self.arc_lengths.len() - 1;
+        // distance 를 넘는 첫 샘플의 바로 앞 구간에서 선형 보간한다
+        let k = self
+            .arc_lengths
+            .partition_point(|&length| length <= distance)
+            .clamp(1, last)
+            - 1;
+        let span = self.arc_lengths[k + 1] - self.arc_lengths[k];
+        let fraction = if span > 0.0 {
+            ((distance - self.arc_lengths[k]) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let global = (k as f32 + fraction) / SAMPLES_PER_SEGMENT as f32;
+        let segment = (global as usize).min(self.segments.len() - 1);
+        (segment, global - segment as f32)
+    }
+}
+
+fn bezier([p0, p1, p2, p3]: &[Vec3; 4], u: f32) -> Vec3 {
+    let v = 1.0 - u;
+    p0 * (v * v * v) + p1 * (3.0 * v * v * u) + p2 * (3.0 * v * u * u) + p3 * (u * u * u)
+}
+
+fn bezier_derivative([p0, p1, p2, p3]: &[Vec3; 4], u: f32) -> Vec3 {
+    let v = 1.0 - u;
+    (p1 - p0) * (3.0 * v * v) + (p2 - p1) * (6.0 * v * u) + (p3 - p2) * (3.0 * u * u)
+}
+
+/// 스플라인을 따라 일정한 속도로 움직이면서 진행 방향을 바라보는 카메라를 만든다.
+///
+/// 매 프레임 `update` 에 지난 시간을 넘기면 `speed` 만큼 곡선 위를 나아가고, 그 위치의 `Camera` 를
+/// 돌려준다. 시야각, 종횡비, near, far 는 `new` 에 넘긴 카메라의 값을 그대로 쓴다.
+#[derive(Clone, Debug)]
+pub struct CameraPath {
+    spline: BezierSpline,
+    lens: Camera,
+    /// 초당 움직이는 거리 (월드 단위)
+    pub speed: f32,
+    /// 끝에 닿으면 처음으로 돌아간다. `false` 면 끝에서 멈춘다
+    pub looping: bool,
+    // 시작에서부터 곡선을 따라 온 거리
+    distance: f32,
+}
+
+impl CameraPath {
+    pub fn new(spline: BezierSpline, speed: f32, lens: Camera) -> Self {
+        Self {
+            spline,
+            lens,
+            speed,
+            looping: true,
+            distance: 0.0,
+        }
+    }
+
+    pub fn spline(&self) -> &BezierSpline {
+        &self.spline
+    }
+
+    /// 캔버스 크기가 바뀌면 호출한다.
+    pub fn set_aspect(&mut self, aspect: f32) {
+        self.lens.aspect = aspect;
+    }
+
+    /// 지금 위치의 호 길이 기준 매개변수 (0 ~ 1)
+    pub fn progress(&self) -> f32 {
+        let length = self.spline.length();
+        if length > 0.0 {
+            self.distance / length
+        } else {
+            0.0
+        }
+    }
+
+    pub fn set_progress(&mut self, t: f32) {
+        self.distance = t.clamp(0.0, 1.0) * self.spline.length();
+    }
+
+    /// `dt_secs` 만큼 나아가고 그 위치의 카메라를 돌려준다.
+    pub fn update(&mut self, dt_secs: f32) -> Camera {
+        let length = self.spline.length();
+        self.distance += self.speed * dt_secs;
+        self.distance = if self.looping && length > 0.0 {
+            self.distance.rem_euclid(length)
+        } else {
+            self.distance.clamp(0.0, length)
+        };
+        self.camera()
+    }
+
+    /// 지금 위치에서 곡선의 진행 방향을 바라보는 카메라
+    pub fn camera(&self) -> Camera {
+        let t = self.progress();
+        let eye = self.spline.evaluate(t);
+        Camera {
+            eye,
+            target: eye + self.spline.tangent(t),
+            ..self.lens
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-2, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn evaluate_is_arc_length_parameterized() {
+        // 손잡이가 한쪽에 몰려 있어서 베지어 매개변수로는 0.5 가 가운데가 아니다
+        let spline = BezierSpline::new(vec![[
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::new(3.0, 0.0, 0.0),
+        ]]);
+        assert!((spline.length() - 3.0).abs() < 1e-3);
+        assert_near(
+            bezier(&spline.segments()[0], 0.5),
+            Vec3::new(0.375, 0.0, 0.0),
+        );
+
+        assert_near(spline.evaluate(0.0), Vec3::ZERO);
+        assert_near(spline.evaluate(0.5), Vec3::new(1.5, 0.0, 0.0));
+        assert_near(spline.evaluate(1.0), Vec3::new(3.0, 0.0, 0.0));
+        assert_near(spline.evaluate(2.0), Vec3::new(3.0, 0.0, 0.0));
+        assert_near(spline.tangent(0.5), Vec3::X);
+    }
+
+    #[test]
+    fn segments_share_t_by_length() {
+        // 길이 1 인 세그먼트 뒤에 길이 3 인 세그먼트
+        let line = |a: Vec3, b: Vec3| [a, a.lerp(b, 1.0 / 3.0), a.lerp(b, 2.0 / 3.0), b];
+        let corner = Vec3::new(1.0, 0.0, 0.0);
+        let end = Vec3::new(1.0, 0.0, 3.0);
+        let spline = BezierSpline::new(vec![line(Vec3::ZERO, corner), line(corner, end)]);
+
+        assert!((spline.length() - 4.0).abs() < 1e-3);
+        assert_near(spline.evaluate(0.25), corner);
+        assert_near(spline.evaluate(0.5), Vec3::new(1.0, 0.0, 1.0));
+        assert_near(spline.tangent(0.1), Vec3::X);
+        assert_near(spline.tangent(0.75), Vec3::Z);
+    }
+
+    #[test]
+    fn closed_spline_passes_through_points() {
+        let points = [
+            Vec3::new(5.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 5.0),
+            Vec3::new(-5.0, 0.0, 0.0),
+            Vec3::new(0.0, -1.0, -5.0),
+        ];
+        let spline = BezierSpline::closed_through(&points);
+        assert_eq!(spline.segments().len(), 4);
+        for (segment, point) in spline.segments().iter().zip(points) {
+            assert_eq!(segment[0], point);
+        }
+        assert_near(spline.evaluate(0.0), spline.evaluate(1.0));
+
+        // 이음매 양쪽의 손잡이가 한 직선 위에 있다
+        let (incoming, outgoing) = (spline.segments()[0][2], spline.segments()[1][1]);
+        let joint = points[1];
+        assert!(
+            (joint - incoming)
+                .normalize()
+                .dot((outgoing - joint).normalize())
+                > 0.999
+        );
+    }
+
+    #[test]
+    fn camera_path_moves_at_speed_and_wraps() {
+        let spline = BezierSpline::new(vec![[
+            Vec3::ZERO,
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 0.0),
+        ]]);
+        let lens = Camera::new(Vec3::ZERO, Vec3::NEG_Z, 1.5);
+        let mut path = CameraPath::new(spline, 2.0, lens);
+
+        let camera = path.update(0.5);
+        assert_near(camera.eye, Vec3::new(1.0, 0.0, 0.0));
+        assert_near((camera.target - camera.eye).normalize(), Vec3::X);
+        assert_eq!(camera.aspect, 1.5);
+
+        // 끝을 지나면 처음으로 돌아간다
+        let camera = path.update(2.0);
+        assert_near(camera.eye, Vec3::new(1.0, 0.0, 0.0));
+
+        // 반복하지 않으면 끝에서 멈춘다
+        path.looping = false;
+        let camera = path.update(10.0);
+        assert_near(camera.eye, Vec3::new(4.0, 0.0, 0.0));
+        assert_eq!(path.progress(), 1.0);
+    }
+}
@@ -0,0 +1,188 @@
+use glam::{Vec2, Vec4};
+use wgpu::util::DeviceExt;
+
+use crate::{GpuContext, SpriteAnimator, Vertex, VertexBuffer};
+
+/// 스프라이트 하나를 그리기 위한 정보
+#[derive(Clone, Copy, Debug)]
+pub struct Sprite {
+    /// 스프라이트 중심 위치
+    pub position: Vec2,
+    pub size: Vec2,
+    /// 중심을 기준으로 한 반시계 방향 회전 (라디안)
+    pub rotation: f32,
+    /// 텍스처에서 사용할 영역 `(u, v, 너비, 높이)`
+    pub uv_rect: Vec4,
+    /// 텍스처 색에 곱해지는 색
+    pub tint: Vec4,
+}
+
+impl Sprite {
+    /// 텍스처 전체를 흰색 틴트로 그리는 회전 없는 스프라이트
+    pub fn new(position: Vec2, size: Vec2) -> Self {
+        Self {
+            position,
+            size,
+            rotation: 0.0,
+            uv_rect: Vec4::new(0.0, 0.0, 1.0, 1.0),
+            tint: Vec4::ONE,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SpriteVertex {
+    pub position: [f32; 2],
+    pub uv: [f32; 2],
+    pub tint: [f32; 4],
+}
+
+impl Vertex for SpriteVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4];
+}
+
+// 왼쪽 아래부터 반시계 방향
+const CORNERS: [Vec2; 4] = [
+    Vec2::new(-0.5, -0.5),
+    Vec2::new(0.5, -0.5),
+    Vec2::new(0.5, 0.5),
+    Vec2::new(-0.5, 0.5),
+];
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 2, 3, 0];
+
+/// 한 프레임 동안 쌓인 스프라이트들을 버퍼 하나에 올려서 `draw_indexed` 한 번으로 그린다.
+///
+/// 매 프레임 `clear` → `push` → `prepare` → `draw` 순서로 사용한다.
+/// 버텍스 셰이더는 `SpriteVertex` 의 위치, UV, 틴트를 `@location(0..3)` 으로 받는다.
+pub struct SpriteBatch {
+    sprites: Vec<Sprite>,
+    vertices: Vec<SpriteVertex>,
+    vertex_buffer: VertexBuffer<SpriteVertex>,
+    index_buffer: wgpu::Buffer,
+    capacity: usize,
+    // 마지막 prepare 에서 올린 스프라이트 수
+    prepared: usize,
+}
+
+impl SpriteBatch {
+    pub fn new(device: &wgpu::Device, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (vertex_buffer, index_buffer) = create_buffers(device, capacity);
+
+        Self {
+            sprites: Vec::with_capacity(capacity),
+            vertices: Vec::with_capacity(capacity * 4),
+            vertex_buffer,
+            index_buffer,
+            capacity,
+            prepared: 0,
+        }
+    }
+
+    pub fn push(&mut self, sprite: Sprite) {
+        self.sprites.push(sprite);
+    }
+
+    /// 애니메이션의 지금 프레임을 `position` 에 `animator.size` 크기로 쌓는다.
+    /// 시간은 미리 `SpriteAnimator::update` 로 넘겨 둔다.
+    pub fn draw_animated_sprite(&mut self, animator: &SpriteAnimator, position: Vec2) {
+        self.push(Sprite {
+            uv_rect: Vec4::from_array(animator.current_rect()),
+            ..Sprite::new(position, animator.size)
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.sprites.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.sprites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sprites.is_empty()
+    }
+
+    /// GPU 버퍼에 담을 수 있는 스프라이트 수
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 쌓인 스프라이트를 버텍스로 변환해서 한 번에 업로드한다.
+    /// 용량이 부족하면 버퍼를 두 배씩 키워서 다시 만든다.
+    pub fn prepare(&mut self, gpu: &GpuContext) {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        if self.sprites.len() > self.capacity {
+            self.capacity = self.sprites.len().next_power_of_two();
+            let (vertex_buffer, index_buffer) = create_buffers(device, self.capacity);
+            self.vertex_buffer = vertex_buffer;
+            self.index_buffer = index_buffer;
+        }
+
+        self.vertices.clear();
+        for sprite in &self.sprites {
+            let (sin, cos) = sprite.rotation.sin_cos();
+            for corner in CORNERS {
+                let local = corner * sprite.size;
+                let rotated =
+                    Vec2::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos);
+                // 텍스처 좌표는 왼쪽 위가 (0, 0) 이므로 v 를 뒤집는다
+                let uv = Vec2::new(
+                    sprite.uv_rect.x + (corner.x + 0.5) * sprite.uv_rect.z,
+                    sprite.uv_rect.y + (0.5 - corner.y) * sprite.uv_rect.w,
+                );
+
+                self.vertices.push(SpriteVertex {
+                    position: (sprite.position + rotated).to_array(),
+                    uv: uv.to_array(),
+                    tint: sprite.tint.to_array(),
+                });
+            }
+        }
+
+        if !self.vertices.is_empty() {
+            self.vertex_buffer.write(queue, &self.vertices);
+        }
+        self.prepared = self.sprites.len();
+    }
+
+    /// 마지막 `prepare` 로 올린 스프라이트를 그린다.
+    /// 파이프라인과 텍스처 바인드 그룹은 호출하는 쪽에서 설정한다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        if self.prepared == 0 {
+            return;
+        }
+
+        self.vertex_buffer.bind(render_pass, 0);
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..(self.prepared * QUAD_INDICES.len()) as u32, 0, 0..1);
+    }
+
+    /// `wgpu::VertexState::buffers` 에 넘길 레이아웃
+    pub fn vertex_layout() -> wgpu::VertexBufferLayout<'static> {
+        SpriteVertex::layout()
+    }
+}
+
+// 스프라이트마다 꼭짓점 4개, 인덱스는 0,1,2,2,3,0 패턴을 반복한다
+fn create_buffers(
+    device: &wgpu::Device,
+    capacity: usize,
+) -> (VertexBuffer<SpriteVertex>, wgpu::Buffer) {
+    let vertices = vec![bytemuck::Zeroable::zeroed(); capacity * 4];
+    let vertex_buffer = VertexBuffer::writable(device, &vertices);
+
+    let indices: Vec<u32> = (0..capacity as u32)
+        .flat_map(|sprite| QUAD_INDICES.map(|index| sprite * 4 + index))
+        .collect();
+    let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Sprite Index Buffer"),
+        contents: bytemuck::cast_slice(&indices),
+        usage: wgpu::BufferUsages::INDEX,
+    });
+
+    (vertex_buffer, index_buffer)
+}
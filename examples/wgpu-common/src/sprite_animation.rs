@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::AtlasRect;
+
+/// 마지막 프레임에 닿았을 때 어떻게 할지
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoopMode {
+    /// 마지막 프레임에서 멈춘다
+    Once,
+    /// 첫 프레임으로 돌아간다
+    #[default]
+    Repeat,
+    /// 거꾸로 재생해서 첫 프레임까지 돌아온 뒤 다시 앞으로 간다
+    PingPong,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    rect: AtlasRect,
+    duration_ms: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Playback {
+    name: String,
+    loop_mode: LoopMode,
+    frame: usize,
+    // 지금 프레임에서 지난 시간 (밀리초)
+    elapsed_ms: f32,
+    // PingPong 에서 거꾸로 가는 중인지
+    reversed: bool,
+    playing: bool,
+}
+
+/// 아틀라스 영역을 프레임마다 바꿔 가며 보여 주는 스프라이트 애니메이션.
+///
+/// 이름으로 프레임 목록을 등록해 두고 `play` 로 고른 뒤, 매 프레임 `update` 로 시간을 넘기면
+/// 지금 보여 줄 영역을 돌려준다. 그릴 때는 `SpriteBatch::draw_animated_sprite` 에 넘긴다.
+#[derive(Clone, Debug)]
+pub struct SpriteAnimator {
+    animations: HashMap<String, Vec<Frame>>,
+    playback: Option<Playback>,
+    /// 스프라이트를 그릴 크기
+    pub size: Vec2,
+}
+
+impl SpriteAnimator {
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            animations: HashMap::new(),
+            playback: None,
+            size,
+        }
+    }
+
+    /// `(영역, 보여 줄 시간(밀리초))` 목록을 `name` 으로 등록한다. 같은 이름이 있으면 바꾼다.
+    ///
+    /// # Panics
+    ///
+    /// 프레임이 없거나 시간이 0 이하인 프레임이 있으면 panic 한다.
+    pub fn add_animation(&mut self, name: &str, frames: &[(AtlasRect, f32)]) {
+        assert!(!frames.is_empty(), "animation '{}' has no frames", name);
+        assert!(
+            frames.iter().all(|&(_, duration_ms)| duration_ms > 0.0),
+            "animation '{}' has a frame with non-positive duration",
+            name
+        );
+        self.animations.insert(
+            name.to_string(),
+            frames
+                .iter()
+                .map(|&(rect, duration_ms)| Frame { rect, duration_ms })
+                .collect(),
+        );
+    }
+
+    /// `name` 을 첫 프레임부터 재생한다. 이미 같은 애니메이션을 재생 중이면 반복 방식만 바꾸고
+    /// 이어서 재생하므로, 매 프레임 불러도 된다.
+    ///
+    /// # Panics
+    ///
+    /// `name` 이 등록되어 있지 않으면 panic 한다.
+    pub fn play(&mut self, name: &str, loop_mode: LoopMode) {
+        assert!(
+            self.animations.contains_key(name),
+            "unknown animation '{}'",
+            name
+        );
+        if let Some(playback) = &mut self.playback
+            && playback.name == name
+            && playback.playing
+        {
+            playback.loop_mode = loop_mode;
+            return;
+        }
+
+        self.playback = Some(Playback {
+            name: name.to_string(),
+            loop_mode,
+            frame: 0,
+            elapsed_ms: 0.0,
+            reversed: false,
+            playing: true,
+        });
+    }
+
+    /// 지금 프레임에서 멈춘다. 다시 `play` 하면 처음부터 재생한다.
+    pub fn stop(&mut self) {
+        if let Some(playback) = &mut self.playback {
+            playback.playing = false;
+        }
+    }
+
+    /// `LoopMode::Once` 가 끝났거나 `stop` 했으면 `false`
+    pub fn is_playing(&self) -> bool {
+        self.playback
+            .as_ref()
+            .is_some_and(|playback| playback.playing)
+    }
+
+    /// 재생 중이거나 마지막으로 재생한 애니메이션의 이름
+    pub fn current_animation(&self) -> Option<&str> {
+        self.playback
+            .as_ref()
+            .map(|playback| playback.name.as_str())
+    }
+
+    /// 지금 보여 줄 프레임의 번호
+    pub fn current_frame(&self) -> usize {
+        self.playback.as_ref().map_or(0, |playback| playback.frame)
+    }
+
+    /// 지금 보여 줄 영역. 아무것도 재생하지 않았으면 텍스처 전체다.
+    pub fn current_rect(&self) -> AtlasRect {
+        self.playback
+            .as_ref()
+            .map_or([0.0, 0.0, 1.0, 1.0], |playback| {
+                self.animations[&playback.name][playback.frame].rect
+            })
+    }
+
+    /// `dt_ms` 만큼 시간을 넘기고 지금 보여 줄 영역을 돌려준다.
+    /// 한 번에 여러 프레임을 지나갈 만큼 시간이 흘러도 프레임을 건너뛰어 따라잡는다.
+    pub fn update(&mut self, dt_ms: f32) -> AtlasRect {
+        if let Some(playback) = &mut self.playback
+            && playback.playing
+        {
+            let frames = &self.animations[&playback.name];
+            playback.elapsed_ms += dt_ms;
+            while playback.playing && playback.elapsed_ms >= frames[playback.frame].duration_ms {
+                playback.elapsed_ms -= frames[playback.frame].duration_ms;
+                advance(playback, frames.len());
+            }
+        }
+        self.current_rect()
+    }
+}
+
+// 다음 프레임으로 넘긴다. Once 가 끝나면 마지막 프레임에서 멈춘다
+fn advance(playback: &mut Playback, len: usize) {
+    let last = len - 1;
+    match playback.loop_mode {
+        LoopMode::Once => {
+            if playback.frame == last {
+                playback.playing = false;
+                playback.elapsed_ms = 0.0;
+            } else {
+                playback.frame += 1;
+            }
+        }
+        LoopMode::Repeat => playback.frame = (playback.frame + 1) % len,
+        LoopMode::PingPong => {
+            if len == 1 {
+                return;
+            }
+            // 양 끝 프레임은 두 번 보여 주지 않고 바로 방향을 바꾼다
+            if playback.reversed && playback.frame == 0 {
+                playback.reversed = false;
+            } else if !playback.reversed && playback.frame == last {
+                playback.reversed = true;
+            }
+            if playback.reversed {
+                playback.frame -= 1;
+            } else {
+                playback.frame += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 가로로 n 칸인 스프라이트 시트의 i 번째 칸
+    fn cell(i: usize, n: usize) -> AtlasRect {
+        [i as f32 / n as f32, 0.0, 1.0 / n as f32, 1.0]
+    }
+
+    fn animator(frames: usize) -> SpriteAnimator {
+        let mut animator = SpriteAnimator::new(Vec2::splat(32.0));
+        let frames: Vec<_> = (0..frames).map(|i| (cell(i, frames), 100.0)).collect();
+        animator.add_animation("walk", &frames);
+        animator
+    }
+
+    fn frames_over(animator: &mut SpriteAnimator, steps: usize) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                animator.update(100.0);
+                animator.current_frame()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn once_stops_on_last_frame() {
+        let mut animator = animator(3);
+        animator.play("walk", LoopMode::Once);
+        assert_eq!(frames_over(&mut animator, 4), [1, 2, 2, 2]);
+        assert!(!animator.is_playing());
+        assert_eq!(animator.current_rect(), cell(2, 3));
+    }
+
+    #[test]
+    fn repeat_wraps_around() {
+        let mut animator = animator(3);
+        animator.play("walk", LoopMode::Repeat);
+        assert_eq!(frames_over(&mut animator, 5), [1, 2, 0, 1, 2]);
+
+        // 프레임 시간보다 길게 넘기면 여러 프레임을 건너뛴다
+        assert_eq!(animator.update(250.0), cell(1, 3));
+        assert_eq!(animator.update(50.0), cell(2, 3));
+    }
+
+    #[test]
+    fn ping_pong_bounces_without_repeating_ends() {
+        let mut animator = animator(3);
+        animator.play("walk", LoopMode::PingPong);
+        assert_eq!(frames_over(&mut animator, 6), [1, 2, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn play_same_animation_keeps_progress() {
+        let mut animator = animator(4);
+        animator.play("walk", LoopMode::Repeat);
+        animator.update(150.0);
+        animator.play("walk", LoopMode::Repeat);
+        assert_eq!(animator.current_frame(), 1);
+
+        // 멈춘 뒤 다시 재생하면 처음부터
+        animator.stop();
+        assert_eq!(animator.update(1000.0), cell(1, 4));
+        animator.play("walk", LoopMode::Repeat);
+        assert_eq!(animator.current_frame(), 0);
+    }
+}
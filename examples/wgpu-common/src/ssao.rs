@@ -0,0 +1,459 @@
+use glam::{Mat4, Vec3};
+use wgpu::util::DeviceExt;
+
+/// 커널 샘플 수의 최대값. ssao.wgsl 의 `kernel` 배열 길이와 같아야 한다
+pub const MAX_KERNEL_SIZE: usize = 64;
+// 노이즈 텍스처 한 변의 텍셀 수. 블러도 같은 크기(4x4)로 해서 반복 무늬를 지운다
+const NOISE_SIZE: u32 = 4;
+
+// ssao.wgsl 의 SsaoParams 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SsaoParams {
+    projection: [[f32; 4]; 4],
+    inv_projection: [[f32; 4]; 4],
+    view: [[f32; 4]; 4],
+    kernel: [[f32; 4]; MAX_KERNEL_SIZE],
+    radius: f32,
+    kernel_size: u32,
+    _padding: [f32; 2],
+}
+
+/// G-버퍼의 깊이와 법선으로 화면 공간 앰비언트 오클루전(SSAO)을 계산하는 후처리.
+///
+/// 픽셀마다 법선 쪽 반구 안의 샘플 점들을 투영해서, 그 자리의 깊이가 샘플보다 앞에 있으면
+/// 가려졌다고 센다. 반구는 4x4 노이즈 텍스처의 방향으로 픽셀마다 돌리고,
+/// 그 때문에 생기는 무늬는 깊이 차이로 가중치를 준 4x4 바이래터럴 블러로 지운다.
+///
+/// 깊이는 `DepthTexture::FORMAT`, 법선은 월드 공간 법선을 [0, 1] 로 옮겨 담고
+/// 배경의 알파가 0 인 텍스처여야 한다. 결과는 `OUTPUT_FORMAT` 의 한 채널(1 이면 가려지지 않음)이다.
+pub struct SsaoPass {
+    device: wgpu::Device,
+    ssao_bind_group_layout: wgpu::BindGroupLayout,
+    blur_bind_group_layout: wgpu::BindGroupLayout,
+    ssao_pipeline: wgpu::RenderPipeline,
+    blur_pipeline: wgpu::RenderPipeline,
+    noise_view: wgpu::TextureView,
+    noise_texture: wgpu::Texture,
+    // 첫 apply 에서 노이즈 텍스처로 복사하고 버린다
+    noise_upload: Option<wgpu::Buffer>,
+    ao_view: wgpu::TextureView,
+    kernel: Vec<[f32; 4]>,
+    radius: f32,
+    view: Mat4,
+    projection: Mat4,
+    size: (u32, u32),
+}
+
+impl SsaoPass {
+    /// `apply` 의 `output_view` 포맷
+    pub const OUTPUT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+
+    /// `kernel_size` 는 픽셀마다 검사하는 샘플 수(최대 `MAX_KERNEL_SIZE`),
+    /// `radius` 는 반구의 반지름(뷰 공간 거리)이다.
+    pub fn new(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        kernel_size: usize,
+        radius: f32,
+    ) -> Self {
+        assert!(
+            (1..=MAX_KERNEL_SIZE).contains(&kernel_size),
+            "kernel size must be in 1..={}",
+            MAX_KERNEL_SIZE
+        );
+
+        let uniform_entry = wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let depth_entry = wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Depth,
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+
+        // 0: 파라미터, 1: 깊이, 2: 법선, 3: 노이즈
+        let ssao_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("SSAO Bind Group Layout"),
+                entries: &[
+                    uniform_entry,
+                    depth_entry,
+                    texture_entry(2),
+                    texture_entry(3),
+                ],
+            });
+        // 0: 파라미터, 1: 깊이, 2: 블러 전 AO
+        let blur_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("SSAO Blur Bind Group Layout"),
+                entries: &[uniform_entry, depth_entry, texture_entry(2)],
+            });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("SSAO Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("ssao.wgsl").into()),
+        });
+        let create_pipeline = |label, layout: &wgpu::BindGroupLayout, entry_point| {
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(label),
+                bind_group_layouts: &[layout],
+                push_constant_ranges: &[],
+            });
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: Self::OUTPUT_FORMAT,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let ssao_pipeline = create_pipeline("SSAO Pipeline", &ssao_bind_group_layout, "fs_ssao");
+        let blur_pipeline =
+            create_pipeline("SSAO Blur Pipeline", &blur_bind_group_layout, "fs_blur");
+
+        // 큐가 없으므로 노이즈는 버퍼에 담아 두었다가 첫 apply 의 인코더에서 복사한다.
+        // 버퍼에서 텍스처로 복사할 때 행 간격은 256 바이트의 배수여야 한다
+        let mut noise_bytes = vec![0u8; (wgpu::COPY_BYTES_PER_ROW_ALIGNMENT * NOISE_SIZE) as usize];
+        for (i, rotation) in noise_rotations().iter().enumerate() {
+            let row = i / NOISE_SIZE as usize * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+            let offset = row + i % NOISE_SIZE as usize * 4;
+            for (byte, value) in noise_bytes[offset..offset + 4].iter_mut().zip(rotation) {
+                *byte = ((value * 127.0).round() as i8) as u8;
+            }
+        }
+        let noise_upload = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("SSAO Noise Upload Buffer"),
+            contents: &noise_bytes,
+            usage: wgpu::BufferUsages::COPY_SRC,
+        });
+        let noise_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("SSAO Noise Texture"),
+            size: wgpu::Extent3d {
+                width: NOISE_SIZE,
+                height: NOISE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Snorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let noise_view = noise_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            device: device.clone(),
+            ssao_bind_group_layout,
+            blur_bind_group_layout,
+            ssao_pipeline,
+            blur_pipeline,
+            noise_view,
+            noise_texture,
+            noise_upload: Some(noise_upload),
+            ao_view: create_ao_view(device, width, height),
+            kernel: hemisphere_kernel(kernel_size),
+            radius,
+            view: Mat4::IDENTITY,
+            projection: Mat4::IDENTITY,
+            size: (width, height),
+        }
+    }
+
+    /// 깊이와 법선을 그린 카메라. 다음 `apply` 부터 쓰인다
+    pub fn set_camera(&mut self, view: Mat4, projection: Mat4) {
+        self.view = view;
+        self.projection = projection;
+    }
+
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn kernel_size(&self) -> usize {
+        self.kernel.len()
+    }
+
+    /// G-버퍼 크기가 바뀌면 블러 전 AO 텍스처를 다시 만든다.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if (width, height) == self.size {
+            return;
+        }
+        self.size = (width, height);
+        self.ao_view = create_ao_view(&self.device, width, height);
+    }
+
+    /// `depth_view` 와 `normal_view` 로 AO 를 계산하고 블러해서 `output_view` 에 쓴다.
+    pub fn apply(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth_view: &wgpu::TextureView,
+        normal_view: &wgpu::TextureView,
+        output_view: &wgpu::TextureView,
+    ) {
+        if let Some(upload) = self.noise_upload.take() {
+            encoder.copy_buffer_to_texture(
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &upload,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                        rows_per_image: Some(NOISE_SIZE),
+                    },
+                },
+                self.noise_texture.as_image_copy(),
+                wgpu::Extent3d {
+                    width: NOISE_SIZE,
+                    height: NOISE_SIZE,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        let mut kernel = [[0.0; 4]; MAX_KERNEL_SIZE];
+        kernel[..self.kernel.len()].copy_from_slice(&self.kernel);
+        let params = SsaoParams {
+            projection: self.projection.to_cols_array_2d(),
+            inv_projection: self.projection.inverse().to_cols_array_2d(),
+            view: self.view.to_cols_array_2d(),
+            kernel,
+            radius: self.radius,
+            kernel_size: self.kernel.len() as u32,
+            _padding: [0.0; 2],
+        };
+        let params_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("SSAO Params Buffer"),
+                contents: bytemuck::bytes_of(&params),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let ssao_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("SSAO Bind Group"),
+            layout: &self.ssao_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(normal_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&self.noise_view),
+                },
+            ],
+        });
+        let blur_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("SSAO Blur Bind Group"),
+            layout: &self.blur_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&self.ao_view),
+                },
+            ],
+        });
+
+        let passes = [
+            (
+                "SSAO Pass",
+                &self.ssao_pipeline,
+                &ssao_bind_group,
+                &self.ao_view,
+            ),
+            (
+                "SSAO Blur Pass",
+                &self.blur_pipeline,
+                &blur_bind_group,
+                output_view,
+            ),
+        ];
+        for (label, pipeline, bind_group, target) in passes {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
+}
+
+fn create_ao_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("SSAO Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: SsaoPass::OUTPUT_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+// particle_update.wgsl 과 같은 PCG 해시로 [0, 1) 난수를 만든다
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        let state = self.0.wrapping_mul(747796405).wrapping_add(2891336453);
+        let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+        self.0 = (word >> 22) ^ word;
+        self.0 as f32 / 4294967296.0
+    }
+}
+
+// +z 쪽 단위 반구 안의 샘플. 가까운 곳의 가림이 더 중요하므로 원점 가까이에 몰리도록 늘어놓는다
+fn hemisphere_kernel(kernel_size: usize) -> Vec<[f32; 4]> {
+    let mut random = Random(1);
+    (0..kernel_size)
+        .map(|i| {
+            let direction = Vec3::new(
+                random.next() * 2.0 - 1.0,
+                random.next() * 2.0 - 1.0,
+                random.next(),
+            )
+            .normalize_or(Vec3::Z);
+            let t = i as f32 / kernel_size as f32;
+            let scale = 0.1 + 0.9 * t * t;
+            (direction * random.next() * scale).extend(0.0).into()
+        })
+        .collect()
+}
+
+// 반구를 법선 둘레로 돌릴 xy 평면 위의 방향
+fn noise_rotations() -> Vec<[f32; 4]> {
+    let mut random = Random(2);
+    (0..NOISE_SIZE * NOISE_SIZE)
+        .map(|_| {
+            let angle = random.next() * std::f32::consts::TAU;
+            [angle.cos(), angle.sin(), 0.0, 0.0]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernel_stays_inside_hemisphere() {
+        let kernel = hemisphere_kernel(MAX_KERNEL_SIZE);
+        assert_eq!(kernel.len(), MAX_KERNEL_SIZE);
+        for sample in &kernel {
+            let sample = Vec3::new(sample[0], sample[1], sample[2]);
+            assert!(sample.z >= 0.0, "{sample}");
+            assert!(sample.length() <= 1.0, "{sample}");
+        }
+
+        // 앞쪽 절반은 원점에 더 가깝게 몰려 있다
+        let average_length = |samples: &[[f32; 4]]| {
+            samples
+                .iter()
+                .map(|s| Vec3::new(s[0], s[1], s[2]).length())
+                .sum::<f32>()
+                / samples.len() as f32
+        };
+        let (near, far) = kernel.split_at(MAX_KERNEL_SIZE / 2);
+        assert!(average_length(near) < average_length(far));
+    }
+
+    #[test]
+    fn noise_rotates_around_z() {
+        let rotations = noise_rotations();
+        assert_eq!(rotations.len(), (NOISE_SIZE * NOISE_SIZE) as usize);
+        for [x, y, z, _] in rotations {
+            assert_eq!(z, 0.0);
+            assert!(((x * x + y * y).sqrt() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn params_match_wgsl_layout() {
+        // mat4 3개 + vec4 64개 + f32, u32 와 패딩
+        assert_eq!(
+            std::mem::size_of::<SsaoParams>(),
+            64 * 3 + 16 * MAX_KERNEL_SIZE + 16
+        );
+    }
+}
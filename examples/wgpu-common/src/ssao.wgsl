@@ -0,0 +1,124 @@
+struct SsaoParams {
+    projection: mat4x4<f32>,
+    inv_projection: mat4x4<f32>,
+    view: mat4x4<f32>,
+    // +z 쪽 단위 반구 안의 샘플. kernel_size 개만 쓴다
+    kernel: array<vec4<f32>, 64>,
+    radius: f32,
+    kernel_size: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> params: SsaoParams;
+@group(0) @binding(1)
+var depth_texture: texture_depth_2d;
+// fs_ssao 에서는 G-버퍼 법선, fs_blur 에서는 블러 전 AO
+@group(0) @binding(2)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(3)
+var noise_texture: texture_2d<f32>;
+
+// 평평한 면이 자기 자신을 가리지 않도록 샘플을 조금 앞으로 당긴다
+const BIAS: f32 = 0.025;
+// 블러에서 깊이 차이에 따라 가중치가 줄어드는 정도
+const DEPTH_SHARPNESS: f32 = 8.0;
+
+struct FullscreenOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// fullscreen.wgsl 과 같다
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    var out: FullscreenOutput;
+    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return out;
+}
+
+// 왼쪽 위가 (0, 0) 인 텍스처 좌표와 깊이로 뷰 공간 위치를 되돌린다
+fn view_position(uv: vec2<f32>, depth: f32) -> vec3<f32> {
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    let position = params.inv_projection * ndc;
+    return position.xyz / position.w;
+}
+
+fn pixel_uv(pixel: vec2<i32>) -> vec2<f32> {
+    return (vec2<f32>(pixel) + 0.5) / vec2<f32>(textureDimensions(depth_texture));
+}
+
+@fragment
+fn fs_ssao(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let encoded_normal = textureLoad(input_texture, pixel, 0);
+    // 지오메트리가 없는 픽셀은 가려지지 않는다
+    if encoded_normal.a == 0.0 {
+        return vec4<f32>(1.0);
+    }
+
+    let position = view_position(in.uv, textureLoad(depth_texture, pixel, 0));
+    let world_normal = encoded_normal.xyz * 2.0 - 1.0;
+    let normal = normalize((params.view * vec4<f32>(world_normal, 0.0)).xyz);
+
+    // 4x4 픽셀마다 같은 무늬가 반복되도록 노이즈 방향으로 반구를 돌린다
+    let noise = textureLoad(noise_texture, pixel % vec2<i32>(4), 0).xyz;
+    let tangent = normalize(noise - normal * dot(noise, normal));
+    let bitangent = cross(normal, tangent);
+    let tbn = mat3x3<f32>(tangent, bitangent, normal);
+
+    let size = vec2<f32>(textureDimensions(depth_texture));
+    var occlusion = 0.0;
+    for (var i = 0u; i < params.kernel_size; i += 1u) {
+        let sample_position = position + tbn * params.kernel[i].xyz * params.radius;
+
+        let clip = params.projection * vec4<f32>(sample_position, 1.0);
+        let ndc = clip.xy / clip.w;
+        let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+        if any(uv < vec2<f32>(0.0)) || any(uv >= vec2<f32>(1.0)) {
+            continue;
+        }
+
+        let sample_pixel = vec2<i32>(uv * size);
+        let scene_z = view_position(uv, textureLoad(depth_texture, sample_pixel, 0)).z;
+        // 반지름보다 훨씬 앞에 있는 물체는 가림에 덜 반영한다
+        let range = smoothstep(0.0, 1.0, params.radius / abs(position.z - scene_z));
+        // 오른손 좌표계의 뷰 공간은 -z 를 바라보므로 z 가 클수록 카메라에 가깝다
+        if scene_z >= sample_position.z + BIAS {
+            occlusion += range;
+        }
+    }
+
+    return vec4<f32>(1.0 - occlusion / f32(params.kernel_size));
+}
+
+// 노이즈 크기와 같은 4x4 영역을 평균 내되, 깊이가 많이 다른 픽셀은 덜 섞어서 경계를 지킨다
+@fragment
+fn fs_blur(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let center_depth = textureLoad(depth_texture, pixel, 0);
+    if center_depth >= 1.0 {
+        return vec4<f32>(1.0);
+    }
+    let center_z = view_position(in.uv, center_depth).z;
+
+    let max_pixel = vec2<i32>(textureDimensions(depth_texture)) - 1;
+    var sum = 0.0;
+    var total_weight = 0.0;
+    for (var y = -2; y < 2; y += 1) {
+        for (var x = -2; x < 2; x += 1) {
+            let sample_pixel = clamp(pixel + vec2<i32>(x, y), vec2<i32>(0), max_pixel);
+            let depth = textureLoad(depth_texture, sample_pixel, 0);
+            if depth >= 1.0 {
+                continue;
+            }
+            let z = view_position(pixel_uv(sample_pixel), depth).z;
+            let weight = 1.0 / (1.0 + abs(z - center_z) * DEPTH_SHARPNESS);
+            sum += textureLoad(input_texture, sample_pixel, 0).r * weight;
+            total_weight += weight;
+        }
+    }
+
+    return vec4<f32>(sum / max(total_weight, 1e-4));
+}
@@ -0,0 +1,360 @@
+use wgpu::util::DeviceExt;
+
+use crate::HdrRenderTarget;
+
+/// `SsrPass::set_roughness_threshold` 의 기본값
+pub const DEFAULT_ROUGHNESS_THRESHOLD: f32 = 0.5;
+
+// ssr.wgsl 의 SsrParams 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SsrParams {
+    max_steps: u32,
+    step_size: f32,
+    roughness_threshold: f32,
+    thickness: f32,
+}
+
+/// G-버퍼의 깊이와 법선으로 화면 공간 반사(SSR)를 더하는 후처리.
+///
+/// 거칠기가 `roughness_threshold` 보다 낮은 픽셀마다 반사 방향으로 광선을 `step_size`
+/// (월드 거리) 씩 최대 `max_steps` 번 전진시키면서, 광선이 깊이 버퍼에 보이는 표면 뒤로
+/// `thickness` 이내로 들어가면 부딪힌 것으로 본다. 부딪힌 곳은 이분법으로 좁힌 뒤
+/// 그 자리의 `color_view` 색을 반사로 섞는다. 화면 밖의 물체는 반사되지 않는다.
+///
+/// 깊이는 `DepthTexture::FORMAT`, 법선은 월드 공간 법선을 [0, 1] 로 옮겨 담고
+/// 알파에 매끄러움(1 - 거칠기, 배경은 0)을 담은 텍스처여야 한다.
+/// `color_view` 와 `output_view` 는 `OUTPUT_FORMAT` 이고 같은 텍스처일 수 없다.
+///
+/// `camera_bind_group` 은 `view_proj` 를 담은 `CameraUniform` 의 바인드 그룹으로,
+/// `ShaderStages::VERTEX | ShaderStages::FRAGMENT` 에서 보이도록 만들어야 한다.
+pub struct SsrPass {
+    device: wgpu::Device,
+    trace_bind_group_layout: wgpu::BindGroupLayout,
+    composite_bind_group_layout: wgpu::BindGroupLayout,
+    trace_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+    // rgb 는 부딪힌 곳의 색, a 는 섞는 비율
+    reflection_view: wgpu::TextureView,
+    max_steps: u32,
+    step_size: f32,
+    roughness_threshold: f32,
+    thickness: f32,
+    size: (u32, u32),
+}
+
+impl SsrPass {
+    /// `apply` 의 `color_view` 와 `output_view` 포맷
+    pub const OUTPUT_FORMAT: wgpu::TextureFormat = HdrRenderTarget::FORMAT;
+
+    /// `max_steps` 는 광선 하나가 전진하는 최대 횟수, `step_size` 는 한 번에 전진하는
+    /// 월드 거리다. 두께의 기본값은 `step_size` 의 두 배다.
+    pub fn new(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        max_steps: u32,
+        step_size: f32,
+    ) -> Self {
+        assert!(max_steps > 0, "max steps must be positive");
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("SSR Camera Bind Group Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+
+        let uniform_entry = wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let depth_entry = wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Depth,
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+
+        // 0: 파라미터, 1: 깊이, 2: 법선, 3: 장면 색
+        let trace_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("SSR Trace Bind Group Layout"),
+                entries: &[
+                    uniform_entry,
+                    depth_entry,
+                    texture_entry(2),
+                    texture_entry(3),
+                ],
+            });
+        // 3: 장면 색, 4: 반사
+        let composite_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("SSR Composite Bind Group Layout"),
+                entries: &[texture_entry(3), texture_entry(4)],
+            });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("SSR Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("ssr.wgsl").into()),
+        });
+        let create_pipeline = |label, layout: &wgpu::BindGroupLayout, entry_point| {
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(label),
+                bind_group_layouts: &[&camera_bind_group_layout, layout],
+                push_constant_ranges: &[],
+            });
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: Self::OUTPUT_FORMAT,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let trace_pipeline =
+            create_pipeline("SSR Trace Pipeline", &trace_bind_group_layout, "fs_trace");
+        let composite_pipeline = create_pipeline(
+            "SSR Composite Pipeline",
+            &composite_bind_group_layout,
+            "fs_composite",
+        );
+
+        Self {
+            device: device.clone(),
+            trace_bind_group_layout,
+            composite_bind_group_layout,
+            trace_pipeline,
+            composite_pipeline,
+            reflection_view: create_reflection_view(device, width, height),
+            max_steps,
+            step_size,
+            roughness_threshold: DEFAULT_ROUGHNESS_THRESHOLD,
+            thickness: step_size * 2.0,
+            size: (width, height),
+        }
+    }
+
+    /// 거칠기가 이 값 이상인 픽셀은 반사하지 않는다. 0 ~ 1 로 제한된다.
+    pub fn set_roughness_threshold(&mut self, threshold: f32) {
+        self.roughness_threshold = threshold.clamp(0.0, 1.0);
+    }
+
+    pub fn roughness_threshold(&self) -> f32 {
+        self.roughness_threshold
+    }
+
+    /// 광선이 표면 뒤로 이 거리(월드)보다 깊이 들어가면 물체 뒤를 지나간 것으로 본다.
+    pub fn set_thickness(&mut self, thickness: f32) {
+        self.thickness = thickness.max(0.0);
+    }
+
+    pub fn thickness(&self) -> f32 {
+        self.thickness
+    }
+
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    pub fn step_size(&self) -> f32 {
+        self.step_size
+    }
+
+    /// G-버퍼 크기가 바뀌면 반사 텍스처를 다시 만든다.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if (width, height) == self.size {
+            return;
+        }
+        self.size = (width, height);
+        self.reflection_view = create_reflection_view(&self.device, width, height);
+    }
+
+    /// `color_view` 에 반사를 섞어서 `output_view` 에 쓴다.
+    pub fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth_view: &wgpu::TextureView,
+        normal_view: &wgpu::TextureView,
+        color_view: &wgpu::TextureView,
+        output_view: &wgpu::TextureView,
+        camera_bind_group: &wgpu::BindGroup,
+    ) {
+        let params = self.params();
+        let params_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("SSR Params Buffer"),
+                contents: bytemuck::bytes_of(&params),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let trace_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("SSR Trace Bind Group"),
+            layout: &self.trace_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(normal_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(color_view),
+                },
+            ],
+        });
+        let composite_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("SSR Composite Bind Group"),
+            layout: &self.composite_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(color_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(&self.reflection_view),
+                },
+            ],
+        });
+
+        let passes = [
+            (
+                "SSR Trace Pass",
+                &self.trace_pipeline,
+                &trace_bind_group,
+                &self.reflection_view,
+            ),
+            (
+                "SSR Composite Pass",
+                &self.composite_pipeline,
+                &composite_bind_group,
+                output_view,
+            ),
+        ];
+        for (label, pipeline, bind_group, target) in passes {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            render_pass.set_pipeline(pipeline);
+            // fs_composite 는 카메라를 쓰지 않지만 파이프라인 레이아웃이 같다
+            render_pass.set_bind_group(0, camera_bind_group, &[]);
+            render_pass.set_bind_group(1, bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
+
+    fn params(&self) -> SsrParams {
+        SsrParams {
+            max_steps: self.max_steps,
+            step_size: self.step_size,
+            roughness_threshold: self.roughness_threshold,
+            thickness: self.thickness,
+        }
+    }
+}
+
+fn create_reflection_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("SSR Reflection Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: SsrPass::OUTPUT_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_match_wgsl_layout() {
+        // u32 하나와 f32 셋
+        assert_eq!(std::mem::size_of::<SsrParams>(), 16);
+        let params = SsrParams {
+            max_steps: 64,
+            step_size: 0.1,
+            roughness_threshold: DEFAULT_ROUGHNESS_THRESHOLD,
+            thickness: 0.2,
+        };
+        let words: &[u32] = bytemuck::cast_slice(bytemuck::bytes_of(&params));
+        assert_eq!(words[0], 64);
+        assert_eq!(f32::from_bits(words[1]), 0.1);
+        assert_eq!(f32::from_bits(words[3]), 0.2);
+    }
+}
@@ -0,0 +1,206 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+struct SsrParams {
+    max_steps: u32,
+    step_size: f32,
+    roughness_threshold: f32,
+    thickness: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var<uniform> params: SsrParams;
+@group(1) @binding(1)
+var depth_texture: texture_depth_2d;
+// 월드 공간 법선을 [0, 1] 로 옮겨 담고, 알파는 1 - 거칠기(배경은 0)
+@group(1) @binding(2)
+var normal_texture: texture_2d<f32>;
+// 반사할 장면 색. fs_composite 에서는 반사를 섞을 바탕이다
+@group(1) @binding(3)
+var color_texture: texture_2d<f32>;
+// fs_trace 의 결과. rgb 는 부딪힌 곳의 색, a 는 섞는 비율
+@group(1) @binding(4)
+var reflection_texture: texture_2d<f32>;
+
+// 화면 가장자리에서 이 비율만큼은 반사를 서서히 줄인다
+const EDGE_FADE: f32 = 0.1;
+// 부딪힌 구간을 반으로 나눠 가며 좁히는 횟수
+const REFINE_STEPS: i32 = 4;
+
+struct FullscreenOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// fullscreen.wgsl 과 같다
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    var out: FullscreenOutput;
+    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return out;
+}
+
+// WGSL 에는 역행렬 함수가 없으므로 여인수 전개로 구한다
+fn inverse4(m: mat4x4<f32>) -> mat4x4<f32> {
+    let a00 = m[0][0]; let a01 = m[0][1]; let a02 = m[0][2]; let a03 = m[0][3];
+    let a10 = m[1][0]; let a11 = m[1][1]; let a12 = m[1][2]; let a13 = m[1][3];
+    let a20 = m[2][0]; let a21 = m[2][1]; let a22 = m[2][2]; let a23 = m[2][3];
+    let a30 = m[3][0]; let a31 = m[3][1]; let a32 = m[3][2]; let a33 = m[3][3];
+
+    let b00 = a00 * a11 - a01 * a10;
+    let b01 = a00 * a12 - a02 * a10;
+    let b02 = a00 * a13 - a03 * a10;
+    let b03 = a01 * a12 - a02 * a11;
+    let b04 = a01 * a13 - a03 * a11;
+    let b05 = a02 * a13 - a03 * a12;
+    let b06 = a20 * a31 - a21 * a30;
+    let b07 = a20 * a32 - a22 * a30;
+    let b08 = a20 * a33 - a23 * a30;
+    let b09 = a21 * a32 - a22 * a31;
+    let b10 = a21 * a33 - a23 * a31;
+    let b11 = a22 * a33 - a23 * a32;
+
+    let det = b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06;
+    let inv_det = 1.0 / det;
+
+    return mat4x4<f32>(
+        vec4<f32>(
+            a11 * b11 - a12 * b10 + a13 * b09,
+            a02 * b10 - a01 * b11 - a03 * b09,
+            a31 * b05 - a32 * b04 + a33 * b03,
+            a22 * b04 - a21 * b05 - a23 * b03,
+        ) * inv_det,
+        vec4<f32>(
+            a12 * b08 - a10 * b11 - a13 * b07,
+            a00 * b11 - a02 * b08 + a03 * b07,
+            a32 * b02 - a30 * b05 - a33 * b01,
+            a20 * b05 - a22 * b02 + a23 * b01,
+        ) * inv_det,
+        vec4<f32>(
+            a10 * b10 - a11 * b08 + a13 * b06,
+            a01 * b08 - a00 * b10 - a03 * b06,
+            a30 * b04 - a31 * b02 + a33 * b00,
+            a21 * b02 - a20 * b04 - a23 * b00,
+        ) * inv_det,
+        vec4<f32>(
+            a11 * b07 - a10 * b09 - a12 * b06,
+            a00 * b09 - a01 * b07 + a02 * b06,
+            a31 * b01 - a30 * b03 - a32 * b00,
+            a20 * b03 - a21 * b01 + a22 * b00,
+        ) * inv_det,
+    );
+}
+
+// 왼쪽 위가 (0, 0) 인 텍스처 좌표와 깊이로 월드 위치를 되돌린다
+fn world_position(inv_view_proj: mat4x4<f32>, uv: vec2<f32>, depth: f32) -> vec3<f32> {
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    let position = inv_view_proj * ndc;
+    return position.xyz / position.w;
+}
+
+// 월드 위치를 화면에 투영한 (텍스처 좌표, 깊이). 카메라 뒤면 깊이가 음수다
+fn project(position: vec3<f32>) -> vec3<f32> {
+    let clip = camera.view_proj * vec4<f32>(position, 1.0);
+    if clip.w <= 0.0 {
+        return vec3<f32>(0.0, 0.0, -1.0);
+    }
+    let ndc = clip.xyz / clip.w;
+    return vec3<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5, ndc.z);
+}
+
+fn on_screen(uv: vec2<f32>) -> bool {
+    return all(uv >= vec2<f32>(0.0)) && all(uv < vec2<f32>(1.0));
+}
+
+// 광선 위의 점이 같은 픽셀에 보이는 표면보다 얼마나 뒤에 있는지 (카메라에서 잰 거리 차이).
+// 양수면 표면 뒤로 들어갔다. 화면 밖이면 -1 을 돌려준다
+fn depth_behind(inv_view_proj: mat4x4<f32>, position: vec3<f32>, size: vec2<f32>) -> f32 {
+    let projected = project(position);
+    if projected.z < 0.0 || !on_screen(projected.xy) {
+        return -1.0;
+    }
+    let scene_depth = textureLoad(depth_texture, vec2<i32>(projected.xy * size), 0);
+    let near = world_position(inv_view_proj, projected.xy, 0.0);
+    let scene = world_position(inv_view_proj, projected.xy, scene_depth);
+    return distance(position, near) - distance(scene, near);
+}
+
+// 거칠기가 낮은 픽셀에서 반사 방향으로 광선을 조금씩 전진시키며 깊이 버퍼와 비교한다
+@fragment
+fn fs_trace(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let encoded_normal = textureLoad(normal_texture, pixel, 0);
+    let roughness = 1.0 - encoded_normal.a;
+    // 배경(알파 0)은 거칠기 1 이므로 여기서 함께 걸러진다
+    if roughness >= params.roughness_threshold {
+        return vec4<f32>(0.0);
+    }
+
+    let inv_view_proj = inverse4(camera.view_proj);
+    let size = vec2<f32>(textureDimensions(depth_texture));
+    let depth = textureLoad(depth_texture, pixel, 0);
+    let position = world_position(inv_view_proj, in.uv, depth);
+    // 같은 픽셀의 근평면 위치에서 표면으로 향하는 방향이 시선 방향이다
+    let view_direction = normalize(position - world_position(inv_view_proj, in.uv, 0.0));
+    let normal = normalize(encoded_normal.xyz * 2.0 - 1.0);
+    let ray = reflect(view_direction, normal) * params.step_size;
+
+    // 출발한 표면에 바로 부딪히지 않도록 법선 쪽으로 조금 띄운다
+    let origin = position + normal * params.step_size * 0.5;
+    var previous = origin;
+    for (var i = 1u; i <= params.max_steps; i += 1u) {
+        let current = origin + ray * f32(i);
+        let behind = depth_behind(inv_view_proj, current, size);
+        if behind < 0.0 {
+            // 화면 밖으로 나갔거나 아직 표면 앞에 있다
+            if !on_screen(project(current).xy) {
+                break;
+            }
+            previous = current;
+            continue;
+        }
+        if behind > params.thickness {
+            // 물체 뒤로 지나갔으므로 부딪히지 않았다
+            previous = current;
+            continue;
+        }
+
+        // 마지막 두 점 사이를 반씩 나눠서 표면에 더 가까운 점을 찾는다
+        var front = previous;
+        var back = current;
+        for (var j = 0; j < REFINE_STEPS; j += 1) {
+            let middle = (front + back) * 0.5;
+            if depth_behind(inv_view_proj, middle, size) >= 0.0 {
+                back = middle;
+            } else {
+                front = middle;
+            }
+        }
+
+        let hit_uv = project(back).xy;
+        let hit_color = textureLoad(color_texture, vec2<i32>(hit_uv * size), 0).rgb;
+
+        // 화면 가장자리, 광선 끝, 거칠기 한계에 가까울수록 반사를 약하게 한다
+        let edge = min(hit_uv, 1.0 - hit_uv);
+        let edge_fade = clamp(min(edge.x, edge.y) / EDGE_FADE, 0.0, 1.0);
+        let distance_fade = 1.0 - f32(i) / f32(params.max_steps);
+        let roughness_fade = 1.0 - roughness / params.roughness_threshold;
+        return vec4<f32>(hit_color, edge_fade * distance_fade * roughness_fade);
+    }
+
+    return vec4<f32>(0.0);
+}
+
+@fragment
+fn fs_composite(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let color = textureLoad(color_texture, pixel, 0);
+    let reflection = textureLoad(reflection_texture, pixel, 0);
+    return vec4<f32>(mix(color.rgb, reflection.rgb, reflection.a), color.a);
+}
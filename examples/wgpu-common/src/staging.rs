@@ -0,0 +1,119 @@
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MapState {
+    Mapped,
+    Pending,
+    Unmapped,
+}
+
+/// CPU 에서 매핑된 상태로 미리 할당해 두는 `MAP_WRITE | COPY_SRC` 버퍼.
+///
+/// 한 프레임의 사용 순서는 다음과 같다.
+/// 1. `write` 로 데이터를 채운다 (버퍼가 언매핑된다)
+/// 2. `copy_to` 로 복사 명령을 기록하고 제출한다
+/// 3. 제출 후 `remap` 으로 다음 프레임을 위해 다시 매핑을 요청한다
+///
+/// 매핑은 GPU 가 복사를 끝낸 뒤 비동기로 완료되므로 `is_ready` 가 `false` 인 동안에는
+/// 쓸 수 없다. 여러 개를 돌려가며 사용하면 대기 없이 매 프레임 업로드할 수 있다.
+pub struct StagingBuffer {
+    buffer: wgpu::Buffer,
+    capacity: wgpu::BufferAddress,
+    len: wgpu::BufferAddress,
+    // map_async 콜백은 네이티브에서 Send 여야 하므로 Arc<Mutex> 로 공유한다
+    state: Arc<Mutex<MapState>>,
+}
+
+impl StagingBuffer {
+    pub fn new(device: &wgpu::Device, capacity: wgpu::BufferAddress) -> Self {
+        let capacity = capacity.next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Staging Buffer"),
+            size: capacity,
+            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: true,
+        });
+
+        Self {
+            buffer,
+            capacity,
+            len: 0,
+            state: Arc::new(Mutex::new(MapState::Mapped)),
+        }
+    }
+
+    pub fn capacity(&self) -> wgpu::BufferAddress {
+        self.capacity
+    }
+
+    /// 매핑이 끝나서 `write` 할 수 있는지 여부
+    pub fn is_ready(&self) -> bool {
+        self.map_state() == MapState::Mapped
+    }
+
+    /// 매핑된 영역에 `data` 를 쓰고 언매핑한다.
+    /// 아직 매핑되지 않았으면 아무것도 하지 않고 `false` 를 반환한다.
+    ///
+    /// # Panics
+    ///
+    /// `data` 가 용량보다 크면 panic 한다.
+    pub fn write<T: bytemuck::Pod>(&mut self, data: &[T]) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let len =
+            (bytes.len() as wgpu::BufferAddress).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+        assert!(
+            len <= self.capacity,
+            "staging buffer overflow: {} > {}",
+            len,
+            self.capacity
+        );
+
+        {
+            let mut mapped = self.buffer.slice(..len).get_mapped_range_mut();
+            mapped[..bytes.len()].copy_from_slice(bytes);
+        }
+        self.buffer.unmap();
+
+        self.len = len;
+        self.set_map_state(MapState::Unmapped);
+        true
+    }
+
+    /// 마지막으로 쓴 데이터를 `dst_buffer` 의 처음으로 복사하는 명령을 기록한다.
+    pub fn copy_to(&self, encoder: &mut wgpu::CommandEncoder, dst_buffer: &wgpu::Buffer) {
+        if self.len == 0 {
+            return;
+        }
+        encoder.copy_buffer_to_buffer(&self.buffer, 0, dst_buffer, 0, self.len);
+    }
+
+    /// 복사 명령을 제출한 뒤 호출해서 다음 `write` 를 위해 매핑을 요청한다.
+    pub fn remap(&self) {
+        if self.map_state() != MapState::Unmapped {
+            return;
+        }
+
+        self.set_map_state(MapState::Pending);
+        let state = Arc::clone(&self.state);
+        self.buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Write, move |result| {
+                *state.lock().unwrap() = match result {
+                    Ok(()) => MapState::Mapped,
+                    Err(_) => MapState::Unmapped,
+                };
+            });
+    }
+
+    fn map_state(&self) -> MapState {
+        *self.state.lock().unwrap()
+    }
+
+    fn set_map_state(&self, state: MapState) {
+        *self.state.lock().unwrap() = state;
+    }
+}
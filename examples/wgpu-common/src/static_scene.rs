@@ -0,0 +1,51 @@
+/// 움직이지 않는 물체들의 그리기 명령을 한 번만 기록해 둔 렌더 번들.
+///
+/// 매 프레임 메시마다 `set_vertex_buffer`, `draw_indexed` 를 다시 기록하는 대신
+/// 로드할 때 `wgpu::RenderBundleEncoder` 로 기록해 두고 `execute_bundles` 한 번으로 재생한다.
+/// 번들은 바인드 그룹을 통째로 기억하므로, 카메라처럼 바뀌는 값은 버퍼 내용만 갱신해야 한다.
+pub struct StaticScene {
+    bundle: wgpu::RenderBundle,
+}
+
+impl StaticScene {
+    /// `record` 에서 파이프라인, 바인드 그룹, 그리기를 기록한다.
+    ///
+    /// `color_formats` 와 `depth_format` 은 번들을 실행할 렌더 패스의 어태치먼트와 같아야 한다.
+    /// 번들 안에서는 깊이만 쓰고 스텐실은 건드리지 않는다.
+    pub fn new<'a>(
+        device: &wgpu::Device,
+        color_formats: &[Option<wgpu::TextureFormat>],
+        depth_format: Option<wgpu::TextureFormat>,
+        record: impl FnOnce(&mut wgpu::RenderBundleEncoder<'a>),
+    ) -> Self {
+        let mut encoder =
+            device.create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
+                label: Some("Static Scene Bundle Encoder"),
+                color_formats,
+                depth_stencil: depth_format.map(|format| wgpu::RenderBundleDepthStencil {
+                    format,
+                    depth_read_only: false,
+                    stencil_read_only: true,
+                }),
+                sample_count: 1,
+                multiview: None,
+            });
+        record(&mut encoder);
+
+        let bundle = encoder.finish(&wgpu::RenderBundleDescriptor {
+            label: Some("Static Scene Bundle"),
+        });
+        Self { bundle }
+    }
+
+    /// `RenderPassBuilder::execute_bundle` 등에 넘길 번들
+    pub fn bundle(&self) -> &wgpu::RenderBundle {
+        &self.bundle
+    }
+
+    /// 이미 시작된 렌더 패스에서 번들을 실행한다.
+    /// 실행한 뒤에는 파이프라인과 바인딩이 풀리므로 이어서 그리려면 다시 설정해야 한다.
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.execute_bundles(std::iter::once(&self.bundle));
+    }
+}
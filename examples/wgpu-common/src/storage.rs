@@ -0,0 +1,128 @@
+use std::cell::Cell;
+
+/// 쓰는 데이터가 용량을 넘으면 스스로 다시 만들어지는 스토리지 버퍼.
+///
+/// 버퍼가 다시 만들어지면 이전 버퍼를 가리키던 바인드 그룹은 더 이상 쓸 수 없으므로
+/// `invalidated` 가 `true` 일 때 `bind_group_entry` 로 바인드 그룹을 다시 만들어야 한다.
+pub struct GrowableStorageBuffer {
+    device: wgpu::Device,
+    label: String,
+    usage: wgpu::BufferUsages,
+    buffer: wgpu::Buffer,
+    capacity: wgpu::BufferAddress,
+    len: wgpu::BufferAddress,
+    invalidated: Cell<bool>,
+}
+
+impl GrowableStorageBuffer {
+    /// `usage` 에는 `STORAGE | COPY_DST` 가 항상 추가된다.
+    pub fn new(
+        device: &wgpu::Device,
+        label: &str,
+        capacity: wgpu::BufferAddress,
+        usage: wgpu::BufferUsages,
+    ) -> Self {
+        let usage = usage | wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST;
+        let capacity = capacity
+            .max(wgpu::COPY_BUFFER_ALIGNMENT)
+            .next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+
+        Self {
+            device: device.clone(),
+            label: label.to_string(),
+            usage,
+            buffer: create_buffer(device, label, capacity, usage),
+            capacity,
+            len: 0,
+            invalidated: Cell::new(false),
+        }
+    }
+
+    pub fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+    }
+
+    /// 현재 GPU 버퍼의 크기 (바이트)
+    pub fn capacity(&self) -> wgpu::BufferAddress {
+        self.capacity
+    }
+
+    /// 마지막으로 쓴 데이터의 크기 (바이트)
+    pub fn len(&self) -> wgpu::BufferAddress {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 버퍼가 다시 만들어져서 바인드 그룹을 새로 만들어야 하는지 여부.
+    /// `bind_group_entry` 를 호출하면 `false` 로 돌아간다.
+    pub fn invalidated(&self) -> bool {
+        self.invalidated.get()
+    }
+
+    /// `data` 를 버퍼의 처음에 쓴다. 용량이 모자라면 필요한 크기의 2배
+    /// (디바이스 제한까지)로 버퍼를 다시 만든다.
+    ///
+    /// # Panics
+    ///
+    /// `data` 가 디바이스의 최대 스토리지 버퍼 크기보다 크면 panic 한다.
+    pub fn write<T: bytemuck::Pod>(&mut self, queue: &wgpu::Queue, data: &[T]) {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let required =
+            (bytes.len() as wgpu::BufferAddress).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+
+        if required > self.capacity {
+            let limits = self.device.limits();
+            let max_size = limits
+                .max_buffer_size
+                .min(limits.max_storage_buffer_binding_size as wgpu::BufferAddress);
+            assert!(
+                required <= max_size,
+                "{}: {} bytes exceeds max storage buffer size {}",
+                self.label,
+                required,
+                max_size
+            );
+
+            self.buffer.destroy();
+            self.capacity = (required * 2).min(max_size);
+            self.buffer = create_buffer(&self.device, &self.label, self.capacity, self.usage);
+            self.invalidated.set(true);
+        }
+
+        // write_buffer 는 4 바이트 단위여야 하므로 남는 부분은 0 으로 채운다
+        if bytes.len() as wgpu::BufferAddress == required {
+            queue.write_buffer(&self.buffer, 0, bytes);
+        } else {
+            let mut padded = bytes.to_vec();
+            padded.resize(required as usize, 0);
+            queue.write_buffer(&self.buffer, 0, &padded);
+        }
+        self.len = bytes.len() as wgpu::BufferAddress;
+    }
+
+    /// 버퍼 전체를 `binding` 번에 바인딩하는 항목
+    pub fn bind_group_entry(&self, binding: u32) -> wgpu::BindGroupEntry<'_> {
+        self.invalidated.set(false);
+        wgpu::BindGroupEntry {
+            binding,
+            resource: self.buffer.as_entire_binding(),
+        }
+    }
+}
+
+fn create_buffer(
+    device: &wgpu::Device,
+    label: &str,
+    size: wgpu::BufferAddress,
+    usage: wgpu::BufferUsages,
+) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage,
+        mapped_at_creation: false,
+    })
+}
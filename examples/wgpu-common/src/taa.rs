@@ -0,0 +1,327 @@
+use glam::{Mat4, Vec3};
+use wgpu::util::DeviceExt;
+
+use crate::FullscreenTriangle;
+
+// 지터 위치를 몇 프레임마다 반복할지
+const JITTER_SEQUENCE_LENGTH: u32 = 8;
+
+/// 프레임마다 조금씩 다른 서브 픽셀 위치에서 그린 장면을 히스토리 텍스처에 누적하는
+/// 템포럴 안티 에일리어싱(TAA).
+///
+/// 메인 패스는 `jitter_projection` 으로 옮긴 투영 행렬로 입력 텍스처에 그리고, `apply` 는 이전 결과와
+/// `mix(prev, curr, BLEND_FACTOR)` 로 섞어서 출력과 다음 프레임의 히스토리에 함께 쓴다.
+/// 여러 프레임에 걸쳐 픽셀 안의 여러 위치를 평균 내므로 경계가 부드러워지지만,
+/// 움직이는 물체는 이전 위치가 남아서 잔상이 생긴다.
+/// 뷰포트는 렌더 타깃 밖으로 나갈 수 없으므로 지터는 뷰포트 대신 투영 행렬에 넣는다.
+pub struct TaaPass {
+    device: wgpu::Device,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    // 현재 프레임을 섞는 비율. 히스토리가 없으면 1 이다
+    params: wgpu::Buffer,
+    format: wgpu::TextureFormat,
+    size: (u32, u32),
+    // 번갈아 읽고 쓰는 히스토리. current 번을 읽고 다른 쪽에 쓴다
+    history: [wgpu::TextureView; 2],
+    current: usize,
+    // 입력 뷰와, 히스토리 0 번과 1 번을 각각 읽는 바인드 그룹. 입력이 바뀌거나 리사이즈하면 다시 만든다
+    bind_groups: Option<(wgpu::TextureView, [wgpu::BindGroup; 2])>,
+    has_history: bool,
+    frame: u32,
+}
+
+impl TaaPass {
+    /// 현재 프레임을 섞는 비율
+    pub const BLEND_FACTOR: f32 = 0.1;
+
+    /// `format` 은 입력, 출력, 히스토리 텍스처의 포맷이다.
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("TAA Bind Group Layout"),
+            entries: &[
+                texture_entry(0),
+                texture_entry(1),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let fullscreen = FullscreenTriangle::new(device);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("TAA Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("taa.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("TAA Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let target = Some(wgpu::ColorTargetState {
+            format,
+            blend: None,
+            write_mask: wgpu::ColorWrites::ALL,
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("TAA Pipeline"),
+            layout: Some(&layout),
+            vertex: fullscreen.vertex_state(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[target.clone(), target],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // WGSL 의 유니폼 버퍼는 16 바이트 단위로 잡힌다
+        let params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("TAA Params Buffer"),
+            contents: bytemuck::bytes_of(&[1.0f32, 0.0, 0.0, 0.0]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Self {
+            device: device.clone(),
+            bind_group_layout,
+            pipeline,
+            params,
+            format,
+            size: (width, height),
+            history: create_history(device, format, width, height),
+            current: 0,
+            bind_groups: None,
+            has_history: false,
+            frame: 0,
+        }
+    }
+
+    /// 히스토리를 새 크기로 다시 만들고 누적을 처음부터 시작한다.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.size = (width, height);
+        self.history = create_history(&self.device, self.format, width, height);
+        self.bind_groups = None;
+        self.reset();
+    }
+
+    /// 장면이 크게 바뀌어서 이전 프레임을 섞으면 안 될 때 호출한다.
+    pub fn reset(&mut self) {
+        self.has_history = false;
+    }
+
+    /// 이번 프레임에 장면을 옮길 거리(픽셀). 각 성분은 [-0.5, 0.5) 범위이고 y 는 아래쪽이 + 다
+    pub fn jitter(&self) -> (f32, f32) {
+        jitter_offset(self.frame)
+    }
+
+    /// `projection` 을 이번 프레임의 지터만큼 옮긴 행렬. 메인 패스의 투영 행렬 대신 쓴다.
+    pub fn jitter_projection(&self, projection: Mat4) -> Mat4 {
+        jitter_matrix(self.jitter(), self.size) * projection
+    }
+
+    /// `input` 을 히스토리와 섞어서 `output` 에 쓰고 다음 프레임의 지터로 넘어간다.
+    ///
+    /// 바인드 그룹은 `input` 별로 한 번만 만들므로 매 프레임 같은 입력 뷰를 넘기는 것이 좋다.
+    pub fn apply(
+        &mut self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        let blend = if self.has_history {
+            Self::BLEND_FACTOR
+        } else {
+            1.0
+        };
+        queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&[blend, 0.0, 0.0, 0.0]));
+
+        if self
+            .bind_groups
+            .as_ref()
+            .is_none_or(|(cached, _)| cached != input)
+        {
+            let bind_groups = std::array::from_fn(|read| self.create_bind_group(input, read));
+            self.bind_groups = Some((input.clone(), bind_groups));
+        }
+        let (read, write) = (self.current, 1 - self.current);
+        let (_, bind_groups) = self.bind_groups.as_ref().unwrap();
+
+        {
+            let attachment = |view| {
+                Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })
+            };
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("TAA Pass"),
+                color_attachments: &[attachment(output), attachment(&self.history[write])],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, &bind_groups[read], &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        self.current = write;
+        self.has_history = true;
+        self.frame = (self.frame + 1) % JITTER_SEQUENCE_LENGTH;
+    }
+
+    // input 과 history[read] 를 읽는 바인드 그룹
+    fn create_bind_group(&self, input: &wgpu::TextureView, read: usize) -> wgpu::BindGroup {
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("TAA Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&self.history[read]),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.params.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
+
+fn create_history(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+) -> [wgpu::TextureView; 2] {
+    std::array::from_fn(|_| {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("TAA History Texture"),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    })
+}
+
+// 밑이 base 인 Halton 수열의 index 번째 값. [0, 1) 을 고르게 채운다
+fn halton(mut index: u32, base: u32) -> f32 {
+    let mut result = 0.0;
+    let mut fraction = 1.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+// 0 은 (0, 0) 이 되므로 1 부터 쓴다
+fn jitter_offset(frame: u32) -> (f32, f32) {
+    let index = frame % JITTER_SEQUENCE_LENGTH + 1;
+    (halton(index, 2) - 0.5, halton(index, 3) - 0.5)
+}
+
+// 픽셀 단위 지터를 클립 공간에서 옮기는 행렬. NDC 는 2 가 화면 전체이고 y 가 위쪽이 + 다.
+// w 로 나누기 전에 더하므로 원근 투영에서도 모든 깊이가 같은 픽셀 수만큼 움직인다
+fn jitter_matrix((x, y): (f32, f32), (width, height): (u32, u32)) -> Mat4 {
+    Mat4::from_translation(Vec3::new(
+        2.0 * x / width.max(1) as f32,
+        -2.0 * y / height.max(1) as f32,
+        0.0,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn halton_sequence() {
+        let base2: Vec<f32> = (1..5).map(|i| halton(i, 2)).collect();
+        assert_eq!(base2, [0.5, 0.25, 0.75, 0.125]);
+        let base3: Vec<f32> = (1..4).map(|i| halton(i, 3)).collect();
+        assert!((base3[0] - 1.0 / 3.0).abs() < 1e-6);
+        assert!((base3[1] - 2.0 / 3.0).abs() < 1e-6);
+        assert!((base3[2] - 1.0 / 9.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn jitter_stays_inside_pixel_and_repeats() {
+        let offsets: Vec<(f32, f32)> = (0..JITTER_SEQUENCE_LENGTH).map(jitter_offset).collect();
+        for (x, y) in &offsets {
+            assert!((-0.5..0.5).contains(x) && (-0.5..0.5).contains(y));
+        }
+        // 한 주기 안에서는 같은 위치가 나오지 않는다
+        for (i, a) in offsets.iter().enumerate() {
+            assert!(offsets[i + 1..].iter().all(|b| a != b));
+        }
+        assert_eq!(jitter_offset(JITTER_SEQUENCE_LENGTH), offsets[0]);
+    }
+
+    #[test]
+    fn jitter_matrix_moves_by_pixels_in_ndc() {
+        let matrix = jitter_matrix((0.25, -0.5), (200, 100));
+        let point = matrix * glam::Vec4::new(0.0, 0.0, 0.5, 1.0);
+        assert!((point.x - 0.0025).abs() < 1e-6);
+        assert!((point.y - 0.01).abs() < 1e-6);
+        assert_eq!(point.z, 0.5);
+
+        // 원근 투영 뒤의 점도 w 로 나누면 같은 거리만큼 움직인다
+        let projection = Mat4::perspective_rh(1.0, 2.0, 0.1, 100.0);
+        let world = glam::Vec4::new(1.0, 2.0, -10.0, 1.0);
+        let (plain, jittered) = (projection * world, matrix * projection * world);
+        let shift = jittered.truncate() / jittered.w - plain.truncate() / plain.w;
+        assert!((shift.x - 0.0025).abs() < 1e-6);
+        assert!((shift.y - 0.01).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,36 @@
+struct TaaParams {
+    // 현재 프레임을 섞는 비율. 히스토리가 없는 첫 프레임은 1 이다
+    blend: f32,
+};
+
+@group(0) @binding(0)
+var current_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var history_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var<uniform> params: TaaParams;
+
+// 버텍스 셰이더는 FullscreenTriangle 이 제공한다
+struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// 화면과 다음 프레임이 읽을 히스토리에 같은 값을 쓴다
+struct TaaOutput {
+    @location(0) color: vec4<f32>,
+    @location(1) history: vec4<f32>,
+};
+
+@fragment
+fn fs_main(in: FragmentInput) -> TaaOutput {
+    let pixel = vec2<i32>(in.position.xy);
+    let current = textureLoad(current_texture, pixel, 0);
+    let previous = textureLoad(history_texture, pixel, 0);
+    let color = mix(previous, current, params.blend);
+
+    var out: TaaOutput;
+    out.color = color;
+    out.history = color;
+    return out;
+}
@@ -0,0 +1,138 @@
+use crate::Index;
+
+/// MikkTSpace 로 탄젠트를 만들 수 있는 버텍스
+pub trait TangentVertex {
+    fn position(&self) -> [f32; 3];
+    fn normal(&self) -> [f32; 3];
+    fn tex_coords(&self) -> [f32; 2];
+    /// xyz 는 u 가 커지는 방향, w 는 바이탄젠트 방향(+1 또는 -1)
+    fn set_tangent(&mut self, tangent: [f32; 4]);
+}
+
+/// 삼각형 목록의 UV 미분으로 버텍스마다 탄젠트를 계산한다.
+///
+/// Blender, glTF 와 같은 MikkTSpace 알고리즘을 쓰므로 그 도구들로 구운 노멀 맵과 맞는다.
+/// 여러 삼각형이 공유하는 버텍스는 마지막에 계산된 값이 남는다.
+/// 삼각형이 없는 등 탄젠트를 만들 수 없으면 `false` 를 반환하고 버텍스를 건드리지 않는다.
+pub fn generate_tangents<V, I>(vertices: &mut [V], indices: &[I]) -> bool
+where
+    V: TangentVertex,
+    I: Index + Into<u32>,
+{
+    let mut geometry = Geometry { vertices, indices };
+    mikktspace::generate_tangents(&mut geometry)
+}
+
+struct Geometry<'a, V, I> {
+    vertices: &'a mut [V],
+    indices: &'a [I],
+}
+
+impl<V: TangentVertex, I: Index + Into<u32>> Geometry<'_, V, I> {
+    fn index(&self, face: usize, vert: usize) -> usize {
+        self.indices[face * 3 + vert].into() as usize
+    }
+}
+
+impl<V: TangentVertex, I: Index + Into<u32>> mikktspace::Geometry for Geometry<'_, V, I> {
+    fn num_faces(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.vertices[self.index(face, vert)].position()
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.vertices[self.index(face, vert)].normal()
+    }
+
+    // glTF 노멀 맵의 초록 채널은 v 가 작아지는 쪽을 가리키므로 v 를 뒤집어서 넘긴다
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        let [u, v] = self.vertices[self.index(face, vert)].tex_coords();
+        [u, 1.0 - v]
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        let index = self.index(face, vert);
+        self.vertices[index].set_tangent(tangent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Default)]
+    struct TestVertex {
+        position: [f32; 3],
+        tex_coords: [f32; 2],
+        tangent: [f32; 4],
+    }
+
+    impl TangentVertex for TestVertex {
+        fn position(&self) -> [f32; 3] {
+            self.position
+        }
+
+        fn normal(&self) -> [f32; 3] {
+            [0.0, 0.0, 1.0]
+        }
+
+        fn tex_coords(&self) -> [f32; 2] {
+            self.tex_coords
+        }
+
+        fn set_tangent(&mut self, tangent: [f32; 4]) {
+            self.tangent = tangent;
+        }
+    }
+
+    // z = 0 평면의 단위 사각형. `flip_u` 면 텍스처를 좌우로 뒤집어서 붙인다
+    fn quad(flip_u: bool) -> Vec<TestVertex> {
+        [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+            .into_iter()
+            .map(|[x, y]| TestVertex {
+                position: [x, y, 0.0],
+                tex_coords: [if flip_u { 1.0 - x } else { x }, 1.0 - y],
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    const QUAD_INDICES: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+    fn assert_tangent(vertices: &[TestVertex], expected: [f32; 4]) {
+        for vertex in vertices {
+            for (actual, expected) in vertex.tangent.iter().zip(expected) {
+                assert!((actual - expected).abs() < 1e-5, "{:?}", vertex.tangent);
+            }
+        }
+    }
+
+    #[test]
+    fn tangent_follows_u_direction() {
+        let mut vertices = quad(false);
+        assert!(generate_tangents(&mut vertices, &QUAD_INDICES));
+        // 바이탄젠트 N x T = (0, 1, 0) 이 v 가 작아지는 위쪽을 가리킨다
+        assert_tangent(&vertices, [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn mirrored_uv_flips_handedness() {
+        let mut vertices = quad(true);
+        assert!(generate_tangents(&mut vertices, &QUAD_INDICES));
+        assert_tangent(&vertices, [-1.0, 0.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn empty_mesh_fails() {
+        let mut vertices = quad(false);
+        assert!(!generate_tangents::<_, u32>(&mut vertices, &[]));
+        assert_tangent(&vertices, [0.0; 4]);
+    }
+}
@@ -0,0 +1,195 @@
+use glam::Vec3;
+
+use crate::{GpuContext, Mesh, Vertex};
+
+/// 지형 메시의 버텍스. 위치와 중앙 차분으로 구한 법선
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct TerrainVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+}
+
+impl Vertex for TerrainVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+/// 흑백 하이트맵의 픽셀 하나를 버텍스 하나로 만든 격자 지형.
+///
+/// 픽셀 `(x, z)` 는 `x * tile_size`, `z * tile_size` 에 놓이고 높이는 `픽셀 값 / 255 * max_height` 다.
+/// 격자의 가운데가 원점에 오도록 옮긴다. 파이프라인은 그리는 쪽에서 `TerrainVertex::layout()` 으로 만든다.
+pub struct Terrain {
+    mesh: Mesh,
+    heights: Vec<f32>,
+    width: u32,
+    depth: u32,
+    tile_size: f32,
+}
+
+impl Terrain {
+    /// PNG 같은 이미지 파일을 디코딩해서 지형을 만든다. 컬러 이미지는 밝기만 사용한다.
+    pub fn new(
+        gpu: &GpuContext,
+        heightmap_bytes: &[u8],
+        tile_size: f32,
+        max_height: f32,
+    ) -> image::ImageResult<Self> {
+        let image = image::load_from_memory(heightmap_bytes)?;
+        Ok(Self::from_image(gpu, &image, tile_size, max_height))
+    }
+
+    pub fn from_image(
+        gpu: &GpuContext,
+        heightmap: &image::DynamicImage,
+        tile_size: f32,
+        max_height: f32,
+    ) -> Self {
+        let luma = heightmap.to_luma8();
+        let (width, depth) = luma.dimensions();
+        let heights: Vec<f32> = luma
+            .pixels()
+            .map(|pixel| pixel.0[0] as f32 / 255.0 * max_height)
+            .collect();
+
+        let (vertices, indices) = terrain_geometry(&heights, width, depth, tile_size);
+        Self {
+            mesh: Mesh::new(gpu, &vertices, &indices),
+            heights,
+            width,
+            depth,
+            tile_size,
+        }
+    }
+
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    /// 파이프라인을 설정한 렌더 패스에 카메라를 0번 그룹으로 바인딩하고 지형을 그린다.
+    pub fn draw(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        camera_bind_group: &wgpu::BindGroup,
+    ) {
+        render_pass.set_bind_group(0, camera_bind_group, &[]);
+        self.mesh.draw(render_pass);
+    }
+
+    /// 월드 x, z 에서 주변 네 버텍스를 보간한 높이. 지형 밖은 가장자리 높이다
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        sample_height(&self.heights, self.width, self.depth, self.tile_size, x, z)
+    }
+
+    /// x, z 방향으로 지형이 차지하는 크기
+    pub fn extent(&self) -> (f32, f32) {
+        (
+            (self.width - 1) as f32 * self.tile_size,
+            (self.depth - 1) as f32 * self.tile_size,
+        )
+    }
+}
+
+fn sample_height(heights: &[f32], width: u32, depth: u32, tile_size: f32, x: f32, z: f32) -> f32 {
+    let gx = (x / tile_size + (width - 1) as f32 * 0.5).clamp(0.0, (width - 1) as f32);
+    let gz = (z / tile_size + (depth - 1) as f32 * 0.5).clamp(0.0, (depth - 1) as f32);
+
+    let (x0, z0) = (gx.floor() as u32, gz.floor() as u32);
+    let (x1, z1) = ((x0 + 1).min(width - 1), (z0 + 1).min(depth - 1));
+    let (tx, tz) = (gx - x0 as f32, gz - z0 as f32);
+
+    let height = |x: u32, z: u32| heights[(z * width + x) as usize];
+    let near = height(x0, z0) + (height(x1, z0) - height(x0, z0)) * tx;
+    let far = height(x0, z1) + (height(x1, z1) - height(x0, z1)) * tx;
+    near + (far - near) * tz
+}
+
+// 버텍스는 행(z) 우선 순서다. 위에서 봤을 때 CCW 가 되도록 칸마다 삼각형 두 개를 만든다
+fn terrain_geometry(
+    heights: &[f32],
+    width: u32,
+    depth: u32,
+    tile_size: f32,
+) -> (Vec<TerrainVertex>, Vec<u32>) {
+    let height = |x: u32, z: u32| heights[(z * width + x) as usize];
+    let offset_x = (width - 1) as f32 * tile_size * 0.5;
+    let offset_z = (depth - 1) as f32 * tile_size * 0.5;
+
+    let mut vertices = Vec::with_capacity((width * depth) as usize);
+    for z in 0..depth {
+        for x in 0..width {
+            // 가장자리는 한쪽 차분이 되도록 이웃을 안쪽으로 자르고 실제 거리로 나눈다
+            let (left, right) = (x.saturating_sub(1), (x + 1).min(width - 1));
+            let (back, front) = (z.saturating_sub(1), (z + 1).min(depth - 1));
+            let dx =
+                (height(right, z) - height(left, z)) / ((right - left).max(1) as f32 * tile_size);
+            let dz =
+                (height(x, front) - height(x, back)) / ((front - back).max(1) as f32 * tile_size);
+
+            vertices.push(TerrainVertex {
+                position: [
+                    x as f32 * tile_size - offset_x,
+                    height(x, z),
+                    z as f32 * tile_size - offset_z,
+                ],
+                normal: Vec3::new(-dx, 1.0, -dz).normalize().into(),
+            });
+        }
+    }
+
+    let cells = (width.saturating_sub(1) * depth.saturating_sub(1)) as usize;
+    let mut indices = Vec::with_capacity(cells * 6);
+    for z in 0..depth.saturating_sub(1) {
+        for x in 0..width.saturating_sub(1) {
+            let i = z * width + x;
+            let (right, below) = (i + 1, i + width);
+            indices.extend_from_slice(&[i, below, right, right, below, below + 1]);
+        }
+    }
+
+    (vertices, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_grid_centered_on_origin() {
+        let (vertices, indices) = terrain_geometry(&[0.0; 12], 4, 3, 2.0);
+        assert_eq!(vertices.len(), 12);
+        assert_eq!(indices.len(), 3 * 2 * 6);
+        assert_eq!(vertices[0].position, [-3.0, 0.0, -2.0]);
+        assert_eq!(vertices[11].position, [3.0, 0.0, 2.0]);
+        // 평평하면 모든 법선이 위를 향한다
+        assert!(vertices.iter().all(|v| v.normal == [0.0, 1.0, 0.0]));
+
+        // 첫 삼각형이 위에서 봤을 때 CCW 라서 법선이 +y 다
+        let corner = |i: u32| Vec3::from(vertices[i as usize].position);
+        let (a, b, c) = (corner(indices[0]), corner(indices[1]), corner(indices[2]));
+        assert!((b - a).cross(c - a).y > 0.0);
+    }
+
+    #[test]
+    fn normals_lean_away_from_slope() {
+        // x 방향으로 1 칸마다 1 씩 올라가는 경사. 법선은 (-1, 1, 0) 방향이다
+        let heights: Vec<f32> = (0..9).map(|i| (i % 3) as f32).collect();
+        let (vertices, _) = terrain_geometry(&heights, 3, 3, 1.0);
+        let expected = Vec3::new(-1.0, 1.0, 0.0).normalize();
+        for vertex in &vertices {
+            assert!(Vec3::from(vertex.normal).abs_diff_eq(expected, 1e-6));
+        }
+    }
+
+    #[test]
+    fn samples_height_between_vertices() {
+        // 2x2 격자, 칸 크기 2 라서 버텍스는 x, z = -1, 1 에 있다
+        let heights = [0.0, 1.0, 2.0, 3.0];
+        assert_eq!(sample_height(&heights, 2, 2, 2.0, -1.0, -1.0), 0.0);
+        assert_eq!(sample_height(&heights, 2, 2, 2.0, 1.0, 1.0), 3.0);
+        assert_eq!(sample_height(&heights, 2, 2, 2.0, 0.0, 0.0), 1.5);
+        assert_eq!(sample_height(&heights, 2, 2, 2.0, 0.0, -1.0), 0.5);
+        // 지형 밖은 가장자리로 자른다
+        assert_eq!(sample_height(&heights, 2, 2, 2.0, 10.0, -10.0), 1.0);
+    }
+}
@@ -0,0 +1,32 @@
+//! GPU 가 필요한 테스트에서 쓰는 디바이스.
+//!
+//! 이런 테스트는 `WGPU_TESTS=1 cargo test` 처럼 `WGPU_TESTS` 를 설정했을 때만 돌고,
+//! 설정하지 않으면 `#[cfg_attr(not(wgpu_tests), ignore = ...)]` 로 ignore 된다.
+
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+/// 기본 어댑터로 만든 디바이스와 큐. 어댑터가 없으면 `None` 이고, 디바이스를 만들지 못하면 패닉한다
+pub(crate) fn device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()));
+    let Ok(adapter) = adapter else {
+        eprintln!("no GPU adapter, skipping");
+        return None;
+    };
+    let device = block_on(adapter.request_device(&wgpu::DeviceDescriptor::default()))
+        .expect("Failed to request device from the GPU adapter");
+    Some(device)
+}
+
+/// 네이티브 wgpu 의 future 는 디바이스를 poll 하면 끝나므로 이벤트 루프 없이 기다린다.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{GpuContext, SamplerBuilder, SrgbMode};
+
+/// GPU 에 올라간 2D 텍스처와 기본 뷰, 샘플러
+#[derive(Clone)]
+pub struct Texture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+}
+
+impl Texture {
+    /// `image` 크레이트가 지원하는 포맷(PNG, JPEG)의 바이트를 디코딩해서 GPU 에 올린다.
+    pub fn from_bytes(gpu: &GpuContext, bytes: &[u8], label: &str) -> image::ImageResult<Self> {
+        let image = image::load_from_memory(bytes)?;
+        Ok(Self::from_image(gpu, &image, label))
+    }
+
+    /// 이미지를 sRGB 로 인코딩된 색으로 보고 `Rgba8UnormSrgb` 텍스처로 올린다.
+    pub fn from_image(gpu: &GpuContext, image: &image::DynamicImage, label: &str) -> Self {
+        Self::from_image_with_mode(gpu, image, label, SrgbMode::Enabled)
+    }
+
+    /// `SrgbMode::Disabled` 면 `Rgba8Unorm` 으로 올려서 샘플링할 때 선형으로 디코딩하지 않는다.
+    /// 법선 맵처럼 색이 아닌 데이터를 담은 이미지도 이렇게 올린다.
+    pub fn from_image_with_mode(
+        gpu: &GpuContext,
+        image: &image::DynamicImage,
+        label: &str,
+        mode: SrgbMode,
+    ) -> Self {
+        let (device, queue) = (gpu.device(), gpu.queue());
+        let rgba = image.to_rgba8();
+        let (width, height) = rgba.dimensions();
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: mode.texture_format(),
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &rgba,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        Self::from_texture(device, texture, label)
+    }
+
+    // 이미 데이터를 올린 텍스처에 기본 뷰와 선형 샘플러를 붙인다
+    pub(crate) fn from_texture(device: &wgpu::Device, texture: wgpu::Texture, label: &str) -> Self {
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = SamplerBuilder::linear().label(label).build(device);
+
+        Self {
+            texture,
+            view,
+            sampler,
+        }
+    }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    pub fn sampler(&self) -> &wgpu::Sampler {
+        &self.sampler
+    }
+}
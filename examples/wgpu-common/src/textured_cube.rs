@@ -0,0 +1,155 @@
+use glam::Vec3;
+use wgpu::util::DeviceExt;
+
+use crate::{BindGroupBuilder, GpuContext, Texture, Vertex, VertexBuffer};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PositionNormalUvVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+}
+
+impl Vertex for PositionNormalUvVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x2];
+}
+
+// (법선, u축, v축) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다.
+// 큐브맵과 같이 +X, -X, +Y, -Y, +Z, -Z 순서다
+const FACES: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+];
+
+// 면 하나의 인덱스 수
+const FACE_INDICES: u32 = 6;
+
+/// 면마다 다른 텍스처를 입힌 한 변이 1 인 큐브.
+///
+/// 꼭짓점을 공유하면 면마다 UV 를 따로 줄 수 없으므로 면당 4개씩 24개를 만들고,
+/// 인덱스 36개로 그린다. 텍스처 크기가 서로 달라도 되도록 텍스처 배열 대신
+/// 면마다 바인드 그룹을 만들고, 면 하나에 드로우 콜 하나씩 6번 그린다.
+///
+/// 텍스처 바인드 그룹은 `@binding(0)` 텍스처, `@binding(1)` 샘플러다.
+pub struct TexturedCube {
+    vertex_buffer: VertexBuffer<PositionNormalUvVertex>,
+    index_buffer: wgpu::Buffer,
+    faces: [Texture; 6],
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_groups: [wgpu::BindGroup; 6],
+}
+
+impl TexturedCube {
+    /// `faces` 는 +X, -X, +Y, -Y, +Z, -Z 면의 텍스처다.
+    /// 텍스처의 위쪽이 +Y 면은 -Z, -Y 면은 +Z, 나머지 면은 +Y 를 향한다.
+    pub fn new(gpu: &GpuContext, faces: [Texture; 6]) -> Self {
+        let device = gpu.device();
+        let (vertices, indices) = cube_geometry();
+        let vertex_buffer = VertexBuffer::from_data(device, &vertices);
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Textured Cube Index Buffer"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        let built = faces.each_ref().map(|texture| {
+            BindGroupBuilder::new()
+                .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+                .sampler(1, texture.sampler(), wgpu::ShaderStages::FRAGMENT)
+                .build(device, "Textured Cube Face Bind Group")
+        });
+        // 항목이 같은 레이아웃이므로 첫 번째 것만 파이프라인에 쓴다
+        let bind_group_layout = built[0].0.clone();
+        let bind_groups = built.map(|(_, bind_group)| bind_group);
+
+        Self {
+            vertex_buffer,
+            index_buffer,
+            faces,
+            bind_group_layout,
+            bind_groups,
+        }
+    }
+
+    /// 면 텍스처 바인드 그룹의 레이아웃
+    pub fn bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    pub fn face(&self, index: usize) -> &Texture {
+        &self.faces[index]
+    }
+
+    /// 면마다 `group` 번 바인드 그룹을 그 면의 텍스처로 바꿔 가며 그린다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>, group: u32) {
+        self.vertex_buffer.bind(render_pass, 0);
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        for (face, bind_group) in (0..).zip(&self.bind_groups) {
+            render_pass.set_bind_group(group, bind_group, &[]);
+            let start = face * FACE_INDICES;
+            render_pass.draw_indexed(start..start + FACE_INDICES, 0, 0..1);
+        }
+    }
+}
+
+fn cube_geometry() -> (Vec<PositionNormalUvVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (normal, u, v) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+
+        // 텍스처 좌표는 v 가 아래로 커지므로 v축 방향과 반대다
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            vertices.push(PositionNormalUvVertex {
+                position: ((n + u * su + v * sv) * 0.5).into(),
+                normal,
+                uv: [(su + 1.0) * 0.5, (1.0 - sv) * 0.5],
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_four_vertices_and_six_indices_per_face() {
+        let (vertices, indices) = cube_geometry();
+        assert_eq!(vertices.len(), 24);
+        assert_eq!(indices.len(), 36);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+    }
+
+    #[test]
+    fn triangles_face_outward() {
+        let (vertices, indices) = cube_geometry();
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(vertices[triangle[i] as usize].position));
+            let normal = Vec3::from(vertices[triangle[0] as usize].normal);
+            assert!((b - a).cross(c - a).dot(normal) > 0.0);
+        }
+    }
+
+    #[test]
+    fn each_face_covers_the_whole_texture() {
+        let (vertices, _) = cube_geometry();
+        for face in vertices.chunks(4) {
+            let mut uvs: Vec<[f32; 2]> = face.iter().map(|vertex| vertex.uv).collect();
+            uvs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(uvs, [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]]);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use glam::Vec2;
+
+use crate::{BindGroupBuilder, FullscreenTriangle, GpuContext, Texture, UniformBuffer};
+
+// tilemap.wgsl 의 TilemapParams 와 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct TilemapParams {
+    offset: [f32; 2],
+    zoom: f32,
+    tile_size: u32,
+    map_size: [u32; 2],
+    tileset_columns: u32,
+    tile_count: u32,
+}
+
+/// 타일 번호 격자를 한 번의 전체 화면 그리기로 그리는 2D 타일맵.
+///
+/// 격자는 칸마다 `u16` 하나인 `R16Uint` 텍스처로 올리고, 프래그먼트 셰이더가 픽셀마다
+/// 칸을 찾아 타일 번호를 읽은 뒤 타일셋 아틀라스에서 해당 텍셀을 가져온다.
+/// 맵 크기와 상관없이 드로우 콜은 하나이고 버텍스 버퍼도 없다.
+///
+/// 타일셋은 `tile_size` 크기의 정사각형 타일을 왼쪽 위부터 행 우선으로 번호를 매긴다.
+/// 격자 텍스처는 스토리지 텍스처 대신 샘플링 텍스처다. WebGL2 에는 스토리지 텍스처가 없다.
+pub struct Tilemap {
+    queue: wgpu::Queue,
+    tiles: Vec<u16>,
+    map_size: (u32, u32),
+    tile_texture: wgpu::Texture,
+    params: TilemapParams,
+    params_uniform: UniformBuffer<TilemapParams>,
+    fullscreen: FullscreenTriangle,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Tilemap {
+    /// 아무것도 그리지 않는 칸
+    pub const EMPTY: u16 = u16::MAX;
+
+    /// `tiles` 는 `map_size.0 * map_size.1` 개의 타일 번호를 행 우선으로 담는다.
+    pub fn new(
+        gpu: &GpuContext,
+        color_format: wgpu::TextureFormat,
+        tileset: &Texture,
+        tile_size: u32,
+        map_size: (u32, u32),
+        tiles: Vec<u16>,
+    ) -> Self {
+        assert_eq!(
+            tiles.len(),
+            (map_size.0 * map_size.1) as usize,
+            "tile grid does not match map size"
+        );
+        let (device, queue) = (gpu.device(), gpu.queue());
+
+        let size = wgpu::Extent3d {
+            width: map_size.0,
+            height: map_size.1,
+            depth_or_array_layers: 1,
+        };
+        let tile_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Tilemap Tile Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R16Uint,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        write_tiles(queue, &tile_texture, (0, 0), size, &tiles);
+
+        let tileset_size = tileset.texture().size();
+        let columns = (tileset_size.width / tile_size).max(1);
+        let params = TilemapParams {
+            offset: [0.0; 2],
+            zoom: 1.0,
+            tile_size,
+            map_size: [map_size.0, map_size.1],
+            tileset_columns: columns,
+            tile_count: columns * (tileset_size.height / tile_size),
+        };
+        let params_uniform = UniformBuffer::new(gpu, &params, 0, wgpu::ShaderStages::FRAGMENT);
+
+        let tile_view = tile_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let (texture_layout, bind_group) = BindGroupBuilder::new()
+            .entry(
+                0,
+                wgpu::ShaderStages::FRAGMENT,
+                wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Uint,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                wgpu::BindingResource::TextureView(&tile_view),
+            )
+            .texture(1, tileset.view(), wgpu::ShaderStages::FRAGMENT)
+            .build(device, "Tilemap Bind Group");
+
+        let fullscreen = FullscreenTriangle::new(device);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Tilemap Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("tilemap.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Tilemap Pipeline Layout"),
+            bind_group_layouts: &[params_uniform.bind_group_layout(), &texture_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Tilemap Pipeline"),
+            layout: Some(&layout),
+            vertex: fullscreen.vertex_state(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            queue: queue.clone(),
+            tiles,
+            map_size,
+            tile_texture,
+            params,
+            params_uniform,
+            fullscreen,
+            pipeline,
+            bind_group,
+        }
+    }
+
+    /// 가로, 세로 칸 수
+    pub fn map_size(&self) -> (u32, u32) {
+        self.map_size
+    }
+
+    pub fn tile(&self, x: u32, y: u32) -> Option<u16> {
+        (x < self.map_size.0 && y < self.map_size.1)
+            .then(|| self.tiles[(y * self.map_size.0 + x) as usize])
+    }
+
+    /// 칸 하나를 바꾼다. 격자 전체를 다시 올리지 않고 1x1 영역만 `write_texture` 한다.
+    /// 맵 밖의 칸은 무시한다.
+    pub fn set_tile(&mut self, x: u32, y: u32, tile_id: u16) {
+        if x >= self.map_size.0 || y >= self.map_size.1 {
+            return;
+        }
+        self.tiles[(y * self.map_size.0 + x) as usize] = tile_id;
+
+        let size = wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        };
+        write_tiles(&self.queue, &self.tile_texture, (x, y), size, &[tile_id]);
+    }
+
+    /// 화면 왼쪽 위에 보일 월드 좌표(타일셋 픽셀 단위)와 확대 배율
+    pub fn set_view(&mut self, offset: Vec2, zoom: f32) {
+        self.params.offset = offset.into();
+        self.params.zoom = zoom.max(f32::EPSILON);
+        self.params_uniform.update(&self.queue, &self.params);
+    }
+
+    /// 화면 픽셀 좌표 아래의 칸. 맵 밖이면 `None`
+    pub fn tile_at(&self, screen: Vec2) -> Option<(u32, u32)> {
+        screen_to_tile(screen, &self.params)
+    }
+
+    /// 맵을 그린다. 빈 칸은 기존 내용이 그대로 보인다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, self.params_uniform.bind_group(), &[]);
+        render_pass.set_bind_group(1, &self.bind_group, &[]);
+        self.fullscreen.draw(render_pass);
+    }
+}
+
+fn write_tiles(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    origin: (u32, u32),
+    size: wgpu::Extent3d,
+    tiles: &[u16],
+) {
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d {
+                x: origin.0,
+                y: origin.1,
+                z: 0,
+            },
+            aspect: wgpu::TextureAspect::All,
+        },
+        bytemuck::cast_slice(tiles),
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(2 * size.width),
+            rows_per_image: Some(size.height),
+        },
+        size,
+    );
+}
+
+// tilemap.wgsl 의 fs_main 과 같은 계산
+fn screen_to_tile(screen: Vec2, params: &TilemapParams) -> Option<(u32, u32)> {
+    let world = Vec2::from(params.offset) + screen / params.zoom;
+    let cell = (world / params.tile_size as f32).floor();
+    let inside = cell.x >= 0.0
+        && cell.y >= 0.0
+        && cell.x < params.map_size[0] as f32
+        && cell.y < params.map_size[1] as f32;
+    inside.then_some((cell.x as u32, cell.y as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(offset: [f32; 2], zoom: f32) -> TilemapParams {
+        TilemapParams {
+            offset,
+            zoom,
+            tile_size: 16,
+            map_size: [4, 3],
+            tileset_columns: 4,
+            tile_count: 8,
+        }
+    }
+
+    #[test]
+    fn maps_screen_pixels_to_cells() {
+        let params = params([0.0, 0.0], 2.0);
+        assert_eq!(screen_to_tile(Vec2::new(0.0, 0.0), &params), Some((0, 0)));
+        // 2 배 확대라서 화면 32 픽셀이 한 칸이다
+        assert_eq!(screen_to_tile(Vec2::new(31.9, 32.0), &params), Some((0, 1)));
+        assert_eq!(
+            screen_to_tile(Vec2::new(127.0, 95.0), &params),
+            Some((3, 2))
+        );
+        assert_eq!(screen_to_tile(Vec2::new(128.0, 0.0), &params), None);
+    }
+
+    #[test]
+    fn offset_scrolls_the_map() {
+        let params = params([20.0, -8.0], 1.0);
+        assert_eq!(screen_to_tile(Vec2::new(0.0, 8.0), &params), Some((1, 0)));
+        // 맵 위쪽 바깥
+        assert_eq!(screen_to_tile(Vec2::new(0.0, 0.0), &params), None);
+    }
+}
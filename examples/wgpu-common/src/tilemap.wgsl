@@ -0,0 +1,45 @@
+struct TilemapParams {
+    // 화면 왼쪽 위 픽셀에 보이는 월드 좌표. 월드 좌표는 타일셋의 픽셀 단위다
+    offset: vec2<f32>,
+    // 월드 1 픽셀이 화면에서 차지하는 픽셀 수
+    zoom: f32,
+    tile_size: u32,
+    map_size: vec2<u32>,
+    tileset_columns: u32,
+    tile_count: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> params: TilemapParams;
+
+// 칸마다 타일 번호 하나
+@group(1) @binding(0)
+var tiles: texture_2d<u32>;
+@group(1) @binding(1)
+var tileset: texture_2d<f32>;
+
+// tilemap.rs 의 Tilemap::EMPTY
+const EMPTY_TILE: u32 = 65535u;
+
+// FullscreenTriangle 이 화면 전체를 덮으므로 픽셀마다 어느 칸의 어느 텍셀인지 계산한다
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let world = params.offset + position.xy / params.zoom;
+    let cell = floor(world / f32(params.tile_size));
+    if any(cell < vec2<f32>(0.0)) || any(cell >= vec2<f32>(params.map_size)) {
+        discard;
+    }
+
+    let tile = textureLoad(tiles, vec2<u32>(cell), 0).r;
+    if tile == EMPTY_TILE || tile >= params.tile_count {
+        discard;
+    }
+
+    // 타일셋은 텍셀을 그대로 읽어서 옆 타일이 섞이지 않게 한다
+    let atlas_cell = vec2<u32>(tile % params.tileset_columns, tile / params.tileset_columns);
+    let local = min(
+        vec2<u32>(world - cell * f32(params.tile_size)),
+        vec2<u32>(params.tile_size - 1u),
+    );
+    return textureLoad(tileset, atlas_cell * params.tile_size + local, 0);
+}
@@ -0,0 +1,26 @@
+struct ToneMapParams {
+    exposure: f32,
+};
+
+@group(0) @binding(0)
+var hdr_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var hdr_sampler: sampler;
+@group(1) @binding(0)
+var<uniform> params: ToneMapParams;
+
+// 버텍스 셰이더는 FullscreenTriangle 이 제공한다
+struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Fragment shader
+// TONEMAP_OPERATOR 는 컴파일 전에 tonemap_operators.wgsl 의 함수 이름으로 바뀐다.
+// OUTPUT_ENCODING 은 출력 포맷이 sRGB 가 아니면 linear_to_srgb, 아니면 빈 문자열로 바뀐다
+@fragment
+fn fs_main(in: FragmentInput) -> @location(0) vec4<f32> {
+    let hdr = textureSample(hdr_texture, hdr_sampler, in.uv).rgb * params.exposure;
+    let mapped = TONEMAP_OPERATOR(hdr);
+    return vec4<f32>(OUTPUT_ENCODING(mapped), 1.0);
+}
@@ -0,0 +1,38 @@
+// 톤 매핑 연산자 모음. 모두 [0, inf) 범위의 선형 색을 [0, 1] 로 줄인다
+
+fn tonemap_clamp(color: vec3<f32>) -> vec3<f32> {
+    return clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+fn tonemap_reinhard(color: vec3<f32>) -> vec3<f32> {
+    return color / (color + vec3<f32>(1.0));
+}
+
+// Krzysztof Narkowicz 의 ACES 필름 커브 근사
+fn tonemap_aces(color: vec3<f32>) -> vec3<f32> {
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    return clamp((color * (a * color + b)) / (color * (c * color + d) + e), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+// John Hable 의 Uncharted 2 필름 커브
+fn uncharted2_curve(x: vec3<f32>) -> vec3<f32> {
+    let a = 0.15;
+    let b = 0.50;
+    let c = 0.10;
+    let d = 0.20;
+    let e = 0.02;
+    let f = 0.30;
+    return ((x * (a * x + c * b) + d * e) / (x * (a * x + b) + d * f)) - e / f;
+}
+
+fn tonemap_uncharted2(color: vec3<f32>) -> vec3<f32> {
+    // 흰색으로 보일 선형 밝기와 커브에 넣기 전에 곱하는 노출
+    let white_point = 11.2;
+    let exposure_bias = 2.0;
+    let curve = uncharted2_curve(color * exposure_bias);
+    return curve / uncharted2_curve(vec3<f32>(white_point));
+}
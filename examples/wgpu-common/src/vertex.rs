@@ -0,0 +1,102 @@
+use std::marker::PhantomData;
+
+use wgpu::util::DeviceExt;
+
+/// 버텍스 버퍼에 올릴 수 있는 `#[repr(C)]` 구조체.
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct ColorVertex {
+///     position: [f32; 3],
+///     color: [f32; 3],
+/// }
+///
+/// impl Vertex for ColorVertex {
+///     const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+///         &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+/// }
+/// ```
+pub trait Vertex: bytemuck::Pod {
+    /// 셰이더의 `@location(n)` 과 대응되는 속성 목록
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute];
+
+    fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// `T` 타입 버텍스 배열을 담고 있는 GPU 버퍼
+pub struct VertexBuffer<T: Vertex> {
+    buffer: wgpu::Buffer,
+    len: u32,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Vertex> VertexBuffer<T> {
+    pub fn from_data(device: &wgpu::Device, data: &[T]) -> Self {
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Vertex Buffer"),
+            contents: bytemuck::cast_slice(data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        Self {
+            buffer,
+            len: data.len() as u32,
+            _marker: PhantomData,
+        }
+    }
+
+    /// `write` 나 버퍼 복사로 내용을 바꿀 수 있는(`COPY_DST`) 버퍼를 만든다.
+    pub fn writable(device: &wgpu::Device, data: &[T]) -> Self {
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Vertex Buffer"),
+            contents: bytemuck::cast_slice(data),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Self {
+            buffer,
+            len: data.len() as u32,
+            _marker: PhantomData,
+        }
+    }
+
+    /// `queue.write_buffer` 로 앞부분을 덮어쓴다. `writable` 로 만든 버퍼에만 사용할 수 있다.
+    pub fn write(&self, queue: &wgpu::Queue, data: &[T]) {
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(data));
+    }
+
+    /// `wgpu::VertexState::buffers` 에 넘길 레이아웃
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        T::layout()
+    }
+
+    pub fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+    }
+
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// `slot` 에 버퍼를 바인딩한다.
+    pub fn bind(&self, render_pass: &mut wgpu::RenderPass<'_>, slot: u32) {
+        render_pass.set_vertex_buffer(slot, self.buffer.slice(..));
+    }
+
+    /// 0번 슬롯에 바인딩하고 모든 버텍스를 그린다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        self.bind(render_pass, 0);
+        render_pass.draw(0..self.len, 0..1);
+    }
+}
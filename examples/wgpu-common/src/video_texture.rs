@@ -0,0 +1,174 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlMediaElement, HtmlVideoElement};
+
+use crate::{GpuContext, Texture};
+
+/// `<video>` 로 재생하는 동영상의 현재 프레임을 담는 텍스처.
+///
+/// 디코딩은 브라우저가 하므로 WebM, H.264 처럼 브라우저가 재생할 수 있는 포맷이면 된다.
+/// 매 프레임 `update` 를 부르면 화면에 붙이지 않은 2D 캔버스에 현재 프레임을 그리고,
+/// 픽셀을 읽어서 `queue.write_texture` 로 올린다. 프레임이 바뀌지 않았으면 올리지 않는다.
+///
+/// 동영상은 소리 없이 반복 재생하고, 크기와 관계없이 `size` 로 늘이거나 줄여서 올린다.
+/// 다른 출처의 동영상은 CORS 를 허용해야 픽셀을 읽을 수 있다.
+pub struct VideoTexture {
+    video: HtmlVideoElement,
+    context: CanvasRenderingContext2d,
+    texture: Texture,
+    size: (u32, u32),
+    // oncanplay 가 불린 뒤로 true
+    ready: Rc<Cell<bool>>,
+    // 마지막으로 올린 프레임의 재생 시각 (초)
+    uploaded_time: Option<f64>,
+    _on_can_play: Closure<dyn FnMut()>,
+}
+
+impl VideoTexture {
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// `video_src_url` 을 재생하는 `<video>` 를 만들고, 재생할 수 있게 되면 재생을 시작한다.
+    pub fn new(gpu: &GpuContext, video_src_url: &str, size: (u32, u32)) -> Result<Self, JsValue> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or("no document")?;
+
+        let video: HtmlVideoElement = document.create_element("video")?.dyn_into()?;
+        // 자동 재생은 소리가 없어야 허용되고, iOS 는 전체 화면으로 바뀌지 않도록 playsinline 이 필요하다
+        video.set_muted(true);
+        video.set_loop(true);
+        video.set_autoplay(true);
+        video.set_attribute("playsinline", "")?;
+        video.set_cross_origin(Some("anonymous"));
+
+        let ready = Rc::new(Cell::new(false));
+        let on_can_play = {
+            let (video, ready) = (video.clone(), ready.clone());
+            Closure::wrap(Box::new(move || {
+                ready.set(true);
+                // autoplay 를 막는 브라우저도 있으므로 직접 재생한다. 거절되면 멈춘 첫 프레임을 보여 준다
+                let _ = video.play();
+            }) as Box<dyn FnMut()>)
+        };
+        video.set_oncanplay(Some(on_can_play.as_ref().unchecked_ref()));
+        video.set_src(video_src_url);
+
+        let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+        canvas.set_width(size.0);
+        canvas.set_height(size.1);
+        let context: CanvasRenderingContext2d = canvas
+            .get_context("2d")?
+            .ok_or("2d canvas context is not supported")?
+            .dyn_into()?;
+
+        let device = gpu.device();
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Video Texture"),
+            size: wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        Ok(Self {
+            video,
+            context,
+            texture: Texture::from_texture(device, texture, "Video Texture"),
+            size,
+            ready,
+            uploaded_time: None,
+            _on_can_play: on_can_play,
+        })
+    }
+
+    pub fn texture_view(&self) -> &wgpu::TextureView {
+        self.texture.view()
+    }
+
+    /// 뷰와 선형 샘플러를 함께 담은 텍스처. `TexturedCube` 처럼 `Texture` 를 받는 곳에 넘긴다.
+    /// 복제해도 같은 GPU 텍스처를 가리키므로 `update` 가 올린 프레임이 보인다.
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn video(&self) -> &HtmlVideoElement {
+        &self.video
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// 재생할 수 있을 만큼 데이터를 받았는지
+    pub fn is_ready(&self) -> bool {
+        self.ready.get()
+    }
+
+    /// 현재 프레임이 마지막으로 올린 프레임과 다르면 텍스처에 올리고 `true` 를 돌려준다.
+    /// CORS 를 허용하지 않는 동영상이면 픽셀을 읽을 수 없어서 `Err` 를 돌려준다.
+    pub fn update(&mut self, queue: &wgpu::Queue) -> Result<bool, JsValue> {
+        if !self.is_ready() || self.video.ready_state() < HtmlMediaElement::HAVE_CURRENT_DATA {
+            return Ok(false);
+        }
+        let time = self.video.current_time();
+        if self.uploaded_time == Some(time) {
+            return Ok(false);
+        }
+
+        let (width, height) = self.size;
+        self.context
+            .draw_image_with_html_video_element_and_dw_and_dh(
+                &self.video,
+                0.0,
+                0.0,
+                width as f64,
+                height as f64,
+            )?;
+        let pixels = self
+            .context
+            .get_image_data(0.0, 0.0, width as f64, height as f64)?
+            .data();
+
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: self.texture.texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.uploaded_time = Some(time);
+        Ok(true)
+    }
+}
+
+impl Drop for VideoTexture {
+    fn drop(&mut self) {
+        // 화면에 붙이지 않은 요소라도 재생 중이면 계속 디코딩하므로 멈추고 소스를 비운다
+        self.video.set_oncanplay(None);
+        let _ = self.video.pause();
+        let _ = self.video.remove_attribute("src");
+        self.video.load();
+    }
+}
@@ -0,0 +1,32 @@
+struct VignetteParams {
+    intensity: f32,
+    radius: f32,
+    softness: f32,
+};
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var input_sampler: sampler;
+@group(1) @binding(0)
+var<uniform> params: VignetteParams;
+
+// 버텍스 셰이더는 FullscreenTriangle 이 제공한다
+struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Fragment shader
+// 화면 중심에서 radius 보다 먼 곳부터 softness 에 걸쳐 intensity 만큼 어두워진다
+@fragment
+fn fs_main(in: FragmentInput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.uv);
+
+    // 가로로 긴 화면에서도 원 모양이 되도록 종횡비를 곱한다
+    let size = vec2<f32>(textureDimensions(input_texture));
+    let offset = (in.uv - 0.5) * vec2<f32>(size.x / size.y, 1.0);
+    let falloff = smoothstep(params.radius, params.radius + params.softness, length(offset));
+
+    return vec4<f32>(color.rgb * (1.0 - params.intensity * falloff), color.a);
+}
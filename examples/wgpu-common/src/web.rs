@@ -0,0 +1,206 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    Document, Element, HtmlAnchorElement, HtmlCanvasElement, ResizeObserver, ResizeObserverEntry,
+};
+
+/// `requestAnimationFrame` 으로 매 프레임 `frame` 을 호출한다.
+/// `frame` 이 `false` 를 반환하면 루프를 멈춘다.
+///
+/// 탭이 숨겨지면 다음 프레임을 요청하지 않고 쉬었다가, 다시 보이면 `VisibilityObserver` 가
+/// 루프를 이어서 돌린다. 숨겨진 탭에서 서피스를 건드려 `SurfaceError::Lost` 가 나는 것을 막는다.
+pub fn start_animation_loop(mut frame: impl FnMut() -> bool + 'static) {
+    let f = Rc::new(RefCell::new(None));
+    let g = Rc::clone(&f);
+    // 탭이 숨겨져서 다음 프레임을 요청하지 않은 상태
+    let paused = Rc::new(Cell::new(false));
+    let observer = Rc::new(RefCell::new(None));
+
+    let loop_paused = Rc::clone(&paused);
+    let loop_observer = Rc::clone(&observer);
+    *g.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        if is_document_hidden() {
+            loop_paused.set(true);
+            return;
+        }
+
+        if !frame() {
+            // 루프가 끝났으므로 다시 시작하지 않도록 리스너를 뗀다
+            loop_observer.borrow_mut().take();
+            return;
+        }
+
+        // Schedule next frame
+        request_animation_frame(f.borrow().as_ref().unwrap());
+    }) as Box<dyn FnMut()>));
+
+    let resume = Rc::clone(&g);
+    *observer.borrow_mut() = VisibilityObserver::new(move |hidden| {
+        if !hidden && paused.replace(false) {
+            request_animation_frame(resume.borrow().as_ref().unwrap());
+        }
+    })
+    .ok();
+
+    request_animation_frame(g.borrow().as_ref().unwrap());
+}
+
+pub fn request_animation_frame(f: &Closure<dyn FnMut()>) {
+    web_sys::window()
+        .unwrap()
+        .request_animation_frame(f.as_ref().unchecked_ref())
+        .expect("Failed to request animation frame");
+}
+
+pub fn get_canvas(canvas_id: &str) -> Result<HtmlCanvasElement, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("Failed to get window"))?;
+    let document = window
+        .document()
+        .ok_or_else(|| JsValue::from_str("Failed to get document"))?;
+
+    document
+        .get_element_by_id(canvas_id)
+        .ok_or_else(|| JsValue::from_str(&format!("Canvas with id '{}' not found", canvas_id)))?
+        .dyn_into::<HtmlCanvasElement>()
+        .map_err(|_| JsValue::from_str("Element is not a canvas"))
+}
+
+/// 캔버스 CSS 크기에 대한 렌더링 해상도 배율.
+/// 최종 배율은 `device_pixel_ratio * factor` 이다.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RenderScale {
+    /// 기기의 물리 픽셀 해상도
+    #[default]
+    Native,
+    /// 물리 픽셀 해상도의 절반
+    Half,
+    Custom(f64),
+}
+
+impl RenderScale {
+    pub fn factor(self) -> f64 {
+        match self {
+            RenderScale::Native => 1.0,
+            RenderScale::Half => 0.5,
+            RenderScale::Custom(factor) => factor,
+        }
+    }
+}
+
+pub fn get_canvas_size(canvas: &HtmlCanvasElement, scale: RenderScale) -> (u32, u32) {
+    let client_rect = canvas.get_bounding_client_rect();
+    physical_size((client_rect.width(), client_rect.height()), scale)
+}
+
+/// CSS 픽셀 크기를 렌더링할 픽셀 크기로 바꾼다.
+pub fn physical_size(logical_size: (f64, f64), scale: RenderScale) -> (u32, u32) {
+    let pixel_ratio = web_sys::window().unwrap().device_pixel_ratio() * scale.factor();
+    (
+        (logical_size.0 * pixel_ratio) as u32,
+        (logical_size.1 * pixel_ratio) as u32,
+    )
+}
+
+/// `observe_resize` 로 등록한 `ResizeObserver`. drop 되면 관찰을 멈춘다.
+pub struct ResizeObserverHandle {
+    observer: ResizeObserver,
+    _callback: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl Drop for ResizeObserverHandle {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// `element` 의 크기가 실제로 바뀔 때만 CSS 픽셀 크기로 `on_resize` 를 호출한다.
+/// 매 프레임 크기를 비교하는 대신 사용한다.
+pub fn observe_resize(
+    element: &Element,
+    mut on_resize: impl FnMut((f64, f64)) + 'static,
+) -> Result<ResizeObserverHandle, JsValue> {
+    let callback = Closure::wrap(Box::new(move |entries: js_sys::Array| {
+        // 한 번에 여러 변경이 모이면 마지막 크기만 사용한다
+        if let Some(entry) = entries
+            .iter()
+            .last()
+            .and_then(|entry| entry.dyn_into::<ResizeObserverEntry>().ok())
+        {
+            let rect = entry.content_rect();
+            on_resize((rect.width(), rect.height()));
+        }
+    }) as Box<dyn FnMut(js_sys::Array)>);
+
+    let observer = ResizeObserver::new(callback.as_ref().unchecked_ref())?;
+    observer.observe(element);
+
+    Ok(ResizeObserverHandle {
+        observer,
+        _callback: callback,
+    })
+}
+
+/// `document.hidden`. 문서를 얻을 수 없으면 보이는 것으로 본다.
+pub fn is_document_hidden() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .is_some_and(|document| document.hidden())
+}
+
+/// `visibilitychange` 이벤트 리스너. drop 되면 리스너를 뗀다.
+pub struct VisibilityObserver {
+    document: Document,
+    callback: Closure<dyn FnMut()>,
+}
+
+impl VisibilityObserver {
+    /// 탭이 숨겨지거나 다시 보일 때마다 `document.hidden` 값으로 `on_change` 를 호출한다.
+    pub fn new(mut on_change: impl FnMut(bool) + 'static) -> Result<Self, JsValue> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| JsValue::from_str("Failed to get document"))?;
+
+        let callback = {
+            let document = document.clone();
+            Closure::wrap(Box::new(move || on_change(document.hidden())) as Box<dyn FnMut()>)
+        };
+        document.add_event_listener_with_callback(
+            "visibilitychange",
+            callback.as_ref().unchecked_ref(),
+        )?;
+
+        Ok(Self { document, callback })
+    }
+}
+
+impl Drop for VisibilityObserver {
+    fn drop(&mut self) {
+        let _ = self.document.remove_event_listener_with_callback(
+            "visibilitychange",
+            self.callback.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+/// `bytes` 를 base64 data URL 로 만들어 `file_name` 으로 다운로드시킨다.
+pub fn download_bytes(bytes: &[u8], mime_type: &str, file_name: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("Failed to get document"))?;
+
+    let anchor = document
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    anchor.set_href(&format!(
+        "data:{};base64,{}",
+        mime_type,
+        BASE64.encode(bytes)
+    ));
+    anchor.set_download(file_name);
+    anchor.click();
+    Ok(())
+}
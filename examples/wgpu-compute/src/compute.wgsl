@@ -0,0 +1,44 @@
+struct GradientVertex {
+    position: vec4<f32>,
+    color: vec4<f32>,
+};
+
+struct Params {
+    time: f32,
+    grid_size: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var<storage, read_write> vertices: array<GradientVertex>;
+
+// 셀 하나를 두 개의 삼각형(6개 꼭짓점)으로 채운다
+const CORNERS = array<vec2<f32>, 6>(
+    vec2<f32>(0.0, 0.0),
+    vec2<f32>(1.0, 0.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(0.0, 1.0),
+    vec2<f32>(0.0, 0.0),
+);
+
+@compute @workgroup_size(8, 8)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_size || id.y >= params.grid_size) {
+        return;
+    }
+
+    let cell_size = 1.6 / f32(params.grid_size);
+    let base = (id.y * params.grid_size + id.x) * 6u;
+
+    for (var i = 0u; i < 6u; i++) {
+        let corner = vec2<f32>(id.xy) + CORNERS[i];
+        let uv = corner / f32(params.grid_size);
+        let position = vec2<f32>(-0.8, -0.8) + corner * cell_size;
+        let wave = 0.5 + 0.5 * sin(params.time + uv.x * 6.0);
+
+        vertices[base + i].position = vec4<f32>(position, 0.0, 1.0);
+        vertices[base + i].color = vec4<f32>(uv.x, uv.y * wave, 1.0 - uv.x, 1.0);
+    }
+}
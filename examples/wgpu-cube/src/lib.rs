@@ -0,0 +1,392 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    CullMode, DepthTexture, FormatSelector, GpuContext, InitError, Mesh, UniformBuffer, Vertex,
+};
+
+thread_local! {
+    // JS 에서 호출하는 함수들이 사용하는 State
+    static STATE: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// (법선, u축, v축, 색) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+type Face = ([f32; 3], [f32; 3], [f32; 3], [f32; 3]);
+
+const FACES: [Face; 6] = [
+    (
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ),
+    (
+        [-1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 1.0, 1.0],
+    ),
+    (
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+    ),
+    (
+        [0.0, -1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, -1.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+    ),
+];
+
+// 면마다 색이 다르도록 꼭짓점을 공유하지 않고 면당 4개씩 만든다
+fn cube_geometry() -> (Vec<ColorVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (normal, u, v, color) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = (n + u * su + v * sv) * 0.5;
+            vertices.push(ColorVertex {
+                position: position.into(),
+                color,
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    // CullMode::ALL 순서로 만든 파이프라인. 같은 파이프라인 레이아웃을 공유한다
+    render_pipelines: [wgpu::RenderPipeline; 3],
+    cull_mode: CullMode,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    uniform: UniformBuffer<[[f32; 4]; 4]>,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 메시 생성
+        let (vertices, indices) = cube_geometry();
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+
+        // MVP 행렬을 담을 유니폼 버퍼
+        let uniform = UniformBuffer::new(
+            &gpu,
+            &Mat4::IDENTITY.to_cols_array_2d(),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        // 컬링 모드만 다른 파이프라인을 미리 만들어 두고 그릴 때 고른다
+        let render_pipelines = CullMode::ALL.map(|cull_mode| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Render Pipeline"),
+                layout: Some(&render_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[ColorVertex::layout()],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_config.format,
+                        blend: Some(wgpu::BlendState::REPLACE),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: cull_mode.face(),
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: Some(DepthTexture::depth_stencil_state()),
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+                cache: None,
+            })
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipelines,
+            // 컬링 없이 깊이 테스트만으로 앞면이 뒷면을 가리도록 한다
+            cull_mode: CullMode::None,
+            depth_texture,
+            mesh,
+            uniform,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    /// 미리 만들어 둔 파이프라인 중 하나를 고르므로 파이프라인을 다시 만들지 않는다.
+    fn set_cull_mode(&mut self, mode: CullMode) {
+        self.cull_mode = mode;
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        let aspect = self.size.0 as f32 / self.size.1 as f32;
+        let projection = Mat4::perspective_rh(45f32.to_radians(), aspect, 0.1, 100.0);
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 1.5, 3.0), Vec3::ZERO, Vec3::Y);
+        let model = Mat4::from_rotation_y(time_secs) * Mat4::from_rotation_x(time_secs * 0.7);
+        let mvp = projection * view * model;
+
+        self.uniform
+            .update(self.gpu.queue(), &mvp.to_cols_array_2d());
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipelines[self.cull_mode.index()]);
+            render_pass.set_bind_group(0, self.uniform.bind_group(), &[]);
+            self.mesh.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    STATE.with_borrow_mut(|current| *current = Some(Rc::clone(&state)));
+    start_render_loop(state);
+    Ok(())
+}
+
+/// 그리지 않을 면을 고른다. `"none"`, `"back"`, `"front"` 중 하나여야 한다.
+#[wasm_bindgen]
+pub fn set_cull_mode(mode: &str) -> Result<(), JsValue> {
+    let mode = match mode {
+        "none" => CullMode::None,
+        "back" => CullMode::Back,
+        "front" => CullMode::Front,
+        _ => return Err(JsValue::from_str(&format!("Unknown cull mode '{}'", mode))),
+    };
+
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut()) {
+            state.set_cull_mode(mode);
+        }
+    });
+    Ok(())
+}
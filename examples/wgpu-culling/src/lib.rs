@@ -0,0 +1,931 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Vec2, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    Aabb, BvhTree, Camera, CameraUniform, DepthTexture, EntityId, FormatSelector, FrameTimer,
+    Frustum, GpuContext, GpuDrivenRenderer, InitError, InstanceBuffer, InstanceRaw, Mesh,
+    OcclusionQueryPool, Ray, RenderPassBuilder, StaticScene, Transform, UniformBuffer, Vertex,
+};
+
+// 한 변에 놓는 큐브 수. 전체 GRID_SIZE * GRID_SIZE 개를 각각 따로 그린다
+const GRID_SIZE: i32 = 48;
+const GRID_SPACING: f32 = 2.0;
+// benchmark_static_scene 에서 번들로 묶는 큐브 수
+const STATIC_BENCHMARK_MESHES: usize = 1000;
+
+thread_local! {
+    // JS 에서 호출하는 함수들이 사용하는 State
+    static STATE: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// (법선, u축, v축, 색) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+type Face = ([f32; 3], [f32; 3], [f32; 3], [f32; 3]);
+
+const FACES: [Face; 6] = [
+    (
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ),
+    (
+        [-1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 1.0, 1.0],
+    ),
+    (
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+    ),
+    (
+        [0.0, -1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, -1.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+    ),
+];
+
+// 면마다 색이 다르도록 꼭짓점을 공유하지 않고 면당 4개씩 만든다.
+// 오브젝트마다 모델 행렬을 두는 대신 월드 위치를 버텍스에 미리 더해 둔다
+fn cube_geometry(center: Vec3) -> (Vec<ColorVertex>, Vec<u16>) {
+    box_geometry(center, Vec3::splat(0.5))
+}
+
+fn box_geometry(center: Vec3, half_extents: Vec3) -> (Vec<ColorVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (normal, u, v, color) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = center + (n + u * su + v * sv) * half_extents;
+            vertices.push(ColorVertex {
+                position: position.into(),
+                color,
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+// 바닥에 격자로 늘어놓은 큐브 하나
+struct SceneObject {
+    mesh: Mesh,
+    bounds: Aabb,
+}
+
+fn scene_objects(gpu: &GpuContext) -> Vec<SceneObject> {
+    let half = GRID_SIZE / 2;
+    (-half..GRID_SIZE - half)
+        .flat_map(|x| (-half..GRID_SIZE - half).map(move |z| (x, z)))
+        .map(|(x, z)| {
+            let center = Vec3::new(x as f32 * GRID_SPACING, 0.0, z as f32 * GRID_SPACING);
+            let (vertices, indices) = cube_geometry(center);
+            let bounds = Aabb::from_points(vertices.iter().map(|v| Vec3::from(v.position)))
+                .expect("cube has vertices");
+            SceneObject {
+                mesh: Mesh::new(gpu, &vertices, &indices),
+                bounds,
+            }
+        })
+        .collect()
+}
+
+// 원점을 중심으로 정사각형에 가깝게 늘어놓은 `count` 개의 큐브 인스턴스와 월드 바운딩 박스
+fn instance_grid(count: u32) -> Vec<(Transform, Aabb)> {
+    let side = (count as f32).sqrt().ceil() as u32;
+    let half = side as f32 * GRID_SPACING * 0.5;
+    (0..count)
+        .map(|i| {
+            let center = Vec3::new(
+                (i % side) as f32 * GRID_SPACING - half,
+                0.0,
+                (i / side) as f32 * GRID_SPACING - half,
+            );
+            (
+                Transform::from_translation(center),
+                Aabb::new(center - Vec3::splat(0.5), center + Vec3::splat(0.5)),
+            )
+        })
+        .collect()
+}
+
+// 카메라를 둘러싼 네 벽. 모서리는 비워 두어서 틈으로 바깥 큐브가 보인다
+fn occluder_meshes(gpu: &GpuContext) -> Vec<Mesh> {
+    [
+        (Vec3::new(5.0, 1.5, 0.0), Vec3::new(0.25, 2.0, 4.0)),
+        (Vec3::new(-5.0, 1.5, 0.0), Vec3::new(0.25, 2.0, 4.0)),
+        (Vec3::new(0.0, 1.5, 5.0), Vec3::new(4.0, 2.0, 0.25)),
+        (Vec3::new(0.0, 1.5, -5.0), Vec3::new(4.0, 2.0, 0.25)),
+    ]
+    .into_iter()
+    .map(|(center, half_extents)| {
+        let (vertices, indices) = box_geometry(center, half_extents);
+        Mesh::new(gpu, &vertices, &indices)
+    })
+    .collect()
+}
+
+// 카메라 바인드 그룹은 그대로 두고 유니폼 내용만 바꾸므로 번들을 다시 기록할 필요가 없다
+fn record_static_scene(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    pipeline: &wgpu::RenderPipeline,
+    camera_bind_group: &wgpu::BindGroup,
+    occluders: &[Mesh],
+    objects: &[SceneObject],
+) -> StaticScene {
+    StaticScene::new(
+        device,
+        &[Some(format)],
+        Some(DepthTexture::FORMAT),
+        |bundle| {
+            bundle.set_pipeline(pipeline);
+            bundle.set_bind_group(0, camera_bind_group, &[]);
+            for occluder in occluders {
+                occluder.draw_bundle(bundle);
+            }
+            for object in objects {
+                object.mesh.draw_bundle(bundle);
+            }
+        },
+    )
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    // 색과 깊이를 쓰지 않고 깊이 테스트만 한다. 가려졌던 큐브가 다시 보이는지 확인할 때 쓴다
+    occlusion_test_pipeline: wgpu::RenderPipeline,
+    // 모델 행렬을 인스턴스 속성으로 받는다. GPU 컬링 벤치마크에서 쓴다
+    instanced_pipeline: wgpu::RenderPipeline,
+    supports_compute: bool,
+    depth_texture: DepthTexture,
+    // 컬링하지 않고 항상 그리는 벽
+    occluders: Vec<Mesh>,
+    objects: Vec<SceneObject>,
+    // 컬링을 끈 프레임은 모든 큐브를 기록해 둔 번들로 그린다
+    static_scene: StaticScene,
+    // objects 의 인덱스를 EntityId 로 사용한다
+    bvh: BvhTree,
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    canvas_id: String,
+    size: (u32, u32),
+    culling: bool,
+    last_frame_culled_draws: u32,
+    // objects 의 인덱스로 쿼리한다
+    occlusion_queries: OcclusionQueryPool,
+    occlusion_culling: bool,
+    last_frame_occluded_draws: u32,
+    frame_timer: FrameTimer,
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        let occluders = occluder_meshes(&gpu);
+        let objects = scene_objects(&gpu);
+        let bounds: Vec<(EntityId, Aabb)> = objects
+            .iter()
+            .enumerate()
+            .map(|(i, object)| (EntityId(i as u32), object.bounds))
+            .collect();
+        let bvh = BvhTree::new(&bounds);
+
+        // 카메라: 격자 가운데에서 바깥쪽을 둘러본다
+        let camera = Camera::new(
+            Vec3::new(0.0, 3.0, 0.0),
+            Vec3::new(1.0, 2.0, 0.0),
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let create_pipeline = |label,
+                               entry_point,
+                               buffers: &[wgpu::VertexBufferLayout],
+                               write_mask,
+                               depth_write_enabled| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&render_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    buffers,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_config.format,
+                        blend: Some(wgpu::BlendState::REPLACE),
+                        write_mask,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: Some(wgpu::Face::Back),
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    depth_write_enabled,
+                    ..DepthTexture::depth_stencil_state()
+                }),
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+                cache: None,
+            })
+        };
+        let render_pipeline = create_pipeline(
+            "Render Pipeline",
+            "vs_main",
+            &[ColorVertex::layout()],
+            wgpu::ColorWrites::ALL,
+            true,
+        );
+        let occlusion_test_pipeline = create_pipeline(
+            "Occlusion Test Pipeline",
+            "vs_main",
+            &[ColorVertex::layout()],
+            wgpu::ColorWrites::empty(),
+            false,
+        );
+        let instanced_pipeline = create_pipeline(
+            "Instanced Pipeline",
+            "vs_instanced",
+            &[ColorVertex::layout(), InstanceBuffer::layout()],
+            wgpu::ColorWrites::ALL,
+            true,
+        );
+        // 컴퓨트 셰이더는 WebGL2 에서 지원되지 않으므로 GPU 컬링 벤치마크는 WebGPU 에서만 한다
+        let supports_compute = adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS);
+
+        let static_scene = record_static_scene(
+            device,
+            surface_config.format,
+            &render_pipeline,
+            camera_uniform.bind_group(),
+            &occluders,
+            &objects,
+        );
+        let occlusion_queries = OcclusionQueryPool::new(device, objects.len() as u32);
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            occlusion_test_pipeline,
+            instanced_pipeline,
+            supports_compute,
+            depth_texture,
+            occluders,
+            objects,
+            static_scene,
+            bvh,
+            camera,
+            camera_uniform,
+            canvas_id: canvas_id.to_string(),
+            size,
+            culling: true,
+            last_frame_culled_draws: 0,
+            occlusion_queries,
+            occlusion_culling: false,
+            last_frame_occluded_draws: 0,
+            frame_timer: FrameTimer::new(),
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        // 제자리에서 천천히 돌면서 바라본다
+        let angle = time_secs * 0.3;
+        self.camera.target = self.camera.eye + Vec3::new(angle.cos(), -0.2, angle.sin());
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        if self.occlusion_culling {
+            (self.last_frame_culled_draws, self.last_frame_occluded_draws) =
+                self.draw_scene_occluded(&view, self.culling);
+        } else {
+            self.last_frame_culled_draws = self.draw_scene(&view, self.culling);
+            self.last_frame_occluded_draws = 0;
+        }
+        output.present();
+
+        Ok(())
+    }
+
+    /// 마지막 프레임에서 절두체 밖이라 건너뛴 큐브 수
+    fn last_frame_culled_draws(&self) -> u32 {
+        self.last_frame_culled_draws
+    }
+
+    /// 마지막 프레임에서 지난 오클루전 쿼리 결과로 건너뛴 큐브 수
+    fn last_frame_occluded_draws(&self) -> u32 {
+        self.last_frame_occluded_draws
+    }
+
+    fn set_occlusion_culling(&mut self, enabled: bool) {
+        if enabled && !self.occlusion_culling {
+            // 꺼져 있는 동안의 결과는 믿을 수 없으므로 모두 보인다고 보고 다시 시작한다
+            self.occlusion_queries.reset();
+        }
+        self.occlusion_culling = enabled;
+    }
+
+    fn begin_frame(&self, view: &wgpu::TextureView) -> RenderPassBuilder {
+        RenderPassBuilder::new(&self.gpu, view)
+            .clear_color(wgpu::Color {
+                r: 0.1,
+                g: 0.2,
+                b: 0.3,
+                a: 1.0,
+            })
+            .depth(self.depth_texture.view())
+    }
+
+    // 장면을 `view` 에 그리고 컬링된 그리기 수를 반환한다.
+    // 컬링하지 않을 때는 그리기 명령이 매 프레임 같으므로 번들을 실행한다
+    fn draw_scene(&self, view: &wgpu::TextureView, culling: bool) -> u32 {
+        if !culling {
+            self.draw_bundle(view, &self.static_scene);
+            return 0;
+        }
+
+        let mut frame = self
+            .begin_frame(view)
+            .frustum(Frustum::from_view_projection(self.camera.view_projection()));
+        frame.set_pipeline(&self.render_pipeline);
+        for occluder in &self.occluders {
+            frame.draw_mesh(occluder, self.camera_uniform.bind_group());
+        }
+        for object in &self.objects {
+            frame.draw_mesh_bounded(
+                &object.mesh,
+                self.camera_uniform.bind_group(),
+                &object.bounds,
+            );
+        }
+
+        let culled_draws = frame.culled_draws();
+        frame.submit();
+        culled_draws
+    }
+
+    // 지난 오클루전 쿼리에서 가려졌던 큐브는 진짜로 그리지 않고 깊이 테스트만 해서 다시 쿼리한다.
+    // 절두체 밖에서 건너뛴 수와 가려져서 건너뛴 수를 반환한다
+    fn draw_scene_occluded(&mut self, view: &wgpu::TextureView, culling: bool) -> (u32, u32) {
+        self.occlusion_queries.begin_frame();
+        let frustum = Frustum::from_view_projection(self.camera.view_projection());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Occlusion Culling Encoder"),
+                });
+        let mut culled_draws = 0;
+        let mut occluded_draws = 0;
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Occlusion Culling Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: Some(self.occlusion_queries.query_set()),
+                timestamp_writes: None,
+            });
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+
+            let mut visible = Vec::new();
+            let mut occluded = Vec::new();
+            for (i, object) in self.objects.iter().enumerate() {
+                if culling && !frustum.test_aabb(&object.bounds) {
+                    culled_draws += 1;
+                } else if self.occlusion_queries.is_visible(i as u32) {
+                    visible.push(i);
+                } else {
+                    occluded.push(i);
+                }
+            }
+
+            // 가려진 큐브의 쿼리가 벽과 보이는 큐브의 깊이에 대해 테스트되도록 나중에 그린다
+            render_pass.set_pipeline(&self.render_pipeline);
+            for occluder in &self.occluders {
+                occluder.draw(&mut render_pass);
+            }
+            for &i in &visible {
+                let mesh = &self.objects[i].mesh;
+                self.occlusion_queries
+                    .query(&mut render_pass, i as u32, |pass| mesh.draw(pass));
+            }
+            render_pass.set_pipeline(&self.occlusion_test_pipeline);
+            for &i in &occluded {
+                let mesh = &self.objects[i].mesh;
+                self.occlusion_queries
+                    .query(&mut render_pass, i as u32, |pass| mesh.draw(pass));
+                occluded_draws += 1;
+            }
+        }
+        self.occlusion_queries.resolve(&mut encoder);
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        self.occlusion_queries.read_back();
+        (culled_draws, occluded_draws)
+    }
+
+    // `objects` 의 그리기 명령을 매번 새로 기록해서 그린다
+    fn draw_recorded(&self, view: &wgpu::TextureView, objects: &[SceneObject]) {
+        let mut frame = self.begin_frame(view);
+        frame.set_pipeline(&self.render_pipeline);
+        for object in objects {
+            frame.draw_mesh(&object.mesh, self.camera_uniform.bind_group());
+        }
+        frame.submit();
+    }
+
+    fn draw_bundle(&self, view: &wgpu::TextureView, scene: &StaticScene) {
+        let mut frame = self.begin_frame(view);
+        frame.execute_bundle(scene.bundle());
+        frame.submit();
+    }
+
+    // 인스턴스 파이프라인과 카메라를 설정한 패스에서 `draw` 를 호출해서 `view` 에 그린다
+    fn draw_instanced(
+        &self,
+        view: &wgpu::TextureView,
+        encode: impl FnOnce(&mut wgpu::CommandEncoder),
+        draw: impl FnOnce(&mut wgpu::RenderPass<'_>),
+    ) {
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Instanced Encoder"),
+                });
+        encode(&mut encoder);
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Instanced Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            render_pass.set_pipeline(&self.instanced_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            draw(&mut render_pass);
+        }
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+    }
+
+    // 캔버스의 CSS 픽셀 좌표를 지나는 광선이 처음 부딪히는 큐브
+    fn pick(&self, position: Vec2) -> Option<EntityId> {
+        let canvas = get_canvas(&self.canvas_id).ok()?;
+        let rect = canvas.get_bounding_client_rect();
+        let screen_size = Vec2::new(rect.width() as f32, rect.height() as f32);
+
+        let ray = Ray::from_screen(position, screen_size, self.camera.view_projection());
+        self.bvh.cast_ray(ray)
+    }
+
+    // 벤치마크용 오프스크린 텍스처. 화면에 보이지 않게 같은 장면을 여러 번 그린다
+    fn benchmark_target(&self) -> wgpu::Texture {
+        self.gpu.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("Benchmark Target"),
+            size: wgpu::Extent3d {
+                width: self.size.0,
+                height: self.size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.surface_config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_ms() -> f64 {
+    web_sys::window().unwrap().performance().unwrap().now()
+}
+
+fn now_secs() -> f32 {
+    (now_ms() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.frame_timer.begin_frame();
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+
+                state.frame_timer.end_frame();
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+fn with_state<T>(f: impl FnOnce(&mut State) -> T) -> Result<T, JsValue> {
+    let state = STATE
+        .with_borrow(|state| state.clone())
+        .ok_or_else(|| JsValue::from_str("Renderer is not running"))?;
+    let mut state = state
+        .try_borrow_mut()
+        .map_err(|_| JsValue::from_str("State borrowed elsewhere"))?;
+    Ok(f(&mut state))
+}
+
+/// 절두체 컬링을 켜거나 끈다.
+#[wasm_bindgen]
+pub fn set_culling(enabled: bool) -> Result<(), JsValue> {
+    with_state(|state| state.culling = enabled)
+}
+
+/// 마지막 프레임에서 컬링된 그리기 수
+#[wasm_bindgen]
+pub fn last_frame_culled_draws() -> Result<u32, JsValue> {
+    with_state(|state| state.last_frame_culled_draws())
+}
+
+/// 오클루전 쿼리로 가려진 큐브를 건너뛰는 기능을 켜거나 끈다.
+#[wasm_bindgen]
+pub fn set_occlusion_culling(enabled: bool) -> Result<(), JsValue> {
+    with_state(|state| state.set_occlusion_culling(enabled))
+}
+
+/// 마지막 프레임에서 가려져서 건너뛴 그리기 수
+#[wasm_bindgen]
+pub fn last_frame_occluded_draws() -> Result<u32, JsValue> {
+    with_state(|state| state.last_frame_occluded_draws())
+}
+
+/// 캔버스 왼쪽 위를 기준으로 한 CSS 픽셀 좌표 아래에 있는 큐브의 번호.
+/// 아무것도 없으면 `undefined` 를 반환한다.
+#[wasm_bindgen]
+pub fn pick(x: f32, y: f32) -> Result<Option<u32>, JsValue> {
+    with_state(|state| state.pick(Vec2::new(x, y)).map(|EntityId(id)| id))
+}
+
+/// 평균 FPS
+#[wasm_bindgen]
+pub fn fps() -> Result<f32, JsValue> {
+    with_state(|state| state.frame_timer.fps())
+}
+
+/// 같은 장면을 `frames` 번 오프스크린 텍스처에 그리면서 컬링 유무에 따른
+/// CPU 기록/제출 시간을 비교한다.
+#[wasm_bindgen]
+pub fn benchmark_culling(frames: u32) -> Result<String, JsValue> {
+    with_state(|state| {
+        let target = state.benchmark_target();
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let measure = |culling: bool| {
+            let start = now_ms();
+            let mut culled = 0;
+            for _ in 0..frames {
+                culled = state.draw_scene(&view, culling);
+            }
+            (now_ms() - start, culled)
+        };
+        let (unculled_ms, _) = measure(false);
+        let (culled_ms, culled) = measure(true);
+
+        let report = format!(
+            "{} frames x {} cubes: no culling {:.2} ms, culling {:.2} ms ({} culled per frame)",
+            frames,
+            state.objects.len(),
+            unculled_ms,
+            culled_ms,
+            culled,
+        );
+        console::log_1(&report.clone().into());
+        report
+    })
+}
+
+/// 큐브 1000 개를 `frames` 번 오프스크린 텍스처에 그리면서, 매 프레임 그리기 명령을
+/// 다시 기록하는 경우와 미리 기록한 렌더 번들을 실행하는 경우의 CPU 시간을 비교한다.
+#[wasm_bindgen]
+pub fn benchmark_static_scene(frames: u32) -> Result<String, JsValue> {
+    with_state(|state| {
+        let target = state.benchmark_target();
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let objects = &state.objects[..STATIC_BENCHMARK_MESHES.min(state.objects.len())];
+        let record_start = now_ms();
+        let scene = record_static_scene(
+            state.gpu.device(),
+            state.surface_config.format,
+            &state.render_pipeline,
+            state.camera_uniform.bind_group(),
+            &[],
+            objects,
+        );
+        let record_ms = now_ms() - record_start;
+
+        let measure = |draw: &dyn Fn()| {
+            let start = now_ms();
+            for _ in 0..frames {
+                draw();
+            }
+            now_ms() - start
+        };
+        let recorded_ms = measure(&|| state.draw_recorded(&view, objects));
+        let bundle_ms = measure(&|| state.draw_bundle(&view, &scene));
+
+        let report = format!(
+            "{} frames x {} meshes: re-recording {:.2} ms, render bundle {:.2} ms (bundle recorded once in {:.2} ms)",
+            frames,
+            objects.len(),
+            recorded_ms,
+            bundle_ms,
+            record_ms,
+        );
+        console::log_1(&report.clone().into());
+        report
+    })
+}
+
+/// 같은 큐브 `instances` 개를 `frames` 번 오프스크린 텍스처에 그리면서, CPU 에서 절두체 컬링하고
+/// 보이는 인스턴스를 올리는 경우와 `GpuDrivenRenderer` 가 컴퓨트 셰이더로 컬링하고 간접 그리기 하는
+/// 경우의 CPU 시간을 비교한다. WebGPU 에서만 동작한다.
+#[wasm_bindgen]
+pub fn benchmark_gpu_culling(instances: u32, frames: u32) -> Result<String, JsValue> {
+    with_state(|state| {
+        if !state.supports_compute {
+            return Err(JsValue::from_str(
+                "Compute shaders are not supported on this adapter",
+            ));
+        }
+
+        let (vertices, indices) = cube_geometry(Vec3::ZERO);
+        let mesh = Mesh::new(&state.gpu, &vertices, &indices);
+        let instances = instance_grid(instances);
+        let gpu_renderer = GpuDrivenRenderer::new(state.gpu.device(), &mesh, &instances);
+        // CPU 컬링 결과를 매 프레임 앞에서부터 덮어쓴다
+        let cpu_instance_buffer = state.gpu.device().create_buffer(&wgpu::BufferDescriptor {
+            label: Some("CPU Culled Instance Buffer"),
+            size: (instances.len().max(1) * std::mem::size_of::<InstanceRaw>())
+                as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let target = state.benchmark_target();
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let frustum = Frustum::from_view_projection(state.camera.view_projection());
+
+        let measure = |draw: &dyn Fn()| {
+            let start = now_ms();
+            for _ in 0..frames {
+                draw();
+            }
+            now_ms() - start
+        };
+        let cpu_ms = measure(&|| {
+            let visible: Vec<InstanceRaw> = instances
+                .iter()
+                .filter(|(_, aabb)| frustum.test_aabb(aabb))
+                .map(|(transform, _)| InstanceRaw::from(transform))
+                .collect();
+            state
+                .gpu
+                .queue()
+                .write_buffer(&cpu_instance_buffer, 0, bytemuck::cast_slice(&visible));
+            state.draw_instanced(
+                &view,
+                |_| {},
+                |pass| {
+                    pass.set_vertex_buffer(1, cpu_instance_buffer.slice(..));
+                    mesh.draw_instanced(pass, 0..visible.len() as u32);
+                },
+            );
+        });
+        let gpu_ms = measure(&|| {
+            state.draw_instanced(
+                &view,
+                |encoder| gpu_renderer.cull(encoder, state.gpu.queue(), &frustum),
+                |pass| gpu_renderer.draw(pass),
+            );
+        });
+
+        let report = format!(
+            "{} frames x {} instances: CPU culling {:.2} ms, GPU culling {:.2} ms",
+            frames,
+            instances.len(),
+            cpu_ms,
+            gpu_ms,
+        );
+        console::log_1(&report.clone().into());
+        Ok(report)
+    })?
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    STATE.with_borrow_mut(|current| *current = Some(Rc::clone(&state)));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,35 @@
+@group(0) @binding(0)
+var albedo_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var normal_texture: texture_2d<f32>;
+// SsaoPass 의 결과. 1 이면 가려지지 않았다
+@group(0) @binding(2)
+var ao_texture: texture_2d<f32>;
+
+const LIGHT_DIRECTION: vec3<f32> = vec3<f32>(0.4, 0.8, 0.6);
+const AMBIENT: f32 = 0.15;
+
+// 버텍스 버퍼 없이 화면을 덮는 삼각형 하나를 만든다
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(position.xy);
+    let albedo = textureLoad(albedo_texture, pixel, 0);
+    let encoded_normal = textureLoad(normal_texture, pixel, 0);
+
+    // 지오메트리가 없는 픽셀은 알파가 0 이므로 배경색을 그대로 쓴다
+    if encoded_normal.a == 0.0 {
+        return albedo;
+    }
+
+    let normal = normalize(encoded_normal.xyz * 2.0 - 1.0);
+    let diffuse = max(dot(normal, normalize(LIGHT_DIRECTION)), 0.0);
+    // 앰비언트는 주변에서 오는 빛이므로 가려진 만큼 어둡게 한다
+    let ao = textureLoad(ao_texture, pixel, 0).r;
+    return vec4<f32>(albedo.rgb * (AMBIENT * ao + diffuse), 1.0);
+}
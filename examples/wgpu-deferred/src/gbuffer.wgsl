@@ -0,0 +1,46 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+    @location(3) roughness: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) color: vec3<f32>,
+    @location(2) roughness: f32,
+};
+
+// 두 컬러 타깃에 동시에 쓴다
+struct GBufferOutput {
+    @location(0) albedo: vec4<f32>,
+    @location(1) normal: vec4<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.color = in.color;
+    out.roughness = in.roughness;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> GBufferOutput {
+    var out: GBufferOutput;
+    out.albedo = vec4<f32>(in.color, 1.0);
+    // Rgba8Unorm 에 담을 수 있도록 [-1, 1] 을 [0, 1] 로 옮긴다.
+    // 알파에는 SsrPass 가 읽는 매끄러움을 담는다. 배경(알파 0)과 구분되도록 0 보다 크게 둔다
+    out.normal = vec4<f32>(normalize(in.normal) * 0.5 + 0.5, max(1.0 - in.roughness, 1.0 / 255.0));
+    return out;
+}
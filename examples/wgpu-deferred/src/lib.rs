@@ -0,0 +1,554 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::Vec3;
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BindGroupBuilder, Camera, CameraUniform, DepthTexture, FormatSelector, GpuContext,
+    HdrRenderTarget, InitError, Mesh, RenderPassBuilder, RenderTarget, SsaoPass, SsrPass,
+    ToneMapOperator, ToneMapPass, UniformBuffer, Vertex,
+};
+
+// 법선을 [0, 1] 로 옮겨 담으므로 두 타깃 모두 어디서나 렌더 타깃으로 쓸 수 있는 포맷을 쓴다
+const ALBEDO_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const NORMAL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const SSAO_KERNEL_SIZE: usize = 32;
+const SSAO_RADIUS: f32 = 0.5;
+const SSR_MAX_STEPS: u32 = 64;
+const SSR_STEP_SIZE: f32 = 0.1;
+// 바닥만 SsrPass 의 기본 문턱값(0.5)보다 매끄러워서 큐브를 비춘다
+const CUBE_ROUGHNESS: f32 = 0.8;
+const FLOOR_ROUGHNESS: f32 = 0.1;
+const BACKGROUND: wgpu::Color = wgpu::Color {
+    r: 0.1,
+    g: 0.2,
+    b: 0.3,
+    a: 1.0,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct NormalVertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+    color: [f32; 3],
+    roughness: f32,
+}
+
+impl Vertex for NormalVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &wgpu::vertex_attr_array![
+        0 => Float32x3, 1 => Float32x3, 2 => Float32x3, 3 => Float32
+    ];
+}
+
+// (법선, u축, v축, 색) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+type Face = ([f32; 3], [f32; 3], [f32; 3], [f32; 3]);
+
+const FACES: [Face; 6] = [
+    (
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ),
+    (
+        [-1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 1.0, 1.0],
+    ),
+    (
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+    ),
+    (
+        [0.0, -1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, -1.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+    ),
+];
+
+// 면마다 색이 다르도록 꼭짓점을 공유하지 않고 면당 4개씩 만든다
+fn cube_geometry() -> (Vec<NormalVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (normal, u, v, color) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = (n + u * su + v * sv) * 0.5;
+            vertices.push(NormalVertex {
+                position: position.into(),
+                normal,
+                color,
+                roughness: CUBE_ROUGHNESS,
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+// 큐브가 놓인 바닥. 큐브와 닿는 곳에 앰비언트 오클루전이 생기고, 큐브가 비친다
+fn floor_geometry() -> (Vec<NormalVertex>, Vec<u16>) {
+    let vertices = [[-3.0, -3.0], [3.0, -3.0], [3.0, 3.0], [-3.0, 3.0]]
+        .map(|[x, z]| NormalVertex {
+            position: [x, -0.5, z],
+            normal: [0.0, 1.0, 0.0],
+            color: [0.8, 0.8, 0.8],
+            roughness: FLOOR_ROUGHNESS,
+        })
+        .to_vec();
+    (vertices, vec![0, 2, 1, 0, 3, 2])
+}
+
+/// 알베도(타깃 0)와 법선(타깃 1, 알파는 1 - 거칠기)을 담는 G-버퍼와 SSAO 결과.
+/// 라이팅 패스에서 읽을 수 있도록 바인드 그룹도 함께 만든다.
+struct GBuffer {
+    albedo_view: wgpu::TextureView,
+    normal_view: wgpu::TextureView,
+    ao_view: wgpu::TextureView,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+}
+
+impl GBuffer {
+    fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let albedo_view =
+            create_target_view(device, "G-Buffer Albedo", ALBEDO_FORMAT, width, height);
+        let normal_view =
+            create_target_view(device, "G-Buffer Normal", NORMAL_FORMAT, width, height);
+        let ao_view = create_target_view(
+            device,
+            "Ambient Occlusion",
+            SsaoPass::OUTPUT_FORMAT,
+            width,
+            height,
+        );
+
+        let (bind_group_layout, bind_group) = BindGroupBuilder::new()
+            .texture(0, &albedo_view, wgpu::ShaderStages::FRAGMENT)
+            .texture(1, &normal_view, wgpu::ShaderStages::FRAGMENT)
+            .texture(2, &ao_view, wgpu::ShaderStages::FRAGMENT)
+            .build(device, "G-Buffer Bind Group");
+
+        Self {
+            albedo_view,
+            normal_view,
+            ao_view,
+            bind_group_layout,
+            bind_group,
+        }
+    }
+
+    // 배경은 알베도에 배경색, 법선에 알파 0 으로 남는다
+    fn targets(&self) -> [RenderTarget; 2] {
+        [
+            RenderTarget::new(&self.albedo_view, ALBEDO_FORMAT).clear_color(BACKGROUND),
+            RenderTarget::new(&self.normal_view, NORMAL_FORMAT)
+                .clear_color(wgpu::Color::TRANSPARENT),
+        ]
+    }
+}
+
+fn create_target_view(
+    device: &wgpu::Device,
+    label: &str,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    geometry_pipeline: wgpu::RenderPipeline,
+    lighting_pipeline: wgpu::RenderPipeline,
+    gbuffer: GBuffer,
+    ssao: SsaoPass,
+    ssr: SsrPass,
+    // 라이팅 결과와 반사를 섞은 결과. 톤 매핑으로 서피스에 옮긴다
+    lit_target: HdrRenderTarget,
+    reflected_target: HdrRenderTarget,
+    tone_map: ToneMapPass,
+    depth_texture: DepthTexture,
+    meshes: [Mesh; 2],
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 메시 생성
+        let meshes = [cube_geometry(), floor_geometry()]
+            .map(|(vertices, indices)| Mesh::new(&gpu, &vertices, &indices));
+
+        // 카메라. SsrPass 가 프래그먼트 셰이더에서도 읽는다
+        let camera = Camera::new(
+            Vec3::new(0.0, 1.5, 3.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+        );
+
+        let gbuffer = GBuffer::new(device, size.0, size.1);
+        let ssao = SsaoPass::new(device, size.0, size.1, SSAO_KERNEL_SIZE, SSAO_RADIUS);
+        let ssr = SsrPass::new(device, size.0, size.1, SSR_MAX_STEPS, SSR_STEP_SIZE);
+        let lit_target = HdrRenderTarget::new(device, size.0, size.1);
+        let reflected_target = HdrRenderTarget::new(device, size.0, size.1);
+        // 조명 결과가 1 을 넘지 않으므로 예전처럼 그대로 잘라서 옮긴다
+        let tone_map = ToneMapPass::new(&gpu, surface_config.format, ToneMapOperator::Clamp);
+
+        // 지오메트리 패스: 알베도와 법선을 G-버퍼에 쓴다
+        let gbuffer_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("G-Buffer Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("gbuffer.wgsl").into()),
+        });
+
+        let geometry_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Geometry Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let geometry_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Geometry Pipeline"),
+            layout: Some(&geometry_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &gbuffer_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[NormalVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &gbuffer_shader,
+                entry_point: Some("fs_main"),
+                targets: &RenderTarget::color_target_states(&gbuffer.targets()),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // 라이팅 패스: 화면을 덮는 삼각형으로 G-버퍼를 읽어서 조명을 계산한다.
+        // 반사에서 다시 읽도록 서피스 대신 HDR 텍스처에 그린다
+        let deferred_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Deferred Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("deferred.wgsl").into()),
+        });
+
+        let lighting_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Lighting Pipeline Layout"),
+                bind_group_layouts: &[&gbuffer.bind_group_layout],
+                push_constant_ranges: &[],
+            });
+
+        let lighting_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Lighting Pipeline"),
+            layout: Some(&lighting_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &deferred_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &deferred_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: HdrRenderTarget::FORMAT,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            geometry_pipeline,
+            lighting_pipeline,
+            gbuffer,
+            ssao,
+            ssr,
+            lit_target,
+            reflected_target,
+            tone_map,
+            depth_texture,
+            meshes,
+            camera,
+            camera_uniform,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        // 큐브 주위를 돌면서 바라본다
+        let radius = 3.0;
+        self.camera.eye = Vec3::new(radius * time_secs.cos(), 1.5, radius * time_secs.sin());
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+        self.ssao
+            .set_camera(self.camera.view_matrix(), self.camera.projection_matrix());
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut geometry = RenderPassBuilder::with_targets(&self.gpu, &self.gbuffer.targets())
+            .depth(self.depth_texture.view());
+        geometry.set_pipeline(&self.geometry_pipeline);
+        for mesh in &self.meshes {
+            geometry.draw_mesh(mesh, self.camera_uniform.bind_group());
+        }
+        geometry.submit();
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Lighting Encoder"),
+                });
+        self.ssao.apply(
+            &mut encoder,
+            self.depth_texture.view(),
+            &self.gbuffer.normal_view,
+            &self.gbuffer.ao_view,
+        );
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Lighting Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: self.lit_target.view(),
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(BACKGROUND),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.lighting_pipeline);
+            render_pass.set_bind_group(0, &self.gbuffer.bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+        self.ssr.apply(
+            &mut encoder,
+            self.depth_texture.view(),
+            &self.gbuffer.normal_view,
+            self.lit_target.view(),
+            self.reflected_target.view(),
+            self.camera_uniform.bind_group(),
+        );
+        self.tone_map
+            .apply(&mut encoder, self.reflected_target.view(), &view);
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        // 같은 항목으로 만든 레이아웃이므로 라이팅 파이프라인을 다시 만들 필요는 없다
+        self.gbuffer = GBuffer::new(self.gpu.device(), new_size.0, new_size.1);
+        self.ssao.resize(new_size.0, new_size.1);
+        self.ssr.resize(new_size.0, new_size.1);
+        self.lit_target
+            .resize(self.gpu.device(), new_size.0, new_size.1);
+        self.reflected_target
+            .resize(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,475 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BindGroupBuilder, CameraUniform, DepthTexture, FormatSelector, FpsCamera, GpuContext,
+    InitError, InputListeners, InputState, Mesh, PointerLock, PositionNormalUvVertex,
+    SamplerBuilder, Texture, UniformBuffer, Vertex,
+};
+
+// shader.wgsl 의 FOG_COLOR 와 같게 두어서 먼 곳이 배경으로 자연스럽게 사라진다
+const FOG_COLOR: wgpu::Color = wgpu::Color {
+    r: 0.55,
+    g: 0.65,
+    b: 0.75,
+    a: 1.0,
+};
+// 상자를 흩어 놓는 격자의 반지름(칸)과 한 칸의 크기
+const GRID_RADIUS: i32 = 7;
+const CELL_SIZE: f32 = 3.0;
+const FLOOR_HALF_SIZE: f32 = 40.0;
+// 텍스처 한 장이 덮는 월드 크기
+const TEXTURE_TILE: f32 = 1.0;
+const EYE_HEIGHT: f32 = 1.7;
+
+// (법선, u축, v축) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+const FACES: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+];
+
+// 정수 좌표를 [0, 1] 로 섞는다
+fn hash(x: u32, y: u32) -> f32 {
+    let mut h = x.wrapping_mul(374_761_393) ^ y.wrapping_mul(668_265_263);
+    h = (h ^ (h >> 13)).wrapping_mul(1_274_126_177);
+    (h ^ (h >> 16)) as f32 / u32::MAX as f32
+}
+
+// 나무 상자: 테두리 판자와 대각선 버팀목
+fn crate_image() -> image::RgbaImage {
+    image::RgbaImage::from_fn(64, 64, |x, y| {
+        let border = x < 6 || y < 6 || x >= 58 || y >= 58;
+        let brace = (x as i32 - y as i32).abs() < 5;
+        let base: [f32; 3] = if border || brace {
+            [0.55, 0.36, 0.18]
+        } else if y % 12 == 0 {
+            [0.3, 0.2, 0.1]
+        } else {
+            [0.72, 0.52, 0.3]
+        };
+        let shade = 0.9 + hash(x, y) * 0.2;
+        let [r, g, b] = base.map(|c| ((c * shade).min(1.0) * 255.0) as u8);
+        image::Rgba([r, g, b, 255])
+    })
+}
+
+// 바닥 타일: 줄눈으로 나뉜 두 가지 색의 바둑판
+fn floor_image() -> image::RgbaImage {
+    image::RgbaImage::from_fn(64, 64, |x, y| {
+        let base: [f32; 3] = if x % 32 < 2 || y % 32 < 2 {
+            [0.3, 0.3, 0.32]
+        } else if (x / 32 + y / 32) % 2 == 0 {
+            [0.62, 0.62, 0.6]
+        } else {
+            [0.5, 0.5, 0.48]
+        };
+        let shade = 0.95 + hash(x, y) * 0.1;
+        let [r, g, b] = base.map(|c| ((c * shade).min(1.0) * 255.0) as u8);
+        image::Rgba([r, g, b, 255])
+    })
+}
+
+// 가운데가 center, 크기가 size 인 상자를 덧붙인다. UV 는 월드 크기에 비례해서 텍스처가 반복된다
+fn push_box(
+    vertices: &mut Vec<PositionNormalUvVertex>,
+    indices: &mut Vec<u16>,
+    center: Vec3,
+    size: Vec3,
+) {
+    for (normal, u, v) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+        let (width, height) = (
+            (u * size).abs().max_element(),
+            (v * size).abs().max_element(),
+        );
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = center + (n + u * su + v * sv) * size * 0.5;
+            vertices.push(PositionNormalUvVertex {
+                position: position.into(),
+                normal,
+                uv: [
+                    (su + 1.0) * 0.5 * width / TEXTURE_TILE,
+                    (1.0 - sv) * 0.5 * height / TEXTURE_TILE,
+                ],
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+}
+
+// 격자 칸마다 확률적으로 크기가 다른 상자를 놓는다. 시작 위치 주변은 비워 둔다
+fn boxes_geometry() -> (Vec<PositionNormalUvVertex>, Vec<u16>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for gz in -GRID_RADIUS..=GRID_RADIUS {
+        for gx in -GRID_RADIUS..=GRID_RADIUS {
+            if gx.abs() <= 1 && gz.abs() <= 1 {
+                continue;
+            }
+            let (hx, hz) = (gx as u32, gz as u32);
+            if hash(hx, hz) < 0.55 {
+                continue;
+            }
+
+            // 한 변 1 ~ 2 인 상자를 가끔 두 층으로 쌓는다
+            let side = 1.0 + hash(hz, hx);
+            let jitter = (hash(hx + 17, hz) - 0.5) * (CELL_SIZE - side);
+            let center = Vec3::new(gx as f32 * CELL_SIZE + jitter, 0.0, gz as f32 * CELL_SIZE);
+            let size = Vec3::splat(side);
+            push_box(
+                &mut vertices,
+                &mut indices,
+                center + Vec3::Y * side * 0.5,
+                size,
+            );
+            if hash(hx, hz + 31) > 0.7 {
+                let top = side * 0.6;
+                push_box(
+                    &mut vertices,
+                    &mut indices,
+                    center + Vec3::Y * (side + top * 0.5),
+                    Vec3::splat(top),
+                );
+            }
+        }
+    }
+
+    (vertices, indices)
+}
+
+fn floor_geometry() -> (Vec<PositionNormalUvVertex>, Vec<u16>) {
+    let vertices = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]]
+        .map(|[x, z]| {
+            let (x, z) = (x * FLOOR_HALF_SIZE, z * FLOOR_HALF_SIZE);
+            PositionNormalUvVertex {
+                position: [x, 0.0, z],
+                normal: [0.0, 1.0, 0.0],
+                uv: [x / TEXTURE_TILE, z / TEXTURE_TILE],
+            }
+        })
+        .to_vec();
+    (vertices, vec![0, 2, 1, 0, 3, 2])
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    // (메시, 텍스처 바인드 그룹)
+    meshes: [(Mesh, wgpu::BindGroup); 2],
+    camera: FpsCamera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    input: Rc<RefCell<InputState>>,
+    _input_listeners: InputListeners,
+    _pointer_lock: PointerLock,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // WASD 로 걷고, 캔버스를 클릭해서 커서를 가두면 마우스로 둘러본다
+        let input = Rc::new(RefCell::new(InputState::default()));
+        let input_listeners = InputListeners::register(&canvas, Rc::clone(&input))
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+        let pointer_lock = PointerLock::new(&canvas)
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+
+        let camera = FpsCamera::new(Vec3::new(0.0, EYE_HEIGHT, 0.0), 0.0, 0.0);
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::new(projection(size) * camera.view_matrix()),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 텍스처가 면 크기만큼 반복되도록 UV 가 1 을 넘으면 되풀이하는 샘플러를 쓴다
+        let sampler = SamplerBuilder::linear()
+            .address_mode(
+                wgpu::AddressMode::Repeat,
+                wgpu::AddressMode::Repeat,
+                wgpu::AddressMode::Repeat,
+            )
+            .label("Repeat Sampler")
+            .build(device);
+        let crate_texture = Texture::from_image(&gpu, &crate_image().into(), "Crate");
+        let floor_texture = Texture::from_image(&gpu, &floor_image().into(), "Floor");
+
+        let mut texture_bind_group_layout = None;
+        let meshes = [
+            (boxes_geometry(), &crate_texture, "Crate Bind Group"),
+            (floor_geometry(), &floor_texture, "Floor Bind Group"),
+        ]
+        .map(|((vertices, indices), texture, label)| {
+            let (layout, bind_group) = BindGroupBuilder::new()
+                .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+                .sampler(1, &sampler, wgpu::ShaderStages::FRAGMENT)
+                .build(device, label);
+            texture_bind_group_layout = Some(layout);
+            (Mesh::new(&gpu, &vertices, &indices), bind_group)
+        });
+        // 두 바인드 그룹의 레이아웃은 항목이 같으므로 하나만 파이프라인에 쓴다
+        let texture_bind_group_layout = texture_bind_group_layout.unwrap();
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[
+                    camera_uniform.bind_group_layout(),
+                    &texture_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[PositionNormalUvVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            meshes,
+            camera,
+            camera_uniform,
+            input,
+            _input_listeners: input_listeners,
+            _pointer_lock: pointer_lock,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, dt: f32) {
+        let mut input = self.input.borrow_mut();
+        let view = self.camera.update(dt, &input);
+        input.end_frame();
+
+        self.camera_uniform.update(
+            self.gpu.queue(),
+            &CameraUniform::new(projection(self.size) * view),
+        );
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(FOG_COLOR),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            for (mesh, bind_group) in &self.meshes {
+                render_pass.set_bind_group(1, bind_group, &[]);
+                mesh.draw(&mut render_pass);
+            }
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+    }
+}
+
+fn projection(size: (u32, u32)) -> Mat4 {
+    Mat4::perspective_rh(
+        60f32.to_radians(),
+        size.0 as f32 / size.1 as f32,
+        0.1,
+        100.0,
+    )
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let mut last = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                // 탭이 오래 숨겨졌다 돌아와도 한 번에 멀리 가지 않도록 자른다
+                let now = now_secs();
+                state.update((now - last).min(0.1));
+                last = now;
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
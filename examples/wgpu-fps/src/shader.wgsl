@@ -0,0 +1,50 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var surface_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var surface_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    // 카메라까지의 거리. 멀리 있는 것을 안개색으로 흐린다
+    @location(2) view_depth: f32,
+};
+
+const FOG_COLOR: vec3<f32> = vec3<f32>(0.55, 0.65, 0.75);
+const FOG_START: f32 = 10.0;
+const FOG_END: f32 = 30.0;
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.uv = in.uv;
+    out.view_depth = out.clip_position.w;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let light_dir = normalize(vec3<f32>(0.4, 0.8, 0.6));
+    let diffuse = max(dot(normalize(in.normal), light_dir), 0.0);
+    let color = textureSample(surface_texture, surface_sampler, in.uv).rgb * (0.35 + 0.65 * diffuse);
+    let fog = smoothstep(FOG_START, FOG_END, in.view_depth);
+    return vec4<f32>(mix(color, FOG_COLOR, fog), 1.0);
+}
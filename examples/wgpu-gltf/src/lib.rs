@@ -0,0 +1,491 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::Vec3;
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu::util::DeviceExt;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BindGroupBuilder, Camera, CameraUniform, DepthTexture, FormatSelector, GltfLoader, GltfVertex,
+    GpuContext, InitError, Mesh, Texture, UniformBuffer, Vertex,
+};
+
+thread_local! {
+    // JS 에서 호출하는 함수들이 사용하는 State
+    static STATE: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+    // upload_mesh 로 JS 에서 올린 메시. wasm 의 wgpu 객체는 Send 가 아니라서 Mutex 대신 스레드 로컬에 둔다
+    static MESHES: RefCell<HashMap<u32, Mesh>> = RefCell::new(HashMap::new());
+    static NEXT_MESH_HANDLE: Cell<u32> = const { Cell::new(1) };
+}
+
+/// Khronos glTF 샘플 저장소의 Box 모델
+const BOX_URL: &str = "https://raw.githubusercontent.com/KhronosGroup/glTF-Sample-Assets/main/Models/Box/glTF-Binary/Box.glb";
+
+// shader.wgsl 의 MaterialUniform 과 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct MaterialUniform {
+    base_color: [f32; 4],
+}
+
+// 텍스처, 샘플러, 기본 색을 1번 그룹으로 묶는다
+fn material_bind_group(
+    device: &wgpu::Device,
+    texture: &Texture,
+    base_color: [f32; 4],
+) -> (wgpu::BindGroupLayout, wgpu::BindGroup) {
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Material Buffer"),
+        contents: bytemuck::bytes_of(&MaterialUniform { base_color }),
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
+
+    BindGroupBuilder::new()
+        .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+        .sampler(1, texture.sampler(), wgpu::ShaderStages::FRAGMENT)
+        .uniform_buffer(2, &buffer, wgpu::ShaderStages::FRAGMENT)
+        .build(device, "Material Bind Group")
+}
+
+// JS 에서 받은 배열을 버텍스로 묶는다. 버텍스 수는 위치 배열로 정하고 나머지 배열 길이를 맞춰 본다
+fn custom_vertices(
+    positions: &[f32],
+    normals: &[f32],
+    uvs: &[f32],
+    indices: &[u32],
+) -> Result<Vec<GltfVertex>, String> {
+    if !positions.len().is_multiple_of(3) {
+        return Err(format!(
+            "positions length {} is not a multiple of 3",
+            positions.len()
+        ));
+    }
+    let vertex_count = positions.len() / 3;
+    if normals.len() != vertex_count * 3 {
+        return Err(format!(
+            "normals length {} does not match {} vertices",
+            normals.len(),
+            vertex_count
+        ));
+    }
+    if uvs.len() != vertex_count * 2 {
+        return Err(format!(
+            "uvs length {} does not match {} vertices",
+            uvs.len(),
+            vertex_count
+        ));
+    }
+    if indices.is_empty() || !indices.len().is_multiple_of(3) {
+        return Err(format!(
+            "indices length {} is not a positive multiple of 3",
+            indices.len()
+        ));
+    }
+    if let Some(index) = indices
+        .iter()
+        .find(|&&index| index as usize >= vertex_count)
+    {
+        return Err(format!(
+            "index {} is out of range for {} vertices",
+            index, vertex_count
+        ));
+    }
+
+    Ok(positions
+        .chunks_exact(3)
+        .zip(normals.chunks_exact(3))
+        .zip(uvs.chunks_exact(2))
+        .map(|((position, normal), uv)| GltfVertex {
+            position: [position[0], position[1], position[2]],
+            normal: [normal[0], normal[1], normal[2]],
+            tex_coords: [uv[0], uv[1]],
+            tangent: [1.0, 0.0, 0.0, 1.0],
+        })
+        .collect())
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    // 프리미티브마다 메시와 재질 바인드 그룹
+    meshes: Vec<(Mesh, wgpu::BindGroup)>,
+    // JS 에서 올린 메시 중 그릴 것의 핸들. 흰색 재질로 그린다
+    custom_meshes: Vec<u32>,
+    white_material: wgpu::BindGroup,
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str, model_url: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 모델 로드
+        let gltf_meshes = GltfLoader::load_url(model_url, &gpu).await?;
+        console::log_1(
+            &format!("Loaded {} primitives from {}", gltf_meshes.len(), model_url).into(),
+        );
+
+        // 텍스처가 없는 재질은 흰색 1x1 텍스처에 기본 색만 곱한다
+        let white = Texture::from_image(
+            &gpu,
+            &image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+                1,
+                1,
+                image::Rgba([255; 4]),
+            )),
+            "White Texture",
+        );
+        let (material_layout, white_material) = material_bind_group(device, &white, [1.0; 4]);
+        let meshes = gltf_meshes
+            .into_iter()
+            .map(|gltf_mesh| {
+                let texture = gltf_mesh.base_color_texture.as_ref().unwrap_or(&white);
+                let (_, bind_group) =
+                    material_bind_group(device, texture, gltf_mesh.base_color_factor);
+                (gltf_mesh.mesh, bind_group)
+            })
+            .collect();
+
+        // 카메라
+        let camera = Camera::new(
+            Vec3::new(0.0, 1.5, 3.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout(), &material_layout],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[GltfVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            meshes,
+            custom_meshes: Vec::new(),
+            white_material,
+            camera,
+            camera_uniform,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        // 모델 주위를 돌면서 바라본다
+        let radius = 3.0;
+        self.camera.eye = Vec3::new(radius * time_secs.cos(), 1.5, radius * time_secs.sin());
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            for (mesh, material) in &self.meshes {
+                render_pass.set_bind_group(1, material, &[]);
+                mesh.draw(&mut render_pass);
+            }
+
+            render_pass.set_bind_group(1, &self.white_material, &[]);
+            MESHES.with_borrow(|meshes| {
+                for handle in &self.custom_meshes {
+                    if let Some(mesh) = meshes.get(handle) {
+                        mesh.draw(&mut render_pass);
+                    }
+                }
+            });
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    run_with_model_url(canvas_id, BOX_URL).await
+}
+
+/// `model_url` 의 `.glb` 파일을 `fetch` 로 받아서 그린다.
+#[wasm_bindgen]
+pub async fn run_with_model_url(canvas_id: &str, model_url: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = State::new(canvas_id, model_url).await?;
+    let state = Rc::new(RefCell::new(state));
+    STATE.with_borrow_mut(|current| *current = Some(Rc::clone(&state)));
+    start_render_loop(state);
+    Ok(())
+}
+
+fn with_state<T>(f: impl FnOnce(&mut State) -> Result<T, JsValue>) -> Result<T, JsValue> {
+    STATE.with_borrow(
+        |state| match state.as_ref().map(|state| state.try_borrow_mut()) {
+            Some(Ok(mut state)) => f(&mut state),
+            Some(Err(_)) => Err(JsValue::from_str("State is borrowed elsewhere")),
+            None => Err(JsValue::from_str("run() has not finished yet")),
+        },
+    )
+}
+
+/// 물리 엔진 등에서 만든 지오메트리를 GPU 에 올리고 핸들을 반환한다.
+///
+/// `positions` 와 `normals` 는 버텍스마다 3개, `uvs` 는 2개씩 이어 붙인 배열이고
+/// `indices` 는 반시계 방향 삼각형 목록이다. 길이가 맞지 않으면 에러를 반환한다.
+/// 올리기만 하고 그리지는 않으므로 `draw_mesh` 를 호출해야 화면에 나타난다.
+#[wasm_bindgen]
+pub fn upload_mesh(
+    positions: &[f32],
+    normals: &[f32],
+    uvs: &[f32],
+    indices: &[u32],
+) -> Result<u32, JsValue> {
+    let mut vertices =
+        custom_vertices(positions, normals, uvs, indices).map_err(|e| JsValue::from_str(&e))?;
+
+    with_state(|state| {
+        let mesh = Mesh::new_with_tangents(&state.gpu, &mut vertices, indices);
+        let handle = NEXT_MESH_HANDLE.replace(NEXT_MESH_HANDLE.get() + 1);
+        MESHES.with_borrow_mut(|meshes| meshes.insert(handle, mesh));
+        Ok(handle)
+    })
+}
+
+/// `upload_mesh` 로 올린 메시를 `drop_mesh` 할 때까지 매 프레임 그린다.
+#[wasm_bindgen]
+pub fn draw_mesh(handle: u32) -> Result<(), JsValue> {
+    if !MESHES.with_borrow(|meshes| meshes.contains_key(&handle)) {
+        return Err(JsValue::from_str(&format!(
+            "Unknown mesh handle {}",
+            handle
+        )));
+    }
+
+    with_state(|state| {
+        if !state.custom_meshes.contains(&handle) {
+            state.custom_meshes.push(handle);
+        }
+        Ok(())
+    })
+}
+
+/// 메시를 그리기 목록에서 빼고 GPU 버퍼를 해제한다.
+#[wasm_bindgen]
+pub fn drop_mesh(handle: u32) -> Result<(), JsValue> {
+    if MESHES
+        .with_borrow_mut(|meshes| meshes.remove(&handle))
+        .is_none()
+    {
+        return Err(JsValue::from_str(&format!(
+            "Unknown mesh handle {}",
+            handle
+        )));
+    }
+
+    with_state(|state| {
+        state.custom_meshes.retain(|&drawn| drawn != handle);
+        Ok(())
+    })
+}
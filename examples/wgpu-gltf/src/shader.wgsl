@@ -0,0 +1,51 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+struct MaterialUniform {
+    base_color: vec4<f32>,
+};
+
+@group(1) @binding(0)
+var base_color_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var base_color_sampler: sampler;
+@group(1) @binding(2)
+var<uniform> material: MaterialUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) tex_coords: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+};
+
+// 모든 면에 같은 방향으로 들어오는 빛 (빛을 향하는 방향)
+const LIGHT_DIRECTION: vec3<f32> = vec3<f32>(0.4, 1.0, 0.6);
+const AMBIENT: f32 = 0.2;
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.tex_coords = in.tex_coords;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let base_color = textureSample(base_color_texture, base_color_sampler, in.tex_coords) * material.base_color;
+    let diffuse = max(dot(normalize(in.normal), normalize(LIGHT_DIRECTION)), 0.0);
+    return vec4<f32>(base_color.rgb * (AMBIENT + (1.0 - AMBIENT) * diffuse), base_color.a);
+}
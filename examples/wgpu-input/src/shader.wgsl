@@ -0,0 +1,24 @@
+struct OffsetUniform {
+    offset: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: OffsetUniform;
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) vec4<f32> {
+    var pos = array<vec2<f32>, 3>(
+        vec2<f32>( 0.0,  0.2),
+        vec2<f32>(-0.2, -0.2),
+        vec2<f32>( 0.2, -0.2)
+    );
+
+    return vec4<f32>(pos[in_vertex_index] + uniforms.offset, 0.0, 1.0);
+}
+
+// Fragment shader
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.9, 0.6, 0.2, 1.0);
+}
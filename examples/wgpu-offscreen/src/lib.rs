@@ -0,0 +1,466 @@
+use std::cell::RefCell;
+use std::f32::consts::TAU;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::{MouseEvent, OffscreenCanvas, console};
+use wgpu_common::offscreen::{post_to_main, start_worker_animation_loop};
+use wgpu_common::web::{
+    RenderScale, ResizeObserverHandle, get_canvas, get_canvas_size, observe_resize, physical_size,
+};
+use wgpu_common::{
+    Camera, CameraUniform, CanvasOffscreenRenderer, DepthTexture, FormatSelector, GpuContext,
+    InitError, Mesh, OffscreenCommand, OffscreenCommands, UniformBuffer, Vertex,
+};
+
+// 매듭을 따라가는 분할 수와 관 둘레의 분할 수. 삼각형이 약 50만 개다
+const KNOT_SEGMENTS: u32 = 4096;
+const TUBE_SEGMENTS: u32 = 64;
+// (p, q) 토러스 매듭. 가운데 구멍을 p 번, 관을 q 번 감는다
+const KNOT_P: f32 = 2.0;
+const KNOT_Q: f32 = 3.0;
+const TUBE_RADIUS: f32 = 0.3;
+// 초당 회전 각도와 드래그 1 픽셀당 회전 각도
+const SPIN_SPEED: f32 = 0.6;
+const DRAG_SPEED: f32 = 0.01;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct KnotVertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+}
+
+impl Vertex for KnotVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// 매듭 곡선 위의 점. 반지름은 약 1.5 다
+fn knot_curve(u: f32) -> Vec3 {
+    let qu = KNOT_Q / KNOT_P * u;
+    let r = (2.0 + qu.cos()) * 0.5;
+    Vec3::new(r * u.cos(), r * u.sin(), qu.sin() * 0.5)
+}
+
+// 곡선을 따라 관을 씌운다. 이음매를 위해 양 끝 줄의 버텍스를 한 번씩 더 만든다
+fn torus_knot_geometry() -> (Vec<KnotVertex>, Vec<u32>) {
+    let mut vertices = Vec::with_capacity(((KNOT_SEGMENTS + 1) * (TUBE_SEGMENTS + 1)) as usize);
+    for i in 0..=KNOT_SEGMENTS {
+        let u = i as f32 / KNOT_SEGMENTS as f32 * KNOT_P * TAU;
+        let (p1, p2) = (knot_curve(u), knot_curve(u + 0.01));
+        // 곡선의 접선에 수직인 두 축
+        let tangent = p2 - p1;
+        let binormal = tangent.cross(p2 + p1).normalize();
+        let normal = binormal.cross(tangent).normalize();
+
+        for j in 0..=TUBE_SEGMENTS {
+            let v = j as f32 / TUBE_SEGMENTS as f32 * TAU;
+            let offset = (-v.cos() * normal + v.sin() * binormal) * TUBE_RADIUS;
+            vertices.push(KnotVertex {
+                position: (p1 + offset).into(),
+                normal: offset.normalize().into(),
+            });
+        }
+    }
+
+    let mut indices = Vec::with_capacity((KNOT_SEGMENTS * TUBE_SEGMENTS * 6) as usize);
+    for i in 1..=KNOT_SEGMENTS {
+        for j in 1..=TUBE_SEGMENTS {
+            let a = (TUBE_SEGMENTS + 1) * (i - 1) + (j - 1);
+            let b = (TUBE_SEGMENTS + 1) * i + (j - 1);
+            let c = (TUBE_SEGMENTS + 1) * i + j;
+            let d = (TUBE_SEGMENTS + 1) * (i - 1) + j;
+            indices.extend_from_slice(&[a, b, d, b, c, d]);
+        }
+    }
+
+    (vertices, indices)
+}
+
+// 워커에서 도는 렌더러
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    canvas: OffscreenCanvas,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    model_uniform: UniformBuffer<[[f32; 4]; 4]>,
+    size: (u32, u32),
+    // 드래그로 더한 회전과 드래그 중인 포인터의 x 좌표
+    drag_angle: f32,
+    drag_from: Option<f32>,
+}
+
+impl State {
+    async fn new(canvas: OffscreenCanvas) -> Result<Self, InitError> {
+        let size = (canvas.width().max(1), canvas.height().max(1));
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface =
+            instance.create_surface(wgpu::SurfaceTarget::OffscreenCanvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("worker device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        let (vertices, indices) = torus_knot_geometry();
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+
+        let camera = Camera::new(
+            Vec3::new(0.0, 0.0, 5.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+        let model_uniform = UniformBuffer::new(
+            &gpu,
+            &Mat4::IDENTITY.to_cols_array_2d(),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[
+                    camera_uniform.bind_group_layout(),
+                    model_uniform.bind_group_layout(),
+                ],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[KnotVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            canvas,
+            render_pipeline,
+            depth_texture,
+            mesh,
+            camera,
+            camera_uniform,
+            model_uniform,
+            size,
+            drag_angle: 0.0,
+            drag_from: None,
+        })
+    }
+
+    fn handle_command(&mut self, command: OffscreenCommand) {
+        match command {
+            OffscreenCommand::Resize { width, height } => self.resize((width, height)),
+            // 왼쪽 버튼을 누른 채 움직이면 y 축으로 돌린다
+            OffscreenCommand::Pointer { x, buttons, .. } => {
+                if buttons & 1 == 0 {
+                    self.drag_from = None;
+                    return;
+                }
+                if let Some(from) = self.drag_from {
+                    self.drag_angle += (x - from) * DRAG_SPEED;
+                }
+                self.drag_from = Some(x);
+            }
+        }
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        let model = Mat4::from_rotation_y(self.drag_angle)
+            * Mat4::from_rotation_x(time_secs * SPIN_SPEED * 0.7)
+            * Mat4::from_rotation_z(time_secs * SPIN_SPEED);
+        self.model_uniform
+            .update(self.gpu.queue(), &model.to_cols_array_2d());
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.1,
+                            b: 0.12,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            render_pass.set_bind_group(1, self.model_uniform.bind_group(), &[]);
+            self.mesh.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        // 메인 스레드의 캔버스 요소는 더 이상 크기를 바꿀 수 없으므로 워커에서 바꾼다
+        self.canvas.set_width(new_size.0);
+        self.canvas.set_height(new_size.1);
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+// 워커에는 window 가 없으므로 Date 로 시간을 잰다
+fn now_secs() -> f64 {
+    js_sys::Date::now() / 1000.0
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>, commands: OffscreenCommands) {
+    let start = now_secs();
+    // 1초마다 메인 스레드로 워커의 FPS 를 보낸다
+    let mut fps_window = (start, 0u32);
+
+    start_worker_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                while let Some(command) = commands.pop() {
+                    state.handle_command(command);
+                }
+
+                let now = now_secs();
+                state.update((now - start) as f32);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+
+                fps_window.1 += 1;
+                let elapsed = now - fps_window.0;
+                if elapsed >= 1.0 {
+                    let _ = post_to_main(&(fps_window.1 as f64 / elapsed).into());
+                    fps_window = (now, 0);
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+/// 워커 스크립트가 호출하는 진입점. `CanvasOffscreenRenderer::spawn` 이 만든 워커에서만 호출된다
+#[wasm_bindgen]
+pub async fn worker_main(canvas: OffscreenCanvas, commands: JsValue) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let commands = OffscreenCommands::new(commands)?;
+    let state = Rc::new(RefCell::new(State::new(canvas).await?));
+    start_render_loop(state, commands);
+    Ok(())
+}
+
+// 메인 스레드에서 워커와 이벤트 리스너를 살려 둔다
+struct MainThread {
+    _renderer: Rc<CanvasOffscreenRenderer>,
+    _resize_observer: ResizeObserverHandle,
+    _on_mouse: Closure<dyn FnMut(MouseEvent)>,
+}
+
+thread_local! {
+    static MAIN_THREAD: RefCell<Option<MainThread>> = const { RefCell::new(None) };
+}
+
+/// 메인 스레드에서 호출한다. `module_url` 은 이 모듈(`wgpu_offscreen.js`)의 절대 URL 이고,
+/// `on_worker_fps` 는 워커가 1초마다 보내는 FPS 로 호출된다.
+#[wasm_bindgen]
+pub fn run(
+    canvas_id: &str,
+    module_url: &str,
+    on_worker_fps: js_sys::Function,
+) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let canvas = get_canvas(canvas_id)?;
+    let mut renderer = CanvasOffscreenRenderer::spawn(&canvas, module_url)?;
+    if !renderer.is_shared() {
+        console::log_1(
+            &"SharedArrayBuffer is unavailable, sending commands with postMessage".into(),
+        );
+    }
+    renderer.set_on_message(move |fps| {
+        let _ = on_worker_fps.call1(&JsValue::NULL, &fps);
+    });
+    let renderer = Rc::new(renderer);
+
+    let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+    renderer.send(OffscreenCommand::Resize { width, height });
+
+    let resize_renderer = Rc::clone(&renderer);
+    let resize_observer = observe_resize(&canvas, move |logical_size| {
+        let (width, height) = physical_size(logical_size, RenderScale::Native);
+        resize_renderer.send(OffscreenCommand::Resize { width, height });
+    })?;
+
+    let mouse_renderer = Rc::clone(&renderer);
+    let on_mouse = Closure::wrap(Box::new(move |event: MouseEvent| {
+        mouse_renderer.send(OffscreenCommand::Pointer {
+            x: event.offset_x() as f32,
+            y: event.offset_y() as f32,
+            buttons: event.buttons() as u32,
+        });
+    }) as Box<dyn FnMut(MouseEvent)>);
+    for event in ["mousedown", "mousemove", "mouseup"] {
+        canvas.add_event_listener_with_callback(event, on_mouse.as_ref().unchecked_ref())?;
+    }
+
+    MAIN_THREAD.with_borrow_mut(|main_thread| {
+        *main_thread = Some(MainThread {
+            _renderer: renderer,
+            _resize_observer: resize_observer,
+            _on_mouse: on_mouse,
+        });
+    });
+    Ok(())
+}
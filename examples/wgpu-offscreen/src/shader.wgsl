@@ -0,0 +1,41 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var<uniform> model: mat4x4<f32>;
+
+const LIGHT_DIR: vec3<f32> = vec3<f32>(0.5, 0.8, 0.6);
+const AMBIENT: f32 = 0.2;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * model * vec4<f32>(in.position, 1.0);
+    // 회전만 하므로 모델 행렬로 법선을 바로 돌린다
+    out.normal = (model * vec4<f32>(in.normal, 0.0)).xyz;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let normal = normalize(in.normal);
+    let base = normal * 0.5 + 0.5;
+    let diffuse = max(dot(normal, normalize(LIGHT_DIR)), 0.0);
+    return vec4<f32>(base * (AMBIENT + diffuse * (1.0 - AMBIENT)), 1.0);
+}
@@ -0,0 +1,57 @@
+struct BillboardUniform {
+    view_proj: mat4x4<f32>,
+    // 월드 공간에서 카메라의 오른쪽, 위쪽 방향
+    right: vec4<f32>,
+    up: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: BillboardUniform;
+
+struct InstanceInput {
+    @location(0) position: vec3<f32>,
+    @location(1) size: f32,
+    @location(2) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // 사각형 중심이 (0, 0), 모서리가 (±1, ±1)
+    @location(0) corner: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+const CORNERS = array<vec2<f32>, 6>(
+    vec2<f32>(-1.0, -1.0),
+    vec2<f32>(1.0, -1.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(-1.0, 1.0),
+    vec2<f32>(-1.0, -1.0),
+);
+
+// Vertex shader
+// 파티클 위치를 중심으로 항상 카메라를 향하는 사각형을 만든다
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32, instance: InstanceInput) -> VertexOutput {
+    let corner = CORNERS[vertex_index];
+    let offset = (camera.right.xyz * corner.x + camera.up.xyz * corner.y) * instance.size;
+
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(instance.position + offset, 1.0);
+    out.corner = corner;
+    out.color = instance.color;
+    return out;
+}
+
+// Fragment shader
+// 가운데에서 멀어질수록 투명해지는 둥근 점
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = length(in.corner);
+    if (distance > 1.0) {
+        discard;
+    }
+    let falloff = 1.0 - distance * distance;
+    return vec4<f32>(in.color.rgb, in.color.a * falloff);
+}
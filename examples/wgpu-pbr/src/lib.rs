@@ -0,0 +1,351 @@
+use std::cell::RefCell;
+use std::f32::consts::PI;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Vec3, Vec4};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BrdfLutGenerator, Camera, DepthTexture, DirLight, FormatSelector, GpuContext, InitError,
+    InstanceBuffer, Mesh, PbrEnvironment, PbrMaterial, PbrMaterialBinding, PbrPipeline, PbrVertex,
+    Skybox, Transform,
+};
+
+// 위도, 경도 방향으로 나누는 수
+const SPHERE_STACKS: u32 = 32;
+const SPHERE_SLICES: u32 = 64;
+// 가로는 거칠기, 세로는 금속성을 바꾼 구의 개수
+const GRID_SIZE: u32 = 5;
+const SPHERE_SPACING: f32 = 1.1;
+const SKYBOX_FACE_SIZE: u32 = 256;
+// 환경광은 흐리게 만들어서 쓰므로 작아도 된다
+const ENVIRONMENT_FACE_SIZE: u32 = 64;
+// 컴퓨트 셰이더가 없을 때 CPU 에서 만드는 BRDF LUT 의 크기
+const CPU_BRDF_LUT_SIZE: u32 = 32;
+
+// 반지름 1 인 UV 구. 단위 구에서는 위치가 곧 법선이다. 탄젠트는 Mesh 를 만들 때 채운다
+fn sphere_geometry() -> (Vec<PbrVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(((SPHERE_STACKS + 1) * (SPHERE_SLICES + 1)) as usize);
+    let mut indices = Vec::with_capacity((SPHERE_STACKS * SPHERE_SLICES * 6) as usize);
+
+    for stack in 0..=SPHERE_STACKS {
+        let v = stack as f32 / SPHERE_STACKS as f32;
+        let phi = PI * v;
+        for slice in 0..=SPHERE_SLICES {
+            let u = slice as f32 / SPHERE_SLICES as f32;
+            let theta = 2.0 * PI * u;
+            let normal = Vec3::new(phi.sin() * theta.cos(), phi.cos(), -phi.sin() * theta.sin());
+            vertices.push(PbrVertex {
+                position: normal.into(),
+                normal: normal.into(),
+                uv: [u, v],
+                tangent: [0.0; 4],
+            });
+        }
+    }
+
+    // 바깥에서 봤을 때 CCW 가 되도록 위쪽 행부터 두 개의 삼각형으로 잇는다
+    let row = SPHERE_SLICES + 1;
+    for stack in 0..SPHERE_STACKS {
+        for slice in 0..SPHERE_SLICES {
+            let top = (stack * row + slice) as u16;
+            let bottom = top + row as u16;
+            indices.extend_from_slice(&[top, bottom, top + 1, top + 1, bottom, bottom + 1]);
+        }
+    }
+
+    (vertices, indices)
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    pbr: PbrPipeline,
+    // 인스턴스 순서와 같은 재질
+    materials: Vec<PbrMaterialBinding>,
+    skybox: Skybox,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    instances: InstanceBuffer,
+    camera: Camera,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+        let queue = gpu.queue();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 메시 생성
+        let (mut vertices, indices) = sphere_geometry();
+        let mesh = Mesh::new_with_tangents(&gpu, &mut vertices, &indices);
+
+        // 구를 격자로 늘어놓고 구마다 재질을 하나씩 만든다
+        let offset = (GRID_SIZE - 1) as f32 * SPHERE_SPACING * 0.5;
+        let mut transforms = Vec::with_capacity((GRID_SIZE * GRID_SIZE) as usize);
+        let mut material_params = Vec::with_capacity(transforms.capacity());
+        for row in 0..GRID_SIZE {
+            for column in 0..GRID_SIZE {
+                transforms.push(Transform {
+                    translation: Vec3::new(
+                        column as f32 * SPHERE_SPACING - offset,
+                        offset - row as f32 * SPHERE_SPACING,
+                        0.0,
+                    ),
+                    scale: Vec3::splat(0.45),
+                    ..Transform::IDENTITY
+                });
+                material_params.push(PbrMaterial {
+                    base_color: Vec4::new(0.9, 0.35, 0.2, 1.0),
+                    metallic: row as f32 / (GRID_SIZE - 1) as f32,
+                    roughness: column as f32 / (GRID_SIZE - 1) as f32,
+                    ..Default::default()
+                });
+            }
+        }
+        let instances = InstanceBuffer::new(device, &transforms);
+
+        // 카메라
+        let camera = Camera::new(
+            Vec3::new(0.0, 0.0, 7.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+
+        // 배경과 환경광에 같은 HDR 이미지를 쓴다
+        let sky_image = image::load_from_memory(include_bytes!("sky.hdr"))?;
+        let skybox =
+            Skybox::from_equirectangular(&gpu, surface_format, &sky_image, SKYBOX_FACE_SIZE);
+        let environment =
+            PbrEnvironment::from_equirectangular(&gpu, &sky_image, ENVIRONMENT_FACE_SIZE);
+
+        // BRDF LUT 는 컴퓨트 셰이더로 만들고, WebGL 에서는 CPU 에서 작게 만든다
+        let brdf_lut = if adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        {
+            BrdfLutGenerator::generate(device, queue)
+        } else {
+            BrdfLutGenerator::generate_cpu(device, queue, CPU_BRDF_LUT_SIZE)
+        };
+
+        // PBR 파이프라인 생성
+        let mut pbr = PbrPipeline::new(
+            &gpu,
+            surface_config.format,
+            DepthTexture::FORMAT,
+            &environment,
+            &brdf_lut,
+        );
+        pbr.set_camera(queue, &camera);
+        pbr.set_light(
+            queue,
+            DirLight {
+                direction: Vec3::new(-1.0, -1.0, -1.0),
+                color: Vec3::splat(2.0),
+            },
+        );
+        let materials = material_params
+            .iter()
+            .map(|material| pbr.create_material(&gpu, material))
+            .collect();
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            pbr,
+            materials,
+            skybox,
+            depth_texture,
+            mesh,
+            instances,
+            camera,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        // 카메라가 격자 앞에서 좌우로 천천히 움직여서 반사가 바뀌는 모습을 보여준다
+        let angle = (time_secs * 0.3).sin() * 0.6;
+        self.camera.eye = Vec3::new(7.0 * angle.sin(), 0.0, 7.0 * angle.cos());
+        self.pbr.set_camera(self.gpu.queue(), &self.camera);
+        self.skybox.update(self.gpu.queue(), &self.camera);
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            self.pbr.bind(&mut render_pass);
+            self.instances.bind(&mut render_pass, 1);
+            for (i, material) in self.materials.iter().enumerate() {
+                let i = i as u32;
+                material.bind(&mut render_pass);
+                self.mesh.draw_instanced(&mut render_pass, i..i + 1);
+            }
+
+            self.skybox.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,315 @@
+use std::cell::RefCell;
+use std::f32::consts::PI;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Quat, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    Camera, DepthTexture, DirLight, FormatSelector, GpuContext, InitError, InstanceBuffer, Mesh,
+    PhongMaterial, PhongPipeline, PhongVertex, StencilOutlinePass, Transform,
+};
+
+// 위도, 경도 방향으로 나누는 수
+const SPHERE_STACKS: u32 = 32;
+const SPHERE_SLICES: u32 = 64;
+// 외곽선의 색과 구를 키우는 비율
+const OUTLINE_COLOR: [f32; 4] = [1.0, 0.6, 0.1, 1.0];
+const OUTLINE_THICKNESS: f32 = 0.04;
+
+// 반지름 1 인 UV 구. 단위 구에서는 위치가 곧 법선이다
+fn sphere_geometry() -> (Vec<PhongVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(((SPHERE_STACKS + 1) * (SPHERE_SLICES + 1)) as usize);
+    let mut indices = Vec::with_capacity((SPHERE_STACKS * SPHERE_SLICES * 6) as usize);
+
+    for stack in 0..=SPHERE_STACKS {
+        let phi = PI * stack as f32 / SPHERE_STACKS as f32;
+        for slice in 0..=SPHERE_SLICES {
+            let theta = 2.0 * PI * slice as f32 / SPHERE_SLICES as f32;
+            let normal = Vec3::new(phi.sin() * theta.cos(), phi.cos(), -phi.sin() * theta.sin());
+            vertices.push(PhongVertex {
+                position: normal.into(),
+                normal: normal.into(),
+            });
+        }
+    }
+
+    // 바깥에서 봤을 때 CCW 가 되도록 위쪽 행부터 두 개의 삼각형으로 잇는다
+    let row = SPHERE_SLICES + 1;
+    for stack in 0..SPHERE_STACKS {
+        for slice in 0..SPHERE_SLICES {
+            let top = (stack * row + slice) as u16;
+            let bottom = top + row as u16;
+            indices.extend_from_slice(&[top, bottom, top + 1, top + 1, bottom, bottom + 1]);
+        }
+    }
+
+    (vertices, indices)
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    phong: PhongPipeline,
+    outline: StencilOutlinePass,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    instances: InstanceBuffer,
+    camera: Camera,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+        let queue = gpu.queue();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        // 외곽선 패스가 스텐실을 쓰므로 스텐실이 있는 깊이 텍스처를 만든다
+        let depth_texture = DepthTexture::with_stencil(device, size.0, size.1);
+
+        // 메시 생성
+        let (vertices, indices) = sphere_geometry();
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+        let instances = InstanceBuffer::new(device, &[Transform::IDENTITY]);
+
+        // 카메라
+        let camera = Camera::new(
+            Vec3::new(0.0, 1.0, 4.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+
+        // Phong 파이프라인 생성
+        let mut phong =
+            PhongPipeline::new(&gpu, surface_config.format, DepthTexture::STENCIL_FORMAT);
+        phong.set_camera(queue, &camera);
+        phong.set_light(
+            queue,
+            DirLight {
+                direction: Vec3::new(-1.0, -1.0, -0.5),
+                color: Vec3::ONE,
+            },
+        );
+        phong.set_material(
+            queue,
+            &PhongMaterial {
+                ambient: Vec3::new(0.05, 0.05, 0.1),
+                diffuse: Vec3::new(0.2, 0.4, 0.9),
+                specular: Vec3::splat(0.8),
+                shininess: 64.0,
+            },
+        );
+
+        let outline = StencilOutlinePass::new::<PhongVertex>(
+            device,
+            surface_config.format,
+            DepthTexture::STENCIL_FORMAT,
+        );
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            phong,
+            outline,
+            depth_texture,
+            mesh,
+            instances,
+            camera,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        // 빛은 고정하고 카메라가 구 주위를 돌기 때문에 하이라이트가 구 표면을 따라 움직인다
+        let radius = 4.0;
+        let angle = time_secs * 0.5;
+        self.camera.eye = Vec3::new(radius * angle.sin(), 1.0, radius * angle.cos());
+        self.phong.set_camera(self.gpu.queue(), &self.camera);
+
+        // 구는 제자리에서 천천히 자전한다
+        self.instances.transforms_mut()[0].rotation = Quat::from_rotation_y(time_secs * 0.3);
+        self.instances.update(self.gpu.queue());
+        self.outline.set_transform(
+            self.camera.view_projection(),
+            self.instances.transforms()[0].matrix(),
+        );
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            self.phong.bind(&mut render_pass);
+            self.instances.bind(&mut render_pass, 1);
+            self.mesh
+                .draw_instanced(&mut render_pass, 0..self.instances.len());
+        }
+
+        self.outline.render(
+            &mut encoder,
+            &view,
+            self.depth_texture.view(),
+            &self.mesh,
+            OUTLINE_COLOR,
+            OUTLINE_THICKNESS,
+        );
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::with_stencil(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
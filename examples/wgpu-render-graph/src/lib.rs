@@ -0,0 +1,551 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::Vec3;
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    Camera, CameraUniform, DepthTexture, FormatSelector, FullscreenTriangle, GpuContext,
+    HdrRenderTarget, InitError, Mesh, RenderGraph, RenderGraphContext, RenderGraphNode,
+    RenderPassProfiler, ResourceId, SamplerBuilder, UniformBuffer, Vertex,
+};
+
+thread_local! {
+    // JS 에서 호출하는 함수들이 사용하는 State
+    static STATE: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+}
+
+// 면 색에 곱해서 [0, 1] 범위를 넘는 밝기를 만든다
+const HDR_INTENSITY: f32 = 4.0;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// (법선, u축, v축, 색) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+type Face = ([f32; 3], [f32; 3], [f32; 3], [f32; 3]);
+
+const FACES: [Face; 6] = [
+    (
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ),
+    (
+        [-1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 1.0, 1.0],
+    ),
+    (
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+    ),
+    (
+        [0.0, -1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, -1.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+    ),
+];
+
+// 면마다 색이 다르도록 꼭짓점을 공유하지 않고 면당 4개씩 만든다
+fn cube_geometry() -> (Vec<ColorVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (normal, u, v, color) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = (n + u * su + v * sv) * 0.5;
+            vertices.push(ColorVertex {
+                position: position.into(),
+                color: (Vec3::from(color) * HDR_INTENSITY).into(),
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+/// 큐브를 HDR 텍스처와 깊이 텍스처에 그리는 첫 번째 패스
+struct GeometryNode {
+    pipeline: wgpu::RenderPipeline,
+    mesh: Mesh,
+    camera_bind_group: wgpu::BindGroup,
+    outputs: [ResourceId; 2],
+}
+
+impl RenderGraphNode for GeometryNode {
+    fn name(&self) -> &str {
+        "geometry"
+    }
+
+    fn inputs(&self) -> &[ResourceId] {
+        &[]
+    }
+
+    fn outputs(&self) -> &[ResourceId] {
+        &self.outputs
+    }
+
+    fn execute(&mut self, ctx: &mut RenderGraphContext<'_>) {
+        let [scene, depth] = self.outputs;
+        let mut render_pass = ctx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Geometry Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: ctx.texture_view(scene),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: 0.1,
+                        g: 0.2,
+                        b: 0.3,
+                        a: 1.0,
+                    }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: ctx.texture_view(depth),
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        self.mesh.draw(&mut render_pass);
+    }
+}
+
+/// 입력 텍스처 하나를 읽어서 출력 텍스처 전체에 프래그먼트 셰이더를 돌리는 후처리 패스
+struct FullscreenNode {
+    name: &'static str,
+    pipeline: wgpu::RenderPipeline,
+    sampler: wgpu::Sampler,
+    fullscreen: FullscreenTriangle,
+    inputs: [ResourceId; 1],
+    outputs: [ResourceId; 1],
+}
+
+impl FullscreenNode {
+    fn new(
+        device: &wgpu::Device,
+        name: &'static str,
+        entry_point: &str,
+        output_format: wgpu::TextureFormat,
+        input: ResourceId,
+        output: ResourceId,
+    ) -> Self {
+        let fullscreen = FullscreenTriangle::new(device);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Post Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("post.wgsl").into()),
+        });
+        // 입력 텍스처가 리사이즈로 바뀔 수 있어서 바인드 그룹은 매 프레임 만들고,
+        // 레이아웃은 셰이더에서 자동으로 얻는다
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(name),
+            layout: None,
+            vertex: fullscreen.vertex_state(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some(entry_point),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            name,
+            pipeline,
+            sampler: SamplerBuilder::linear().label(name).build(device),
+            fullscreen,
+            inputs: [input],
+            outputs: [output],
+        }
+    }
+}
+
+impl RenderGraphNode for FullscreenNode {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn inputs(&self) -> &[ResourceId] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[ResourceId] {
+        &self.outputs
+    }
+
+    fn execute(&mut self, ctx: &mut RenderGraphContext<'_>) {
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(self.name),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(ctx.texture_view(self.inputs[0])),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        let mut render_pass = ctx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(self.name),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: ctx.texture_view(self.outputs[0]),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        self.fullscreen.draw(&mut render_pass);
+    }
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    graph: RenderGraph,
+    // 매 프레임 서피스 텍스처의 뷰를 넘겨주는 외부 텍스처
+    surface_texture: ResourceId,
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    // 노드마다 명령을 기록하는 데 걸린 CPU 시간
+    profiler: RenderPassProfiler,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        // 메시 생성
+        let (vertices, indices) = cube_geometry();
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+
+        // 카메라
+        let camera = Camera::new(
+            Vec3::new(0.0, 1.5, 3.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[ColorVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: HdrRenderTarget::FORMAT,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // 렌더 그래프: geometry -> vignette -> tonemap
+        let mut graph = RenderGraph::new(size.0, size.1);
+        let scene = graph.create_texture("Scene Texture", HdrRenderTarget::FORMAT);
+        let depth = graph.create_texture("Depth Texture", DepthTexture::FORMAT);
+        let vignetted = graph.create_texture("Vignette Texture", HdrRenderTarget::FORMAT);
+        let surface_texture = graph.import_texture("Surface Texture");
+
+        // 일부러 실행 순서와 반대로 추가해도 build 가 입력과 출력을 보고 정렬한다
+        graph.add_node(FullscreenNode::new(
+            device,
+            "tonemap",
+            "fs_tonemap",
+            surface_format,
+            vignetted,
+            surface_texture,
+        ));
+        graph.add_node(FullscreenNode::new(
+            device,
+            "vignette",
+            "fs_vignette",
+            HdrRenderTarget::FORMAT,
+            scene,
+            vignetted,
+        ));
+        graph.add_node(GeometryNode {
+            pipeline: render_pipeline,
+            mesh,
+            camera_bind_group: camera_uniform.bind_group().clone(),
+            outputs: [scene, depth],
+        });
+        graph.build()?;
+        console::log_1(&format!("Render graph order: {:?}", graph.execution_order()).into());
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            graph,
+            surface_texture,
+            camera,
+            camera_uniform,
+            profiler: RenderPassProfiler::default(),
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        // 큐브 주위를 돌면서 바라본다
+        let radius = 3.0;
+        self.camera.eye = Vec3::new(radius * time_secs.cos(), 1.5, radius * time_secs.sin());
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Graph Encoder"),
+                });
+        self.graph.set_external(self.surface_texture, &view);
+        self.graph
+            .execute_profiled(&mut encoder, &self.gpu, &mut self.profiler);
+        self.profiler.scope("submit", || {
+            self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        });
+        self.profiler.end_frame();
+
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        // 그래프가 만든 텍스처는 다음 execute 때 새 크기로 다시 만들어진다
+        self.graph.resize(new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    STATE.with_borrow_mut(|current| *current = Some(Rc::clone(&state)));
+    start_render_loop(state);
+    Ok(())
+}
+
+/// 최근 프레임 동안 노드마다 명령을 기록하는 데 걸린 CPU 시간을 콘솔에 표로 출력한다.
+#[wasm_bindgen]
+pub fn print_pass_profile() {
+    STATE.with_borrow(|state| {
+        if let Some(Ok(state)) = state.as_ref().map(|state| state.try_borrow()) {
+            state.profiler.log_summary();
+        }
+    });
+}
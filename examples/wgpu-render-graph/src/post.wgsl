@@ -0,0 +1,26 @@
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+// 버텍스 셰이더는 FullscreenTriangle 이 제공한다
+struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// 화면 가장자리로 갈수록 어둡게 만든다
+@fragment
+fn fs_vignette(in: FragmentInput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.uv).rgb;
+    let distance = length(in.uv - vec2<f32>(0.5));
+    let vignette = smoothstep(0.8, 0.3, distance);
+    return vec4<f32>(color * vignette, 1.0);
+}
+
+// Reinhard 톤 매핑: [0, inf) 범위의 색을 [0, 1) 로 줄인다
+@fragment
+fn fs_tonemap(in: FragmentInput) -> @location(0) vec4<f32> {
+    let hdr = textureSample(input_texture, input_sampler, in.uv).rgb;
+    return vec4<f32>(hdr / (hdr + vec3<f32>(1.0)), 1.0);
+}
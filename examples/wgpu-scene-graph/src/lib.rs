@@ -0,0 +1,535 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Quat, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    Camera, CameraUniform, DebugLines, DepthTexture, FormatSelector, GpuContext, InitError,
+    InstanceBuffer, Mesh, NodeId, SceneGraph, SmoothedLines, Transform, UniformBuffer, Vertex,
+};
+
+thread_local! {
+    // JS 에서 호출하는 함수들이 사용하는 State
+    static STATE: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+}
+
+// 단위 큐브를 감싸는 구의 반지름 (대각선의 절반)
+const CUBE_BOUNDING_RADIUS: f32 = 0.866;
+const LINK_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.8];
+const BOUNDS_COLOR: [f32; 4] = [1.0, 1.0, 0.0, 1.0];
+// SmoothedLines 로 그릴 때 부모와 자식을 잇는 선의 굵기(픽셀)
+const LINK_WIDTH_PX: f32 = 3.0;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+// (법선, u축, v축, 색) - u x v = 법선 이 되도록 골라서 바깥에서 봤을 때 CCW 가 된다
+type Face = ([f32; 3], [f32; 3], [f32; 3], [f32; 3]);
+
+const FACES: [Face; 6] = [
+    (
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ),
+    (
+        [-1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 1.0, 1.0],
+    ),
+    (
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [0.0, 1.0, 0.0],
+    ),
+    (
+        [0.0, -1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ),
+    (
+        [0.0, 0.0, -1.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+    ),
+];
+
+// 면마다 색이 다르도록 꼭짓점을 공유하지 않고 면당 4개씩 만든다
+fn cube_geometry() -> (Vec<ColorVertex>, Vec<u16>) {
+    let mut vertices = Vec::with_capacity(24);
+    let mut indices = Vec::with_capacity(36);
+
+    for (normal, u, v, color) in FACES {
+        let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+        let base = vertices.len() as u16;
+
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let position = (n + u * su + v * sv) * 0.5;
+            vertices.push(ColorVertex {
+                position: position.into(),
+                color,
+            });
+        }
+
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+
+    (vertices, indices)
+}
+
+// 태양 → 행성 → 달 순서로 이어진 노드.
+// 자식의 이동과 크기는 부모 기준이므로 부모가 돌면 자식은 부모 주위를 공전한다
+struct SolarSystem {
+    graph: SceneGraph,
+    sun: NodeId,
+    planet: NodeId,
+    moon: NodeId,
+}
+
+impl SolarSystem {
+    fn new() -> Self {
+        let mut graph = SceneGraph::new();
+        let sun = graph.add(None, Transform::IDENTITY);
+        let planet = graph.add(
+            Some(sun),
+            Transform {
+                scale: Vec3::splat(0.5),
+                ..Transform::from_translation(Vec3::new(4.0, 0.0, 0.0))
+            },
+        );
+        let moon = graph.add(
+            Some(planet),
+            Transform {
+                scale: Vec3::splat(0.4),
+                ..Transform::from_translation(Vec3::new(2.0, 0.0, 0.0))
+            },
+        );
+
+        Self {
+            graph,
+            sun,
+            planet,
+            moon,
+        }
+    }
+
+    // 태양과 행성의 자전만 바꾸면 행성과 달의 공전은 계층을 따라 저절로 따라온다
+    fn update(&mut self, time_secs: f32) {
+        let mut sun = *self.graph.local_transform(self.sun);
+        sun.rotation = Quat::from_rotation_y(time_secs * 0.5);
+        self.graph.set_local_transform(self.sun, sun);
+
+        let mut planet = *self.graph.local_transform(self.planet);
+        planet.rotation = Quat::from_rotation_y(time_secs * 2.0);
+        self.graph.set_local_transform(self.planet, planet);
+    }
+
+    // InstanceBuffer 의 인스턴스 순서
+    fn nodes(&self) -> [NodeId; 3] {
+        [self.sun, self.planet, self.moon]
+    }
+
+    // 부모와 자식을 잇는 선과 각 천체를 감싸는 구.
+    // `smooth_lines` 가 있으면 잇는 선은 그쪽에 굵은 선으로 넣는다
+    fn add_debug_lines(
+        &self,
+        lines: &mut DebugLines,
+        mut smooth_lines: Option<&mut SmoothedLines>,
+    ) {
+        for id in self.nodes() {
+            let world = Transform::from_matrix(self.graph.world_transform(id));
+            lines.add_sphere(
+                world.translation,
+                world.scale.max_element() * CUBE_BOUNDING_RADIUS,
+                BOUNDS_COLOR,
+            );
+
+            if let Some(parent) = self.graph.node(id).parent() {
+                let parent_position = self.graph.world_transform(parent).w_axis.truncate();
+                match smooth_lines.as_deref_mut() {
+                    Some(smooth_lines) => smooth_lines.add(
+                        parent_position,
+                        world.translation,
+                        LINK_COLOR,
+                        LINK_WIDTH_PX,
+                    ),
+                    None => lines.add_line(parent_position, world.translation, LINK_COLOR),
+                }
+            }
+        }
+    }
+
+    fn world_transforms(&self) -> Vec<Transform> {
+        self.nodes()
+            .iter()
+            .map(|&id| Transform::from_matrix(self.graph.world_transform(id)))
+            .collect()
+    }
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    instances: InstanceBuffer,
+    solar_system: SolarSystem,
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    debug_lines: DebugLines,
+    smooth_lines: SmoothedLines,
+    use_smooth_lines: bool,
+    // 깊이 텍스처가 다시 만들어지면 디버그 선에도 다시 알려 준다
+    depth_tested_lines: bool,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 메시 생성
+        let (vertices, indices) = cube_geometry();
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+        let solar_system = SolarSystem::new();
+        let instances = InstanceBuffer::new(device, &solar_system.world_transforms());
+
+        // 카메라
+        let camera = Camera::new(
+            Vec3::new(0.0, 6.0, 10.0),
+            Vec3::ZERO,
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[ColorVertex::layout(), InstanceBuffer::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let debug_lines = DebugLines::new(
+            &gpu,
+            surface_config.format,
+            camera_uniform.bind_group_layout(),
+        );
+        let smooth_lines = SmoothedLines::new(
+            &gpu,
+            surface_config.format,
+            camera_uniform.bind_group_layout(),
+            size.0,
+            size.1,
+        );
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            mesh,
+            instances,
+            solar_system,
+            camera,
+            camera_uniform,
+            debug_lines,
+            smooth_lines,
+            use_smooth_lines: false,
+            depth_tested_lines: false,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        self.solar_system.update(time_secs);
+        self.instances
+            .transforms_mut()
+            .copy_from_slice(&self.solar_system.world_transforms());
+        self.instances.update(self.gpu.queue());
+        let smooth_lines = self.use_smooth_lines.then_some(&mut self.smooth_lines);
+        self.solar_system
+            .add_debug_lines(&mut self.debug_lines, smooth_lines);
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            self.instances.bind(&mut render_pass, 1);
+            self.mesh
+                .draw_instanced(&mut render_pass, 0..self.instances.len());
+        }
+
+        // 메인 패스가 그린 장면 위에 덧그린다
+        self.debug_lines
+            .flush(&mut encoder, &view, self.camera_uniform.bind_group());
+        self.smooth_lines
+            .flush(&mut encoder, &view, self.camera_uniform.bind_group());
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        if self.depth_tested_lines {
+            self.debug_lines
+                .set_depth_test(Some(self.depth_texture.view()));
+            self.smooth_lines
+                .set_depth_test(Some(self.depth_texture.view()));
+        }
+        self.smooth_lines.resize(new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    STATE.with_borrow_mut(|current| *current = Some(Rc::clone(&state)));
+    start_render_loop(state);
+    Ok(())
+}
+
+/// 켜면 디버그 선이 천체에 가려지고, 끄면 항상 위에 그려진다.
+#[wasm_bindgen]
+pub fn set_debug_depth_test(enabled: bool) {
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut()) {
+            let state = &mut *state;
+            state.depth_tested_lines = enabled;
+            let depth_view = enabled.then(|| state.depth_texture.view());
+            state.debug_lines.set_depth_test(depth_view);
+            state.smooth_lines.set_depth_test(depth_view);
+        }
+    });
+}
+
+/// 켜면 부모와 자식을 잇는 선을 `SmoothedLines` 로 굵고 부드럽게 그린다.
+#[wasm_bindgen]
+pub fn set_smooth_lines(enabled: bool) {
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut()) {
+            state.use_smooth_lines = enabled;
+        }
+    });
+}
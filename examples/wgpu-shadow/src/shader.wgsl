@@ -0,0 +1,70 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+struct ShadowUniform {
+    light_view_proj: mat4x4<f32>,
+    bias: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var<uniform> shadow: ShadowUniform;
+@group(1) @binding(1)
+var shadow_map: texture_depth_2d;
+@group(1) @binding(2)
+var shadow_sampler: sampler_comparison;
+
+struct Light {
+    direction: vec3<f32>,
+    _padding: f32,
+};
+
+@group(2) @binding(0)
+var<uniform> light: Light;
+
+const AMBIENT: f32 = 0.2;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) color: vec3<f32>,
+    @location(2) light_position: vec4<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.color = in.color;
+    out.light_position = shadow.light_view_proj * vec4<f32>(in.position, 1.0);
+    return out;
+}
+
+// 1.0 이면 빛을 받고 0.0 이면 그림자 안이다
+fn shadow_factor(light_position: vec4<f32>) -> f32 {
+    let ndc = light_position.xyz / light_position.w;
+    // 클립 공간 y 는 위쪽이 + 이고 텍스처 좌표 v 는 아래쪽이 + 이다
+    let uv = ndc.xy * vec2<f32>(0.5, -0.5) + 0.5;
+    let visibility = textureSampleCompare(shadow_map, shadow_sampler, uv, ndc.z - shadow.bias);
+
+    // 그림자 맵 밖은 빛을 받는다고 본다
+    let inside = all(uv >= vec2<f32>(0.0)) && all(uv <= vec2<f32>(1.0)) && ndc.z <= 1.0;
+    return select(1.0, visibility, inside);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let diffuse = max(dot(normalize(in.normal), normalize(light.direction)), 0.0);
+    let lit = diffuse * shadow_factor(in.light_position);
+    return vec4<f32>(in.color * (AMBIENT + lit), 1.0);
+}
@@ -0,0 +1,13 @@
+struct ShadowUniform {
+    light_view_proj: mat4x4<f32>,
+    bias: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> shadow: ShadowUniform;
+
+// 빛에서 본 깊이만 기록하므로 프래그먼트 셰이더는 없다
+@vertex
+fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    return shadow.light_view_proj * vec4<f32>(position, 1.0);
+}
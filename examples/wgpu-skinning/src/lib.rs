@@ -0,0 +1,458 @@
+use std::cell::RefCell;
+use std::f32::consts::TAU;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Quat, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BonePalette, BoneTransform, Camera, CameraUniform, DepthTexture, FormatSelector, GpuContext,
+    InitError, Mesh, Skeleton, SkinnedVertex, UniformBuffer, Vertex,
+};
+
+// 뼈 인덱스. 부모가 자식보다 앞에 온다
+const PELVIS: usize = 0;
+const SPINE: usize = 1;
+const HEAD: usize = 2;
+const LEFT_THIGH: usize = 3;
+const LEFT_SHIN: usize = 4;
+const RIGHT_THIGH: usize = 5;
+const RIGHT_SHIN: usize = 6;
+const LEFT_UPPER_ARM: usize = 7;
+const LEFT_FOREARM: usize = 8;
+const RIGHT_UPPER_ARM: usize = 9;
+const RIGHT_FOREARM: usize = 10;
+
+// (부모, 부모 기준 위치). 인물은 +z 를 보고 서 있다
+const BONES: [(Option<usize>, [f32; 3]); 11] = [
+    (None, [0.0, 1.0, 0.0]),
+    (Some(PELVIS), [0.0, 0.0, 0.0]),
+    (Some(SPINE), [0.0, 0.55, 0.0]),
+    (Some(PELVIS), [0.12, 0.0, 0.0]),
+    (Some(LEFT_THIGH), [0.0, -0.45, 0.0]),
+    (Some(PELVIS), [-0.12, 0.0, 0.0]),
+    (Some(RIGHT_THIGH), [0.0, -0.45, 0.0]),
+    (Some(SPINE), [0.2, 0.5, 0.0]),
+    (Some(LEFT_UPPER_ARM), [0.0, -0.3, 0.0]),
+    (Some(SPINE), [-0.2, 0.5, 0.0]),
+    (Some(RIGHT_UPPER_ARM), [0.0, -0.3, 0.0]),
+];
+
+// 뼈 하나에 붙은 막대
+struct Limb {
+    bone: usize,
+    // 뼈 기준의 양 끝
+    from: [f32; 3],
+    to: [f32; 3],
+    half_width: f32,
+    // from 쪽 끝을 부모 뼈와 반씩 섞어서 관절이 벌어지지 않게 한다
+    blend_parent: bool,
+}
+
+const fn limb(bone: usize, from: [f32; 3], to: [f32; 3], half_width: f32) -> Limb {
+    Limb {
+        bone,
+        from,
+        to,
+        half_width,
+        blend_parent: false,
+    }
+}
+
+const fn jointed_limb(bone: usize, to: [f32; 3], half_width: f32) -> Limb {
+    Limb {
+        bone,
+        from: [0.0; 3],
+        to,
+        half_width,
+        blend_parent: true,
+    }
+}
+
+const LIMBS: [Limb; 12] = [
+    limb(PELVIS, [-0.14, 0.0, 0.0], [0.14, 0.0, 0.0], 0.05),
+    limb(SPINE, [0.0, 0.0, 0.0], [0.0, 0.55, 0.0], 0.05),
+    limb(SPINE, [-0.22, 0.5, 0.0], [0.22, 0.5, 0.0], 0.04),
+    limb(HEAD, [0.0, 0.05, 0.0], [0.0, 0.3, 0.0], 0.11),
+    limb(LEFT_THIGH, [0.0, 0.0, 0.0], [0.0, -0.45, 0.0], 0.045),
+    jointed_limb(LEFT_SHIN, [0.0, -0.45, 0.0], 0.04),
+    limb(RIGHT_THIGH, [0.0, 0.0, 0.0], [0.0, -0.45, 0.0], 0.045),
+    jointed_limb(RIGHT_SHIN, [0.0, -0.45, 0.0], 0.04),
+    limb(LEFT_UPPER_ARM, [0.0, 0.0, 0.0], [0.0, -0.3, 0.0], 0.035),
+    jointed_limb(LEFT_FOREARM, [0.0, -0.3, 0.0], 0.03),
+    limb(RIGHT_UPPER_ARM, [0.0, 0.0, 0.0], [0.0, -0.3, 0.0], 0.035),
+    jointed_limb(RIGHT_FOREARM, [0.0, -0.3, 0.0], 0.03),
+];
+
+// (법선, u축, v축) - [-1, 1] 상자의 면. u x v = 법선 이라서 바깥에서 봤을 때 CCW 가 된다
+const BOX_FACES: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+];
+
+// 한 걸음(왼발과 오른발 한 번씩)에 걸리는 시간 (초)
+const STRIDE_SECS: f32 = 1.2;
+
+fn skeleton() -> Skeleton {
+    let (bind_pose, parents) = BONES
+        .iter()
+        .map(|(parent, position)| {
+            (
+                BoneTransform::from_translation(Vec3::from(*position)),
+                *parent,
+            )
+        })
+        .unzip();
+    Skeleton::new(bind_pose, parents)
+}
+
+// 막대마다 상자를 바인드 포즈의 메시 공간에 만든다
+fn stick_figure(skeleton: &Skeleton) -> (Vec<SkinnedVertex>, Vec<u16>) {
+    let world = skeleton.world_matrices();
+    let mut vertices = Vec::with_capacity(LIMBS.len() * 24);
+    let mut indices = Vec::with_capacity(LIMBS.len() * 36);
+
+    for limb in &LIMBS {
+        let from = world[limb.bone].transform_point3(Vec3::from(limb.from));
+        let to = world[limb.bone].transform_point3(Vec3::from(limb.to));
+        // 상자의 y 축을 막대 방향에 맞추고 x, z 축은 그에 수직으로 잡는다
+        let axis_y = (to - from).normalize();
+        let axis_x = axis_y.any_orthonormal_vector();
+        let axis_z = axis_x.cross(axis_y);
+        let center = (from + to) * 0.5;
+        let half_length = (to - from).length() * 0.5;
+
+        let bone = limb.bone as u32;
+        let parent = skeleton.parents()[limb.bone].unwrap_or(limb.bone) as u32;
+
+        for (normal, u, v) in BOX_FACES {
+            let (n, u, v) = (Vec3::from(normal), Vec3::from(u), Vec3::from(v));
+            let base = vertices.len() as u16;
+            let world_normal = axis_x * n.x + axis_y * n.y + axis_z * n.z;
+
+            for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                let local = n + u * su + v * sv;
+                let position = center
+                    + axis_x * (local.x * limb.half_width)
+                    + axis_y * (local.y * half_length)
+                    + axis_z * (local.z * limb.half_width);
+
+                let mut vertex = SkinnedVertex::rigid(position.into(), world_normal.into(), bone);
+                if limb.blend_parent && local.y < 0.0 {
+                    vertex.bone_indices = [bone, parent, 0, 0];
+                    vertex.bone_weights = [0.5, 0.5, 0.0, 0.0];
+                }
+                vertices.push(vertex);
+            }
+
+            indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+        }
+    }
+
+    (vertices, indices)
+}
+
+// 제자리에서 걷는 포즈. x 축으로 음수만큼 돌리면 다리가 앞(+z)으로 나간다
+fn walk(skeleton: &mut Skeleton, time_secs: f32) {
+    let phase = time_secs / STRIDE_SECS * TAU;
+    let swing = phase.sin() * 0.5;
+    // 다리를 앞으로 내밀 때 무릎을 굽힌다
+    let knee = |leg_phase: f32| 0.8 * leg_phase.cos().max(0.0);
+
+    skeleton.bones[PELVIS].translation.y = BONES[PELVIS].1[1] + 0.03 * (2.0 * phase).cos();
+    skeleton.bones[PELVIS].rotation = Quat::from_rotation_y(0.1 * phase.sin());
+    skeleton.bones[SPINE].rotation = Quat::from_rotation_y(-0.15 * phase.sin());
+
+    skeleton.bones[LEFT_THIGH].rotation = Quat::from_rotation_x(-swing);
+    skeleton.bones[LEFT_SHIN].rotation = Quat::from_rotation_x(knee(phase));
+    skeleton.bones[RIGHT_THIGH].rotation = Quat::from_rotation_x(swing);
+    skeleton.bones[RIGHT_SHIN].rotation = Quat::from_rotation_x(knee(phase + TAU / 2.0));
+
+    // 팔은 같은 쪽 다리와 반대로 흔든다
+    skeleton.bones[LEFT_UPPER_ARM].rotation = Quat::from_rotation_x(swing * 0.8);
+    skeleton.bones[LEFT_FOREARM].rotation = Quat::from_rotation_x(-0.4);
+    skeleton.bones[RIGHT_UPPER_ARM].rotation = Quat::from_rotation_x(-swing * 0.8);
+    skeleton.bones[RIGHT_FOREARM].rotation = Quat::from_rotation_x(-0.4);
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    mesh: Mesh,
+    skeleton: Skeleton,
+    camera: Camera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    bone_uniform: UniformBuffer<BonePalette>,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // 메시는 바인드 포즈로 한 번만 만들고, 움직임은 뼈 행렬로만 준다
+        let skeleton = skeleton();
+        let (vertices, indices) = stick_figure(&skeleton);
+        let mesh = Mesh::new(&gpu, &vertices, &indices);
+
+        let camera = Camera::new(
+            Vec3::new(0.0, 1.3, 3.0),
+            Vec3::new(0.0, 0.9, 0.0),
+            size.0 as f32 / size.1 as f32,
+        );
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+        let bone_uniform =
+            UniformBuffer::new(&gpu, &skeleton.palette(), 0, wgpu::ShaderStages::VERTEX);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Skinning Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[
+                    camera_uniform.bind_group_layout(),
+                    bone_uniform.bind_group_layout(),
+                ],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[SkinnedVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            mesh,
+            skeleton,
+            camera,
+            camera_uniform,
+            bone_uniform,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        walk(&mut self.skeleton, time_secs);
+        self.bone_uniform
+            .update(self.gpu.queue(), &self.skeleton.palette());
+
+        // 걷는 모습을 여러 방향에서 보도록 천천히 돈다
+        let angle = time_secs * 0.3;
+        self.camera.eye = Vec3::new(3.0 * angle.sin(), 1.3, 3.0 * angle.cos());
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            render_pass.set_bind_group(1, self.bone_uniform.bind_group(), &[]);
+            self.mesh.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+        self.camera.aspect = new_size.0 as f32 / new_size.1 as f32;
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+// Skeleton::palette. 바인드 포즈에서 지금 포즈로 옮기는 뼈 행렬 (MAX_BONES 개)
+@group(1) @binding(0)
+var<uniform> bones: array<mat4x4<f32>, 64>;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) bone_indices: vec4<u32>,
+    @location(3) bone_weights: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    // 선형 블렌드 스키닝: 붙은 뼈들의 행렬을 가중치로 섞은 행렬 하나로 옮긴다
+    let skin = bones[in.bone_indices.x] * in.bone_weights.x
+        + bones[in.bone_indices.y] * in.bone_weights.y
+        + bones[in.bone_indices.z] * in.bone_weights.z
+        + bones[in.bone_indices.w] * in.bone_weights.w;
+
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * skin * vec4<f32>(in.position, 1.0);
+    // 뼈 행렬은 회전과 이동뿐이므로 법선도 같은 행렬의 3x3 부분으로 돌리면 된다
+    out.normal = (skin * vec4<f32>(in.normal, 0.0)).xyz;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let light_dir = normalize(vec3<f32>(0.4, 0.8, 0.6));
+    let diffuse = max(dot(normalize(in.normal), light_dir), 0.0);
+    let color = vec3<f32>(0.9, 0.85, 0.75);
+    return vec4<f32>(color * (0.3 + 0.7 * diffuse), 1.0);
+}
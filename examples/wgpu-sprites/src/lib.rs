@@ -0,0 +1,437 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Vec2, Vec4};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    BindGroupBuilder, CameraUniform, DrawShape, FormatSelector, FrameTimer, GpuContext, InitError,
+    InputListeners, InputState, OrthographicCamera2D, PhysicsDebugDraw, RenderPass2D, Sprite,
+    SpriteBatch, Texture, TextureHandle, UniformBuffer,
+};
+
+const SPRITE_COUNT: usize = 1000;
+// 스프라이트 한 변의 크기 (픽셀)
+const SPRITE_SIZE: f32 = 24.0;
+// 탭이 백그라운드에 있다가 돌아왔을 때 한 번에 너무 많이 움직이지 않도록 제한한다
+const MAX_DELTA_SECS: f32 = 0.1;
+// HUD 의 FPS 막대가 가득 차는 값
+const HUD_MAX_FPS: f32 = 60.0;
+
+struct Ball {
+    position: Vec2,
+    velocity: Vec2,
+    rotation: f32,
+    angular_velocity: f32,
+    tint: Vec4,
+}
+
+// 예제에서만 쓰는 간단한 선형 합동 난수 생성기
+struct Rng(u32);
+
+impl Rng {
+    fn next_f32(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (self.0 >> 8) as f32 / (1 << 24) as f32
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+fn create_balls(size: (u32, u32)) -> Vec<Ball> {
+    let mut rng = Rng(0x1234_5678);
+    (0..SPRITE_COUNT)
+        .map(|_| Ball {
+            position: Vec2::new(rng.range(0.0, size.0 as f32), rng.range(0.0, size.1 as f32)),
+            velocity: Vec2::new(rng.range(-200.0, 200.0), rng.range(-200.0, 200.0)),
+            rotation: rng.range(0.0, std::f32::consts::TAU),
+            angular_velocity: rng.range(-3.0, 3.0),
+            tint: Vec4::new(
+                rng.range(0.3, 1.0),
+                rng.range(0.3, 1.0),
+                rng.range(0.3, 1.0),
+                1.0,
+            ),
+        })
+        .collect()
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    // 가운데 버튼으로 끌어서 옮기고 휠로 확대한다
+    camera: OrthographicCamera2D,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    input: Rc<RefCell<InputState>>,
+    _input_listeners: InputListeners,
+    texture_bind_group: wgpu::BindGroup,
+    sprite_batch: SpriteBatch,
+    // 스프라이트 위에 덧그리는 FPS 표시
+    hud: RenderPass2D,
+    // 공의 충돌 원과 벽을 스프라이트 위에 덧그리는 디버그 뷰
+    physics_debug: PhysicsDebugDraw,
+    hud_ball: TextureHandle,
+    balls: Vec<Ball>,
+    frame_timer: FrameTimer,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        // 처음에는 캔버스의 왼쪽 아래가 (0, 0), 오른쪽 위가 (width, height) 인 픽셀 좌표계다
+        let camera = OrthographicCamera2D::new(size);
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::from(&camera),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        // 텍스처 로드
+        let texture = Texture::from_bytes(&gpu, include_bytes!("ball.png"), "ball")?;
+
+        let (texture_bind_group_layout, texture_bind_group) = BindGroupBuilder::new()
+            .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+            .sampler(1, texture.sampler(), wgpu::ShaderStages::FRAGMENT)
+            .build(device, "Texture Bind Group");
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[
+                    camera_uniform.bind_group_layout(),
+                    &texture_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[SpriteBatch::vertex_layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    // 스프라이트의 투명한 가장자리를 위해 알파 블렌딩을 사용한다
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let sprite_batch = SpriteBatch::new(device, SPRITE_COUNT);
+
+        let physics_debug = PhysicsDebugDraw::new(
+            &gpu,
+            surface_config.format,
+            camera_uniform.bind_group_layout(),
+        );
+        let mut hud = RenderPass2D::new(&gpu, surface_config.format);
+        let hud_ball = hud.add_texture(&texture);
+
+        let input = Rc::new(RefCell::new(InputState::default()));
+        let input_listeners = InputListeners::register(&canvas, Rc::clone(&input))
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+
+        let mut frame_timer = FrameTimer::new();
+        frame_timer.set_debug(true);
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            camera,
+            camera_uniform,
+            input,
+            _input_listeners: input_listeners,
+            texture_bind_group,
+            sprite_batch,
+            hud,
+            physics_debug,
+            hud_ball,
+            balls: create_balls(size),
+            frame_timer,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, delta_secs: f32) {
+        {
+            let mut input = self.input.borrow_mut();
+            let pixel_ratio = web_sys::window().unwrap().device_pixel_ratio() as f32;
+            self.camera.handle_input(&input, pixel_ratio);
+            input.end_frame();
+        }
+        self.camera_uniform
+            .update(self.gpu.queue(), &CameraUniform::from(&self.camera));
+
+        let bounds = Vec2::new(self.size.0 as f32, self.size.1 as f32);
+        let half = SPRITE_SIZE / 2.0;
+
+        self.sprite_batch.clear();
+        for ball in &mut self.balls {
+            ball.position += ball.velocity * delta_secs;
+            ball.rotation += ball.angular_velocity * delta_secs;
+
+            // 캔버스 가장자리에 닿으면 튕겨 나온다
+            if ball.position.x < half || ball.position.x > bounds.x - half {
+                ball.velocity.x = -ball.velocity.x;
+            }
+            if ball.position.y < half || ball.position.y > bounds.y - half {
+                ball.velocity.y = -ball.velocity.y;
+            }
+            ball.position = ball
+                .position
+                .clamp(Vec2::splat(half), (bounds - half).max(Vec2::splat(half)));
+
+            self.sprite_batch.push(Sprite {
+                rotation: ball.rotation,
+                tint: ball.tint,
+                ..Sprite::new(ball.position, Vec2::splat(SPRITE_SIZE))
+            });
+            self.physics_debug.add(DrawShape::Circle {
+                center: ball.position,
+                radius: half,
+                color: ball.tint.into(),
+            });
+        }
+
+        // 공이 튕기는 벽
+        let corners = [
+            Vec2::ZERO,
+            Vec2::new(bounds.x, 0.0),
+            bounds,
+            Vec2::new(0.0, bounds.y),
+        ];
+        for i in 0..corners.len() {
+            self.physics_debug.add(DrawShape::Edge {
+                a: corners[i],
+                b: corners[(i + 1) % corners.len()],
+                color: [1.0, 0.3, 0.3, 1.0],
+            });
+        }
+
+        self.sprite_batch.prepare(&self.gpu);
+
+        // HUD 는 왼쪽 위가 원점인 픽셀 좌표로 그린다
+        let fps_ratio = (self.frame_timer.fps() / HUD_MAX_FPS).clamp(0.0, 1.0);
+        self.hud
+            .draw_rect(8.0, 8.0, 176.0, 40.0, [0.0, 0.0, 0.0, 0.6]);
+        self.hud
+            .draw_textured_rect(14.0, 14.0, 28.0, 28.0, self.hud_ball);
+        self.hud
+            .draw_rect(50.0, 22.0, 120.0 * fps_ratio, 12.0, [0.3, 0.9, 0.4, 1.0]);
+        self.hud
+            .draw_circle(170.0, 28.0, 4.0, [1.0, 1.0, 1.0, 1.0], 12);
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            render_pass.set_bind_group(1, &self.texture_bind_group, &[]);
+            // 스프라이트 1000개를 draw_indexed 한 번으로 그린다
+            self.sprite_batch.draw(&mut render_pass);
+        }
+
+        // 스프라이트를 다 그린 뒤, HUD 보다는 아래에 덧그린다
+        self.physics_debug
+            .flush(&mut encoder, &view, self.camera_uniform.bind_group());
+        self.hud.flush(&mut encoder, &view, self.size);
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.camera.resize(new_size);
+    }
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                state.frame_timer.begin_frame();
+
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                let delta_secs = state.frame_timer.delta_secs().min(MAX_DELTA_SECS);
+                state.update(delta_secs);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+
+                state.frame_timer.end_frame();
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{FormatSelector, GpuContext, InitError, StagingBuffer, Vertex, VertexBuffer};
+
+// 512 x 512 개의 점 = 프레임당 2 MiB 업로드
+const GRID_SIZE: u32 = 512;
+// 매핑이 끝나길 기다리지 않도록 여러 개의 스테이징 버퍼를 돌려가며 쓴다
+const STAGING_BUFFER_COUNT: usize = 3;
+const REPORT_INTERVAL_MS: f64 = 2000.0;
+
+thread_local! {
+    static USE_STAGING: Cell<bool> = const { Cell::new(true) };
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct PointVertex {
+    position: [f32; 2],
+    height: f32,
+}
+
+impl Vertex for PointVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32];
+}
+
+fn grid_vertices() -> Vec<PointVertex> {
+    let step = 1.8 / (GRID_SIZE - 1) as f32;
+    (0..GRID_SIZE * GRID_SIZE)
+        .map(|i| PointVertex {
+            position: [
+                -0.9 + (i % GRID_SIZE) as f32 * step,
+                -0.9 + (i / GRID_SIZE) as f32 * step,
+            ],
+            height: 0.0,
+        })
+        .collect()
+}
+
+// 업로드에 걸린 CPU 시간 누적값
+#[derive(Default)]
+struct UploadStats {
+    frames: u32,
+    staged_frames: u32,
+    upload_ms: f64,
+    bytes: u64,
+    since_ms: f64,
+}
+
+impl UploadStats {
+    fn report(&mut self, now_ms: f64) {
+        if now_ms - self.since_ms < REPORT_INTERVAL_MS || self.frames == 0 {
+            return;
+        }
+
+        let mode = if USE_STAGING.get() {
+            "staging"
+        } else {
+            "write_buffer"
+        };
+        let avg_ms = self.upload_ms / self.frames as f64;
+        let throughput = self.bytes as f64 / (1024.0 * 1024.0) / (self.upload_ms / 1000.0);
+        console::log_1(
+            &format!(
+                "[{}] {} frames ({} staged), avg upload {:.3} ms, {:.1} MiB/s",
+                mode, self.frames, self.staged_frames, avg_ms, throughput
+            )
+            .into(),
+        );
+
+        *self = UploadStats {
+            since_ms: now_ms,
+            ..Default::default()
+        };
+    }
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    vertices: Vec<PointVertex>,
+    vertex_buffer: VertexBuffer<PointVertex>,
+    staging_buffers: Vec<StagingBuffer>,
+    stats: UploadStats,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        // 매 프레임 덮어쓸 버텍스 버퍼와 스테이징 버퍼 생성
+        let vertices = grid_vertices();
+        let vertex_buffer = VertexBuffer::writable(device, &vertices);
+        let staging_buffers = (0..STAGING_BUFFER_COUNT)
+            .map(|_| StagingBuffer::new(device, vertex_buffer.buffer().size()))
+            .collect();
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[PointVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::PointList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            vertices,
+            vertex_buffer,
+            staging_buffers,
+            stats: UploadStats {
+                since_ms: now_ms(),
+                ..Default::default()
+            },
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, time_secs: f32) {
+        for vertex in &mut self.vertices {
+            let [x, y] = vertex.position;
+            vertex.height = (x * 10.0 + time_secs * 2.0).sin() * (y * 8.0 + time_secs).cos();
+        }
+    }
+
+    // 스테이징 버퍼를 쓸 수 있으면 복사 명령을, 아니면 write_buffer 를 사용한다.
+    // 사용한 스테이징 버퍼의 인덱스를 반환한다.
+    fn upload(&mut self, encoder: &mut wgpu::CommandEncoder) -> Option<usize> {
+        let start = now_ms();
+
+        let staged = if USE_STAGING.get() {
+            self.staging_buffers
+                .iter_mut()
+                .position(|staging| staging.is_ready())
+                .inspect(|&index| {
+                    let staging = &mut self.staging_buffers[index];
+                    staging.write(&self.vertices);
+                    staging.copy_to(encoder, self.vertex_buffer.buffer());
+                })
+        } else {
+            None
+        };
+
+        if staged.is_none() {
+            self.vertex_buffer.write(self.gpu.queue(), &self.vertices);
+        }
+
+        self.stats.frames += 1;
+        self.stats.staged_frames += staged.is_some() as u32;
+        self.stats.upload_ms += now_ms() - start;
+        self.stats.bytes += std::mem::size_of_val(self.vertices.as_slice()) as u64;
+
+        staged
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        // 매핑 완료 콜백이 호출되도록 한다
+        let _ = self.gpu.device().poll(wgpu::PollType::Poll);
+
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        let staged = self.upload(&mut encoder);
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            self.vertex_buffer.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        if let Some(index) = staged {
+            self.staging_buffers[index].remap();
+        }
+        self.stats.report(now_ms());
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+    }
+}
+
+fn now_ms() -> f64 {
+    web_sys::window().unwrap().performance().unwrap().now()
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_ms();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(((now_ms() - start) / 1000.0) as f32);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+/// `false` 로 설정하면 매 프레임 `queue.write_buffer` 로 업로드한다.
+#[wasm_bindgen]
+pub fn set_use_staging(enabled: bool) {
+    USE_STAGING.set(enabled);
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) height: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(in.position.x, in.position.y + in.height * 0.1, 0.0, 1.0);
+    out.color = mix(vec3<f32>(0.1, 0.4, 1.0), vec3<f32>(1.0, 0.9, 0.3), in.height * 0.5 + 0.5);
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
@@ -0,0 +1,361 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    CameraUniform, ChunkCoord, ChunkGeometry, ChunkStreamer, DepthTexture, FormatSelector,
+    FpsCamera, GpuContext, InitError, InputListeners, InputState, PointerLock, TerrainVertex,
+    UniformBuffer, Vertex,
+};
+
+// shader.wgsl 의 FOG_COLOR 와 같게 두어서 청크가 안개 속에서 나타나고 사라진다
+const FOG_COLOR: wgpu::Color = wgpu::Color {
+    r: 0.62,
+    g: 0.72,
+    b: 0.82,
+    a: 1.0,
+};
+const CHUNK_SIZE: f32 = 16.0;
+// 청크 한 변을 나누는 칸 수
+const CHUNK_RESOLUTION: u32 = 32;
+// shader.wgsl 의 FOG_END 보다 조금 멀어야 청크 가장자리가 보이지 않는다
+const VIEW_DISTANCE: f32 = 90.0;
+// 파일을 받아 오는 시간을 흉내 내서 청크마다 기다린다 (밀리초)
+const LOAD_LATENCY_MS: i32 = 150;
+
+// 끝없이 이어지는 언덕. 청크 경계에서도 같은 값이 나오도록 월드 좌표만으로 정한다
+fn height(x: f32, z: f32) -> f32 {
+    6.0 * (x * 0.03).sin() * (z * 0.025).cos()
+        + 2.0 * ((x + z) * 0.09).sin()
+        + 0.6 * (x * 0.31 - z * 0.27).sin()
+}
+
+// 청크 하나의 격자. 버텍스는 월드 좌표라서 청크마다 변환 행렬이 필요 없다
+fn chunk_geometry(coord: ChunkCoord) -> ChunkGeometry<TerrainVertex> {
+    let origin = coord.origin(CHUNK_SIZE);
+    let step = CHUNK_SIZE / CHUNK_RESOLUTION as f32;
+    let side = CHUNK_RESOLUTION + 1;
+
+    let mut vertices = Vec::with_capacity((side * side) as usize);
+    for j in 0..side {
+        for i in 0..side {
+            let (x, z) = (origin.x + i as f32 * step, origin.z + j as f32 * step);
+            // 이웃 청크와 법선이 이어지도록 격자 밖의 높이도 함수로 구한다
+            let dx = (height(x + step, z) - height(x - step, z)) / (2.0 * step);
+            let dz = (height(x, z + step) - height(x, z - step)) / (2.0 * step);
+            vertices.push(TerrainVertex {
+                position: [x, height(x, z), z],
+                normal: Vec3::new(-dx, 1.0, -dz).normalize().into(),
+            });
+        }
+    }
+
+    let mut indices = Vec::with_capacity((CHUNK_RESOLUTION * CHUNK_RESOLUTION * 6) as usize);
+    for j in 0..CHUNK_RESOLUTION {
+        for i in 0..CHUNK_RESOLUTION {
+            let index = j * side + i;
+            let (right, below) = (index + 1, index + side);
+            indices.extend_from_slice(&[index, below, right, right, below, below + 1]);
+        }
+    }
+
+    ChunkGeometry { vertices, indices }
+}
+
+// setTimeout 이 부를 때까지 기다린다
+async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    chunks: ChunkStreamer<TerrainVertex>,
+    camera: FpsCamera,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    input: Rc<RefCell<InputState>>,
+    _input_listeners: InputListeners,
+    _pointer_lock: PointerLock,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        // WASD 로 날아다니고, 캔버스를 클릭해서 커서를 가두면 마우스로 둘러본다
+        let input = Rc::new(RefCell::new(InputState::default()));
+        let input_listeners = InputListeners::register(&canvas, Rc::clone(&input))
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+        let pointer_lock = PointerLock::new(&canvas)
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+
+        let mut camera = FpsCamera::new(Vec3::new(0.0, 12.0, 0.0), 0.0, -0.2);
+        camera.move_speed = 15.0;
+        let camera_uniform = UniformBuffer::new(
+            &gpu,
+            &CameraUniform::new(projection(size) * camera.view_matrix()),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        let chunks = ChunkStreamer::new(CHUNK_SIZE, VIEW_DISTANCE, |coord| async move {
+            sleep(LOAD_LATENCY_MS).await;
+            chunk_geometry(coord)
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[camera_uniform.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[TerrainVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            chunks,
+            camera,
+            camera_uniform,
+            input,
+            _input_listeners: input_listeners,
+            _pointer_lock: pointer_lock,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, dt: f32) {
+        let mut input = self.input.borrow_mut();
+        let view = self.camera.update(dt, &input);
+        input.end_frame();
+
+        self.chunks.update(&self.gpu, self.camera.position);
+        self.camera_uniform.update(
+            self.gpu.queue(),
+            &CameraUniform::new(projection(self.size) * view),
+        );
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(FOG_COLOR),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+            self.chunks.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+    }
+}
+
+fn projection(size: (u32, u32)) -> Mat4 {
+    Mat4::perspective_rh(
+        60f32.to_radians(),
+        size.0 as f32 / size.1 as f32,
+        0.1,
+        VIEW_DISTANCE * 2.0,
+    )
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let mut last = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                // 탭이 오래 숨겨졌다 돌아와도 한 번에 멀리 가지 않도록 자른다
+                let now = now_secs();
+                state.update((now - last).min(0.1));
+                last = now;
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
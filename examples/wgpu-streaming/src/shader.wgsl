@@ -0,0 +1,51 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) height: f32,
+    @location(2) view_depth: f32,
+};
+
+const FOG_COLOR: vec3<f32> = vec3<f32>(0.62, 0.72, 0.82);
+const FOG_START: f32 = 40.0;
+const FOG_END: f32 = 80.0;
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.height = in.position.y;
+    out.view_depth = out.clip_position.w;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let n = normalize(in.normal);
+    // 낮은 곳은 풀, 가파른 곳은 바위, 높은 곳은 눈
+    let grass = vec3<f32>(0.3, 0.55, 0.2);
+    let rock = vec3<f32>(0.45, 0.4, 0.35);
+    let snow = vec3<f32>(0.95, 0.95, 0.97);
+    var base = mix(rock, grass, smoothstep(0.75, 0.9, n.y));
+    base = mix(base, snow, smoothstep(5.0, 7.0, in.height));
+
+    let light_dir = normalize(vec3<f32>(0.4, 0.8, 0.3));
+    let diffuse = max(dot(n, light_dir), 0.0);
+    let color = base * (0.3 + 0.7 * diffuse);
+    let fog = smoothstep(FOG_START, FOG_END, in.view_depth);
+    return vec4<f32>(mix(color, FOG_COLOR, fog), 1.0);
+}
@@ -0,0 +1,52 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+// lib.rs 의 MAX_HEIGHT 와 같다
+const MAX_HEIGHT: f32 = 24.0;
+const LIGHT_DIR: vec3<f32> = vec3<f32>(0.4, 0.8, 0.3);
+const AMBIENT: f32 = 0.25;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) height: f32,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.height = in.position.y / MAX_HEIGHT;
+    return out;
+}
+
+// 낮은 곳은 풀, 높은 곳은 바위와 눈. 가파른 비탈은 바위로 칠한다
+fn terrain_color(height: f32, slope: f32) -> vec3<f32> {
+    let grass = vec3<f32>(0.25, 0.45, 0.15);
+    let rock = vec3<f32>(0.45, 0.4, 0.35);
+    let snow = vec3<f32>(0.95, 0.95, 0.97);
+    var color = mix(grass, rock, smoothstep(0.3, 0.55, height));
+    color = mix(color, rock, smoothstep(0.15, 0.35, slope));
+    return mix(color, snow, smoothstep(0.7, 0.85, height));
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let normal = normalize(in.normal);
+    let slope = 1.0 - normal.y;
+    let diffuse = max(dot(normal, normalize(LIGHT_DIR)), 0.0);
+    let color = terrain_color(in.height, slope) * (AMBIENT + diffuse * (1.0 - AMBIENT));
+    return vec4<f32>(color, 1.0);
+}
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use glam::Vec4;
+use wgpu_common::{GpuContext, Sprite, SpriteBatch};
+
+// 이웃한 글리프가 샘플링될 때 번지지 않도록 띄우는 간격
+const GLYPH_PADDING: u32 = 1;
+
+// 글리프를 줄 단위로 채워 넣는 아틀라스 텍스처 한 장
+struct AtlasPage {
+    texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+    batch: SpriteBatch,
+    cursor: (u32, u32),
+    row_height: u32,
+}
+
+impl AtlasPage {
+    // 남은 공간에 width x height 영역을 할당한다. 자리가 없으면 None
+    fn allocate(&mut self, width: u32, height: u32, page_size: u32) -> Option<(u32, u32)> {
+        let width = width + GLYPH_PADDING;
+        let height = height + GLYPH_PADDING;
+
+        if self.cursor.0 + width > page_size {
+            self.cursor = (0, self.cursor.1 + self.row_height);
+            self.row_height = 0;
+        }
+        if self.cursor.1 + height > page_size || width > page_size {
+            return None;
+        }
+
+        let origin = self.cursor;
+        self.cursor.0 += width;
+        self.row_height = self.row_height.max(height);
+        Some(origin)
+    }
+}
+
+/// `R8Unorm` 글리프 비트맵을 모아 두는 아틀라스 페이지 묶음.
+///
+/// 페이지마다 `SpriteBatch` 를 하나씩 두고, 페이지 바인드 그룹은
+/// `@binding(0)` 텍스처, `@binding(1)` 필터링 샘플러다.
+pub(crate) struct GlyphAtlas {
+    gpu: Arc<GpuContext>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    pages: Vec<AtlasPage>,
+    page_size: u32,
+}
+
+impl GlyphAtlas {
+    pub(crate) fn new(gpu: &Arc<GpuContext>, page_size: u32) -> Self {
+        let device = gpu.device();
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Glyph Atlas Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Glyph Atlas Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self {
+            gpu: gpu.clone(),
+            bind_group_layout,
+            sampler,
+            pages: Vec::new(),
+            page_size,
+        }
+    }
+
+    pub(crate) fn bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    pub(crate) fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pages.iter().all(|page| page.batch.is_empty())
+    }
+
+    pub(crate) fn push(&mut self, page: usize, sprite: Sprite) {
+        self.pages[page].batch.push(sprite);
+    }
+
+    /// 쌓인 사각형을 GPU 에 올리고 비운다.
+    pub(crate) fn prepare(&mut self) {
+        for page in &mut self.pages {
+            page.batch.prepare(&self.gpu);
+            page.batch.clear();
+        }
+    }
+
+    /// 페이지마다 `group` 번 바인드 그룹을 바꿔 가며 그린다.
+    pub(crate) fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>, group: u32) {
+        for page in &self.pages {
+            render_pass.set_bind_group(group, &page.bind_group, &[]);
+            page.batch.draw(render_pass);
+        }
+    }
+
+    /// 비트맵을 올리고 `(페이지, uv 사각형)` 을 반환한다.
+    /// 마지막 페이지가 가득 차면 새 페이지를 만든다.
+    pub(crate) fn upload(
+        &mut self,
+        width: u32,
+        height: u32,
+        texels: &[u8],
+    ) -> Option<(usize, Vec4)> {
+        let page_size = self.page_size;
+        let allocation = self
+            .pages
+            .last_mut()
+            .and_then(|page| page.allocate(width, height, page_size));
+        let (page_index, origin) = match allocation {
+            Some(origin) => (self.pages.len() - 1, origin),
+            None => {
+                let mut page = self.create_page();
+                // 페이지보다 큰 글리프는 그리지 않는다
+                let origin = page.allocate(width, height, page_size)?;
+                self.pages.push(page);
+                (self.pages.len() - 1, origin)
+            }
+        };
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        self.gpu.queue().write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.pages[page_index].texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: origin.0,
+                    y: origin.1,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            texels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        let uv_rect = Vec4::new(
+            origin.0 as f32,
+            origin.1 as f32,
+            width as f32,
+            height as f32,
+        ) / page_size as f32;
+        Some((page_index, uv_rect))
+    }
+
+    fn create_page(&self) -> AtlasPage {
+        let texture = self.gpu.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("Glyph Atlas"),
+            size: wgpu::Extent3d {
+                width: self.page_size,
+                height: self.page_size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group = self
+            .gpu
+            .device()
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Glyph Atlas Bind Group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            });
+
+        AtlasPage {
+            texture,
+            bind_group,
+            batch: SpriteBatch::new(self.gpu.device(), 256),
+            cursor: (0, 0),
+            row_height: 0,
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    FormatSelector, FrameStats, FrameTimer, GpuContext, InitError, PerfCounter, Vertex,
+    VertexBuffer,
+};
+
+mod atlas;
+mod labels;
+pub mod sdf_text;
+pub mod stats_overlay;
+pub mod text;
+pub mod world_label;
+
+pub use sdf_text::SdfFontRenderer;
+pub use stats_overlay::StatsOverlay;
+pub use text::TextRenderer;
+pub use world_label::{BatchedText, WorldLabel};
+
+// FPS 카운터 글자 크기 (CSS 픽셀)
+const FONT_SIZE: f32 = 20.0;
+// SDF 제목이 커졌다 작아지는 범위 (CSS 픽셀)
+const TITLE_SIZE_RANGE: (f32, f32) = (24.0, 160.0);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex for ColorVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+}
+
+const VERTICES: &[ColorVertex] = &[
+    ColorVertex {
+        position: [0.0, 0.5, 0.0],
+        color: [1.0, 0.0, 0.0],
+    },
+    ColorVertex {
+        position: [-0.5, -0.5, 0.0],
+        color: [0.0, 1.0, 0.0],
+    },
+    ColorVertex {
+        position: [0.5, -0.5, 0.0],
+        color: [0.0, 0.0, 1.0],
+    },
+];
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: VertexBuffer<ColorVertex>,
+    text_renderer: TextRenderer,
+    sdf_renderer: SdfFontRenderer,
+    stats_overlay: StatsOverlay,
+    stats: FrameStats,
+    frame_timer: FrameTimer,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        // 버텍스 버퍼 생성. 매 프레임 회전시킨 꼭짓점을 다시 올린다
+        let vertex_buffer = VertexBuffer::writable(device, VERTICES);
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[VertexBuffer::<ColorVertex>::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let text_renderer = TextRenderer::new(
+            &gpu,
+            surface_config.format,
+            include_bytes!("DejaVuSansMono.ttf"),
+            size,
+        )
+        // 폰트 파일을 파싱하지 못하면 에셋 에러로 돌려준다
+        .map_err(|e| InitError::AssetLoadFailed(e.to_string()))?;
+        let sdf_renderer = SdfFontRenderer::new(
+            &gpu,
+            surface_config.format,
+            include_bytes!("DejaVuSansMono.ttf"),
+            size,
+        )
+        .map_err(|e| InitError::AssetLoadFailed(e.to_string()))?;
+        let mut stats_overlay = StatsOverlay::new(
+            &gpu,
+            surface_config.format,
+            include_bytes!("DejaVuSansMono.ttf"),
+            size,
+        )
+        .map_err(|e| InitError::AssetLoadFailed(e.to_string()))?;
+        stats_overlay.set_scale(web_sys::window().unwrap().device_pixel_ratio() as f32);
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            vertex_buffer,
+            text_renderer,
+            sdf_renderer,
+            stats_overlay,
+            stats: FrameStats::new(),
+            frame_timer: FrameTimer::new(),
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    // 삼각형을 z 축으로 조금씩 돌려서 버텍스 버퍼에 다시 올린다
+    fn update(&mut self) {
+        let angle = self.frame_timer.frame_count() as f32 * 0.01;
+        let (sin, cos) = angle.sin_cos();
+        let vertices: Vec<ColorVertex> = VERTICES
+            .iter()
+            .map(|vertex| {
+                let [x, y, z] = vertex.position;
+                ColorVertex {
+                    position: [x * cos - y * sin, x * sin + y * cos, z],
+                    ..*vertex
+                }
+            })
+            .collect();
+        self.stats.write_buffer(
+            self.gpu.queue(),
+            self.vertex_buffer.buffer(),
+            0,
+            bytemuck::cast_slice(&vertices),
+        );
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            // 드로우 호출을 PerfCounter 로 감싸서 센다
+            let mut counter = PerfCounter::new(&mut render_pass, &mut self.stats);
+            counter.set_pipeline(&self.render_pipeline);
+            counter.draw_with(|render_pass| self.vertex_buffer.draw(render_pass));
+
+            // 같은 패스의 마지막에 왼쪽 위 HUD 를 그린다
+            self.stats_overlay.render(&mut render_pass, &self.stats);
+        }
+
+        // 삼각형 위 오른쪽에 FPS 카운터를 덧그린다
+        let scale = web_sys::window().unwrap().device_pixel_ratio() as f32;
+        let frame_count = self.frame_timer.frame_count();
+        // 프레임마다 색이 천천히 바뀌도록 한다
+        let hue = (frame_count % 360) as f32 / 360.0 * std::f32::consts::TAU;
+        let color = [
+            0.75 + 0.25 * hue.cos(),
+            0.75 + 0.25 * (hue + 2.0).cos(),
+            0.75 + 0.25 * (hue + 4.0).cos(),
+            1.0,
+        ];
+        let text = format!("FPS: {:.1}\nFrame: {}", self.frame_timer.fps(), frame_count);
+        let (width, _) = self.text_renderer.measure(&text, FONT_SIZE * scale);
+        self.text_renderer.draw_string(
+            &text,
+            self.size.0 as f32 - width - 12.0 * scale,
+            12.0 * scale,
+            FONT_SIZE * scale,
+            color,
+        );
+        self.text_renderer.flush(&mut encoder, &view);
+
+        // 아래쪽 가운데에 크기가 계속 바뀌는 제목을 SDF 로 그린다. 한 번 만든 거리장을 늘여 쓴다
+        let pulse = 0.5 - 0.5 * (frame_count as f32 * 0.02).cos();
+        let title_size =
+            (TITLE_SIZE_RANGE.0 + (TITLE_SIZE_RANGE.1 - TITLE_SIZE_RANGE.0) * pulse) * scale;
+        let (title_width, title_height) = self.sdf_renderer.measure("wgpu", title_size);
+        self.sdf_renderer.draw_string(
+            "wgpu",
+            (self.size.0 as f32 - title_width) / 2.0,
+            self.size.1 as f32 - title_height - 12.0 * scale,
+            title_size,
+            [1.0, 1.0, 1.0, 0.9],
+        );
+        self.sdf_renderer.flush(&mut encoder, &view);
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.text_renderer.resize(new_size);
+        self.sdf_renderer.resize(new_size);
+        self.stats_overlay.resize(new_size);
+    }
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                state.frame_timer.begin_frame();
+                // 이전 프레임과의 간격을 기록하고 이번 프레임의 카운터를 비운다
+                let frame_time_ms = state.frame_timer.delta_secs() as f64 * 1000.0;
+                state.stats.end_frame(frame_time_ms);
+                state.stats.begin_frame();
+
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update();
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+
+                state.frame_timer.end_frame();
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
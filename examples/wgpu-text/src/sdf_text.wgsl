@@ -0,0 +1,43 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var t_atlas: texture_2d<f32>;
+@group(1) @binding(1)
+var s_atlas: sampler;
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4<f32>(in.position, 0.0, 1.0);
+    out.uv = in.uv;
+    out.color = in.color;
+    return out;
+}
+
+// Fragment shader
+// 아틀라스의 R 채널은 글리프 경계까지의 부호 있는 거리이다. 0.5 가 경계, 안쪽이 더 크다
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let sdf_value = textureSample(t_atlas, s_atlas, in.uv).r;
+    // 확대해도 경계 근처의 좁은 구간에서만 알파가 바뀌므로 가장자리가 흐려지지 않는다
+    let alpha = smoothstep(0.47, 0.53, sdf_value);
+    return vec4<f32>(in.color.rgb, in.color.a * alpha);
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use glam::{Vec2, Vec4};
+use wgpu_common::{CameraUniform, FrameStats, GpuContext, Sprite, SpriteBatch, UniformBuffer};
+
+use crate::text::{TextRenderer, screen_camera};
+
+// 글자 크기와 여백 (CSS 픽셀). set_scale 로 기기 픽셀 비율을 곱한다
+const FONT_SIZE: f32 = 14.0;
+const MARGIN: f32 = 8.0;
+const PADDING: f32 = 6.0;
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const BACKGROUND_COLOR: Vec4 = Vec4::new(0.0, 0.0, 0.0, 0.6);
+
+/// 왼쪽 위에 반투명한 배경과 함께 `FrameStats` 를 그리는 HUD.
+///
+/// 프레임 시간, 1% low, 드로우 호출 수, 업로드한 버퍼 크기를 보여 준다.
+/// 장면을 그린 렌더 패스의 마지막에 `render` 를 호출한다.
+pub struct StatsOverlay {
+    text: TextRenderer,
+    background_pipeline: wgpu::RenderPipeline,
+    background: SpriteBatch,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    gpu: Arc<GpuContext>,
+    screen_size: (u32, u32),
+    scale: f32,
+}
+
+impl StatsOverlay {
+    /// `format` 은 `render` 에 넘길 렌더 패스의 컬러 타깃 포맷이다.
+    pub fn new(
+        gpu: &Arc<GpuContext>,
+        format: wgpu::TextureFormat,
+        font_bytes: &[u8],
+        screen_size: (u32, u32),
+    ) -> Result<Self, &'static str> {
+        let device = gpu.device();
+        let text = TextRenderer::new(gpu, format, font_bytes, screen_size)?;
+
+        let camera_uniform = UniformBuffer::new(
+            gpu,
+            &CameraUniform::from(&screen_camera(screen_size)),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Stats Overlay Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("overlay.wgsl").into()),
+        });
+
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Stats Overlay Pipeline Layout"),
+            bind_group_layouts: &[camera_uniform.bind_group_layout()],
+            push_constant_ranges: &[],
+        });
+
+        let background_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Stats Overlay Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[SpriteBatch::vertex_layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            text,
+            background_pipeline,
+            background: SpriteBatch::new(device, 1),
+            camera_uniform,
+            gpu: gpu.clone(),
+            screen_size,
+            scale: 1.0,
+        })
+    }
+
+    /// 캔버스 크기가 바뀌면 호출한다.
+    pub fn resize(&mut self, screen_size: (u32, u32)) {
+        if screen_size == self.screen_size {
+            return;
+        }
+
+        self.screen_size = screen_size;
+        self.text.resize(screen_size);
+        self.camera_uniform.update(
+            self.gpu.queue(),
+            &CameraUniform::from(&screen_camera(screen_size)),
+        );
+    }
+
+    /// 글자 크기와 여백에 곱할 배율. 보통 `device_pixel_ratio` 를 넘긴다.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    /// `render_pass` 의 컬러 타깃 왼쪽 위에 통계를 그린다.
+    /// 렌더 패스를 연 채로 글리프와 버텍스를 올리므로 패스를 제출하기 전에 호출하면 된다.
+    pub fn render(&mut self, render_pass: &mut wgpu::RenderPass<'_>, stats: &FrameStats) {
+        let text = format!(
+            "frame   {:>7.2} ms\n1% low  {:>7.2} ms\ndraws   {:>7}\nupload  {:>10}",
+            stats.frame_time_ms(),
+            stats.low_1_percent_ms(),
+            stats.draw_calls(),
+            format_bytes(stats.uploaded_bytes()),
+        );
+
+        let font_size = FONT_SIZE * self.scale;
+        let margin = MARGIN * self.scale;
+        let padding = PADDING * self.scale;
+        let (width, height) = self.text.measure(&text, font_size);
+
+        self.text.draw_string(
+            &text,
+            margin + padding,
+            margin + padding,
+            font_size,
+            TEXT_COLOR,
+        );
+        self.text.prepare();
+
+        // 배경은 y 가 위로 증가하는 좌표계이므로 화면 높이에서 뺀다
+        let size = Vec2::new(width, height) + Vec2::splat(padding * 2.0);
+        let center = Vec2::new(
+            margin + size.x / 2.0,
+            self.screen_size.1 as f32 - margin - size.y / 2.0,
+        );
+        self.background.clear();
+        self.background.push(Sprite {
+            tint: BACKGROUND_COLOR,
+            ..Sprite::new(center, size)
+        });
+        self.background.prepare(&self.gpu);
+
+        render_pass.set_pipeline(&self.background_pipeline);
+        render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+        self.background.draw(render_pass);
+        self.text.draw(render_pass);
+    }
+}
+
+// 1024 단위로 B, KiB, MiB 를 고른다
+fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f < KIB {
+        format!("{} B", bytes)
+    } else if bytes_f < KIB * KIB {
+        format!("{:.1} KiB", bytes_f / KIB)
+    } else {
+        format!("{:.1} MiB", bytes_f / (KIB * KIB))
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use glam::{Vec2, Vec4};
+use wgpu_common::{CameraUniform, GpuContext, OrthoCamera, Sprite, SpriteBatch, UniformBuffer};
+
+use crate::atlas::GlyphAtlas;
+
+/// 아틀라스 페이지 한 장의 한 변 크기 (픽셀)
+pub const PAGE_SIZE: u32 = 512;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    character: char,
+    size_px: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Glyph {
+    // 공백처럼 그릴 픽셀이 없는 글리프는 None
+    atlas: Option<(usize, Vec4)>,
+    size: Vec2,
+    // 펜 위치(베이스라인)에서 비트맵 왼쪽 아래까지의 거리
+    offset: Vec2,
+    advance: f32,
+}
+
+/// `fontdue` 로 TTF 글리프를 래스터라이즈해서 아틀라스 텍스처에 모아 두고,
+/// 문자열을 텍스처를 입힌 사각형 배치로 그린다.
+///
+/// 매 프레임 `draw_string` 으로 문자열을 쌓은 뒤 `flush` 로 한 번에 그린다.
+/// 좌표는 캔버스 왼쪽 위가 (0, 0) 인 픽셀 좌표이다.
+pub struct TextRenderer {
+    gpu: Arc<GpuContext>,
+    font: fontdue::Font,
+    render_pipeline: wgpu::RenderPipeline,
+    camera_uniform: UniformBuffer<CameraUniform>,
+    atlas: GlyphAtlas,
+    glyphs: HashMap<GlyphKey, Glyph>,
+    screen_size: (u32, u32),
+}
+
+impl TextRenderer {
+    /// `format` 은 `flush` 에 넘길 출력 텍스처의 포맷이다.
+    pub fn new(
+        gpu: &Arc<GpuContext>,
+        format: wgpu::TextureFormat,
+        font_bytes: &[u8],
+        screen_size: (u32, u32),
+    ) -> Result<Self, &'static str> {
+        let device = gpu.device();
+        let font = fontdue::Font::from_bytes(font_bytes, fontdue::FontSettings::default())?;
+
+        let camera_uniform = UniformBuffer::new(
+            gpu,
+            &CameraUniform::from(&screen_camera(screen_size)),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        let atlas = GlyphAtlas::new(gpu, PAGE_SIZE);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Text Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("text.wgsl").into()),
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Text Pipeline Layout"),
+                bind_group_layouts: &[
+                    camera_uniform.bind_group_layout(),
+                    atlas.bind_group_layout(),
+                ],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Text Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[SpriteBatch::vertex_layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu: gpu.clone(),
+            font,
+            render_pipeline,
+            camera_uniform,
+            atlas,
+            glyphs: HashMap::new(),
+            screen_size,
+        })
+    }
+
+    /// 캔버스 크기가 바뀌면 호출한다.
+    pub fn resize(&mut self, screen_size: (u32, u32)) {
+        if screen_size == self.screen_size {
+            return;
+        }
+
+        self.screen_size = screen_size;
+        self.camera_uniform.update(
+            self.gpu.queue(),
+            &CameraUniform::from(&screen_camera(screen_size)),
+        );
+    }
+
+    /// 지금까지 할당된 아틀라스 페이지 수
+    pub fn page_count(&self) -> usize {
+        self.atlas.page_count()
+    }
+
+    /// `(x, y)` 를 왼쪽 위로 하는 위치에 문자열을 쌓는다. `\n` 에서 줄을 바꾼다.
+    pub fn draw_string(&mut self, text: &str, x: f32, y: f32, size_px: f32, color: [f32; 4]) {
+        let size_px = size_px.round().max(1.0) as u32;
+        let line_metrics = self.font.horizontal_line_metrics(size_px as f32);
+        let (ascent, line_height) = line_metrics
+            .map(|metrics| (metrics.ascent, metrics.new_line_size))
+            .unwrap_or((size_px as f32, size_px as f32));
+
+        let tint = Vec4::from_array(color);
+        let mut pen = Vec2::new(x, y + ascent);
+
+        for character in text.chars() {
+            if character == '\n' {
+                pen = Vec2::new(x, pen.y + line_height);
+                continue;
+            }
+
+            let glyph = self.glyph(character, size_px);
+            if let Some((page, uv_rect)) = glyph.atlas {
+                // 픽셀 좌표는 아래로 갈수록 y 가 커지지만 SpriteBatch 는 위쪽이 +y 이다
+                let left = pen.x + glyph.offset.x;
+                let bottom = pen.y - glyph.offset.y;
+                let center = Vec2::new(
+                    left + glyph.size.x / 2.0,
+                    self.screen_size.1 as f32 - (bottom - glyph.size.y / 2.0),
+                );
+
+                self.atlas.push(
+                    page,
+                    Sprite {
+                        uv_rect,
+                        tint,
+                        ..Sprite::new(center, glyph.size)
+                    },
+                );
+            }
+
+            pen.x += glyph.advance;
+        }
+    }
+
+    /// `draw_string` 으로 그렸을 때 문자열이 차지하는 `(너비, 높이)` (픽셀)
+    pub fn measure(&mut self, text: &str, size_px: f32) -> (f32, f32) {
+        let size_px = size_px.round().max(1.0) as u32;
+        let line_height = self
+            .font
+            .horizontal_line_metrics(size_px as f32)
+            .map_or(size_px as f32, |metrics| metrics.new_line_size);
+
+        let mut width: f32 = 0.0;
+        let mut line_count = 0;
+        for line in text.split('\n') {
+            let line_width: f32 = line
+                .chars()
+                .map(|character| self.glyph(character, size_px).advance)
+                .sum();
+            width = width.max(line_width);
+            line_count += 1;
+        }
+        (width, line_height * line_count as f32)
+    }
+
+    /// 쌓인 문자열을 `target_view` 위에 덧그리고 비운다.
+    pub fn flush(&mut self, encoder: &mut wgpu::CommandEncoder, target_view: &wgpu::TextureView) {
+        if self.atlas.is_empty() {
+            return;
+        }
+
+        self.prepare();
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Text Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        self.draw(&mut render_pass);
+    }
+
+    /// 쌓인 문자열을 GPU 에 올리고 비운다. 이미 열려 있는 렌더 패스에 `draw` 로 그릴 때 사용한다.
+    pub fn prepare(&mut self) {
+        self.atlas.prepare();
+    }
+
+    /// 마지막 `prepare` 로 올린 문자열을 그린다. 파이프라인과 바인드 그룹은 이 안에서 설정한다.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, self.camera_uniform.bind_group(), &[]);
+        self.atlas.draw(render_pass, 1);
+    }
+
+    // 캐시에 없는 글리프는 래스터라이즈해서 아틀라스에 올린다
+    fn glyph(&mut self, character: char, size_px: u32) -> Glyph {
+        let key = GlyphKey { character, size_px };
+        if let Some(glyph) = self.glyphs.get(&key) {
+            return *glyph;
+        }
+
+        let (metrics, coverage) = self.font.rasterize(character, size_px as f32);
+        let (width, height) = (metrics.width as u32, metrics.height as u32);
+
+        let atlas = if width == 0 || height == 0 {
+            None
+        } else {
+            self.atlas.upload(width, height, &coverage)
+        };
+
+        let glyph = Glyph {
+            atlas,
+            size: Vec2::new(width as f32, height as f32),
+            offset: Vec2::new(metrics.xmin as f32, metrics.ymin as f32),
+            advance: metrics.advance_width,
+        };
+        self.glyphs.insert(key, glyph);
+        glyph
+    }
+}
+
+// 왼쪽 아래가 (0, 0), 오른쪽 위가 (width, height) 인 픽셀 좌표계
+pub(crate) fn screen_camera(size: (u32, u32)) -> OrthoCamera {
+    OrthoCamera::new(0.0, size.0 as f32, 0.0, size.1 as f32)
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use glam::{Mat4, Vec2, Vec3};
+use wgpu_common::{GpuContext, world_to_screen};
+
+use crate::text::TextRenderer;
+
+/// 3D 장면의 한 점 위에 떠 있는 글자
+#[derive(Clone, Debug)]
+pub struct WorldLabel {
+    pub world_position: Vec3,
+    pub text: String,
+    pub color: [f32; 4],
+    /// `BatchedText::font_size` 에 곱하는 배율
+    pub scale: f32,
+}
+
+/// 월드 좌표에 붙은 라벨을 화면에 투영해서 3D 장면 위에 글자로 덧그린다.
+///
+/// 매 프레임 `push` 로 라벨을 쌓고 장면을 그린 뒤 `flush` 를 부르면, 라벨마다 위치를 픽셀 좌표로
+/// 바꿔 가로 가운데, 세로 아래쪽이 그 점에 오도록 `TextRenderer` 로 그린다. 깊이가 [0, 1] 을
+/// 벗어나는 라벨은 버리고, 가까운 라벨이 위에 오도록 먼 것부터 그린다. 깊이 테스트는 하지 않으므로
+/// 라벨은 항상 지오메트리 위에 보인다.
+pub struct BatchedText {
+    text_renderer: TextRenderer,
+    labels: Vec<WorldLabel>,
+    font_size: f32,
+    screen_size: (u32, u32),
+}
+
+impl BatchedText {
+    /// `format` 은 `flush` 에 넘길 출력 텍스처의 포맷이다.
+    pub fn new(
+        gpu: &Arc<GpuContext>,
+        format: wgpu::TextureFormat,
+        font_bytes: &[u8],
+        screen_size: (u32, u32),
+    ) -> Result<Self, &'static str> {
+        Ok(Self {
+            text_renderer: TextRenderer::new(gpu, format, font_bytes, screen_size)?,
+            labels: Vec::new(),
+            font_size: 16.0,
+            screen_size,
+        })
+    }
+
+    /// 캔버스 크기가 바뀌면 호출한다.
+    pub fn resize(&mut self, screen_size: (u32, u32)) {
+        self.screen_size = screen_size;
+        self.text_renderer.resize(screen_size);
+    }
+
+    /// `scale` 이 1 인 라벨의 글자 크기 (픽셀)
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+
+    pub fn set_font_size(&mut self, font_size: f32) {
+        self.font_size = font_size;
+    }
+
+    /// 다음 `flush` 에 그릴 라벨을 쌓는다.
+    pub fn push(&mut self, label: WorldLabel) {
+        self.labels.push(label);
+    }
+
+    /// 쌓인 라벨을 `view_proj` 로 투영해서 `target_view` 위에 덧그리고 비운다.
+    pub fn flush(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        target_view: &wgpu::TextureView,
+        view_proj: Mat4,
+    ) {
+        let screen_size = Vec2::new(self.screen_size.0 as f32, self.screen_size.1 as f32);
+        let mut visible: Vec<(Vec3, WorldLabel)> = self
+            .labels
+            .drain(..)
+            .filter_map(|label| {
+                world_to_screen(view_proj, label.world_position, screen_size)
+                    .map(|screen| (screen, label))
+            })
+            .collect();
+        // 먼 것부터 그려야 가까운 라벨이 위에 덮인다
+        visible.sort_by(|a, b| b.0.z.total_cmp(&a.0.z));
+
+        for (screen, label) in visible {
+            let size_px = self.font_size * label.scale;
+            let (width, height) = self.text_renderer.measure(&label.text, size_px);
+            // 글리프가 텍셀 경계에 맞도록 픽셀 단위로 반올림한다
+            self.text_renderer.draw_string(
+                &label.text,
+                (screen.x - width / 2.0).round(),
+                (screen.y - height).round(),
+                size_px,
+                label.color,
+            );
+        }
+        self.text_renderer.flush(encoder, target_view);
+    }
+}
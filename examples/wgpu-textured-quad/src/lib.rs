@@ -0,0 +1,343 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    AssetLoader, BindGroupBuilder, CompressedTextureLoader, FormatSelector, GpuContext, InitError,
+    Mesh, SrgbMode, Texture, Vertex,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct TexturedVertex {
+    position: [f32; 3],
+    uv: [f32; 2],
+}
+
+impl Vertex for TexturedVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2];
+}
+
+// 텍스처 좌표는 왼쪽 위가 (0, 0) 이다
+const VERTICES: &[TexturedVertex] = &[
+    TexturedVertex {
+        position: [-0.5, 0.5, 0.0],
+        uv: [0.0, 0.0],
+    },
+    TexturedVertex {
+        position: [-0.5, -0.5, 0.0],
+        uv: [0.0, 1.0],
+    },
+    TexturedVertex {
+        position: [0.5, -0.5, 0.0],
+        uv: [1.0, 1.0],
+    },
+    TexturedVertex {
+        position: [0.5, 0.5, 0.0],
+        uv: [1.0, 0.0],
+    },
+];
+
+const INDICES: &[u16] = &[0, 1, 2, 2, 3, 0];
+
+/// 사각형에 입힐 텍스처
+enum TextureSource<'a> {
+    /// 내장된 체커 PNG
+    Embedded,
+    /// `fetch` 로 받는 이미지
+    Url(&'a str),
+    /// 내장된 BC1(DXT1) 체커 DDS
+    Dds,
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    mesh: Mesh,
+    texture_bind_group: wgpu::BindGroup,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str, source: TextureSource<'_>) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                // 어댑터가 BC 압축을 지원할 때만 켠다
+                required_features: adapter.features() & CompressedTextureLoader::FEATURES,
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        // 메시 생성
+        let mesh = Mesh::new(&gpu, VERTICES, INDICES);
+
+        // 텍스처 로드
+        let texture = match source {
+            TextureSource::Embedded => {
+                Texture::from_bytes(&gpu, include_bytes!("checker.png"), "checker")?
+            }
+            TextureSource::Url(url) => AssetLoader::new().load_texture(url, &gpu).await?,
+            TextureSource::Dds => {
+                let loader = CompressedTextureLoader::new(&gpu);
+                console::log_1(
+                    &if loader.is_supported() {
+                        "BC compression supported, uploading compressed blocks"
+                    } else {
+                        "BC compression unavailable, decompressing to RGBA8"
+                    }
+                    .into(),
+                );
+                loader.load(
+                    &gpu,
+                    include_bytes!("checker.dds"),
+                    "checker dds",
+                    SrgbMode::Enabled,
+                )?
+            }
+        };
+
+        let (texture_bind_group_layout, texture_bind_group) = BindGroupBuilder::new()
+            .texture(0, texture.view(), wgpu::ShaderStages::FRAGMENT)
+            .sampler(1, texture.sampler(), wgpu::ShaderStages::FRAGMENT)
+            .build(device, "Texture Bind Group");
+
+        // 셰이더 생성
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        // 렌더 파이프라인 생성
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[&texture_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[TexturedVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            mesh,
+            texture_bind_group,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, &self.texture_bind_group, &[]);
+            self.mesh.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+    }
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(
+        State::new(canvas_id, TextureSource::Embedded).await?,
+    ));
+    start_render_loop(state);
+    Ok(())
+}
+
+/// `texture_url` 의 이미지를 `fetch` 로 받아서 사각형에 입힌다.
+#[wasm_bindgen]
+pub async fn run_with_texture_url(canvas_id: &str, texture_url: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = State::new(canvas_id, TextureSource::Url(texture_url)).await?;
+    start_render_loop(Rc::new(RefCell::new(state)));
+    Ok(())
+}
+
+/// 내장된 DDS 파일을 BC1 텍스처로 올린다. BC 압축을 지원하지 않으면 `Rgba8` 로 풀어서 올린다.
+#[wasm_bindgen]
+pub async fn run_dds(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = State::new(canvas_id, TextureSource::Dds).await?;
+    start_render_loop(Rc::new(RefCell::new(state)));
+    Ok(())
+}
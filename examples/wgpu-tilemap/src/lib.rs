@@ -0,0 +1,360 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::Vec2;
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    FormatSelector, GpuContext, InitError, InputListeners, InputState, Texture, Tilemap,
+};
+
+// 타일셋의 타일 한 칸 크기(픽셀)와 가로, 세로 타일 수
+const TILE_SIZE: u32 = 16;
+const TILESET_COLUMNS: u32 = 4;
+const TILESET_ROWS: u32 = 2;
+// 맵의 가로, 세로 칸 수
+const MAP_SIZE: (u32, u32) = (96, 64);
+// 초당 스크롤 거리(월드 픽셀)와 확대 배율 범위
+const SCROLL_SPEED: f32 = 240.0;
+const MIN_ZOOM: f32 = 1.0;
+const MAX_ZOOM: f32 = 8.0;
+
+// 타일셋의 타일 번호
+const GRASS: u16 = 0;
+const DIRT: u16 = 1;
+const WATER: u16 = 2;
+const SAND: u16 = 3;
+const STONE: u16 = 4;
+const FLOWERS: u16 = 5;
+const TREE: u16 = 6;
+const WALL: u16 = 7;
+
+// 좌표로 정해지는 0..1 의 의사 난수
+fn hash(x: u32, y: u32) -> f32 {
+    let mut h = x.wrapping_mul(374_761_393) ^ y.wrapping_mul(668_265_263);
+    h = (h ^ (h >> 13)).wrapping_mul(1_274_126_177);
+    (h ^ (h >> 16)) as f32 / u32::MAX as f32
+}
+
+// 타일마다 기본색에 텍셀마다 밝기를 조금씩 흔들고, 몇 가지는 무늬를 더한다
+fn tileset_image() -> image::RgbaImage {
+    image::RgbaImage::from_fn(
+        TILE_SIZE * TILESET_COLUMNS,
+        TILE_SIZE * TILESET_ROWS,
+        |x, y| {
+            let tile = (y / TILE_SIZE * TILESET_COLUMNS + x / TILE_SIZE) as u16;
+            let (lx, ly) = (x % TILE_SIZE, y % TILE_SIZE);
+            let center = (lx as f32 - 7.5).hypot(ly as f32 - 7.5);
+            let base: [f32; 3] = match tile {
+                GRASS => [0.3, 0.6, 0.2],
+                DIRT => [0.5, 0.35, 0.2],
+                WATER if (lx + ly * 3) % 8 == 0 => [0.5, 0.7, 0.95],
+                WATER => [0.2, 0.4, 0.8],
+                SAND => [0.85, 0.78, 0.5],
+                STONE if lx == 0 || ly == 0 => [0.35, 0.35, 0.38],
+                STONE => [0.55, 0.55, 0.58],
+                FLOWERS if hash(lx, ly) > 0.9 => [0.95, 0.85, 0.2],
+                FLOWERS => [0.3, 0.6, 0.2],
+                TREE if center < 6.5 => [0.1, 0.4, 0.15],
+                TREE => [0.3, 0.6, 0.2],
+                // 벽돌: 4 픽셀마다 줄눈, 줄마다 반 칸씩 엇갈린다
+                _ if ly % 4 == 0 || (lx + (ly / 4 % 2) * 4) % 8 == 0 => [0.75, 0.72, 0.65],
+                _ => [0.6, 0.25, 0.2],
+            };
+            let shade = 0.9 + hash(x, y) * 0.2;
+            let [r, g, b] = base.map(|c| ((c * shade).min(1.0) * 255.0) as u8);
+            image::Rgba([r, g, b, 255])
+        },
+    )
+}
+
+// 사인파를 겹친 높이로 물, 모래, 풀, 흙, 돌을 나누고 풀밭에는 꽃과 나무를 흩뿌린다
+fn generate_map() -> Vec<u16> {
+    let (width, height) = MAP_SIZE;
+    (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            let (fx, fy) = (x as f32, y as f32);
+            let h = (fx * 0.15).sin() + (fy * 0.11).cos() + ((fx + fy) * 0.07).sin();
+            match h {
+                h if h < -0.8 => WATER,
+                h if h < -0.5 => SAND,
+                h if h < 0.9 => match hash(x, y) {
+                    r if r > 0.93 => TREE,
+                    r if r > 0.85 => FLOWERS,
+                    _ => GRASS,
+                },
+                h if h < 1.5 => DIRT,
+                _ => STONE,
+            }
+        })
+        .collect()
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    tilemap: Tilemap,
+    input: Rc<RefCell<InputState>>,
+    _input_listeners: InputListeners,
+    // 화면 왼쪽 위의 월드 좌표와 확대 배율
+    offset: Vec2,
+    zoom: f32,
+    // 마우스로 칠할 타일
+    brush: u16,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let tileset = Texture::from_image(
+            &gpu,
+            &image::DynamicImage::ImageRgba8(tileset_image()),
+            "Tileset",
+        );
+        let tilemap = Tilemap::new(
+            &gpu,
+            surface_config.format,
+            &tileset,
+            TILE_SIZE,
+            MAP_SIZE,
+            generate_map(),
+        );
+
+        let input = Rc::new(RefCell::new(InputState::default()));
+        let input_listeners = InputListeners::register(&canvas, Rc::clone(&input))
+            .map_err(|e| InitError::EventListenerFailed(format!("{:?}", e)))?;
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            tilemap,
+            input,
+            _input_listeners: input_listeners,
+            offset: Vec2::ZERO,
+            zoom: 3.0,
+            brush: WALL,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    fn update(&mut self, delta_secs: f32) {
+        let mut direction = Vec2::ZERO;
+        let mut paint_at = None;
+        {
+            let input = self.input.borrow();
+            if input.is_key_down("KeyW") || input.is_key_down("ArrowUp") {
+                direction.y -= 1.0;
+            }
+            if input.is_key_down("KeyS") || input.is_key_down("ArrowDown") {
+                direction.y += 1.0;
+            }
+            if input.is_key_down("KeyA") || input.is_key_down("ArrowLeft") {
+                direction.x -= 1.0;
+            }
+            if input.is_key_down("KeyD") || input.is_key_down("ArrowRight") {
+                direction.x += 1.0;
+            }
+            // 숫자 키 1~8 로 칠할 타일을 고른다
+            for tile in 0..(TILESET_COLUMNS * TILESET_ROWS) as u16 {
+                if input.is_key_down(&format!("Digit{}", tile + 1)) {
+                    self.brush = tile;
+                }
+            }
+
+            // 휠을 굴리면 화면 가운데를 기준으로 확대, 축소한다
+            let scroll = input.scroll_delta().1;
+            if scroll != 0.0 {
+                let center = Vec2::new(self.size.0 as f32, self.size.1 as f32) * 0.5;
+                let world_center = self.offset + center / self.zoom;
+                self.zoom = (self.zoom * (-scroll * 0.002).exp()).clamp(MIN_ZOOM, MAX_ZOOM);
+                self.offset = world_center - center / self.zoom;
+            }
+
+            // 마우스 위치는 CSS 픽셀이므로 렌더링 픽셀로 바꾼다
+            if input.is_mouse_button_down(0) {
+                let pixel_ratio = web_sys::window().unwrap().device_pixel_ratio() as f32;
+                paint_at = Some(Vec2::from(input.mouse_position()) * pixel_ratio);
+            }
+        }
+
+        self.offset += direction * SCROLL_SPEED * delta_secs;
+        self.tilemap.set_view(self.offset, self.zoom);
+
+        if let Some((x, y)) = paint_at.and_then(|screen| self.tilemap.tile_at(screen))
+            && self.tilemap.tile(x, y) != Some(self.brush)
+        {
+            self.tilemap.set_tile(x, y, self.brush);
+        }
+
+        self.input.borrow_mut().end_frame();
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.05,
+                            g: 0.05,
+                            b: 0.08,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            self.tilemap.draw(&mut render_pass);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let mut last_frame = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                let now = now_secs();
+                state.update(now - last_frame);
+                last_frame = now;
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+struct TimeUniform {
+    elapsed: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> time: TimeUniform;
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) vec4<f32> {
+    var pos = array<vec2<f32>, 3>(
+        vec2<f32>( 0.0,  0.5),
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>( 0.5, -0.5)
+    );
+
+    return vec4<f32>(pos[in_vertex_index], 0.0, 1.0);
+}
+
+// Fragment shader
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    // 채널마다 위상을 다르게 해서 색이 천천히 바뀌도록 한다
+    let t = time.elapsed;
+    let color = 0.5 + 0.5 * cos(vec3<f32>(t, t + 2.0, t + 4.0));
+    return vec4<f32>(color, 1.0);
+}
@@ -0,0 +1,47 @@
+use std::env;
+
+const SHADER_PATH: &str = "src/shader.wgsl";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SHADER_PATH);
+
+    // 웹에서는 SPIR-V 를 쓸 수 없으므로 spirv 기능을 켠 네이티브 빌드에서만 컴파일한다
+    let spirv_enabled = env::var_os("CARGO_FEATURE_SPIRV").is_some();
+    let is_wasm = env::var("CARGO_CFG_TARGET_ARCH").is_ok_and(|arch| arch == "wasm32");
+    if spirv_enabled && !is_wasm {
+        #[cfg(feature = "spirv")]
+        compile_spirv();
+    }
+}
+
+#[cfg(feature = "spirv")]
+fn compile_spirv() {
+    use std::fs;
+    use std::path::PathBuf;
+
+    let source = fs::read_to_string(SHADER_PATH).expect("Failed to read shader source");
+    let module = naga::front::wgsl::parse_str(&source)
+        .unwrap_or_else(|e| panic!("{}", e.emit_to_string(&source)));
+
+    let info = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .unwrap_or_else(|e| panic!("{}", e.emit_to_string(&source)));
+
+    // wgpu 는 SPIR-V 를 읽을 때 Y 를 다시 뒤집지 않고, Vulkan 백엔드도 뷰포트로 뒤집으므로
+    // 여기서 뒤집으면 삼각형이 위아래로 뒤집혀서 컬링된다
+    let options = naga::back::spv::Options {
+        flags: naga::back::spv::Options::default()
+            .flags
+            .difference(naga::back::spv::WriterFlags::ADJUST_COORDINATE_SPACE),
+        ..Default::default()
+    };
+    let words =
+        naga::back::spv::write_vec(&module, &info, &options, None).expect("Failed to write SPIR-V");
+
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("shader.spv"), bytes).expect("Failed to write shader.spv");
+}
@@ -1,29 +1,302 @@
-use std::cell::RefCell;
+//! 화면에 삼각형 하나를 그리는 예제.
+//!
+//! 렌더링 해상도는 [`Resolution`] 으로 정한다. 기본값인 `Auto` 는 캔버스의 CSS 크기에
+//! 기기 픽셀 비율과 렌더 배율을 곱한 크기로 그리므로 항상 선명하지만, 창이 커지면
+//! 그만큼 픽셀 수가 늘어서 프래그먼트 비용도 커진다. `Fixed` 는 캔버스 크기와 상관없이
+//! 정한 해상도로 그리고 브라우저가 그 결과를 `object-fit: contain` 으로 늘려서 보여 준다.
+//! 비용이 일정하고 결과 이미지의 크기를 예측할 수 있지만, 캔버스가 해상도보다 크면
+//! 흐릿하거나 계단이 보이고 비율이 다르면 위아래나 좌우에 여백이 생긴다.
+
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::sync::Arc;
 
+use glam::{Mat4, Vec3};
 use wasm_bindgen::prelude::*;
-use web_sys::{HtmlCanvasElement, console};
+use web_sys::console;
+#[cfg(feature = "native")]
+use wgpu_common::HotShader;
+#[cfg(target_arch = "wasm32")]
+use wgpu_common::ShaderCache;
+#[cfg(all(not(target_arch = "wasm32"), feature = "spirv"))]
+use wgpu_common::SpirVShader;
+use wgpu_common::web::{RenderScale, download_bytes};
+#[cfg(target_arch = "wasm32")]
+use wgpu_common::web::{
+    ResizeObserverHandle, get_canvas, observe_resize, physical_size, start_animation_loop,
+};
+use wgpu_common::{
+    AntiAliasMode, BlendMode, CaptureError, DepthTexture, FormatSelector, FrameTimer, FxaaEffect,
+    GpuContext, GpuTimer, HdrRenderTarget, InitError, MemoryStats, MsaaConfig, PipelineCache,
+    PipelineKey, PipelineLayoutCache, PipelineStats, PipelineStatsQuery, PostProcessEffect,
+    RenderMode, ShaderLoader, SrgbMode, TaaPass, TextureReadback, ToneMapOperator, ToneMapPass,
+    UniformBuffer, WgpuDebugLayer, capture, linear_to_srgb, print_limits_diff,
+};
+
+// 파이프라인 캐시 벤치마크에서 돌려가며 사용하는 블렌드 상태
+#[cfg(target_arch = "wasm32")]
+const BENCHMARK_BLEND_STATES: [Option<wgpu::BlendState>; 4] = [
+    Some(wgpu::BlendState::REPLACE),
+    Some(wgpu::BlendState::ALPHA_BLENDING),
+    Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+    None,
+];
+#[cfg(target_arch = "wasm32")]
+const BENCHMARK_PIPELINE_COUNT: usize = 100;
+// GpuTimer 에서 메인 렌더 패스를 가리키는 이름
+const MAIN_PASS_LABEL: &str = "main";
+
+thread_local! {
+    // 가장 최근에 시작된 State. JS 에서 호출하는 함수들이 사용한다.
+    static STATE: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+
+    // set_render_scale 로 바뀐 값은 다음 리사이즈 검사 때 State 에 반영된다
+    static RENDER_SCALE: Cell<RenderScale> = const { Cell::new(RenderScale::Native) };
+
+    // set_background_color 로 바뀐 값은 다음 프레임의 render 에서 읽는다
+    static BACKGROUND_COLOR: Cell<wgpu::Color> = const {
+        Cell::new(wgpu::Color {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+            a: 1.0,
+        })
+    };
+}
 
-struct State {
-    device: wgpu::Device,
-    queue: wgpu::Queue,
+/// `State` 를 만들 때 정하고 실행 중에는 바꾸지 않는 렌더링 설정
+#[derive(Clone, Copy, Debug, Default)]
+struct RenderOptions {
+    anti_alias: AntiAliasMode,
+    /// 장면을 `Rgba16Float` 텍스처에 그린 뒤 톤 매핑해서 출력한다
+    hdr: bool,
+    srgb_mode: SrgbMode,
+}
+
+// 장면을 서피스 대신 `source` 에 그린 뒤 서피스로 옮기면서 적용하는 안티 에일리어싱
+struct PostAntiAlias {
+    source: wgpu::TextureView,
+    pass: PostAntiAliasPass,
+}
+
+enum PostAntiAliasPass {
+    Fxaa(FxaaEffect),
+    Taa(TaaPass),
+}
+
+impl PostAntiAlias {
+    // `mode` 가 후처리가 필요 없는 모드면 None
+    fn new(
+        device: &wgpu::Device,
+        mode: AntiAliasMode,
+        format: wgpu::TextureFormat,
+        size: (u32, u32),
+    ) -> Option<Self> {
+        let pass = match mode {
+            AntiAliasMode::Fxaa => PostAntiAliasPass::Fxaa(FxaaEffect::new(device, format)),
+            AntiAliasMode::Taa => {
+                PostAntiAliasPass::Taa(TaaPass::new(device, format, size.0, size.1))
+            }
+            AntiAliasMode::None | AntiAliasMode::Msaa(_) => return None,
+        };
+        Some(Self {
+            source: create_post_anti_alias_source(device, format, size),
+            pass,
+        })
+    }
+
+    fn resize(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat, size: (u32, u32)) {
+        self.source = create_post_anti_alias_source(device, format, size);
+        if let PostAntiAliasPass::Taa(taa) = &mut self.pass {
+            taa.resize(size.0, size.1);
+        }
+    }
+
+    // TAA 는 이번 프레임의 지터만큼 메인 패스의 투영 행렬을 옮긴다
+    fn jitter_projection(&self, projection: Mat4) -> Mat4 {
+        match &self.pass {
+            PostAntiAliasPass::Taa(taa) => taa.jitter_projection(projection),
+            PostAntiAliasPass::Fxaa(_) => projection,
+        }
+    }
+
+    fn apply(
+        &mut self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        output: &wgpu::TextureView,
+    ) {
+        match &mut self.pass {
+            PostAntiAliasPass::Fxaa(fxaa) => fxaa.apply(encoder, &self.source, output),
+            PostAntiAliasPass::Taa(taa) => taa.apply(queue, encoder, &self.source, output),
+        }
+    }
+}
+
+fn create_post_anti_alias_source(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    (width, height): (u32, u32),
+) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Anti-Alias Source Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// 서피스 텍스처의 크기를 정하는 방법
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Resolution {
+    /// 캔버스나 창의 크기를 따라간다
+    #[default]
+    Auto,
+    /// 창 크기와 상관없이 이 크기로 그린다
+    Fixed(u32, u32),
+}
+
+/// 화면에 삼각형 하나를 그리는 렌더러.
+/// 웹에서는 캔버스, `native` 기능을 켠 네이티브 빌드에서는 winit 창에 그린다.
+pub struct State {
+    gpu: Arc<GpuContext>,
     surface: wgpu::Surface<'static>,
     surface_config: wgpu::SurfaceConfiguration,
+    srgb_mode: SrgbMode,
+    // 서피스 텍스처를 렌더 타깃으로 쓸 때의 뷰 포맷. sRGB 여부가 서피스 포맷과 다를 수 있다
+    view_format: wgpu::TextureFormat,
+    // 서피스가 지원하는 프레젠트 모드
+    present_modes: Vec<wgpu::PresentMode>,
+    shader: wgpu::ShaderModule,
+    // 셰이더의 camera.projection. TAA 지터가 없으면 단위 행렬이다
+    camera: UniformBuffer<[[f32; 4]; 4]>,
+    render_pipeline_layout: wgpu::PipelineLayout,
+    pipeline_layout_cache: PipelineLayoutCache,
+    pipeline_cache: PipelineCache,
+    pipeline_key: PipelineKey,
     render_pipeline: wgpu::RenderPipeline,
-    canvas_id: String,
+    depth_texture: DepthTexture,
+    anti_alias: AntiAliasMode,
+    // 어댑터가 장면 포맷과 깊이 포맷에 대해 지원하는 샘플 수
+    supported_msaa: Vec<MsaaConfig>,
+    msaa: MsaaConfig,
+    msaa_view: Option<wgpu::TextureView>,
+    // FXAA 와 TAA 에서만 있다
+    post_anti_alias: Option<PostAntiAlias>,
+    // HDR 로 시작하면 장면을 이 타깃에 그린 뒤 톤 매핑해서 서피스에 출력한다
+    hdr: Option<(HdrRenderTarget, ToneMapPass)>,
+    // 렌더 배율과 CSS 픽셀 크기는 캔버스 리사이즈에서만 쓴다
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    render_scale: RenderScale,
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    logical_size: (f64, f64),
+    resolution: Resolution,
+    // Fixed 모드에서 CSS 를 바꿀 캔버스. winit 창에 그리면 None
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    canvas: Option<web_sys::HtmlCanvasElement>,
     size: (u32, u32),
+    frame_timer: FrameTimer,
+    // 타임스탬프 쿼리를 지원하지 않으면 None
+    gpu_timer: Option<GpuTimer>,
+    // 파이프라인 통계 쿼리를 지원하지 않으면(웹 포함) None
+    pipeline_stats: Option<PipelineStatsQuery>,
+    memory_stats: MemoryStats,
+    render_mode: RenderMode,
+    blend_mode: BlendMode,
+    wireframe_supported: bool,
+    // 파일에서 불러온 셰이더. 바뀌면 다음 프레임에 파이프라인을 다시 만든다
+    #[cfg(feature = "native")]
+    hot_shader: Option<HotShader>,
 }
 
 impl State {
-    async fn new(canvas_id: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let canvas = get_canvas(canvas_id).map_err(|e| format!("Failed to get canvas: {:?}", e))?;
-        let size = get_canvas_size(&canvas);
+    #[cfg(target_arch = "wasm32")]
+    async fn new(
+        canvas: &web_sys::HtmlCanvasElement,
+        options: RenderOptions,
+    ) -> Result<Self, InitError> {
+        let render_scale = RENDER_SCALE.get();
+        let client_rect = canvas.get_bounding_client_rect();
+        let logical_size = (client_rect.width(), client_rect.height());
+        let size = physical_size(logical_size, render_scale);
 
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
 
-        let surface = instance
-            .create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))
-            .unwrap();
+        let mut state = Self::from_surface(
+            &instance,
+            surface,
+            size,
+            logical_size,
+            render_scale,
+            options,
+        )
+        .await?;
+        state.canvas = Some(canvas.clone());
+        Ok(state)
+    }
+
+    /// winit 창에 그리는 `State` 를 만든다. 서피스가 창을 참조하므로 `Arc` 로 넘긴다.
+    #[cfg(feature = "native")]
+    pub async fn new_winit(
+        window: &std::sync::Arc<winit::window::Window>,
+    ) -> Result<Self, InitError> {
+        let inner_size = window.inner_size();
+        let size = (inner_size.width.max(1), inner_size.height.max(1));
+        let logical_size = inner_size.to_logical::<f64>(window.scale_factor());
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Window(Box::new(
+            std::sync::Arc::clone(window),
+        )))?;
+
+        let mut state = Self::from_surface(
+            &instance,
+            surface,
+            size,
+            (logical_size.width, logical_size.height),
+            RenderScale::Native,
+            RenderOptions::default(),
+        )
+        .await?;
+
+        // 소스 트리의 shader.wgsl 을 고치면 실행 중에 바로 반영된다
+        let path = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/shader.wgsl"));
+        match HotShader::load(path, state.gpu.device()) {
+            Ok(hot_shader) => {
+                state.rebuild_pipeline(hot_shader.module());
+                state.hot_shader = Some(hot_shader);
+            }
+            Err(error) => eprintln!("shader hot reload disabled: {error}"),
+        }
+        Ok(state)
+    }
+
+    // 웹도 winit 도 아닌 빌드에서는 서피스를 만들 방법이 없다
+    #[cfg_attr(not(any(target_arch = "wasm32", feature = "native")), allow(dead_code))]
+    async fn from_surface(
+        instance: &wgpu::Instance,
+        surface: wgpu::Surface<'static>,
+        size: (u32, u32),
+        logical_size: (f64, f64),
+        render_scale: RenderScale,
+        options: RenderOptions,
+    ) -> Result<Self, InitError> {
+        let RenderOptions {
+            anti_alias,
+            hdr,
+            srgb_mode,
+        } = options;
 
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions {
@@ -31,147 +304,546 @@ impl State {
                 compatible_surface: Some(&surface),
                 force_fallback_adapter: false,
             })
-            .await
-            .unwrap();
+            .await?;
 
-        let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor {
+        // 어댑터가 요구 한도를 만족하지 못하면 request_device 가 실패하기 전에 어느 항목인지 출력한다
+        let required_limits = wgpu::Limits::default();
+        if !required_limits.check_limits(&adapter.limits()) {
+            print_limits_diff(&adapter.limits(), &required_limits);
+        }
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
                 label: Some("main device"),
-                required_features: wgpu::Features::default(),
-                required_limits: wgpu::Limits::default(),
+                // 지원하는 경우에만 타임스탬프, 파이프라인 통계 쿼리와 와이어프레임, SPIR-V 직접 전달을 켠다
+                required_features: adapter.features()
+                    & (GpuTimer::FEATURES
+                        | PipelineStatsQuery::FEATURES
+                        | RenderMode::WIREFRAME_FEATURES
+                        | SHADER_FEATURES),
+                required_limits,
                 ..Default::default()
-            })
-            .await
-            .unwrap();
+            },
+        )
+        .await?;
+        let device = gpu.device();
+        WgpuDebugLayer::install(device);
 
         let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .find(|f| f.is_srgb())
-            .copied()
-            .unwrap_or(surface_caps.formats[0]);
+        // sRGB 변형을 서피스가 직접 지원하지 않으면 뷰 포맷으로만 바꿔서 그린다
+        let surface_format = FormatSelector::for_srgb_mode(srgb_mode)
+            .select(&surface_caps.formats)
+            .chosen;
+        let view_format = srgb_mode.apply(surface_format);
 
         let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            // 프레임 캡처를 위해 지원하면 서피스 텍스처를 복사할 수 있게 한다
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | (surface_caps.usages & wgpu::TextureUsages::COPY_SRC),
             format: surface_format,
             width: size.0,
             height: size.1,
             present_mode: surface_caps.present_modes[0],
             alpha_mode: surface_caps.alpha_modes[0],
-            view_formats: vec![],
+            view_formats: if view_format == surface_format {
+                vec![]
+            } else {
+                vec![view_format]
+            },
             desired_maximum_frame_latency: 2,
         };
 
-        surface.configure(&device, &surface_config);
+        surface.configure(device, &surface_config);
 
-        // 셰이더 생성
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        let hdr = hdr.then(|| {
+            let target = HdrRenderTarget::new(device, size.0, size.1);
+            let tone_map = ToneMapPass::new(&gpu, view_format, ToneMapOperator::default());
+            (target, tone_map)
         });
+        // 삼각형 파이프라인과 MSAA 텍스처가 그리는 포맷
+        let color_format = color_format(&hdr, view_format);
+
+        let supported_msaa: Vec<MsaaConfig> = [
+            MsaaConfig::One,
+            MsaaConfig::Two,
+            MsaaConfig::Four,
+            MsaaConfig::Eight,
+        ]
+        .into_iter()
+        .filter(|msaa| msaa.is_supported(&adapter, &[color_format, DepthTexture::FORMAT]))
+        .collect();
+        let (anti_alias, msaa) = match anti_alias.msaa() {
+            Some(msaa) if supported_msaa.contains(&msaa) => (anti_alias, msaa),
+            _ => {
+                console::log_1(
+                    &format!("{:?} is not supported, falling back to None", anti_alias).into(),
+                );
+                (AntiAliasMode::None, MsaaConfig::One)
+            }
+        };
+        let post_anti_alias = PostAntiAlias::new(device, anti_alias, view_format, size);
+        let msaa_view = msaa.create_color_view(device, color_format, size.0, size.1);
+
+        let depth_texture =
+            DepthTexture::with_sample_count(device, size.0, size.1, msaa.sample_count());
+
+        // 셰이더 생성
+        // 컴파일 에러가 panic 대신 InitError 로 전달되도록 에러 스코프로 감싼다
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = load_shader(device).await;
+        if let Some(error) = gpu.device().pop_error_scope().await {
+            return Err(InitError::ShaderCompilationFailed(error.to_string()));
+        }
+
+        let camera = UniformBuffer::new(
+            &gpu,
+            &Mat4::IDENTITY.to_cols_array_2d(),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
 
         // 렌더 파이프라인 생성
-        let render_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Render Pipeline Layout"),
-                bind_group_layouts: &[],
-                push_constant_ranges: &[],
-            });
+        // 바인드 그룹 레이아웃이 같은 파이프라인은 하나의 파이프라인 레이아웃을 공유한다
+        let mut pipeline_layout_cache = PipelineLayoutCache::new();
+        let render_pipeline_layout = pipeline_layout_cache
+            .get_or_create(device, &[camera.bind_group_layout()])
+            .clone();
 
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&render_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        });
+        // 같은 상태의 파이프라인은 캐시에서 재사용한다
+        let mut pipeline_cache = PipelineCache::new();
+        let key = PipelineKey {
+            shader_label: "Shader".to_string(),
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            polygon_mode: RenderMode::Solid.polygon_mode(),
+            blend: BlendMode::default().to_wgpu(),
+            sample_count: msaa.sample_count(),
+            depth_format: Some(DepthTexture::FORMAT),
+        };
+        let render_pipeline = pipeline_cache
+            .get_or_create(key.clone(), |key| {
+                create_render_pipeline(device, &shader, &render_pipeline_layout, color_format, key)
+            })
+            .clone();
+
+        let gpu_timer = GpuTimer::new(&gpu, 1);
+        let pipeline_stats = PipelineStatsQuery::new(&gpu);
+        let wireframe_supported = RenderMode::Wireframe.is_supported(device);
 
         Ok(Self {
-            device,
-            queue,
+            gpu,
             surface,
             surface_config,
+            srgb_mode,
+            view_format,
+            present_modes: surface_caps.present_modes,
+            shader,
+            camera,
+            render_pipeline_layout,
+            pipeline_layout_cache,
+            pipeline_cache,
+            pipeline_key: key,
             render_pipeline,
-            canvas_id: canvas_id.to_string(),
+            depth_texture,
+            anti_alias,
+            supported_msaa,
+            msaa,
+            msaa_view,
+            post_anti_alias,
+            hdr,
+            render_scale,
+            logical_size,
+            resolution: Resolution::Auto,
+            canvas: None,
             size,
+            frame_timer: FrameTimer::new(),
+            gpu_timer,
+            pipeline_stats,
+            memory_stats: MemoryStats::new(),
+            render_mode: RenderMode::Solid,
+            blend_mode: BlendMode::default(),
+            wireframe_supported,
+            #[cfg(feature = "native")]
+            hot_shader: None,
         })
     }
 
-    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+    /// 지원하지 않는 모드를 요청하면 항상 지원되는 `Fifo` 를 사용한다.
+    fn set_present_mode(&mut self, mode: wgpu::PresentMode) {
+        let mode = if self.present_modes.contains(&mode) {
+            mode
+        } else {
+            wgpu::PresentMode::Fifo
+        };
+
+        if mode == self.surface_config.present_mode {
+            return;
+        }
+
+        self.surface_config.present_mode = mode;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+    }
+
+    pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        #[cfg(feature = "native")]
+        if let Some(hot_shader) = &self.hot_shader
+            && hot_shader.poll_reload(self.gpu.device())
+        {
+            self.rebuild_pipeline(hot_shader.module());
+        }
+
         let output = self.surface.get_current_texture()?;
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
+        let view = self.surface_view(&output);
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+        self.encode_frame(&mut encoder, &view);
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.read_back();
+        }
+        if let Some(pipeline_stats) = &mut self.pipeline_stats {
+            pipeline_stats.read_back();
+        }
+        self.memory_stats.update(self.gpu.device());
+
+        Ok(())
+    }
+
+    /// 마지막으로 읽어 온 메인 렌더 패스의 파이프라인 통계.
+    /// 파이프라인 통계 쿼리를 지원하지 않거나 아직 읽기 전이면 `None` 을 반환한다.
+    pub fn pipeline_stats(&self) -> Option<PipelineStats> {
+        self.pipeline_stats.as_ref()?.last_stats()
+    }
+
+    /// 한 프레임을 그려서 RGBA8 바이트로 읽어 온다. 크기는 현재 서피스 크기와 같다.
+    pub async fn capture_frame(&mut self) -> Result<Vec<u8>, CaptureError> {
+        let readback = self.begin_capture()?;
+        readback.read(self.gpu.device()).await
+    }
+
+    // 서피스 텍스처는 present 전에만 유효하므로 그리기와 복사 명령을 함께 제출한다
+    fn begin_capture(&mut self) -> Result<TextureReadback, CaptureError> {
+        if !self
+            .surface_config
+            .usage
+            .contains(wgpu::TextureUsages::COPY_SRC)
+        {
+            return Err(CaptureError::CopyNotSupported);
+        }
+
+        let output = self.surface.get_current_texture()?;
+        let view = self.surface_view(&output);
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Capture Encoder"),
+                });
+        self.encode_frame(&mut encoder, &view);
+        let readback =
+            TextureReadback::copy_from(self.gpu.device(), &mut encoder, &output.texture)?;
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(readback)
+    }
+
+    fn encode_frame(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        // FXAA 와 TAA 는 톤 매핑까지 끝난 장면을 중간 텍스처에서 읽어서 서피스에 쓴다
+        let output_view = match &self.post_anti_alias {
+            Some(post) => &post.source,
+            None => view,
+        };
+        let scene_view = match &self.hdr {
+            Some((target, _)) => target.view(),
+            None => output_view,
+        };
+        // MSAA 를 사용하면 멀티 샘플 텍스처에 그린 뒤 장면 텍스처로 리졸브한다
+        let (color_view, resolve_target, store) = match &self.msaa_view {
+            Some(msaa_view) => (msaa_view, Some(scene_view), wgpu::StoreOp::Discard),
+            None => (scene_view, None, wgpu::StoreOp::Store),
+        };
+
+        let clear_color = self.clear_color();
+
+        // 큐에 쓴 값은 이 인코더를 제출하기 전에 반영된다
+        let projection = match &self.post_anti_alias {
+            Some(post) => post.jitter_projection(Mat4::IDENTITY),
+            None => Mat4::IDENTITY,
+        };
+        self.camera
+            .update(self.gpu.queue(), &projection.to_cols_array_2d());
+
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.begin_frame();
+        }
 
         {
+            let timestamp_writes = self
+                .gpu_timer
+                .as_mut()
+                .and_then(|gpu_timer| gpu_timer.render_timestamp_writes(MAIN_PASS_LABEL));
+
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Render Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
+                    view: color_view,
+                    resolve_target,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.2,
-                            b: 0.3,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store,
                     },
                 })],
-                depth_stencil_attachment: None,
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
                 occlusion_query_set: None,
-                timestamp_writes: None,
+                timestamp_writes,
             });
 
+            if let Some(pipeline_stats) = &mut self.pipeline_stats {
+                pipeline_stats.begin_render(&mut render_pass);
+            }
             render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.camera.bind_group(), &[]);
             render_pass.draw(0..3, 0..1);
+            if let Some(pipeline_stats) = &self.pipeline_stats {
+                pipeline_stats.end_render(&mut render_pass);
+            }
         }
 
-        self.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        if let Some((target, tone_map)) = &self.hdr {
+            tone_map.apply(encoder, target.view(), output_view);
+        }
+        if let Some(post) = &mut self.post_anti_alias {
+            post.apply(self.gpu.queue(), encoder, view);
+        }
 
-        Ok(())
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.resolve(encoder);
+        }
+        if let Some(pipeline_stats) = &mut self.pipeline_stats {
+            pipeline_stats.resolve(encoder);
+        }
+    }
+
+    // 캐시에 없을 때만 파이프라인을 새로 만든다
+    fn pipeline(&mut self, key: PipelineKey) -> wgpu::RenderPipeline {
+        let format = self.color_format();
+        let Self {
+            gpu,
+            shader,
+            camera,
+            pipeline_layout_cache,
+            pipeline_cache,
+            ..
+        } = self;
+        let device = gpu.device();
+
+        pipeline_cache
+            .get_or_create(key, |key| {
+                create_render_pipeline(
+                    device,
+                    shader,
+                    pipeline_layout_cache.get_or_create(device, &[camera.bind_group_layout()]),
+                    format,
+                    key,
+                )
+            })
+            .clone()
+    }
+
+    // 새 셰이더와 그 셰이더로 만든 파이프라인. 검증 에러는 호출한 쪽의 에러 스코프로 전달된다
+    fn compile_shader(&self, wgsl_source: &str) -> (wgpu::ShaderModule, wgpu::RenderPipeline) {
+        let shader = ShaderLoader::new(self.gpu.device()).load_wgsl("Shader", wgsl_source);
+        let pipeline = create_render_pipeline(
+            self.gpu.device(),
+            &shader,
+            &self.render_pipeline_layout,
+            self.color_format(),
+            &self.pipeline_key,
+        );
+        (shader, pipeline)
+    }
+
+    // 이미 검증된 셰이더로 현재 키의 파이프라인을 다시 만든다
+    #[cfg(feature = "native")]
+    fn rebuild_pipeline(&mut self, shader: wgpu::ShaderModule) {
+        let pipeline = create_render_pipeline(
+            self.gpu.device(),
+            &shader,
+            &self.render_pipeline_layout,
+            self.color_format(),
+            &self.pipeline_key,
+        );
+        self.swap_shader(shader, pipeline);
+    }
+
+    // 이전 셰이더로 만든 파이프라인은 캐시에서 모두 버린다
+    fn swap_shader(&mut self, shader: wgpu::ShaderModule, pipeline: wgpu::RenderPipeline) {
+        self.shader = shader;
+        self.pipeline_cache.clear();
+        self.render_pipeline = self
+            .pipeline_cache
+            .get_or_create(self.pipeline_key.clone(), |_| pipeline)
+            .clone();
     }
 
-    fn resize(&mut self, new_size: (u32, u32)) {
+    /// 와이어프레임을 지원하지 않으면 모드를 바꾸지 않고 `false` 를 반환한다.
+    fn set_render_mode(&mut self, mode: RenderMode) -> bool {
+        if mode == RenderMode::Wireframe && !self.wireframe_supported {
+            return false;
+        }
+        if mode == self.render_mode {
+            return true;
+        }
+
+        self.render_mode = mode;
+        self.pipeline_key.polygon_mode = mode.polygon_mode();
+        self.render_pipeline = self.pipeline(self.pipeline_key.clone());
+        true
+    }
+
+    /// 모드가 바뀌었을 때만 파이프라인을 다시 만든다.
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        if mode == self.blend_mode {
+            return;
+        }
+
+        self.blend_mode = mode;
+        self.pipeline_key.blend = mode.to_wgpu();
+        self.render_pipeline = self.pipeline(self.pipeline_key.clone());
+    }
+
+    /// 안티 에일리어싱 방법을 바꾸고 그에 필요한 텍스처와 파이프라인을 다시 만든다.
+    /// 어댑터가 지원하지 않는 MSAA 샘플 수면 바꾸지 않고 `false` 를 반환한다.
+    pub fn set_anti_alias_mode(&mut self, mode: AntiAliasMode) -> bool {
+        let Some(msaa) = mode
+            .msaa()
+            .filter(|msaa| self.supported_msaa.contains(msaa))
+        else {
+            return false;
+        };
+        if mode == self.anti_alias {
+            return true;
+        }
+
+        self.anti_alias = mode;
+        if msaa != self.msaa {
+            self.msaa = msaa;
+            self.pipeline_key.sample_count = msaa.sample_count();
+            self.render_pipeline = self.pipeline(self.pipeline_key.clone());
+            self.msaa_view = msaa.create_color_view(
+                self.gpu.device(),
+                self.color_format(),
+                self.size.0,
+                self.size.1,
+            );
+            self.depth_texture = DepthTexture::with_sample_count(
+                self.gpu.device(),
+                self.size.0,
+                self.size.1,
+                msaa.sample_count(),
+            );
+        }
+        self.post_anti_alias =
+            PostAntiAlias::new(self.gpu.device(), mode, self.view_format, self.size);
+        true
+    }
+
+    fn surface_view(&self, output: &wgpu::SurfaceTexture) -> wgpu::TextureView {
+        output.texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(self.view_format),
+            ..Default::default()
+        })
+    }
+
+    // 배경색은 선형 값이므로 sRGB 인코딩 없이 서피스에 바로 쓰면 미리 인코딩한다
+    fn clear_color(&self) -> wgpu::Color {
+        let color = BACKGROUND_COLOR.get();
+        if self.hdr.is_some() || self.srgb_mode == SrgbMode::Enabled {
+            return color;
+        }
+
+        let encoded = linear_to_srgb(Vec3::new(color.r as f32, color.g as f32, color.b as f32));
+        wgpu::Color {
+            r: encoded.x as f64,
+            g: encoded.y as f64,
+            b: encoded.z as f64,
+            a: color.a,
+        }
+    }
+
+    fn color_format(&self) -> wgpu::TextureFormat {
+        color_format(&self.hdr, self.view_format)
+    }
+
+    /// `SurfaceError::Lost` 를 받았을 때 같은 설정으로 서피스를 다시 구성한다.
+    pub fn reconfigure_surface(&self) {
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+    }
+
+    // ResizeObserver 가 알려준 CSS 픽셀 크기나 배율이 바뀌었을 때 호출된다
+    #[cfg(target_arch = "wasm32")]
+    fn set_logical_size(&mut self, logical_size: (f64, f64), render_scale: RenderScale) {
+        self.logical_size = logical_size;
+        self.render_scale = render_scale;
+        if self.resolution != Resolution::Auto {
+            return;
+        }
+
+        let (width, height) = physical_size(logical_size, render_scale);
+        if (width, height) != self.size {
+            self.resize((width, height));
+            console::log_1(&format!("Resized to: {}x{}", width, height).into());
+        }
+    }
+
+    /// `Fixed` 면 서피스를 그 크기로 바꾸고 캔버스가 비율을 유지하며 늘어나도록 한다.
+    /// `Auto` 로 돌아오면 현재 캔버스 크기에 맞춘다.
+    #[cfg(target_arch = "wasm32")]
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+        if let Some(canvas) = &self.canvas {
+            let style = canvas.style();
+            let _ = match resolution {
+                Resolution::Auto => style.remove_property("object-fit").map(|_| ()),
+                Resolution::Fixed(..) => style.set_property("object-fit", "contain"),
+            };
+        }
+
+        let size = match resolution {
+            Resolution::Auto => physical_size(self.logical_size, self.render_scale),
+            Resolution::Fixed(width, height) => (width, height),
+        };
+        self.resize_surface(size);
+        console::log_1(
+            &format!(
+                "Resolution: {:?}, surface {}x{}",
+                resolution, self.size.0, self.size.1
+            )
+            .into(),
+        );
+    }
+
+    /// `Resolution::Fixed` 에서는 창 크기가 바뀌어도 서피스를 그대로 둔다.
+    pub fn resize(&mut self, new_size: (u32, u32)) {
+        if self.resolution == Resolution::Auto {
+            self.resize_surface(new_size);
+        }
+    }
+
+    fn resize_surface(&mut self, new_size: (u32, u32)) {
         let limits = wgpu::Limits::default();
         let new_size = (
             new_size.0.max(1).min(limits.max_texture_dimension_2d),
@@ -185,43 +857,123 @@ impl State {
         self.size = new_size;
         self.surface_config.width = new_size.0;
         self.surface_config.height = new_size.1;
-        self.surface.configure(&self.device, &self.surface_config);
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.msaa_view = self.msaa.create_color_view(
+            self.gpu.device(),
+            self.color_format(),
+            new_size.0,
+            new_size.1,
+        );
+        if let Some((target, _)) = &mut self.hdr {
+            target.resize(self.gpu.device(), new_size.0, new_size.1);
+        }
+        if let Some(post) = &mut self.post_anti_alias {
+            post.resize(self.gpu.device(), self.view_format, new_size);
+        }
+        self.depth_texture = DepthTexture::with_sample_count(
+            self.gpu.device(),
+            new_size.0,
+            new_size.1,
+            self.msaa.sample_count(),
+        );
     }
 }
 
-fn start_render_loop(state: Rc<RefCell<State>>) {
-    let f = Rc::new(RefCell::new(None));
-    let g = Rc::clone(&f);
+// HDR 을 사용하면 장면은 서피스 대신 HDR 타깃의 포맷으로 그린다
+fn color_format(
+    hdr: &Option<(HdrRenderTarget, ToneMapPass)>,
+    surface_format: wgpu::TextureFormat,
+) -> wgpu::TextureFormat {
+    match hdr {
+        Some(_) => HdrRenderTarget::FORMAT,
+        None => surface_format,
+    }
+}
+
+fn create_render_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    key: &PipelineKey,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Render Pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: key.blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: key.topology,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: Some(wgpu::Face::Back),
+            polygon_mode: key.polygon_mode,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: key.depth_format.map(|format| wgpu::DepthStencilState {
+            format,
+            ..DepthTexture::depth_stencil_state()
+        }),
+        multisample: wgpu::MultisampleState {
+            count: key.sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn start_render_loop(state: Rc<RefCell<State>>, resize_observer: ResizeObserverHandle) {
+    start_animation_loop(move || {
+        // 렌더 루프가 끝나면 관찰도 멈추도록 클로저가 함께 소유한다
+        let _ = &resize_observer;
 
-    *g.borrow_mut() = Some(Closure::wrap(Box::new(move || {
         // try_borrow_mut을 사용하여 panic 방지
         match state.try_borrow_mut() {
             Ok(mut state) => {
-                // Resize canvas if necessary
-                let canvas =
-                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
-                let (width, height) = get_canvas_size(&canvas);
-                if (width, height) != state.size {
-                    state.resize((width, height));
-                    console::log_1(&format!("Resized to: {}x{}", width, height).into());
+                state.frame_timer.begin_frame();
+
+                // set_render_scale 로 바뀐 배율 반영
+                let render_scale = RENDER_SCALE.get();
+                if render_scale != state.render_scale {
+                    let logical_size = state.logical_size;
+                    state.set_logical_size(logical_size, render_scale);
                 }
 
                 match state.render() {
                     Ok(_) => {}
                     Err(wgpu::SurfaceError::Lost) => {
-                        state
-                            .surface
-                            .configure(&state.device, &state.surface_config);
+                        state.reconfigure_surface();
                         console::log_1(&"Surface lost, reconfiguring".into());
                     }
                     Err(wgpu::SurfaceError::OutOfMemory) => {
                         console::log_1(&"Out of memory!".into());
-                        return; // 렌더 루프 중단
+                        return false; // 렌더 루프 중단
                     }
                     Err(e) => {
                         console::log_1(&format!("Render error: {:?}", e).into());
                     }
                 }
+
+                state.frame_timer.end_frame();
             }
             Err(_) => {
                 // State가 다른 곳에서 빌려져 있음 - 이번 프레임 스킵
@@ -229,47 +981,409 @@ fn start_render_loop(state: Rc<RefCell<State>>) {
             }
         }
 
-        // Schedule next frame
-        request_animation_frame(f.borrow().as_ref().unwrap());
-    }) as Box<dyn FnMut()>));
+        true
+    });
+}
 
-    request_animation_frame(g.borrow().as_ref().unwrap());
+// performance 는 브라우저에만 있으므로 벤치마크는 웹 빌드에서만 제공한다
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+    web_sys::window().unwrap().performance().unwrap().now()
 }
 
-fn request_animation_frame(f: &Closure<dyn FnMut()>) {
-    web_sys::window()
-        .unwrap()
-        .request_animation_frame(f.as_ref().unchecked_ref())
-        .expect("Failed to request animation frame");
+#[cfg(all(not(target_arch = "wasm32"), feature = "spirv"))]
+const SHADER_FEATURES: wgpu::Features = SpirVShader::FEATURES;
+#[cfg(not(all(not(target_arch = "wasm32"), feature = "spirv")))]
+const SHADER_FEATURES: wgpu::Features = wgpu::Features::empty();
+
+// spirv 기능을 켠 네이티브 빌드에서는 build.rs 가 컴파일해 둔 SPIR-V 를 사용한다.
+// Vulkan 이면 naga 를 거치지 않고 드라이버에 그대로 넘긴다
+#[cfg(all(not(target_arch = "wasm32"), feature = "spirv"))]
+async fn load_shader(device: &wgpu::Device) -> wgpu::ShaderModule {
+    let words = wgpu::util::make_spirv_raw(include_bytes!(concat!(env!("OUT_DIR"), "/shader.spv")));
+    SpirVShader::from_words(&ShaderLoader::new(device), "Shader", &words)
+        .expect("build.rs wrote an invalid SPIR-V module")
 }
 
-fn get_canvas(canvas_id: &str) -> Result<HtmlCanvasElement, JsValue> {
-    let window = web_sys::window().ok_or_else(|| JsValue::from_str("Failed to get window"))?;
-    let document = window
-        .document()
-        .ok_or_else(|| JsValue::from_str("Failed to get document"))?;
+// 웹에서는 지난번에 저장해 둔 SPIR-V 가 있으면 사용한다. 줄어든 시간은 ShaderCache 가 콘솔에 남긴다
+#[cfg(target_arch = "wasm32")]
+async fn load_shader(device: &wgpu::Device) -> wgpu::ShaderModule {
+    ShaderCache::new()
+        .load(device, "Shader", include_str!("shader.wgsl"))
+        .await
+}
 
-    document
-        .get_element_by_id(canvas_id)
-        .ok_or_else(|| JsValue::from_str(&format!("Canvas with id '{}' not found", canvas_id)))?
-        .dyn_into::<HtmlCanvasElement>()
-        .map_err(|_| JsValue::from_str("Element is not a canvas"))
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "spirv")))]
+async fn load_shader(device: &wgpu::Device) -> wgpu::ShaderModule {
+    ShaderLoader::new(device).load_wgsl("Shader", include_str!("shader.wgsl"))
 }
 
-fn get_canvas_size(canvas: &HtmlCanvasElement) -> (u32, u32) {
-    let device_pixel_ratio = web_sys::window().unwrap().device_pixel_ratio();
-    let client_rect = canvas.get_bounding_client_rect();
-    (
-        (client_rect.width() * device_pixel_ratio) as u32,
-        (client_rect.height() * device_pixel_ratio) as u32,
-    )
+/// 블렌드 상태만 다른 파이프라인 100개를 캐시 없이 만들 때와
+/// `PipelineCache` 를 거칠 때의 시간을 비교한다. 캐시는 처음 만드는 미스와
+/// 이미 있는 파이프라인을 돌려주는 히트를 따로 잰다.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn benchmark_pipeline_cache() -> Result<String, JsValue> {
+    let state = STATE
+        .with_borrow(|state| state.clone())
+        .ok_or_else(|| JsValue::from_str("Renderer is not running"))?;
+    let mut state = state
+        .try_borrow_mut()
+        .map_err(|_| JsValue::from_str("State borrowed elsewhere"))?;
+
+    let keys: Vec<PipelineKey> = (0..BENCHMARK_PIPELINE_COUNT)
+        .map(|i| PipelineKey {
+            blend: BENCHMARK_BLEND_STATES[i % BENCHMARK_BLEND_STATES.len()],
+            ..state.pipeline_key.clone()
+        })
+        .collect();
+
+    // 캐시를 거치는 쪽과 같은 포맷의 파이프라인을 만든다
+    let format = state.color_format();
+
+    // 캐시가 없으면 파이프라인마다 레이아웃도 새로 만든다
+    let start = now_ms();
+    for key in &keys {
+        let layout = state
+            .gpu
+            .device()
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Benchmark Pipeline Layout"),
+                bind_group_layouts: &[state.camera.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+        create_render_pipeline(state.gpu.device(), &state.shader, &layout, format, key);
+    }
+    let uncached_ms = now_ms() - start;
+
+    // 첫 번째는 블렌드 상태마다 한 번씩 미스가 나고, 두 번째는 모두 히트다
+    let cached_before = state.pipeline_cache.len();
+    let start = now_ms();
+    for key in &keys {
+        state.pipeline(key.clone());
+    }
+    let miss_ms = now_ms() - start;
+    let misses = state.pipeline_cache.len() - cached_before;
+
+    let start = now_ms();
+    for key in keys {
+        state.pipeline(key);
+    }
+    let hit_ms = now_ms() - start;
+
+    let report = format!(
+        "{} pipelines: uncached {:.2} ms, cached with {} misses {:.2} ms, all hits {:.2} ms \
+         ({:.1}% less than uncached, {} cached pipelines sharing {} layout)",
+        BENCHMARK_PIPELINE_COUNT,
+        uncached_ms,
+        misses,
+        miss_ms,
+        hit_ms,
+        (1.0 - hit_ms / uncached_ms) * 100.0,
+        state.pipeline_cache.len(),
+        state.pipeline_layout_cache.len(),
+    );
+    console::log_1(&report.clone().into());
+    Ok(report)
+}
+
+/// 수직 동기화를 켜면 `Fifo`, 끄면 `Immediate` 프레젠트 모드를 사용한다.
+#[wasm_bindgen]
+pub fn set_vsync(enabled: bool) {
+    let mode = if enabled {
+        wgpu::PresentMode::Fifo
+    } else {
+        wgpu::PresentMode::Immediate
+    };
+
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut()) {
+            state.set_present_mode(mode);
+        }
+    });
+}
+
+/// 마지막으로 측정된 메인 렌더 패스의 GPU 시간 (마이크로초).
+/// 타임스탬프 쿼리를 지원하지 않거나 아직 측정 전이면 `undefined` 를 반환한다.
+#[wasm_bindgen]
+pub fn gpu_render_pass_micros() -> Option<u64> {
+    STATE.with_borrow(|state| {
+        let state = state.as_ref()?.try_borrow().ok()?;
+        state.gpu_timer.as_ref()?.last_pass_micros(MAIN_PASS_LABEL)
+    })
+}
+
+/// 마지막 프레임에서 읽은 GPU 메모리 사용량.
+/// `{ bufferBytes, textureBytes, bindGroupCount }` 객체를 반환하고, 렌더러가 없으면 `undefined` 를 반환한다.
+#[wasm_bindgen]
+pub fn get_memory_stats() -> JsValue {
+    STATE.with_borrow(|state| {
+        state
+            .as_ref()
+            .and_then(|state| state.try_borrow().ok())
+            .map_or(JsValue::UNDEFINED, |state| state.memory_stats.to_js())
+    })
+}
+
+/// 현재 프레임을 PNG 로 인코딩해서 `<canvas_id>.png` 파일로 다운로드한다.
+#[wasm_bindgen]
+pub async fn download_frame_png(canvas_id: &str) -> Result<(), JsValue> {
+    let state = STATE
+        .with_borrow(|state| state.clone())
+        .ok_or_else(|| JsValue::from_str("Renderer is not running"))?;
+
+    // 매핑을 기다리는 동안 렌더 루프가 State 를 빌릴 수 있도록 먼저 놓아 준다
+    let (readback, gpu) = {
+        let mut state = state
+            .try_borrow_mut()
+            .map_err(|_| JsValue::from_str("State borrowed elsewhere"))?;
+        (state.begin_capture()?, state.gpu.clone())
+    };
+    let (width, height) = (readback.width(), readback.height());
+    let rgba = readback.read(gpu.device()).await?;
+    let png = capture::encode_png(width, height, &rgba)?;
+
+    download_bytes(&png, "image/png", &format!("{}.png", canvas_id))
+}
+
+/// 삼각형을 선으로만 그릴지 정한다. `POLYGON_MODE_LINE` 을 지원하지 않으면 경고만 남긴다.
+#[wasm_bindgen]
+pub fn set_wireframe(enabled: bool) {
+    let mode = if enabled {
+        RenderMode::Wireframe
+    } else {
+        RenderMode::Solid
+    };
+
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut())
+            && !state.set_render_mode(mode)
+        {
+            console::warn_1(
+                &"Wireframe mode requires POLYGON_MODE_LINE, which is not supported".into(),
+            );
+        }
+    });
+}
+
+/// 삼각형의 블렌드 모드를 바꾼다. `"opaque"`, `"alpha"`, `"additive"`, `"premultiplied"`,
+/// `"multiply"` 중 하나다. 삼각형의 알파가 1 이므로 `Additive` 와 `Multiply` 만 결과가 달라 보인다.
+#[wasm_bindgen]
+pub fn set_blend_mode(name: &str) {
+    let Some(mode) = BlendMode::from_name(name) else {
+        console::warn_1(&format!("Unknown blend mode: {}", name).into());
+        return;
+    };
+
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut()) {
+            state.set_blend_mode(mode);
+        }
+    });
+}
+
+/// 안티 에일리어싱 방법을 바꾼다. `"none"`, `"msaa2"`, `"msaa4"`, `"msaa8"`, `"fxaa"`, `"taa"` 중 하나다.
+/// 지원하지 않는 MSAA 샘플 수면 경고만 남긴다.
+#[wasm_bindgen]
+pub fn set_anti_alias_mode(name: &str) {
+    let Some(mode) = AntiAliasMode::from_name(name) else {
+        console::warn_1(&format!("Unknown anti-alias mode: {}", name).into());
+        return;
+    };
+
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut())
+            && !state.set_anti_alias_mode(mode)
+        {
+            console::warn_1(&format!("{:?} is not supported", mode).into());
+        }
+    });
+}
+
+/// 5초마다 콘솔에 FPS 를 출력할지 정한다.
+#[wasm_bindgen]
+pub fn set_fps_logging(enabled: bool) {
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut()) {
+            state.frame_timer.set_debug(enabled);
+        }
+    });
+}
+
+/// 새 WGSL 소스로 셰이더와 렌더 파이프라인을 다시 만든다.
+/// 컴파일이나 파이프라인 검증에 실패하면 기존 파이프라인을 유지하고 에러 메시지를 반환한다.
+#[wasm_bindgen]
+pub async fn reload_shader(wgsl_source: &str) -> Result<(), JsValue> {
+    let state = STATE
+        .with_borrow(|state| state.clone())
+        .ok_or_else(|| JsValue::from_str("Renderer is not running"))?;
+
+    // 에러 스코프를 기다리는 동안에는 렌더 루프가 State 를 빌릴 수 있어야 한다
+    let (gpu, shader, pipeline) = {
+        let state = state
+            .try_borrow()
+            .map_err(|_| JsValue::from_str("State borrowed elsewhere"))?;
+        state
+            .gpu
+            .device()
+            .push_error_scope(wgpu::ErrorFilter::Validation);
+        let (shader, pipeline) = state.compile_shader(wgsl_source);
+        (state.gpu.clone(), shader, pipeline)
+    };
+    if let Some(error) = gpu.device().pop_error_scope().await {
+        return Err(JsValue::from_str(&error.to_string()));
+    }
+
+    state
+        .try_borrow_mut()
+        .map_err(|_| JsValue::from_str("State borrowed elsewhere"))?
+        .swap_shader(shader, pipeline);
+    console::log_1(&"Shader reloaded".into());
+    Ok(())
+}
+
+/// 렌더링 해상도 배율을 바꾼다. 1.0 은 기기 해상도, 0.5 는 가로/세로 절반이다.
+#[wasm_bindgen]
+pub fn set_render_scale(scale: f64) {
+    if !scale.is_finite() || scale <= 0.0 {
+        console::log_1(&format!("Invalid render scale: {}", scale).into());
+        return;
+    }
+
+    let scale = if scale == 1.0 {
+        RenderScale::Native
+    } else if scale == 0.5 {
+        RenderScale::Half
+    } else {
+        RenderScale::Custom(scale)
+    };
+    RENDER_SCALE.set(scale);
+}
+
+/// 캔버스 크기와 상관없이 `width`x`height` 로 그린다. 0 이 있으면 무시한다.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_fixed_resolution(width: u32, height: u32) {
+    if width == 0 || height == 0 {
+        console::log_1(&format!("Invalid resolution: {}x{}", width, height).into());
+        return;
+    }
+    set_resolution(Resolution::Fixed(width, height));
+}
+
+/// 캔버스 크기에 맞춰 그리는 기본 모드로 돌아간다.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_auto_resolution() {
+    set_resolution(Resolution::Auto);
+}
+
+#[cfg(target_arch = "wasm32")]
+fn set_resolution(resolution: Resolution) {
+    STATE.with_borrow(|state| {
+        if let Some(Ok(mut state)) = state.as_ref().map(|state| state.try_borrow_mut()) {
+            state.set_resolution(resolution);
+        }
+    });
+}
+
+/// 삼각형 뒤를 지우는 배경색을 바꾼다. 각 값은 [0, 1] 로 잘린다.
+///
+/// 값은 선형 색 공간으로 해석된다. 서피스가 sRGB 포맷이면 GPU 가 쓰기 전에 sRGB 로
+/// 인코딩하므로, 0.5 는 화면에서 CSS 의 `rgb(188, 188, 188)` 정도로 밝게 보인다.
+/// 색 선택기처럼 sRGB 로 된 값을 쓰려면 JS 에서 선형 값으로 바꿔서 넘긴다.
+#[wasm_bindgen]
+pub fn set_background_color(r: f64, g: f64, b: f64, a: f64) {
+    // NaN 은 clamp 를 지나도 NaN 이므로 0 으로 바꾼다
+    let channel = |value: f64| {
+        if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 1.0)
+        }
+    };
+    BACKGROUND_COLOR.set(wgpu::Color {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+        a: channel(a),
+    });
 }
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    run_msaa(canvas_id, 1).await
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub async fn run_msaa(canvas_id: &str, sample_count: u32) -> Result<(), JsValue> {
+    let msaa = MsaaConfig::from_sample_count(sample_count)
+        .ok_or_else(|| JsValue::from_str(&format!("Invalid sample count: {}", sample_count)))?;
+    start(
+        canvas_id,
+        RenderOptions {
+            anti_alias: AntiAliasMode::Msaa(msaa.sample_count()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// 장면을 `Rgba16Float` 텍스처에 그린 뒤 톤 매핑해서 출력한다.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub async fn run_hdr(canvas_id: &str) -> Result<(), JsValue> {
+    start(
+        canvas_id,
+        RenderOptions {
+            hdr: true,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// `enabled` 가 `false` 면 sRGB 가 아닌 서피스 포맷에 그린다.
+/// 셰이더가 쓴 버텍스 색이 변환 없이 화면에 나가므로 기본 모드보다 어둡게 보인다.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub async fn run_srgb(canvas_id: &str, enabled: bool) -> Result<(), JsValue> {
+    let srgb_mode = if enabled {
+        SrgbMode::Enabled
+    } else {
+        SrgbMode::Disabled
+    };
+    start(
+        canvas_id,
+        RenderOptions {
+            srgb_mode,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn start(canvas_id: &str, options: RenderOptions) -> Result<(), JsValue> {
     console_error_panic_hook::set_once();
 
-    let state = Rc::new(RefCell::new(State::new(canvas_id).await.unwrap()));
-    start_render_loop(state);
+    let canvas =
+        get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+    let state = Rc::new(RefCell::new(State::new(&canvas, options).await?));
+
+    STATE.with_borrow_mut(|current| *current = Some(Rc::clone(&state)));
+
+    // 캔버스 크기가 실제로 바뀔 때만 리사이즈한다
+    let resize_state = Rc::clone(&state);
+    let resize_observer = observe_resize(&canvas, move |logical_size| {
+        if let Ok(mut state) = resize_state.try_borrow_mut() {
+            let render_scale = state.render_scale;
+            state.set_logical_size(logical_size, render_scale);
+        }
+    })?;
+
+    start_render_loop(state, resize_observer);
     Ok(())
 }
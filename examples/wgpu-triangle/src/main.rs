@@ -0,0 +1,96 @@
+//! winit 창에 삼각형을 그리는 네이티브 예제.
+//!
+//! `cargo run -p wgpu-triangle --features native` 로 실행한다.
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+use winit::application::ApplicationHandler;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::window::{Window, WindowId};
+
+use wgpu_triangle::State;
+
+#[derive(Default)]
+struct App {
+    window: Option<Arc<Window>>,
+    state: Option<State>,
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+
+        let attributes = Window::default_attributes().with_title("wgpu-triangle");
+        let window = Arc::new(
+            event_loop
+                .create_window(attributes)
+                .expect("failed to create window"),
+        );
+        match block_on(State::new_winit(&window)) {
+            Ok(state) => self.state = Some(state),
+            Err(error) => {
+                eprintln!("failed to initialize wgpu: {error}");
+                event_loop.exit();
+            }
+        }
+        self.window = Some(window);
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+
+        match event {
+            WindowEvent::CloseRequested => {
+                // 파이프라인 통계는 네이티브에서만 지원하므로 종료할 때 터미널에 남긴다
+                if let Some(stats) = state.pipeline_stats() {
+                    println!("Last frame pipeline statistics: {stats:?}");
+                }
+                event_loop.exit();
+            }
+            WindowEvent::Resized(size) => state.resize((size.width, size.height)),
+            WindowEvent::RedrawRequested => match state.render() {
+                Ok(()) => {}
+                Err(wgpu::SurfaceError::Lost) => state.reconfigure_surface(),
+                Err(wgpu::SurfaceError::OutOfMemory) => {
+                    eprintln!("Out of memory, exiting");
+                    event_loop.exit();
+                }
+                Err(error) => eprintln!("Render error: {error:?}"),
+            },
+            _ => {}
+        }
+    }
+
+    // requestAnimationFrame 대신 이벤트 루프가 비면 다음 프레임을 요청한다
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+}
+
+// 어댑터/디바이스 요청은 네이티브에서 바로 끝나므로 간단한 폴링으로 충분하다
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
+
+fn main() {
+    let event_loop = EventLoop::new().expect("failed to create event loop");
+    let mut app = App::default();
+    event_loop.run_app(&mut app).expect("event loop error");
+}
@@ -1,3 +1,11 @@
+// 꼭짓점은 NDC 좌표이므로 평소에는 단위 행렬이고, TAA 를 켜면 지터만큼 옮긴다
+struct Camera {
+    projection: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
 // Vertex shader
 @vertex
 fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) vec4<f32> {
@@ -7,7 +15,7 @@ fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) ve
         vec2<f32>( 0.5, -0.5)
     );
     
-    return vec4<f32>(pos[in_vertex_index], 0.0, 1.0);
+    return camera.projection * vec4<f32>(pos[in_vertex_index], 0.0, 1.0);
 }
 
 // Fragment shader  
@@ -0,0 +1,347 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3};
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+use wgpu_common::web::{RenderScale, get_canvas, get_canvas_size, start_animation_loop};
+use wgpu_common::{
+    DepthTexture, FormatSelector, GpuContext, InitError, PositionNormalUvVertex, Texture,
+    TexturedCube, UniformBuffer, Vertex, VideoTexture,
+};
+
+/// MDN 예제에 쓰이는 CC0 동영상. CORS 를 허용해서 픽셀을 읽을 수 있다
+const FLOWER_URL: &str =
+    "https://interactive-examples.mdn.mozilla.net/media/cc0-videos/flower.webm";
+// 동영상을 늘이거나 줄여서 올릴 텍스처 크기
+const VIDEO_SIZE: (u32, u32) = (512, 512);
+
+// 동영상을 입히지 않는 면에는 텍스처 큐브 예제의 주사위 눈을 입힌다. +Z 면은 동영상이다
+const FACE_IMAGES: [(&str, &[u8]); 5] = [
+    (
+        "px",
+        include_bytes!("../../wgpu-textured-cube/src/faces/px.png"),
+    ),
+    (
+        "nx",
+        include_bytes!("../../wgpu-textured-cube/src/faces/nx.png"),
+    ),
+    (
+        "py",
+        include_bytes!("../../wgpu-textured-cube/src/faces/py.png"),
+    ),
+    (
+        "ny",
+        include_bytes!("../../wgpu-textured-cube/src/faces/ny.png"),
+    ),
+    (
+        "nz",
+        include_bytes!("../../wgpu-textured-cube/src/faces/nz.png"),
+    ),
+];
+
+// shader.wgsl 의 CubeUniform 과 같은 레이아웃
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct CubeUniform {
+    view_proj: [[f32; 4]; 4],
+    time: f32,
+    // 유니폼 구조체 크기를 16 바이트 배수로 맞춘다
+    _padding: [f32; 3],
+}
+
+impl CubeUniform {
+    fn new(size: (u32, u32), time: f32) -> Self {
+        let aspect = size.0 as f32 / size.1 as f32;
+        let projection = Mat4::perspective_rh(45f32.to_radians(), aspect, 0.1, 100.0);
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 1.5, 3.0), Vec3::ZERO, Vec3::Y);
+        Self {
+            view_proj: (projection * view).to_cols_array_2d(),
+            time,
+            _padding: [0.0; 3],
+        }
+    }
+}
+
+struct State {
+    gpu: Arc<GpuContext>,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    depth_texture: DepthTexture,
+    cube: TexturedCube,
+    video: VideoTexture,
+    // 프레임을 읽지 못하면 한 번만 알리고 더 올리지 않는다
+    video_failed: bool,
+    uniform: UniformBuffer<CubeUniform>,
+    canvas_id: String,
+    size: (u32, u32),
+}
+
+impl State {
+    async fn new(canvas_id: &str, video_url: &str) -> Result<Self, InitError> {
+        let canvas =
+            get_canvas(canvas_id).map_err(|_| InitError::CanvasNotFound(canvas_id.to_string()))?;
+        let size = get_canvas_size(&canvas, RenderScale::Native);
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await?;
+
+        let gpu = GpuContext::request(
+            &adapter,
+            &wgpu::DeviceDescriptor {
+                label: Some("main device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let device = gpu.device();
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = FormatSelector::default()
+            .select(&surface_caps.formats)
+            .chosen;
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.0,
+            height: size.1,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(device, &surface_config);
+
+        let depth_texture = DepthTexture::new(device, size.0, size.1);
+
+        let video = VideoTexture::new(&gpu, video_url, VIDEO_SIZE)
+            .map_err(|e| InitError::AssetLoadFailed(format!("{:?}", e)))?;
+
+        // 면마다 다른 이미지를 따로 디코딩해서 올리고, +Z 면에는 동영상 텍스처를 끼운다.
+        // 복제한 Texture 도 같은 GPU 텍스처를 가리키므로 매 프레임 올린 동영상이 보인다
+        let mut faces = Vec::with_capacity(6);
+        for (label, bytes) in FACE_IMAGES {
+            faces.push(Texture::from_bytes(&gpu, bytes, label)?);
+        }
+        faces.insert(4, video.texture().clone());
+        let faces: [Texture; 6] = faces
+            .try_into()
+            .unwrap_or_else(|_| panic!("Expected six face textures"));
+        let cube = TexturedCube::new(&gpu, faces);
+
+        let uniform = UniformBuffer::new(
+            &gpu,
+            &CubeUniform::new(size, 0.0),
+            0,
+            wgpu::ShaderStages::VERTEX,
+        );
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[uniform.bind_group_layout(), cube.bind_group_layout()],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[PositionNormalUvVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            // 모든 면이 바깥에서 봤을 때 CCW 이므로 뒷면을 컬링한다
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthTexture::depth_stencil_state()),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            gpu,
+            surface,
+            surface_config,
+            render_pipeline,
+            depth_texture,
+            cube,
+            video,
+            video_failed: false,
+            uniform,
+            canvas_id: canvas_id.to_string(),
+            size,
+        })
+    }
+
+    /// 회전은 셰이더가 시간으로 계산하므로 매 프레임 시간과 동영상의 새 프레임만 올린다.
+    fn update(&mut self, time_secs: f32) {
+        self.uniform
+            .update(self.gpu.queue(), &CubeUniform::new(self.size, time_secs));
+
+        if !self.video_failed
+            && let Err(e) = self.video.update(self.gpu.queue())
+        {
+            console::error_1(&format!("Failed to upload video frame: {:?}", e).into());
+            self.video_failed = true;
+        }
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            self.gpu
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(self.depth_texture.attachment()),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, self.uniform.bind_group(), &[]);
+            // 면마다 1번 그룹을 그 면의 텍스처로 바꿔 가며 6번 그린다
+            self.cube.draw(&mut render_pass, 1);
+        }
+
+        self.gpu.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: (u32, u32)) {
+        let limits = wgpu::Limits::default();
+        let new_size = (
+            new_size.0.max(1).min(limits.max_texture_dimension_2d),
+            new_size.1.max(1).min(limits.max_texture_dimension_2d),
+        );
+
+        if new_size == self.size {
+            return;
+        }
+
+        self.size = new_size;
+        self.surface_config.width = new_size.0;
+        self.surface_config.height = new_size.1;
+        self.surface
+            .configure(self.gpu.device(), &self.surface_config);
+        self.depth_texture = DepthTexture::new(self.gpu.device(), new_size.0, new_size.1);
+    }
+}
+
+fn now_secs() -> f32 {
+    (web_sys::window().unwrap().performance().unwrap().now() / 1000.0) as f32
+}
+
+fn start_render_loop(state: Rc<RefCell<State>>) {
+    let start = now_secs();
+
+    start_animation_loop(move || {
+        match state.try_borrow_mut() {
+            Ok(mut state) => {
+                let canvas =
+                    get_canvas(&state.canvas_id).expect("Failed to get canvas for resizing");
+                let (width, height) = get_canvas_size(&canvas, RenderScale::Native);
+                if (width, height) != state.size {
+                    state.resize((width, height));
+                }
+
+                state.update(now_secs() - start);
+
+                match state.render() {
+                    Ok(_) => {}
+                    Err(wgpu::SurfaceError::Lost) => {
+                        state
+                            .surface
+                            .configure(state.gpu.device(), &state.surface_config);
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        console::log_1(&"Out of memory!".into());
+                        return false;
+                    }
+                    Err(e) => {
+                        console::log_1(&format!("Render error: {:?}", e).into());
+                    }
+                }
+            }
+            Err(_) => {
+                console::log_1(&"State borrowed elsewhere, skipping frame".into());
+            }
+        }
+
+        true
+    });
+}
+
+#[wasm_bindgen]
+pub async fn run(canvas_id: &str) -> Result<(), JsValue> {
+    run_with_video_url(canvas_id, FLOWER_URL).await
+}
+
+/// 기본 동영상 대신 `video_url` 을 재생한다. 다른 출처라면 CORS 를 허용해야 한다.
+#[wasm_bindgen]
+pub async fn run_with_video_url(canvas_id: &str, video_url: &str) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let state = Rc::new(RefCell::new(State::new(canvas_id, video_url).await?));
+    start_render_loop(state);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+struct CubeUniform {
+    view_proj: mat4x4<f32>,
+    // 초 단위 경과 시간
+    time: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> cube: CubeUniform;
+
+@group(1) @binding(0)
+var face_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var face_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+};
+
+// Y 축으로 time, X 축으로 0.7 * time 만큼 돌리는 회전 행렬
+fn rotation(time: f32) -> mat3x3<f32> {
+    let cy = cos(time);
+    let sy = sin(time);
+    let cx = cos(time * 0.7);
+    let sx = sin(time * 0.7);
+    let rotate_y = mat3x3<f32>(
+        vec3<f32>(cy, 0.0, -sy),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(sy, 0.0, cy),
+    );
+    let rotate_x = mat3x3<f32>(
+        vec3<f32>(1.0, 0.0, 0.0),
+        vec3<f32>(0.0, cx, sx),
+        vec3<f32>(0.0, -sx, cx),
+    );
+    return rotate_y * rotate_x;
+}
+
+// Vertex shader
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    let model = rotation(cube.time);
+    var out: VertexOutput;
+    out.clip_position = cube.view_proj * vec4<f32>(model * in.position, 1.0);
+    // 회전 행렬은 직교 행렬이라 법선도 같은 행렬로 돌리면 된다
+    out.normal = model * in.normal;
+    out.uv = in.uv;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let light_dir = normalize(vec3<f32>(0.4, 0.8, 0.6));
+    let diffuse = max(dot(normalize(in.normal), light_dir), 0.0);
+    let color = textureSample(face_texture, face_sampler, in.uv).rgb;
+    return vec4<f32>(color * (0.4 + 0.6 * diffuse), 1.0);
+}